@@ -2,14 +2,19 @@ use crate::constants::*;
 use crate::core::platform_info::{long_version, short_description};
 use crate::models::supported_shells::SupportedShell;
 
-use clap::{ArgGroup, Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::path::PathBuf;
 
 // Constants
 
 lazy_static! {
     static ref REPO_REGEX: Regex = Regex::new(r"^[A-Za-z0-9_.-]+/[A-Za-z0-9_.-]+$").unwrap();
+    static ref SOURCE_REPO_REGEX: Regex =
+        Regex::new(r"^(gitlab:|gitea:[A-Za-z0-9.-]+(:[0-9]+)?/)?[A-Za-z0-9_.-]+/[A-Za-z0-9_.-]+$")
+            .unwrap();
+    static ref OWNER_REGEX: Regex = Regex::new(r"^[A-Za-z0-9_.-]+$").unwrap();
     static ref BINARY_NAME_REGEX: Regex = Regex::new(r"^[A-Za-z0-9_-]+$").unwrap();
 }
 
@@ -25,6 +30,78 @@ fn validate_repo_format(s: &str) -> Result<String, String> {
     }
 }
 
+/// Validates that `s` is either a `USERNAME/REPO` slug, optionally prefixed by
+/// its source like in [`validate_source_repo_format`], or a bare `USERNAME` and
+/// returns it unchanged if valid.
+fn validate_repo_or_owner_format(s: &str) -> Result<String, String> {
+    if SOURCE_REPO_REGEX.is_match(s) || OWNER_REGEX.is_match(s) {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "Repository must be in the format USERNAME/REPO or USERNAME, got: {}",
+            s
+        ))
+    }
+}
+
+/// Validates that `s` is a `USERNAME/REPO` slug, optionally prefixed by `gitlab:`
+/// to select GitLab or by `gitea:HOST/` to select a Gitea or Forgejo instance as
+/// release source, and returns it unchanged if valid.
+fn validate_source_repo_format(s: &str) -> Result<String, String> {
+    if SOURCE_REPO_REGEX.is_match(s) {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "Repository must be in the format USERNAME/REPO, gitlab:GROUP/PROJECT or gitea:HOST/OWNER/REPO, got: {}",
+            s
+        ))
+    }
+}
+
+/// Validates that `s` is an http(s) URL with a host and returns it unchanged if valid.
+fn validate_download_url(s: &str) -> Result<String, String> {
+    let host = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+        .and_then(|rest| rest.split(['/', '?', '#']).next())
+        .unwrap_or_default();
+    if host.is_empty() {
+        Err(format!(
+            "URL must start with http:// or https://, got: {}",
+            s
+        ))
+    } else {
+        Ok(s.to_string())
+    }
+}
+
+/// Parses `s` as a semver requirement (e.g. `^1.2` or `>=2, <3`).
+fn parse_version_req(s: &str) -> Result<semver::VersionReq, String> {
+    semver::VersionReq::parse(s).map_err(|e| format!("Invalid version constraint '{}': {}", s, e))
+}
+
+/// Parses a regular expression matching asset names, e.g. `linux.*\.tar\.gz$`.
+fn parse_asset_pattern(s: &str) -> Result<Regex, String> {
+    Regex::new(s).map_err(|e| format!("Invalid asset pattern '{}': {}", s, e))
+}
+
+/// Parses a count that must be at least 1, e.g. a number of jobs.
+fn parse_positive_count(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("Value must be a positive integer, got: {}", s)),
+    }
+}
+
+/// Validates that `s` is an https URL with a host and returns it unchanged if valid.
+fn validate_https_url(s: &str) -> Result<String, String> {
+    if s.starts_with("https://") {
+        validate_download_url(s)
+    } else {
+        Err(format!("URL must start with https://, got: {}", s))
+    }
+}
+
 /// Validates that `s` contains only alphanumeric characters, underscores, and hyphens.
 fn validate_binary_name(s: &str) -> Result<String, String> {
     if BINARY_NAME_REGEX.is_match(s) {
@@ -42,7 +119,7 @@ fn validate_binary_name(s: &str) -> Result<String, String> {
 pub struct UseArgs {
     /// GitHub user and repository in the format USERNAME/REPO
     /// e.g. pirafrank/rust_exif_renamer
-    #[arg(required = true, value_parser = validate_repo_format)]
+    #[arg(required = true, value_parser = validate_source_repo_format)]
     pub repo: String,
 
     /// Version to set as default. If not specified, uses the latest version.
@@ -53,6 +130,156 @@ pub struct UseArgs {
 /// Common arguments shared by subcommands that operate on a GitHub repository.
 #[derive(Parser, Clone)]
 pub struct CmdArgs {
+    /// GitHub user and repository in the format USERNAME/REPO
+    /// e.g. pirafrank/rust_exif_renamer.
+    /// Use gitlab:GROUP/PROJECT to get releases from GitLab,
+    /// or gitea:HOST/OWNER/REPO to get them from a Gitea or Forgejo instance
+    #[arg(required = true, value_parser = validate_source_repo_format)]
+    pub repo: Option<String>,
+
+    /// Optional release tag (defaults to 'latest').
+    /// A leading 'v' may be given or left out, e.g. 1.2.3 finds the release tagged v1.2.3
+    #[arg(long, short, visible_alias = "version")]
+    pub tag: Option<String>,
+
+    /// Prefer MUSL-linked assets, overriding libc auto-detection
+    #[arg(long, conflicts_with = "gnu")]
+    pub musl: bool,
+
+    /// Prefer glibc-linked assets, overriding libc auto-detection
+    #[arg(long, conflicts_with = "musl")]
+    pub gnu: bool,
+
+    /// Select the assets whose name matches this regular expression, in place of
+    /// the automatic selection for the platform. Overrides POOF_ASSET_PATTERN
+    #[arg(long, value_name = "REGEX", value_parser = parse_asset_pattern)]
+    pub asset_pattern: Option<Regex>,
+}
+
+/// Arguments for the `download` subcommand.
+#[derive(Parser, Clone)]
+pub struct DownloadArgs {
+    #[command(flatten)]
+    pub common: CmdArgs,
+
+    /// Download assets for the given CPU architecture instead of the detected one
+    #[arg(long, value_parser = SUPPORTED_ARCHS)]
+    pub arch: Option<String>,
+
+    /// Also extract the downloaded archives and list the executables they contain.
+    /// Nothing is installed or linked
+    #[arg(long)]
+    pub extract: bool,
+
+    /// Download (and extract) to this directory instead of the current one
+    #[arg(long, short, value_name = "DIR")]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for the `install` subcommand.
+#[derive(Parser, Clone)]
+#[command(mut_arg("repo", |arg| arg.required(false).required_unless_present("url")))]
+pub struct InstallArgs {
+    #[command(flatten)]
+    pub common: CmdArgs,
+
+    /// Install the asset at this http(s) URL, skipping release lookup.
+    /// The tag, if any, is used as version (defaults to 'latest')
+    #[arg(long, conflicts_with = "repo", value_parser = validate_download_url)]
+    pub url: Option<String>,
+
+    /// Install the file at this https URL as REPO, skipping release lookup.
+    /// The tag, if any, is used as version (defaults to 'latest')
+    #[arg(long, value_name = "URL", requires = "repo",
+        conflicts_with_all = ["url", "constraint"], value_parser = validate_https_url)]
+    pub source: Option<String>,
+
+    /// Slug to store a --url install under, in the format USERNAME/REPO.
+    /// Defaults to one derived from the URL host and file name
+    #[arg(long, requires = "url", conflicts_with = "repo", value_parser = validate_repo_format)]
+    pub name: Option<String>,
+
+    /// Install the newest release whose version satisfies this semver requirement,
+    /// e.g. '^1.2' or '>=2, <3'
+    #[arg(long, visible_alias = "version-constraint", value_name = "SEMVER_REQ",
+        conflicts_with_all = ["tag", "url"], value_parser = parse_version_req)]
+    pub constraint: Option<semver::VersionReq>,
+
+    /// Name of the command to create in PATH, in place of the binary's own name
+    #[arg(long, value_parser = validate_binary_name)]
+    pub rename: Option<String>,
+
+    /// Install only the executable with this name, when the archive ships several.
+    /// Combine with --rename to link it under another name
+    #[arg(long, visible_alias = "bin-name", value_name = "NAME", value_parser = validate_binary_name)]
+    pub binary: Option<String>,
+
+    /// Always query GitHub, ignoring cached API responses
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Install without adding the binaries to PATH. Use 'poof link' to add them later
+    #[arg(long)]
+    pub no_symlink: bool,
+
+    /// Download and extract assets to this directory instead of the cache directory.
+    /// The directory is kept after installation for inspection
+    #[arg(long, value_name = "PATH")]
+    pub extract_dir: Option<PathBuf>,
+
+    /// Create the symlinks in this directory instead of poof's bin directory.
+    /// Takes precedence over the POOF_BIN_DIR environment variable
+    #[arg(long, value_name = "PATH", conflicts_with = "no_symlink")]
+    pub bin_dir: Option<PathBuf>,
+
+    /// Only download and extract the assets, to --extract-dir or the current directory.
+    /// Nothing is installed or linked
+    #[arg(long, conflicts_with_all = ["no_symlink", "bin_dir", "keep_versions", "rename",
+        "binary", "check_only", "print_path", "print_path_only"])]
+    pub extract_only: bool,
+
+    /// After installing, keep only the N newest versions of the repository.
+    /// Versions in use or pinned are never removed
+    #[arg(long, value_name = "N", value_parser = parse_positive_count)]
+    pub keep_versions: Option<usize>,
+
+    /// Do not warn when running as root
+    #[arg(long)]
+    pub allow_root: bool,
+
+    /// Download and install the version again even if it is already installed,
+    /// replacing the installed files
+    #[arg(long, conflicts_with_all = ["check_only", "extract_only", "print_path_only"])]
+    pub force: bool,
+
+    /// Only check that the release has a compatible asset, without downloading it.
+    /// Exits with code 2 when none is found
+    #[arg(long, visible_alias = "dry-run-download", conflicts_with_all = ["url", "source"])]
+    pub check_only: bool,
+
+    /// Print the compatible assets as JSON
+    #[arg(long, requires = "check_only")]
+    pub json: bool,
+
+    /// Print the install directory, the symlink and the assets to download, then install
+    #[arg(long, conflicts_with = "check_only")]
+    pub print_path: bool,
+
+    /// Print the install directory, the symlink and the assets to download, then stop
+    #[arg(long, conflicts_with_all = ["check_only", "print_path"])]
+    pub print_path_only: bool,
+
+    /// Verify each asset against the sigstore bundle published next to it, before
+    /// extracting it. The signer is checked against the sigstore trusted root
+    #[cfg(feature = "cosign")]
+    #[arg(long, conflicts_with_all = ["url", "source", "check_only", "extract_only",
+        "print_path_only"])]
+    pub verify_cosign: bool,
+}
+
+/// Arguments for the `changelog` subcommand.
+#[derive(Parser, Clone)]
+pub struct ChangelogArgs {
     /// GitHub user and repository in the format USERNAME/REPO
     /// e.g. pirafrank/rust_exif_renamer
     #[arg(required = true, value_parser = validate_repo_format)]
@@ -61,18 +288,68 @@ pub struct CmdArgs {
     /// Optional release tag (defaults to 'latest')
     #[arg(long, short)]
     pub tag: Option<String>,
+
+    /// Write release notes directly to stdout instead of using a pager
+    #[arg(long)]
+    pub no_pager: bool,
 }
 
 /// Arguments for the `update` subcommand.
 #[derive(Parser, Clone)]
 pub struct UpdateArgs {
     /// Github slug in the format USERNAME/REPO
-    #[arg(value_parser = validate_repo_format, required_unless_present_any = ["all"])]
+    #[arg(value_parser = validate_source_repo_format, required_unless_present_any = ["all", "update_self"])]
     pub repo: Option<String>,
 
     /// Update all installed binaries
     #[arg(long, conflicts_with_all = ["repo"])]
     pub all: bool,
+
+    /// Update poof itself, replacing the running executable with the latest release
+    #[arg(long = "self", conflicts_with_all = ["repo", "all"])]
+    pub update_self: bool,
+
+    /// Always query GitHub, ignoring cached API responses
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Number of repositories to update at once with --all (defaults to 1).
+    /// Capped at the number of CPUs
+    #[arg(long, short, value_name = "N", conflicts_with_all = ["repo", "update_self"], value_parser = parse_positive_count)]
+    pub jobs: Option<usize>,
+
+    /// After updating, keep only the N newest versions of each updated repository.
+    /// Versions in use or pinned are never removed
+    #[arg(long, value_name = "N", conflicts_with = "update_self", value_parser = parse_positive_count)]
+    pub keep_versions: Option<usize>,
+
+    /// With --all, stop at the first repository that fails to update
+    #[arg(long, requires = "all")]
+    pub fail_fast: bool,
+
+    /// With --all, print only the final summary instead of the progress of each repository
+    #[arg(long, requires = "all")]
+    pub summary_only: bool,
+}
+
+/// Arguments for the `pin` subcommand.
+#[derive(Parser, Clone)]
+pub struct PinArgs {
+    /// GitHub user and repository in the format USERNAME/REPO
+    #[arg(required = true, value_parser = validate_source_repo_format)]
+    pub repo: String,
+
+    /// Installed version to pin the repository to
+    #[arg(required = true)]
+    pub version: String,
+}
+
+/// Arguments for the `unpin` subcommand.
+#[derive(Parser, Clone)]
+pub struct UnpinArgs {
+    /// GitHub user and repository in the format USERNAME/REPO
+    #[arg(required = true, value_parser = validate_source_repo_format)]
+    pub repo: String,
 }
 
 /// Parses a shell name string into a [`SupportedShell`] variant, returning a friendly error on failure.
@@ -86,13 +363,32 @@ fn parse_shell(s: &str) -> Result<SupportedShell, String> {
     })
 }
 
-/// Arguments for subcommands that require a shell type (completions, enable, init).
+/// Arguments for subcommands that require a shell type (completions, enable, disable, init).
 #[derive(Parser, Clone)]
 pub struct ShellIntegrationArgs {
     /// Shell type to generate completions for, integrate via init command, and more.
-    /// Possible values: bash, elvish, fish, nushell (or nu), powershell (or pwsh), xonsh, zsh
+    /// Possible values: bash, elvish, fish, nushell (or nu), powershell (or pwsh), xonsh, zsh.
+    /// Detected from $SHELL when not given
     #[arg(long, short, value_parser = parse_shell)]
-    pub shell: SupportedShell,
+    pub shell: Option<SupportedShell>,
+}
+
+/// Arguments for the `completions` subcommand.
+#[derive(Parser, Clone)]
+pub struct CompletionsArgs {
+    #[command(flatten)]
+    pub shell: ShellIntegrationArgs,
+
+    /// Write the script to the shell's completions directory instead of stdout.
+    /// Supported for bash (~/.local/share/bash-completion/completions/poof),
+    /// zsh (~/.zsh/completions/_poof), fish (~/.config/fish/completions/poof.fish)
+    /// and nushell (~/.config/nushell/completions/poof.nu)
+    #[arg(long)]
+    pub install: bool,
+
+    /// Skip the confirmation prompt of --install
+    #[arg(long, short, requires = "install")]
+    pub yes: bool,
 }
 
 /// Arguments for the `unlink` subcommand.
@@ -107,21 +403,271 @@ pub struct UnlinkArgs {
     pub yes: bool,
 }
 
+/// Arguments for the `link` subcommand.
+#[derive(Parser, Clone)]
+pub struct LinkArgs {
+    #[command(flatten)]
+    pub target: UseArgs,
+
+    /// Replace symlinks of other repositories with the same name
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Keys `list --sort-by` can order repositories by.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortField {
+    /// Repository name
+    Repo,
+    /// Highest installed version
+    Version,
+    /// Last time a version was installed
+    Date,
+    /// Disk space used by all installed versions
+    Size,
+}
+
 /// Arguments for the `list` subcommand.
 #[derive(Parser, Clone)]
 pub struct ListArgs {
+    /// GitHub user and repository in the format USERNAME/REPO
+    /// e.g. pirafrank/rust_exif_renamer,
+    /// or a USERNAME alone to list all its installed repositories
+    #[arg(required = false, value_parser = validate_repo_or_owner_format)]
+    pub repo: Option<String>,
+
+    /// Print one line per repository using a template.
+    /// Placeholders: {repo}, {active_version}, {versions}, {latest_version}, {count}
+    #[arg(long, conflicts_with = "json")]
+    pub format: Option<String>,
+
+    /// Print the list as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Show when each version has been installed and the SHA-256 checksum of the
+    /// assets it has been installed from
+    #[arg(long, short, conflicts_with_all = ["format", "json"])]
+    pub verbose: bool,
+
+    /// Only list repositories with a newer release available, checking each one online
+    #[arg(long, conflicts_with_all = ["format", "json", "verbose"])]
+    pub only_outdated: bool,
+
+    /// List every repository with its latest release, marking those with a newer one.
+    /// Checks each one online
+    #[arg(long, conflicts_with_all = ["format", "json", "verbose", "only_outdated"])]
+    pub outdated: bool,
+
+    /// Milliseconds to wait between two release checks of --only-outdated or --outdated
+    #[arg(long, value_name = "MILLISECONDS", default_value_t = 200)]
+    pub check_delay: u64,
+
+    /// Sort repositories by this field
+    #[arg(long, value_enum, value_name = "FIELD")]
+    pub sort_by: Option<SortField>,
+
+    /// Reverse the sort order
+    #[arg(long)]
+    pub reverse: bool,
+}
+
+/// Arguments for the `status` subcommand.
+#[derive(Parser, Clone)]
+pub struct StatusArgs {
+    /// Remove broken symlinks from the bin directory
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long, requires = "fix")]
+    pub yes: bool,
+}
+
+/// Arguments for the `repair` subcommand.
+#[derive(Parser, Clone)]
+pub struct RepairArgs {
+    /// Print what would be changed without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the `clean` subcommand.
+#[derive(Parser, Clone)]
+pub struct CleanArgs {
+    /// Print the size of the cache without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long, conflicts_with = "dry_run")]
+    pub yes: bool,
+
+    /// Only delete cached files last modified more than DAYS days ago
+    #[arg(long, value_name = "DAYS")]
+    pub older_than: Option<u64>,
+
+    /// Keep the cached downloads of the newest installed version of each repository
+    #[arg(long)]
+    pub keep_latest: bool,
+}
+
+/// Arguments for the `du` subcommand.
+#[derive(Parser, Clone)]
+pub struct DuArgs {
+    /// Show the size of each installed version too
+    #[arg(long, short)]
+    pub verbose: bool,
+}
+
+/// Arguments for the `info` subcommand.
+#[derive(Parser, Clone)]
+pub struct InfoArgs {
+    /// Show the latest release and the installed versions of USERNAME/REPO
+    /// instead of the platform information
+    #[arg(value_parser = validate_source_repo_format)]
+    pub repo: Option<String>,
+
+    /// Print the information as a JSON object
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for the `show` subcommand.
+#[derive(Parser, Clone)]
+pub struct ShowArgs {
     /// GitHub user and repository in the format USERNAME/REPO
     /// e.g. pirafrank/rust_exif_renamer
-    #[arg(required = false, value_parser = validate_repo_format)]
+    #[arg(required = true, value_parser = validate_source_repo_format)]
+    pub repo: String,
+
+    /// Only show the installed versions, without querying the latest release
+    #[arg(long)]
+    pub installed_only: bool,
+
+    /// Print the information as a JSON object
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for the `history` subcommand.
+#[derive(Parser, Clone)]
+pub struct HistoryArgs {
+    /// Only show the operations on this repository, in the format USERNAME/REPO
+    #[arg(long, value_parser = validate_source_repo_format)]
     pub repo: Option<String>,
+
+    /// Only show the N most recent operations
+    #[arg(long, short = 'n', value_name = "N", value_parser = parse_positive_count)]
+    pub limit: Option<usize>,
+
+    /// Delete the recorded history
+    #[arg(long, conflicts_with_all = ["repo", "limit"])]
+    pub clear: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long, requires = "clear")]
+    pub yes: bool,
+}
+
+/// Arguments for the `env-vars` subcommand.
+#[derive(Parser, Clone)]
+pub struct EnvVarsArgs {
+    /// Show only the variables that are not set in the environment
+    #[arg(long)]
+    pub unset_only: bool,
+}
+
+/// Arguments for subcommands that only choose between text and JSON output.
+#[derive(Parser, Clone)]
+pub struct JsonArgs {
+    /// Print the information as a JSON object
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for the `gc` subcommand.
+#[derive(Parser, Clone)]
+pub struct GcArgs {
+    /// Number of newest versions to keep for each repository, even when unlinked
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub keep_latest: usize,
+
+    /// Print what would be removed without removing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Arguments for the `prune` subcommand.
+#[derive(Parser, Clone)]
+pub struct PruneArgs {
+    /// GitHub user and repository in the format USERNAME/REPO
+    #[arg(value_parser = validate_source_repo_format, required_unless_present_any = ["all"])]
+    pub repo: Option<String>,
+
+    /// Prune all installed repositories
+    #[arg(long, conflicts_with = "repo")]
+    pub all: bool,
+
+    /// Print what would be removed without removing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Arguments for the `verify` subcommand.
+#[derive(Parser, Clone)]
+pub struct VerifyArgs {
+    /// GitHub user and repository in the format USERNAME/REPO
+    #[arg(value_parser = validate_source_repo_format, required_unless_present_any = ["all"])]
+    pub repo: Option<String>,
+
+    /// Version to verify. If not specified, verifies the latest installed version.
+    #[arg(requires = "repo")]
+    pub version: Option<String>,
+
+    /// Verify all installed binaries
+    #[arg(long, conflicts_with_all = ["repo", "version"])]
+    pub all: bool,
+
+    /// Fetch the published checksum files for binaries without a recorded checksum
+    #[arg(long)]
+    pub redownload_checksums: bool,
+}
+
+/// Arguments for the `search` subcommand.
+#[derive(Parser, Clone)]
+pub struct SearchArgs {
+    /// Terms to search GitHub repositories for
+    #[arg(required = true)]
+    pub query: String,
+
+    /// Maximum number of results to show
+    #[arg(long, short = 'n', default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=100))]
+    pub limit: u32,
 }
 
 /// Arguments for the `which` subcommand.
 #[derive(Parser, Clone)]
 pub struct WhichArgs {
     /// Name of the binary to look up
-    #[arg(required = true, value_parser = validate_binary_name)]
-    pub binary_name: String,
+    #[arg(required_unless_present = "all", value_parser = validate_binary_name)]
+    pub binary_name: Option<String>,
+
+    /// List every binary provided by the installed repositories, with its repository and active version
+    #[arg(long, conflicts_with = "binary_name")]
+    pub all: bool,
+
+    /// Print the providing repositories and versions as a JSON array
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// Arguments for the `what` subcommand.
@@ -129,16 +675,25 @@ pub struct WhichArgs {
 pub struct WhatArgs {
     /// GitHub user and repository in the format USERNAME/REPO
     /// e.g. pirafrank/rust_exif_renamer
-    #[arg(required = true, value_parser = validate_repo_format)]
+    #[arg(required = true, value_parser = validate_source_repo_format)]
     pub repo: String,
+
+    /// List the binaries of every installed version, with the versions providing
+    /// each one and their install path
+    #[arg(long, short)]
+    pub verbose: bool,
+
+    /// Print the binaries of every installed version as JSON
+    #[arg(long, conflicts_with = "verbose")]
+    pub json: bool,
 }
 
 /// Arguments for the `uninstall` subcommand.
 #[derive(Parser, Clone)]
-#[command(group(ArgGroup::new("what_to_uninstall").required(true).args(["version", "all"])))]
+#[command(group(ArgGroup::new("what_to_uninstall").required(true).args(["version", "all", "keep_latest", "older_than"])))]
 pub struct UninstallArgs {
     /// GitHub user and repository in the format USERNAME/REPO
-    #[arg(required = true, value_parser = validate_repo_format)]
+    #[arg(required = true, value_parser = validate_source_repo_format)]
     pub repo: String,
 
     /// Version to uninstall
@@ -149,27 +704,46 @@ pub struct UninstallArgs {
     #[arg(long, group = "what_to_uninstall")]
     pub all: bool,
 
+    /// Uninstall every version except the newest one
+    #[arg(long, group = "what_to_uninstall")]
+    pub keep_latest: bool,
+
+    /// Uninstall the versions older than VERSION
+    #[arg(long, value_name = "VERSION", group = "what_to_uninstall")]
+    pub older_than: Option<String>,
+
     /// Skip confirmation prompt
     #[arg(short, long)]
     pub yes: bool,
+
+    /// Uninstall even if the repository is pinned to the version being removed
+    #[arg(long)]
+    pub force: bool,
 }
 
 /// All available poof subcommands.
 #[derive(Subcommand, Clone)]
 pub enum Cmd {
     /// Only perform download for the platform in current directory. Do not install.
-    Download(CmdArgs),
+    Download(DownloadArgs),
 
     /// Download binary for the platform and install it
-    Install(CmdArgs),
+    Install(InstallArgs),
 
     /// List all installed binaries and their versions
     List(ListArgs),
 
-    /// Show which repository provides a binary
+    /// Show the release notes of a repository release
+    Changelog(ChangelogArgs),
+
+    /// Search GitHub for repositories to install
+    Search(SearchArgs),
+
+    /// Show which installed repositories and versions provide a binary, marking the active one
     Which(WhichArgs),
 
-    /// List all binaries provided by the latest version of a repository
+    /// List all binaries provided by the latest version of a repository,
+    /// or with --verbose or --json by every installed version
     What(WhatArgs),
 
     /// Set an installed version of a slug as the default one
@@ -178,32 +752,74 @@ pub enum Cmd {
     /// Update installed binaries of a slug or all installed binaries to their latest versions
     Update(UpdateArgs),
 
-    /// Remove binary from PATH. Use 'poof use' to re-add it
+    /// Pin a repository to an installed version, so that 'update' leaves it alone
+    Pin(PinArgs),
+
+    /// Remove the pin of a repository, allowing 'update' to upgrade it again
+    Unpin(UnpinArgs),
+
+    /// Remove binary from PATH. Use 'poof link' to re-add it
     Unlink(UnlinkArgs),
 
+    /// Add the binaries of an installed version to PATH
+    Link(LinkArgs),
+
     /// Uninstall a version or all versions of a repository
     Uninstall(UninstallArgs),
 
     /// Persistently add poof's bin directory to your shell PATH
     Enable(ShellIntegrationArgs),
 
+    /// Remove poof's bin directory from your shell PATH, undoing 'enable'
+    Disable(ShellIntegrationArgs),
+
     /// Check if poof's bin directory is in the PATH
     Check,
 
+    /// Show the health of every binary managed by poof
+    Status(StatusArgs),
+
+    /// Re-point or remove broken symlinks in the bin directory
+    Repair(RepairArgs),
+
+    /// Remove installed versions that are not linked in the bin directory
+    Gc(GcArgs),
+
+    /// Remove all installed versions of a repository except the active one
+    Prune(PruneArgs),
+
+    /// Show the disk space used by each installed repository
+    #[command(visible_alias = "usage")]
+    Du(DuArgs),
+
+    /// Check installed binaries against the checksums recorded at install time
+    Verify(VerifyArgs),
+
     /// Generate shell completions to stdout
-    Completions(ShellIntegrationArgs),
+    Completions(CompletionsArgs),
 
     /// Generate shell-specific init script to add poof bin directory to PATH
     Init(ShellIntegrationArgs),
 
-    /// Empty the cache directory
-    Clean,
+    /// Delete cached downloads, optionally only older or superseded ones.
+    /// Cached API responses are kept
+    Clean(CleanArgs),
+
+    /// Show install and environment information, or release and install information of a repository
+    Info(InfoArgs),
 
-    /// Show install and environment information
-    Info,
+    /// Show the installed versions of a repository, with their binaries and install date,
+    /// and its latest release
+    Show(ShowArgs),
+
+    /// Show past install, update, uninstall and use operations, newest first
+    History(HistoryArgs),
+
+    /// List the environment variables poof recognises
+    EnvVars(EnvVarsArgs),
 
     /// Show version information
-    Version,
+    Version(JsonArgs),
 }
 
 /// Top-level CLI structure parsed by clap.
@@ -225,4 +841,18 @@ pub struct Cli {
     /// Command to execute
     #[command(subcommand)]
     pub command: Cmd,
+
+    /// Timeout of network operations, in seconds. Overrides POOF_TIMEOUT (defaults to 60)
+    #[arg(long, global = true, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Only print errors and the command output, no progress or informational messages
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// Print errors to stderr as a JSON object with the message and its causes,
+    /// same as POOF_JSON_ERRORS=1. Must be given before the command,
+    /// e.g. 'poof --json install USER/REPO'
+    #[arg(long)]
+    pub json: bool,
 }
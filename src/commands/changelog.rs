@@ -0,0 +1,98 @@
+//! Main file handling 'changelog' command
+
+use anyhow::{Context, Result};
+use log::{debug, info};
+use std::env;
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::github::client::get_release;
+use crate::github::models::Release;
+use crate::output;
+
+/// Maximum number of characters of release notes to display.
+const MAX_CHANGELOG_CHARS: usize = 10_000;
+/// Pager used when the `PAGER` environment variable is not set.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// Display the release notes of `repo` for the given `tag`, or the latest release.
+///
+/// Release notes are piped through a pager when stdout is a terminal and
+/// `no_pager` is `false`, and written directly to stdout otherwise.
+/// Notes longer than [`MAX_CHANGELOG_CHARS`] characters are truncated.
+pub fn show_changelog(repo: &str, tag: Option<&str>, no_pager: bool) -> Result<()> {
+    let release = get_release(repo, tag, true)
+        .with_context(|| format!("Cannot get release information for {}", repo))?;
+
+    if release.body().trim().is_empty() {
+        info!(
+            "No release notes available for {} {}",
+            repo,
+            release.tag_name()
+        );
+        return Ok(());
+    }
+
+    let text = format_changelog(repo, &release);
+    if no_pager || !std::io::stdout().is_terminal() || !print_paged(&text) {
+        output!("{}", text);
+    }
+    Ok(())
+}
+
+/// Build the text to display: a header line followed by the (possibly truncated) notes.
+fn format_changelog(repo: &str, release: &Release) -> String {
+    let body = release.body().replace("\r\n", "\n");
+    format!(
+        "{} {} ({})\n\n{}",
+        repo,
+        release.tag_name(),
+        release.published_at(),
+        truncate_body(body.trim())
+    )
+}
+
+/// Truncate `body` to [`MAX_CHANGELOG_CHARS`] characters, appending a notice when cut.
+fn truncate_body(body: &str) -> String {
+    match body.char_indices().nth(MAX_CHANGELOG_CHARS) {
+        Some((idx, _)) => format!("{}\n\n(truncated)", &body[..idx]),
+        None => body.to_string(),
+    }
+}
+
+/// Pipe `text` through the user's pager.
+///
+/// Returns `false` when the pager cannot be started, so that the caller can
+/// fall back to writing to stdout.
+fn print_paged(text: &str) -> bool {
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return false;
+    };
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            debug!("Cannot start pager '{}': {}", pager, e);
+            return false;
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager may exit before reading everything (e.g. user quits early)
+        let _ = writeln!(stdin, "{}", text);
+    }
+    let _ = child.wait();
+    true
+}
+
+#[cfg(test)]
+mod tests;
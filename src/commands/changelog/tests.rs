@@ -0,0 +1,107 @@
+use super::*;
+use mockito::Server;
+use serde_json::json;
+
+/// Helper to build a release with the given notes.
+fn release_with_body(body: Option<&str>) -> Release {
+    serde_json::from_value(json!({
+        "tag_name": "v1.0.0",
+        "published_at": "2024-01-01T00:00:00Z",
+        "assets": [],
+        "body": body,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_truncate_body_short_text_untouched() {
+    let body = "## What's Changed\n* fix a bug";
+    assert_eq!(truncate_body(body), body);
+}
+
+#[test]
+fn test_truncate_body_exact_limit_untouched() {
+    let body = "a".repeat(MAX_CHANGELOG_CHARS);
+    assert_eq!(truncate_body(&body), body);
+}
+
+#[test]
+fn test_truncate_body_long_text_truncated() {
+    let body = "a".repeat(MAX_CHANGELOG_CHARS + 1);
+    let truncated = truncate_body(&body);
+    assert!(truncated.starts_with(&"a".repeat(MAX_CHANGELOG_CHARS)));
+    assert!(truncated.ends_with("(truncated)"));
+    assert!(!truncated.contains(&"a".repeat(MAX_CHANGELOG_CHARS + 1)));
+}
+
+#[test]
+fn test_truncate_body_multibyte_chars() {
+    // must not panic when the cut falls inside a multi-byte sequence
+    let body = "é".repeat(MAX_CHANGELOG_CHARS + 10);
+    let truncated = truncate_body(&body);
+    assert!(truncated.ends_with("(truncated)"));
+}
+
+#[test]
+fn test_format_changelog_normalizes_line_endings() {
+    let release = release_with_body(Some("line one\r\nline two\r\n"));
+    let text = format_changelog("owner/repo", &release);
+    assert!(text.starts_with("owner/repo v1.0.0 (2024-01-01T00:00:00Z)"));
+    assert!(text.ends_with("line one\nline two"));
+    assert!(!text.contains('\r'));
+}
+
+#[test]
+fn test_release_null_body_is_empty() {
+    let release = release_with_body(None);
+    assert_eq!(release.body(), "");
+}
+
+#[test]
+fn test_show_changelog_for_tag() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/owner/repo/releases/tags/v1.0.0")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "tag_name": "v1.0.0",
+                "published_at": "2024-01-01T00:00:00Z",
+                "assets": [],
+                "body": "## What's Changed",
+            })
+            .to_string(),
+        )
+        .create();
+
+    let result = temp_env::with_vars(
+        vec![
+            ("POOF_GITHUB_API_URL", Some(server.url().as_str())),
+            ("POOF_API_CACHE_TTL_SECONDS", Some("0")),
+        ],
+        || show_changelog("owner/repo", Some("v1.0.0"), true),
+    );
+    mock.assert();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_show_changelog_release_not_found() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/owner/repo/releases/latest")
+        .with_status(404)
+        .with_body(json!({ "message": "Not Found" }).to_string())
+        .create();
+
+    let result = temp_env::with_vars(
+        vec![
+            ("POOF_GITHUB_API_URL", Some(server.url().as_str())),
+            ("POOF_API_CACHE_TTL_SECONDS", Some("0")),
+        ],
+        || show_changelog("owner/repo", None, true),
+    );
+    mock.assert();
+    assert!(result.is_err());
+}
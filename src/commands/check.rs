@@ -21,6 +21,12 @@ pub fn check_if_bin_in_path() -> Result<ExitCode> {
             return Ok(ExitCode::from(2u8));
         }
     };
+    check_if_dir_in_path(&bin_dir)
+}
+
+/// Check whether `bin_dir` is present in `PATH`, with the same exit codes
+/// as [`check_if_bin_in_path`].
+pub fn check_if_dir_in_path(bin_dir: &Path) -> Result<ExitCode> {
     let position = platform_info::check_dir_in_path(bin_dir.to_str().unwrap());
     match position {
         -1 => {
@@ -28,7 +34,7 @@ pub fn check_if_bin_in_path() -> Result<ExitCode> {
             error!(
                 "Please add {} to your PATH. For example, run: \n\n{}\n",
                 bin_dir.display(),
-                get_export_command(&bin_dir)?
+                get_export_command(bin_dir)?
             );
             error!("This is required to run the binaries managed by poof.");
             Ok(ExitCode::from(2u8))
@@ -1,16 +1,30 @@
 //! Main file handling 'cleanup' command
 
 use anyhow::{Context, Result};
-use log::info;
-use std::io::{self, Write};
+use log::{debug, info};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
-use crate::files::datadirs;
+use crate::cli::CleanArgs;
+use crate::commands::list::list_installed_spells;
+use crate::constants::API_CACHE_SUBDIR;
+use crate::files::{datadirs, filesys};
+use crate::utils::prompt::confirm;
+use crate::utils::speed::format_bytes;
+
+const SECONDS_PER_DAY: u64 = 86_400;
 
 /// Interactively delete the download cache directory.
 ///
-/// Prompts the user for confirmation before removing the cache. If the cache
-/// directory does not exist the function returns early with a message.
-pub fn run_clean() -> Result<()> {
+/// Prints the size of each cache entry and prompts the user for confirmation
+/// before removing the cache, then reports the space that was freed.
+/// With `--dry-run` only the sizes are printed. If the cache directory does not
+/// exist the function returns early with a message.
+/// Cached GitHub API responses are kept, they expire on their own.
+///
+/// With `--older-than` and/or `--keep-latest` only the matching cached files
+/// are deleted, see [`clean_selected`].
+pub fn run_clean(args: &CleanArgs) -> Result<()> {
     let cache_dir = datadirs::get_cache_dir().context("Cannot get cache directory path")?;
 
     // fallback albeit cache and data dirs are created at startup
@@ -20,33 +34,277 @@ pub fn run_clean() -> Result<()> {
         return Ok(());
     }
 
+    if args.older_than.is_some() || args.keep_latest {
+        return clean_selected(args, &cache_dir);
+    }
+
     // Show what will be deleted
+    let entries = cache_entries(&cache_dir)
+        .with_context(|| format!("Cannot read cache directory: {}", cache_dir.display()))?;
+    let total: u64 = entries.iter().map(|(_, size)| size).sum();
+    for (path, size) in &entries {
+        let name = path.strip_prefix(&cache_dir).unwrap_or(path);
+        info!("  {:>10}  {}", format_bytes(*size), name.display());
+    }
+    if args.dry_run {
+        info!(
+            "Would free {} from the cache directory: {}",
+            format_bytes(total),
+            cache_dir.display()
+        );
+        return Ok(());
+    }
     info!(
-        "This will delete the cache directory: {}",
-        cache_dir.display()
+        "This will delete the cache directory: {} ({})",
+        cache_dir.display(),
+        format_bytes(total)
     );
 
-    // Ask for confirmation
-    print!("Proceed? (y/yes): ");
-    io::stdout().flush().context("Cannot flush stdout")?;
+    // Skip confirmation if -y flag is set
+    if !args.yes && !confirm()? {
+        info!("Cleanup cancelled.");
+        return Ok(());
+    }
 
-    let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .context("Cannot read user input")?;
+    info!("Deleting cache directory...");
 
-    let input = input.trim().to_lowercase();
+    empty_cache_dir(&cache_dir)
+        .with_context(|| format!("Cannot delete cache directory: {}", cache_dir.display()))?;
 
-    if input == "y" || input == "yes" {
-        info!("Deleting cache directory...");
+    info!(
+        "Cache directory successfully deleted, freed {}.",
+        format_bytes(total)
+    );
 
-        std::fs::remove_dir_all(&cache_dir)
-            .with_context(|| format!("Cannot delete cache directory: {}", cache_dir.display()))?;
+    Ok(())
+}
 
-        info!("Cache directory successfully deleted.");
+/// Delete only the cached files selected by `--older-than` and `--keep-latest`.
+///
+/// `--older-than DAYS` selects the files last modified more than DAYS days ago,
+/// `--keep-latest` spares the downloads of the newest installed version of each
+/// repository. When both are given a file must satisfy both to be deleted.
+/// Directories left empty are removed afterwards.
+fn clean_selected(args: &CleanArgs, cache_dir: &Path) -> Result<()> {
+    let keep_dirs: Vec<PathBuf> = if args.keep_latest {
+        list_installed_spells()
+            .iter()
+            .filter_map(|spell| {
+                let latest = spell.get_latest_version()?;
+                Some(datadirs::get_binary_nest(
+                    cache_dir,
+                    spell.get_name(),
+                    &latest,
+                ))
+            })
+            .collect()
     } else {
+        Vec::new()
+    };
+    let max_age = args
+        .older_than
+        .map(|days| Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)));
+
+    let files = select_cache_files(cache_dir, max_age, &keep_dirs, SystemTime::now())
+        .with_context(|| format!("Cannot read cache directory: {}", cache_dir.display()))?;
+    if files.is_empty() {
+        info!("Nothing to clean up.");
+        return Ok(());
+    }
+    let total: u64 = files.iter().map(|(_, size)| size).sum();
+    for (path, size) in &files {
+        let name = path.strip_prefix(cache_dir).unwrap_or(path);
+        info!("  {:>10}  {}", format_bytes(*size), name.display());
+    }
+    if args.dry_run {
+        info!(
+            "Would delete {} cached files, freeing {}.",
+            files.len(),
+            format_bytes(total)
+        );
+        return Ok(());
+    }
+    info!(
+        "This will delete {} cached files ({}).",
+        files.len(),
+        format_bytes(total)
+    );
+
+    if !args.yes && !confirm()? {
         info!("Cleanup cancelled.");
+        return Ok(());
+    }
+
+    for (path, _) in &files {
+        std::fs::remove_file(path).with_context(|| format!("Cannot delete {}", path.display()))?;
     }
+    remove_empty_dirs(cache_dir);
 
+    info!(
+        "Deleted {} cached files, freed {}.",
+        files.len(),
+        format_bytes(total)
+    );
     Ok(())
 }
+
+/// Returns the files in `cache_dir` to delete, with their size in bytes, sorted by path.
+///
+/// Files modified less than `max_age` before `now` and files inside `keep_dirs`
+/// are left out, as is the API response cache.
+fn select_cache_files(
+    cache_dir: &Path,
+    max_age: Option<Duration>,
+    keep_dirs: &[PathBuf],
+    now: SystemTime,
+) -> Result<Vec<(PathBuf, u64)>> {
+    let mut files: Vec<(PathBuf, u64)> = Vec::new();
+    let mut stack: Vec<PathBuf> = vec![cache_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if dir == cache_dir && entry.file_name() == API_CACHE_SUBDIR {
+                continue;
+            }
+            if entry.file_type()?.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            if keep_dirs.iter().any(|keep| path.starts_with(keep)) {
+                continue;
+            }
+            let metadata = path.symlink_metadata()?;
+            if let Some(max_age) = max_age {
+                let age = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .unwrap_or_default();
+                if age <= max_age {
+                    continue;
+                }
+            }
+            files.push((path, metadata.len()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Best effort removal of the directories left empty inside `dir`, `dir` itself excluded.
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            remove_empty_dirs(&path);
+            if std::fs::remove_dir(&path).is_ok() {
+                debug!("Removed empty directory {}", path.display());
+            }
+        }
+    }
+}
+
+/// Returns the entries of `cache_dir` that `clean` deletes, with their size in bytes,
+/// sorted by path. The API response cache is left out.
+fn cache_entries(cache_dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let mut entries: Vec<(PathBuf, u64)> = Vec::new();
+    for entry in std::fs::read_dir(cache_dir)? {
+        let entry = entry?;
+        if entry.file_name() == API_CACHE_SUBDIR {
+            continue;
+        }
+        let path = entry.path();
+        let size = if entry.file_type()?.is_dir() {
+            filesys::dir_total_size(&path)?
+        } else {
+            path.symlink_metadata()?.len()
+        };
+        entries.push((path, size));
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// Deletes everything in `cache_dir` apart from the API response cache.
+/// The directory itself is deleted too when nothing is left in it.
+fn empty_cache_dir(cache_dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(cache_dir)? {
+        let entry = entry?;
+        if entry.file_name() == API_CACHE_SUBDIR {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(&path)?;
+        } else {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    if std::fs::read_dir(cache_dir)?.next().is_none() {
+        std::fs::remove_dir(cache_dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_entries_sizes_skip_api_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        std::fs::create_dir_all(cache_dir.join("owner/tool/1.0.0")).unwrap();
+        std::fs::write(cache_dir.join("owner/tool/1.0.0/tool.tar.gz"), [0u8; 300]).unwrap();
+        std::fs::write(cache_dir.join("loose.bin"), [0u8; 20]).unwrap();
+        std::fs::create_dir_all(cache_dir.join(API_CACHE_SUBDIR)).unwrap();
+        std::fs::write(cache_dir.join(API_CACHE_SUBDIR).join("r.json"), [0u8; 5]).unwrap();
+
+        let entries = cache_entries(cache_dir).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (cache_dir.join("loose.bin"), 20),
+                (cache_dir.join("owner"), 300),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_cache_files_by_age_and_latest_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        for version in ["1.0.0", "2.0.0"] {
+            let dir = cache_dir.join("owner/tool").join(version).join("asset_1");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("tool.tar.gz"), [0u8; 10]).unwrap();
+        }
+        std::fs::create_dir_all(cache_dir.join(API_CACHE_SUBDIR)).unwrap();
+        std::fs::write(cache_dir.join(API_CACHE_SUBDIR).join("r.json"), [0u8; 5]).unwrap();
+        let day = Duration::from_secs(SECONDS_PER_DAY);
+        let old = cache_dir.join("owner/tool/1.0.0/asset_1/tool.tar.gz");
+        let new = cache_dir.join("owner/tool/2.0.0/asset_1/tool.tar.gz");
+        let keep = vec![cache_dir.join("owner/tool/2.0.0")];
+
+        // files written just now are not older than a day
+        let now = SystemTime::now();
+        let files = select_cache_files(cache_dir, Some(day), &[], now).unwrap();
+        assert!(files.is_empty());
+
+        // two days later both are
+        let later = now + 2 * day;
+        let files = select_cache_files(cache_dir, Some(day), &[], later).unwrap();
+        assert_eq!(files, vec![(old.clone(), 10), (new.clone(), 10)]);
+
+        // the newest installed version is spared
+        let files = select_cache_files(cache_dir, None, &keep, now).unwrap();
+        assert_eq!(files, vec![(old.clone(), 10)]);
+        let files = select_cache_files(cache_dir, Some(day), &keep, now).unwrap();
+        assert!(files.is_empty());
+    }
+}
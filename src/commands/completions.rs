@@ -1,27 +1,126 @@
+use anyhow::{bail, Context, Result};
 use clap::CommandFactory;
 use clap_complete::{generate, Shell};
 use clap_complete_nushell::Nushell;
-use std::io;
+use log::info;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 use crate::cli::Cli;
+use crate::commands::enable::shell_name;
 use crate::models::supported_shells::SupportedShell;
+use crate::utils::prompt::confirm;
 
-/// Generate shell completions to stdout
-pub fn generate_completions(shell: SupportedShell) {
+/// Generate shell completions to `out`
+fn write_completions(shell: SupportedShell, out: &mut dyn Write) {
     let mut cmd = Cli::command();
     let bin_name = cmd.get_name().to_string();
 
     match shell {
-        SupportedShell::Bash => generate(Shell::Bash, &mut cmd, &bin_name, &mut io::stdout()),
-        SupportedShell::Elvish => generate(Shell::Elvish, &mut cmd, &bin_name, &mut io::stdout()),
-        SupportedShell::Fish => generate(Shell::Fish, &mut cmd, &bin_name, &mut io::stdout()),
-        SupportedShell::Nushell => generate(Nushell, &mut cmd, &bin_name, &mut io::stdout()),
-        SupportedShell::PowerShell => {
-            generate(Shell::PowerShell, &mut cmd, &bin_name, &mut io::stdout())
-        }
+        SupportedShell::Bash => generate(Shell::Bash, &mut cmd, &bin_name, out),
+        SupportedShell::Elvish => generate(Shell::Elvish, &mut cmd, &bin_name, out),
+        SupportedShell::Fish => generate(Shell::Fish, &mut cmd, &bin_name, out),
+        SupportedShell::Nushell => generate(Nushell, &mut cmd, &bin_name, out),
+        SupportedShell::PowerShell => generate(Shell::PowerShell, &mut cmd, &bin_name, out),
         // Xonsh can source bash completions via its bash foreign function interface.
         // Native xonsh completion generation is not available in clap_complete.
-        SupportedShell::Xonsh => generate(Shell::Bash, &mut cmd, &bin_name, &mut io::stdout()),
-        SupportedShell::Zsh => generate(Shell::Zsh, &mut cmd, &bin_name, &mut io::stdout()),
+        SupportedShell::Xonsh => generate(Shell::Bash, &mut cmd, &bin_name, out),
+        SupportedShell::Zsh => generate(Shell::Zsh, &mut cmd, &bin_name, out),
+    }
+}
+
+/// Generate shell completions to stdout
+pub fn generate_completions(shell: SupportedShell) {
+    write_completions(shell, &mut io::stdout());
+}
+
+/// Returns the file the completion script of `shell` is installed to,
+/// `None` for shells without a completions directory of their own.
+///
+/// Bash completions go to the user directory of the bash-completion package,
+/// which loads them on demand.
+fn completions_file(shell: SupportedShell, home: &Path) -> Option<PathBuf> {
+    match shell {
+        SupportedShell::Bash => {
+            let data_home = std::env::var_os("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .filter(|dir| dir.is_absolute())
+                .unwrap_or_else(|| home.join(".local").join("share"));
+            Some(
+                data_home
+                    .join("bash-completion")
+                    .join("completions")
+                    .join("poof"),
+            )
+        }
+        SupportedShell::Zsh => Some(home.join(".zsh").join("completions").join("_poof")),
+        SupportedShell::Fish => Some(
+            home.join(".config")
+                .join("fish")
+                .join("completions")
+                .join("poof.fish"),
+        ),
+        SupportedShell::Nushell => Some(
+            home.join(".config")
+                .join("nushell")
+                .join("completions")
+                .join("poof.nu"),
+        ),
+        _ => None,
+    }
+}
+
+/// Write the completion script of `shell` to the directory the shell loads
+/// completions from, creating the directory if needed.
+/// The user is asked for confirmation first, unless `yes` is set.
+pub fn install_completions(shell: SupportedShell, yes: bool) -> Result<()> {
+    let home = dirs::home_dir().context("Cannot find $HOME")?;
+    let Some(path) = completions_file(shell, &home) else {
+        bail!(
+            "Installing completions is not supported for {}. \
+            Redirect the output of 'poof completions --shell {}' instead.",
+            shell_name(shell),
+            shell_name(shell)
+        );
+    };
+
+    if !yes {
+        info!(
+            "The {} completion script will be written to {}",
+            shell_name(shell),
+            path.display()
+        );
+        if !confirm()? {
+            info!("Installation of completions cancelled.");
+            return Ok(());
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Cannot create directory {}", parent.display()))?;
+    }
+
+    let mut script: Vec<u8> = Vec::new();
+    write_completions(shell, &mut script);
+    fs::write(&path, script).with_context(|| format!("Cannot write {}", path.display()))?;
+
+    info!(
+        "Completions installed. Restart your shell or run 'source {}'.",
+        path.display()
+    );
+    match shell {
+        // nushell does not load the completions directory by itself
+        SupportedShell::Nushell => info!(
+            "Add 'source {}' to your config.nu to load them.",
+            path.display()
+        ),
+        SupportedShell::Zsh => info!(
+            "Make sure {} is in your fpath before compinit runs in your .zshrc.",
+            path.parent().unwrap_or(&path).display()
+        ),
+        _ => {}
     }
+    Ok(())
 }
@@ -0,0 +1,46 @@
+//! Remove poof's bin directory from PATH, undoing 'enable'
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use log::info;
+
+use crate::commands::enable::{get_config_path, remove_managed_block};
+use crate::models::supported_shells::SupportedShell;
+
+/// Remove the block added by `enable` from the given shell's configuration file.
+///
+/// Nothing is changed when the file has no poof-managed block, for example
+/// when poof has been added to PATH by hand.
+pub fn run(shell: SupportedShell) -> Result<()> {
+    let home = dirs::home_dir().context("Cannot find $HOME")?;
+    let config_path = get_config_path(shell, &home);
+
+    let text = match fs::read_to_string(&config_path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            info!(
+                "{} does not exist, poof is not enabled there.",
+                config_path.display()
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e).with_context(|| format!("Cannot read {}", config_path.display())),
+    };
+
+    let Some(updated) = remove_managed_block(&text) else {
+        info!(
+            "No poof configuration found in {}. Nothing to do.",
+            config_path.display()
+        );
+        return Ok(());
+    };
+    fs::write(&config_path, updated)
+        .with_context(|| format!("Could not write to {}", config_path.display()))?;
+
+    info!(
+        "Removed poof from {}. Open a new terminal for the change to take effect.",
+        config_path.display()
+    );
+    Ok(())
+}
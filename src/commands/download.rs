@@ -1,13 +1,124 @@
 //! Main file handling 'download' command
 
-use anyhow::{Context, Result};
-use log::{debug, info};
-use std::{fs::File, io::copy, path::PathBuf};
+use anyhow::{anyhow, Context, Result};
+use log::{debug, info, warn};
+use std::{
+    fs::{self, File},
+    io::{self, copy, IsTerminal, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+
+use crate::core::http;
+use crate::files::{archives, checksum, filesys};
+use crate::utils::speed::{format_bytes, format_eta, SpeedTracker};
+
+/// Width of the progress bar, in characters.
+const PROGRESS_BAR_WIDTH: usize = 40;
+/// Minimum time between two redraws of the progress bar.
+const PROGRESS_REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+/// Number of times a download answered with 404 is retried.
+const NOT_FOUND_RETRIES: u32 = 3;
+/// Wait before the first retry of a 404, doubled at each following retry.
+const NOT_FOUND_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Renders the progress line `[{bar}] {bytes}/{total_bytes} {speed}/s ETA {eta}`.
+/// The bar stays empty and the total and ETA read `?` when the size is unknown.
+fn progress_line(downloaded: u64, total: Option<u64>, tracker: &SpeedTracker) -> String {
+    let filled = total
+        .filter(|total| *total > 0)
+        .map(|total| {
+            (downloaded.min(total) as f64 / total as f64 * PROGRESS_BAR_WIDTH as f64) as usize
+        })
+        .unwrap_or(0);
+    let eta = total
+        .map(|total| format_eta(tracker.eta_seconds(total, downloaded)))
+        .unwrap_or_else(|| "?".to_string());
+    format!(
+        "[{}{}] {}/{} {}/s ETA {}",
+        "#".repeat(filled),
+        " ".repeat(PROGRESS_BAR_WIDTH - filled),
+        format_bytes(downloaded),
+        total.map(format_bytes).unwrap_or_else(|| "?".to_string()),
+        format_bytes(tracker.bytes_per_sec() as u64),
+        eta
+    )
+}
+
+/// Writer counting the bytes written to `inner` and drawing a progress bar on stderr.
+struct ProgressWriter<W: Write> {
+    inner: W,
+    /// Expected size of the download, from the `Content-Length` header.
+    total: Option<u64>,
+    downloaded: u64,
+    tracker: SpeedTracker,
+    last_draw: Option<Instant>,
+    /// Whether to draw at all, only when stderr is a terminal and info messages are shown.
+    enabled: bool,
+}
+
+impl<W: Write> ProgressWriter<W> {
+    fn new(inner: W, total: Option<u64>) -> Self {
+        Self {
+            inner,
+            total,
+            downloaded: 0,
+            tracker: SpeedTracker::new(),
+            last_draw: None,
+            enabled: io::stderr().is_terminal() && log::log_enabled!(log::Level::Info),
+        }
+    }
+
+    /// Records a speed sample and redraws the bar, at most every [`PROGRESS_REDRAW_INTERVAL`]
+    /// unless `force` is set.
+    fn draw(&mut self, force: bool) {
+        let now = Instant::now();
+        if !self.enabled
+            || (!force
+                && self
+                    .last_draw
+                    .is_some_and(|last| now.duration_since(last) < PROGRESS_REDRAW_INTERVAL))
+        {
+            return;
+        }
+        self.last_draw = Some(now);
+        self.tracker.record(self.downloaded, now);
+        eprint!(
+            "\r{}",
+            progress_line(self.downloaded, self.total, &self.tracker)
+        );
+    }
+
+    /// Draws the final state of the bar and moves to the next line.
+    fn finish(&mut self) {
+        if self.enabled {
+            self.draw(true);
+            eprintln!();
+        }
+    }
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.downloaded += written as u64;
+        self.draw(false);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
 
 /// Download a single release asset to a local directory.
 ///
 /// Fetches `download_url` and writes the response body to `download_to/filename`.
 /// The destination directory is created if it does not already exist.
+/// A 404 is retried a few times with exponential backoff, as assets of a release
+/// that has just been published may not have reached the CDN yet.
 /// Returns the full path of the saved file on success.
 pub fn download_asset(
     filename: &String,
@@ -16,8 +127,27 @@ pub fn download_asset(
 ) -> Result<PathBuf> {
     info!("Downloading {} from {}", filename, download_url);
 
-    let response = reqwest::blocking::get(download_url)
-        .with_context(|| format!("Cannot initiate download from {}", download_url))?;
+    let mut attempt: u32 = 0;
+    let mut response = loop {
+        let response = http::client()?
+            .get(download_url)
+            .send()
+            .map_err(|e| http::request_error(e, download_url))
+            .with_context(|| format!("Cannot initiate download from {}", download_url))?;
+        if response.status() != StatusCode::NOT_FOUND || attempt == NOT_FOUND_RETRIES {
+            break response;
+        }
+        let delay = NOT_FOUND_BACKOFF * 2u32.pow(attempt);
+        attempt += 1;
+        warn!(
+            "{} is not available yet, retrying in {}ms ({}/{})",
+            filename,
+            delay.as_millis(),
+            attempt,
+            NOT_FOUND_RETRIES
+        );
+        std::thread::sleep(delay);
+    };
 
     let status = response.status(); // for borrowing
     if status.is_success() {
@@ -27,16 +157,21 @@ pub fn download_asset(
 
         // Create the file path and open it for writing
         let target_file_path = download_to.join(filename);
-        let mut file = File::create(&target_file_path)
+        let file = File::create(&target_file_path)
             .with_context(|| format!("Cannot create file {}", target_file_path.display()))?;
 
         debug!("Saving to: {}", target_file_path.display());
 
-        // Copy the response body to the file
-        let content = response
-            .bytes()
-            .context("Cannot read download response bytes")?; // Use context
-        copy(&mut content.as_ref(), &mut file).context("Cannot write downloaded data to file")?;
+        // Stream the response body to the file, the timeout applies to each read
+        let mut file = ProgressWriter::new(file, response.content_length());
+        copy(&mut response, &mut file).map_err(|e| {
+            if http::is_timeout_io(&e) {
+                anyhow!(e).context(http::TimeoutError::new())
+            } else {
+                anyhow!(e).context("Cannot write downloaded data to file")
+            }
+        })?;
+        file.finish();
 
         info!("Download complete.\n");
         Ok(target_file_path.clone())
@@ -47,7 +182,8 @@ pub fn download_asset(
             .unwrap_or_else(|_| "Cannot read error body".to_string());
         anyhow::bail!(
             // with bail! macro we early return with error
-            "Download failed! Status: {}. URL: {}. Server response: {}",
+            "Download failed! Asset: {}. Status: {}. URL: {}. Server response: {}",
+            filename,
             status,
             download_url,
             error_body
@@ -57,5 +193,97 @@ pub fn download_asset(
     }
 }
 
+/// Compute the SHA-256 checksum of the downloaded asset `asset_name` at `path`,
+/// and log it so that users can record what they got.
+pub fn log_asset_checksum(asset_name: &str, path: &Path) -> Result<String> {
+    let hash = checksum::sha256_file(path)
+        .with_context(|| format!("Cannot compute the checksum of {}", asset_name))?;
+    info!("SHA-256 of {}: {}", asset_name, hash);
+    Ok(hash)
+}
+
+/// Fetch a small text file, such as a published checksum file, and return its content.
+pub fn fetch_text(url: &str) -> Result<String> {
+    debug!("Fetching {}", url);
+    let response = http::client()?
+        .get(url)
+        .send()
+        .map_err(|e| http::request_error(e, url))?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("Request to {} failed with status: {}", url, status);
+    }
+    response
+        .text()
+        .with_context(|| format!("Cannot read response body from {}", url))
+}
+
+/// Extract the downloaded asset at `asset_path` into `extract_to` and return the
+/// executables it contained, at their final location.
+///
+/// The archive is unpacked into a staging directory first, then its top-level entries
+/// are moved to `extract_to`, so that nothing is overwritten and only the extracted
+/// files are searched for executables. Assets that are not archives, such as bare
+/// binaries, are left as they are and returned as the only executable.
+pub fn extract_asset(asset_path: &PathBuf, extract_to: &Path) -> Result<Vec<PathBuf>> {
+    if archives::get_validated_archive_format(asset_path).is_err() {
+        debug!(
+            "{} is not a supported archive, nothing to extract",
+            asset_path.display()
+        );
+        return Ok(vec![asset_path.clone()]);
+    }
+    let file_name = asset_path
+        .file_name()
+        .ok_or_else(|| anyhow!("Cannot get filename from {}", asset_path.display()))?;
+    let staging = extract_to.join(format!(".{}.extracting", file_name.to_string_lossy()));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .with_context(|| format!("Cannot remove leftover {}", staging.display()))?;
+    }
+    let result = extract_via_staging(asset_path, &staging, extract_to);
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+/// Unpacks `asset_path` into `staging` and moves its content to `extract_to`.
+fn extract_via_staging(
+    asset_path: &PathBuf,
+    staging: &PathBuf,
+    extract_to: &Path,
+) -> Result<Vec<PathBuf>> {
+    archives::extract_to_dir(asset_path, staging)
+        .with_context(|| format!("Cannot extract archive {}", asset_path.display()))?;
+
+    let mut execs: Vec<PathBuf> = filesys::find_exec_files_in_dir(staging, false)
+        .into_iter()
+        .filter_map(|exec| exec.strip_prefix(staging).ok().map(|p| extract_to.join(p)))
+        .collect();
+    execs.sort();
+
+    let entries: Vec<PathBuf> = fs::read_dir(staging)
+        .with_context(|| format!("Cannot read {}", staging.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    // check everything first, not to leave a half-moved archive behind
+    for entry in &entries {
+        let target = extract_to.join(entry.file_name().unwrap_or_default());
+        if target.exists() {
+            anyhow::bail!(
+                "Cannot extract {}: {} already exists",
+                asset_path.display(),
+                target.display()
+            );
+        }
+    }
+    for entry in &entries {
+        let target = extract_to.join(entry.file_name().unwrap_or_default());
+        fs::rename(entry, &target)
+            .with_context(|| format!("Cannot move {} to {}", entry.display(), target.display()))?;
+    }
+    Ok(execs)
+}
+
 #[cfg(test)]
 mod tests;
@@ -49,6 +49,59 @@ fn test_download_asset_http_error() -> Result<()> {
     assert!(err_msg.contains("Download failed!"));
     assert!(err_msg.contains("404 Not Found"));
     assert!(err_msg.contains("Not Found"));
+    assert!(err_msg.contains("error-file.bin"));
+    assert!(err_msg.contains(&download_url));
+
+    Ok(())
+}
+
+#[test]
+fn test_download_asset_retries_not_found() -> Result<()> {
+    let mut server = Server::new();
+    // the first request hits the 404 mock, then mockito moves on to the next
+    // matching mock as its expected hits are used up
+    let not_found = server
+        .mock("GET", "/late-asset")
+        .with_status(404)
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/late-asset")
+        .with_status(200)
+        .with_body("late content")
+        .expect(1)
+        .create();
+
+    let tmp_dir = tempdir()?;
+    let download_to = tmp_dir.path().to_path_buf();
+    let filename = "late-file.bin".to_string();
+    let download_url = format!("{}/late-asset", server.url());
+
+    let result = download_asset(&filename, &download_url, &download_to)?;
+
+    not_found.assert();
+    ok.assert();
+    assert_eq!(fs::read_to_string(result)?, "late content");
+
+    Ok(())
+}
+
+#[test]
+fn test_download_asset_does_not_retry_other_errors() -> Result<()> {
+    let mut server = Server::new();
+    let forbidden = server
+        .mock("GET", "/forbidden-asset")
+        .with_status(403)
+        .expect(1)
+        .create();
+
+    let tmp_dir = tempdir()?;
+    let download_to = tmp_dir.path().to_path_buf();
+    let filename = "forbidden.bin".to_string();
+    let download_url = format!("{}/forbidden-asset", server.url());
+
+    assert!(download_asset(&filename, &download_url, &download_to).is_err());
+    forbidden.assert();
 
     Ok(())
 }
@@ -109,3 +162,92 @@ fn test_download_asset_fs_error() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_progress_line_with_known_size() {
+    let start = Instant::now();
+    let mut tracker = SpeedTracker::new();
+    tracker.record(0, start);
+    tracker.record(1024, start + Duration::from_secs(1));
+
+    let line = progress_line(1024, Some(4096), &tracker);
+    assert_eq!(
+        line,
+        format!(
+            "[{}{}] 1.0 KiB/4.0 KiB 1.0 KiB/s ETA 3s",
+            "#".repeat(10),
+            " ".repeat(30)
+        )
+    );
+}
+
+#[test]
+fn test_progress_line_without_content_length() {
+    let line = progress_line(512, None, &SpeedTracker::new());
+    assert_eq!(
+        line,
+        format!("[{}] 512 B/? 0 B/s ETA ?", " ".repeat(PROGRESS_BAR_WIDTH))
+    );
+}
+
+/// Writes a tar.gz archive at `path` holding a `tool/tool` shell script and a `tool/README`.
+fn write_tool_archive(path: &Path) -> Result<()> {
+    let encoder =
+        flate2::write::GzEncoder::new(fs::File::create(path)?, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for (name, content, mode) in [
+        ("tool/tool", "#!/bin/sh\necho tool\n", 0o755),
+        ("tool/README", "read me\n", 0o644),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(mode);
+        header.set_cksum();
+        builder.append_data(&mut header, name, content.as_bytes())?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+#[test]
+fn test_extract_asset_reports_executables() -> Result<()> {
+    let tmp_dir = tempdir()?;
+    let archive = tmp_dir.path().join("tool-linux-x86_64.tar.gz");
+    write_tool_archive(&archive)?;
+
+    let execs = extract_asset(&archive, tmp_dir.path())?;
+
+    assert_eq!(execs, vec![tmp_dir.path().join("tool").join("tool")]);
+    assert!(tmp_dir.path().join("tool").join("README").is_file());
+    // only the archive and its extracted content are left
+    assert_eq!(fs::read_dir(tmp_dir.path())?.count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_extract_asset_does_not_overwrite() -> Result<()> {
+    let tmp_dir = tempdir()?;
+    let archive = tmp_dir.path().join("tool-linux-x86_64.tar.gz");
+    write_tool_archive(&archive)?;
+    fs::create_dir(tmp_dir.path().join("tool"))?;
+
+    let err = extract_asset(&archive, tmp_dir.path()).unwrap_err();
+
+    assert!(err.to_string().contains("already exists"), "{}", err);
+    assert_eq!(fs::read_dir(tmp_dir.path().join("tool"))?.count(), 0);
+    assert_eq!(fs::read_dir(tmp_dir.path())?.count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_extract_asset_leaves_bare_binaries() -> Result<()> {
+    let tmp_dir = tempdir()?;
+    let binary = tmp_dir.path().join("tool-linux-x86_64");
+    fs::write(&binary, "#!/bin/sh\necho tool\n")?;
+
+    let execs = extract_asset(&binary, tmp_dir.path())?;
+
+    assert_eq!(execs, vec![binary]);
+    assert_eq!(fs::read_dir(tmp_dir.path())?.count(), 1);
+    Ok(())
+}
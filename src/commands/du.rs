@@ -0,0 +1,141 @@
+//! Main file handling 'du' command
+
+use anyhow::{Context, Result};
+use log::{info, warn};
+use std::path::Path;
+
+use crate::cli::DuArgs;
+use crate::commands::list::list_installed_spells;
+use crate::files::{datadirs, filesys};
+use crate::models::spell::Spell;
+use crate::output;
+use crate::utils::speed::format_bytes;
+
+/// Disk space used by the installed versions of a repository.
+#[derive(Debug, PartialEq, Eq)]
+struct RepoUsage {
+    /// Slug of the repository.
+    repo: String,
+    /// Size in bytes of each installed version, in version order.
+    versions: Vec<(String, u64)>,
+}
+
+impl RepoUsage {
+    /// Total size in bytes across all versions.
+    fn total(&self) -> u64 {
+        self.versions.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// Computes the disk usage of each of `spells`, largest repository first.
+/// Versions that cannot be read are skipped with a warning.
+fn collect_usage(data_dir: &Path, spells: &[Spell]) -> Vec<RepoUsage> {
+    let mut usage: Vec<RepoUsage> = spells
+        .iter()
+        .map(|spell| {
+            let versions = spell
+                .get_versions()
+                .iter()
+                .filter_map(|version| {
+                    let version = version.to_string();
+                    let dir = datadirs::get_binary_nest(data_dir, spell.get_name(), &version);
+                    match filesys::dir_total_size(&dir) {
+                        Ok(size) => Some((version, size)),
+                        Err(e) => {
+                            warn!("Cannot read {}, skipping it: {}", dir.display(), e);
+                            None
+                        }
+                    }
+                })
+                .collect();
+            RepoUsage {
+                repo: spell.get_name().to_string(),
+                versions,
+            }
+        })
+        .collect();
+    // stable sort, repositories of the same size stay in alphabetical order
+    usage.sort_by_key(|repo| std::cmp::Reverse(repo.total()));
+    usage
+}
+
+/// Print the disk space used by each installed repository, largest first.
+/// With `--verbose` the size of each installed version is printed too.
+pub fn run_du(args: &DuArgs) -> Result<()> {
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let usage = collect_usage(&data_dir, &list_installed_spells());
+    if usage.is_empty() {
+        info!("No installed binaries found.");
+        return Ok(());
+    }
+
+    output!("");
+    output!("{:<40}\t{:>10}", "Repository", "Size");
+    output!("{:<40}\t{:>10}", "----------", "----");
+    for repo in &usage {
+        output!("{:<40}\t{:>10}", repo.repo, format_bytes(repo.total()));
+        if args.verbose {
+            for (version, size) in &repo.versions {
+                output!("  {:<38}\t{:>10}", version, format_bytes(*size));
+            }
+        }
+    }
+    let total: u64 = usage.iter().map(RepoUsage::total).sum();
+    output!("{:<40}\t{:>10}", "----------", "----");
+    output!("{:<40}\t{:>10}", "Total", format_bytes(total));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn spell(repo: &str, versions: &[&str]) -> Spell {
+        Spell::new_as_string(
+            repo.to_string(),
+            versions.iter().map(|v| v.to_string()).collect(),
+        )
+    }
+
+    fn install(data_dir: &Path, repo: &str, version: &str, size: usize) {
+        let dir = datadirs::get_binary_nest(data_dir, repo, version);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("bin"), vec![0u8; size]).unwrap();
+    }
+
+    #[test]
+    fn test_collect_usage_sorts_largest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path();
+        install(data_dir, "owner/small", "1.0.0", 10);
+        install(data_dir, "owner/big", "1.0.0", 100);
+        install(data_dir, "owner/big", "2.0.0", 50);
+
+        let usage = collect_usage(
+            data_dir,
+            &[
+                spell("owner/small", &["1.0.0"]),
+                spell("owner/big", &["1.0.0", "2.0.0"]),
+            ],
+        );
+        assert_eq!(usage[0].repo, "owner/big");
+        assert_eq!(usage[0].total(), 150);
+        assert_eq!(
+            usage[0].versions,
+            vec![("1.0.0".to_string(), 100), ("2.0.0".to_string(), 50)]
+        );
+        assert_eq!(usage[1].total(), 10);
+    }
+
+    #[test]
+    fn test_collect_usage_skips_unreadable_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path();
+        install(data_dir, "owner/tool", "1.0.0", 10);
+
+        let usage = collect_usage(data_dir, &[spell("owner/tool", &["1.0.0", "2.0.0"])]);
+        assert_eq!(usage[0].versions, vec![("1.0.0".to_string(), 10)]);
+    }
+}
@@ -2,7 +2,7 @@
 //! Supports all shells: bash, zsh, fish, elvish, nushell (or nu), powershell (or pwsh), xonsh
 
 use std::path::Path;
-use std::{fs, io::Write, path::PathBuf};
+use std::{fs, path::PathBuf};
 
 use anyhow::{Context, Result};
 use log::info;
@@ -11,7 +11,7 @@ use crate::files::datadirs::get_bin_dir;
 use crate::models::supported_shells::SupportedShell;
 
 /// Get the configuration file path for a given shell
-fn get_config_path(shell: SupportedShell, home: &Path) -> PathBuf {
+pub(crate) fn get_config_path(shell: SupportedShell, home: &Path) -> PathBuf {
     match shell {
         SupportedShell::Bash => home.join(".bashrc"),
         SupportedShell::Zsh => home.join(".zshrc"),
@@ -19,17 +19,24 @@ fn get_config_path(shell: SupportedShell, home: &Path) -> PathBuf {
         SupportedShell::Elvish => home.join(".config").join("elvish").join("rc.elv"),
         SupportedShell::Nushell => home.join(".config").join("nushell").join("env.nu"),
         SupportedShell::PowerShell => {
+            // On Windows: ~\Documents\PowerShell\Microsoft.PowerShell_profile.ps1
             // On Linux/macOS: ~/.config/powershell/Microsoft.PowerShell_profile.ps1
-            home.join(".config")
-                .join("powershell")
-                .join("Microsoft.PowerShell_profile.ps1")
+            if cfg!(target_os = "windows") {
+                home.join("Documents")
+                    .join("PowerShell")
+                    .join("Microsoft.PowerShell_profile.ps1")
+            } else {
+                home.join(".config")
+                    .join("powershell")
+                    .join("Microsoft.PowerShell_profile.ps1")
+            }
         }
         SupportedShell::Xonsh => home.join(".xonshrc"),
     }
 }
 
 /// Get the shell name as a string for display purposes
-fn shell_name(shell: SupportedShell) -> &'static str {
+pub(crate) fn shell_name(shell: SupportedShell) -> &'static str {
     match shell {
         SupportedShell::Bash => "bash",
         SupportedShell::Zsh => "zsh",
@@ -41,85 +48,119 @@ fn shell_name(shell: SupportedShell) -> &'static str {
     }
 }
 
-/// Generate the content to add to the shell configuration file
-fn generate_config_content(shell: SupportedShell, bin_dir: &str) -> String {
+/// First line of the block poof manages in shell configuration files.
+const BLOCK_START: &str = "# >>> poof >>>";
+/// Last line of the block poof manages in shell configuration files.
+const BLOCK_END: &str = "# <<< poof <<<";
+/// Marker written by older poof versions above their single configuration line.
+const LEGACY_MARKER: &str = "# added by poof";
+
+/// Generate the line that adds the bin directory to PATH for the given shell
+fn generate_config_line(shell: SupportedShell, bin_dir: &str) -> String {
     match shell {
         SupportedShell::Bash | SupportedShell::Zsh => {
             // For eval-based shells, use dynamic approach
-            format!(
-                "\n# added by poof\neval \"$(poof init --shell {})\"",
-                shell_name(shell)
-            )
+            format!("eval \"$(poof init --shell {})\"", shell_name(shell))
         }
         SupportedShell::Elvish => {
             // Elvish-native PATH update
             // Notice the missing '$' before the round brackets
-            format!(
-                "\n# added by poof\neval \"(poof init --shell {})\"",
-                shell_name(shell)
-            )
+            format!("eval \"(poof init --shell {})\"", shell_name(shell))
         }
         SupportedShell::PowerShell => {
             // PowerShell uses Invoke-Expression
-            "\n# added by poof\nInvoke-Expression (& poof init --shell powershell)".to_string()
+            "Invoke-Expression (& poof init --shell powershell)".to_string()
         }
         SupportedShell::Fish => {
             // Fish uses direct command
-            format!("\n# added by poof\nfish_add_path -p \"{}\"", bin_dir)
+            format!("fish_add_path -p \"{}\"", bin_dir)
         }
         SupportedShell::Nushell => {
             // Nushell uses direct assignment
-            format!(
-                "\n# added by poof\n$env.PATH = ($env.PATH | prepend \"{}\")",
-                bin_dir
-            )
+            format!("$env.PATH = ($env.PATH | prepend \"{}\")", bin_dir)
         }
         SupportedShell::Xonsh => {
             // Xonsh uses Python-like syntax
-            format!("\n# added by poof\n$PATH.insert(0, \"{}\")", bin_dir)
+            format!("$PATH.insert(0, \"{}\")", bin_dir)
         }
     }
 }
 
-/// Check if poof is already enabled in the config file
-fn is_already_enabled(config_path: &PathBuf, shell: SupportedShell) -> bool {
-    if let Ok(text) = fs::read_to_string(config_path) {
-        // Check for the marker comment
-        if text.contains("# added by poof") {
-            return true;
+/// Generate the managed block to add to the shell configuration file
+fn generate_config_content(shell: SupportedShell, bin_dir: &str) -> String {
+    format!(
+        "{}\n{}\n{}",
+        BLOCK_START,
+        generate_config_line(shell, bin_dir),
+        BLOCK_END
+    )
+}
+
+/// Returns the byte range of the poof-managed block in `text`, from its start marker
+/// to the end of its end marker line. The configuration written by older poof
+/// versions, a marker line followed by the configuration line, counts as a block.
+fn managed_block_range(text: &str) -> Option<(usize, usize)> {
+    if let Some(start) = text.find(BLOCK_START) {
+        if let Some(end_offset) = text[start..].find(BLOCK_END) {
+            return Some((start, start + end_offset + BLOCK_END.len()));
         }
+    }
+    let start = text.find(LEGACY_MARKER)?;
+    let after_marker = start + LEGACY_MARKER.len();
+    let end = text[after_marker..]
+        .strip_prefix('\n')
+        .map(|rest| after_marker + 1 + rest.find('\n').unwrap_or(rest.len()))
+        .unwrap_or(after_marker);
+    Some((start, end))
+}
 
-        // Also check for shell-specific patterns
-        match shell {
-            SupportedShell::Bash | SupportedShell::Zsh | SupportedShell::Elvish => {
-                // Check for eval pattern
-                if text.contains(&format!("poof init --shell {}", shell_name(shell))) {
-                    return true;
-                }
-            }
-            SupportedShell::PowerShell => {
-                if text.contains("poof init --shell powershell") {
-                    return true;
-                }
-            }
-            SupportedShell::Fish => {
-                if text.contains("fish_add_path") && text.contains("poof") {
-                    return true;
-                }
-            }
-            SupportedShell::Nushell => {
-                if text.contains("$env.PATH") && text.contains("poof") {
-                    return true;
-                }
-            }
-            SupportedShell::Xonsh => {
-                if text.contains("$PATH.insert") && text.contains("poof") {
-                    return true;
-                }
-            }
+/// Returns `text` with the poof-managed block set to `block`.
+///
+/// An existing block is replaced in place, as is the configuration written by
+/// older poof versions. Otherwise the block is appended. Returns `None` when
+/// `text` has no block but poof is enabled by hand, which is left alone.
+fn upsert_managed_block(text: &str, block: &str, shell: SupportedShell) -> Option<String> {
+    if let Some((start, end)) = managed_block_range(text) {
+        return Some(format!("{}{}{}", &text[..start], block, &text[end..]));
+    }
+    if is_already_enabled(text, shell) {
+        return None;
+    }
+    let separator = match text {
+        "" => "",
+        t if t.ends_with("\n\n") => "",
+        t if t.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    Some(format!("{}{}{}\n", text, separator, block))
+}
+
+/// Returns `text` without the poof-managed block, or `None` when there is none.
+pub(crate) fn remove_managed_block(text: &str) -> Option<String> {
+    let (start, mut end) = managed_block_range(text)?;
+    // drop the line break ending the block, and the blank line added before it
+    if text[end..].starts_with('\n') {
+        end += 1;
+    }
+    let mut before = &text[..start];
+    if before.ends_with("\n\n") {
+        before = &before[..before.len() - 1];
+    }
+    Some(format!("{}{}", before, &text[end..]))
+}
+
+/// Check if poof has been enabled by hand in the config file content
+fn is_already_enabled(text: &str, shell: SupportedShell) -> bool {
+    match shell {
+        SupportedShell::Bash | SupportedShell::Zsh | SupportedShell::Elvish => {
+            // Check for eval pattern
+            text.contains(&format!("poof init --shell {}", shell_name(shell)))
         }
+        SupportedShell::PowerShell => text.contains("poof init --shell powershell"),
+        SupportedShell::Fish => text.contains("fish_add_path") && text.contains("poof"),
+        SupportedShell::Nushell => text.contains("$env.PATH") && text.contains("poof"),
+        SupportedShell::Xonsh => text.contains("$PATH.insert") && text.contains("poof"),
     }
-    false
 }
 
 /// Get the reload instruction for a given shell
@@ -146,9 +187,11 @@ fn get_reload_instruction(shell: SupportedShell, config_path: &Path) -> String {
 
 /// Persistently add poof's bin directory to the given shell's configuration file.
 ///
-/// The function detects the appropriate config file for `shell`, appends the
-/// necessary snippet to it (unless poof is already enabled), and prints the
-/// command needed to reload the shell without opening a new terminal session.
+/// The function detects the appropriate config file for `shell` and writes the
+/// necessary snippet to it, inside a block delimited by marker comments. Running
+/// it again replaces the block in place, so the file never gets duplicate entries.
+/// It then prints the command needed to reload the shell without opening a new
+/// terminal session.
 pub fn run(shell: SupportedShell) -> Result<()> {
     /* 1 ─ get the directory that holds poof's executables */
     let bin_dir = get_bin_dir().context("Cannot locate bin directory")?;
@@ -159,13 +202,25 @@ pub fn run(shell: SupportedShell) -> Result<()> {
 
     let config_path = get_config_path(shell, &home);
 
-    /* 3 ─ if poof is already enabled, do nothing */
-    if is_already_enabled(&config_path, shell) {
+    /* 3 ─ read the current configuration, if any */
+    let text = match fs::read_to_string(&config_path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Cannot read {}", config_path.display())),
+    };
+
+    /* 4 ─ add or replace the managed block, unless poof is enabled by hand */
+    let content = generate_config_content(shell, &bin);
+    let Some(updated) = upsert_managed_block(&text, &content, shell) else {
+        info!("poof already enabled in {}", config_path.display());
+        return Ok(());
+    };
+    if updated == text {
         info!("poof already enabled in {}", config_path.display());
         return Ok(());
     }
 
-    /* 4 ─ create parent directories if needed */
+    /* 5 ─ create parent directories if needed */
     if let Some(parent) = config_path.parent() {
         if !parent.exists() {
             fs::create_dir_all(parent)
@@ -173,16 +228,8 @@ pub fn run(shell: SupportedShell) -> Result<()> {
         }
     }
 
-    /* 5 ─ append the configuration content */
-    let content = generate_config_content(shell, &bin);
-
-    let mut file = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&config_path)
-        .with_context(|| format!("Cannot open {}", config_path.display()))?;
-
-    writeln!(file, "{}", content)
+    /* 6 ─ write the configuration content */
+    fs::write(&config_path, updated)
         .with_context(|| format!("Could not write to {}", config_path.display()))?;
 
     let reload_cmd = get_reload_instruction(shell, &config_path);
@@ -225,10 +272,16 @@ mod tests {
             get_config_path(SupportedShell::Nushell, &home),
             home.join(".config/nushell/env.nu")
         );
+        #[cfg(not(target_os = "windows"))]
         assert_eq!(
             get_config_path(SupportedShell::PowerShell, &home),
             home.join(".config/powershell/Microsoft.PowerShell_profile.ps1")
         );
+        #[cfg(target_os = "windows")]
+        assert_eq!(
+            get_config_path(SupportedShell::PowerShell, &home),
+            home.join("Documents/PowerShell/Microsoft.PowerShell_profile.ps1")
+        );
         assert_eq!(
             get_config_path(SupportedShell::Xonsh, &home),
             home.join(".xonshrc")
@@ -252,18 +305,18 @@ mod tests {
 
         // Test bash (eval)
         let bash_content = generate_config_content(SupportedShell::Bash, bin_dir);
-        assert!(bash_content.contains("# added by poof"));
+        assert!(bash_content.starts_with(BLOCK_START));
         assert!(bash_content.contains("eval \"$(poof init --shell bash)\""));
 
         // Test zsh (eval)
         let zsh_content = generate_config_content(SupportedShell::Zsh, bin_dir);
-        assert!(zsh_content.contains("# added by poof"));
+        assert!(zsh_content.starts_with(BLOCK_START));
         assert!(zsh_content.contains("eval \"$(poof init --shell zsh)\""));
 
         // Test elvish (eval)
         // Notice the missing '$' before the round brackets
         let elvish_content = generate_config_content(SupportedShell::Elvish, bin_dir);
-        assert!(elvish_content.contains("# added by poof"));
+        assert!(elvish_content.starts_with(BLOCK_START));
         assert!(elvish_content.contains("eval \"(poof init --shell elvish)\""));
     }
 
@@ -273,26 +326,26 @@ mod tests {
 
         // Test fish (direct)
         let fish_content = generate_config_content(SupportedShell::Fish, bin_dir);
-        assert!(fish_content.contains("# added by poof"));
+        assert!(fish_content.starts_with(BLOCK_START));
         assert!(fish_content.contains("fish_add_path -p"));
         assert!(fish_content.contains(bin_dir));
 
         // Test nushell (direct)
         let nushell_content = generate_config_content(SupportedShell::Nushell, bin_dir);
-        assert!(nushell_content.contains("# added by poof"));
+        assert!(nushell_content.starts_with(BLOCK_START));
         assert!(nushell_content.contains("$env.PATH"));
         assert!(nushell_content.contains("prepend"));
         assert!(nushell_content.contains(bin_dir));
 
         // Test xonsh (direct)
         let xonsh_content = generate_config_content(SupportedShell::Xonsh, bin_dir);
-        assert!(xonsh_content.contains("# added by poof"));
+        assert!(xonsh_content.starts_with(BLOCK_START));
         assert!(xonsh_content.contains("$PATH.insert"));
         assert!(xonsh_content.contains(bin_dir));
 
         // Test powershell (Invoke-Expression)
         let pwsh_content = generate_config_content(SupportedShell::PowerShell, bin_dir);
-        assert!(pwsh_content.contains("# added by poof"));
+        assert!(pwsh_content.starts_with(BLOCK_START));
         assert!(pwsh_content.contains("Invoke-Expression"));
         assert!(pwsh_content.contains("poof init --shell powershell"));
     }
@@ -335,4 +388,66 @@ mod tests {
         assert!(xonsh_reload.contains("source"));
         assert!(xonsh_reload.contains(".xonshrc"));
     }
+
+    #[test]
+    fn test_upsert_managed_block_appends_once() {
+        let block = generate_config_content(SupportedShell::Bash, "/bin");
+        let text = "alias ll='ls -l'\n";
+
+        let once = upsert_managed_block(text, &block, SupportedShell::Bash).unwrap();
+        assert_eq!(once, format!("alias ll='ls -l'\n\n{}\n", block));
+
+        let twice = upsert_managed_block(&once, &block, SupportedShell::Bash).unwrap();
+        assert_eq!(twice, once);
+    }
+
+    #[test]
+    fn test_upsert_managed_block_replaces_in_place() {
+        let old = generate_config_content(SupportedShell::Fish, "/old/bin");
+        let new = generate_config_content(SupportedShell::Fish, "/new/bin");
+        let text = format!("set -x A 1\n{}\nset -x B 2\n", old);
+
+        let updated = upsert_managed_block(&text, &new, SupportedShell::Fish).unwrap();
+        assert_eq!(updated, format!("set -x A 1\n{}\nset -x B 2\n", new));
+    }
+
+    #[test]
+    fn test_upsert_managed_block_migrates_legacy_marker() {
+        let block = generate_config_content(SupportedShell::Zsh, "/bin");
+        let text = "export A=1\n\n# added by poof\neval \"$(poof init --shell zsh)\"\nexport B=2\n";
+
+        let updated = upsert_managed_block(text, &block, SupportedShell::Zsh).unwrap();
+        assert_eq!(updated, format!("export A=1\n\n{}\nexport B=2\n", block));
+    }
+
+    #[test]
+    fn test_upsert_managed_block_leaves_manual_setup_alone() {
+        let block = generate_config_content(SupportedShell::Bash, "/bin");
+        let text = "eval \"$(poof init --shell bash)\"\n";
+        assert_eq!(
+            upsert_managed_block(text, &block, SupportedShell::Bash),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_managed_block() {
+        let block = generate_config_content(SupportedShell::Bash, "/bin");
+        let original = "alias ll='ls -l'\n";
+        let enabled = upsert_managed_block(original, &block, SupportedShell::Bash).unwrap();
+        assert_eq!(remove_managed_block(&enabled).unwrap(), original);
+
+        let between = format!("export A=1\n{}\nexport B=2\n", block);
+        assert_eq!(
+            remove_managed_block(&between).unwrap(),
+            "export A=1\nexport B=2\n"
+        );
+    }
+
+    #[test]
+    fn test_remove_managed_block_legacy_and_missing() {
+        let legacy = "export A=1\n\n# added by poof\neval \"$(poof init --shell zsh)\"\n";
+        assert_eq!(remove_managed_block(legacy).unwrap(), "export A=1\n");
+        assert_eq!(remove_managed_block("export A=1\n"), None);
+    }
 }
@@ -0,0 +1,87 @@
+//! Main file handling 'env-vars' command
+
+use crate::cli::EnvVarsArgs;
+use crate::constants::ENV_VARS;
+use crate::output;
+
+/// Number of characters of a secret value shown before masking the rest.
+const VISIBLE_SECRET_CHARS: usize = 4;
+
+/// Returns `value` as it can be printed: the value of a token variable keeps
+/// only its first characters visible.
+fn display_value(name: &str, value: &str) -> String {
+    if !name.ends_with("_TOKEN") {
+        return value.to_string();
+    }
+    let visible: String = value.chars().take(VISIBLE_SECRET_CHARS).collect();
+    format!("{}****", visible)
+}
+
+/// Print every environment variable poof recognises, with its current value,
+/// its default and what it does. With `--unset-only` the variables currently
+/// set are left out.
+pub fn run_env_vars(args: &EnvVarsArgs) {
+    output!("");
+    output!(
+        "{:<28}\t{:<28}\t{:<28}\t{}",
+        "Name",
+        "Value",
+        "Default",
+        "Description"
+    );
+    output!(
+        "{:<28}\t{:<28}\t{:<28}\t{}",
+        "----",
+        "-----",
+        "-------",
+        "-----------"
+    );
+    for (name, default, description) in ENV_VARS {
+        let value = std::env::var(name).ok().filter(|v| !v.is_empty());
+        if args.unset_only && value.is_some() {
+            continue;
+        }
+        let value = value
+            .map(|v| display_value(name, &v))
+            .unwrap_or_else(|| "-".to_string());
+        let default = if default.is_empty() { "-" } else { default };
+        output!(
+            "{:<28}\t{:<28}\t{:<28}\t{}",
+            name,
+            value,
+            default,
+            description
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+
+    #[test]
+    fn test_display_value_masks_tokens() {
+        assert_eq!(display_value(GITHUB_TOKEN_ENV, "ghp_secret123"), "ghp_****");
+        assert_eq!(display_value(GITEA_TOKEN_ENV, "ab"), "ab****");
+        assert_eq!(display_value(TIMEOUT_ENV, "30"), "30");
+    }
+
+    #[test]
+    fn test_env_vars_defaults_match_constants() {
+        let default_of = |name: &str| {
+            ENV_VARS
+                .iter()
+                .find(|(n, _, _)| *n == name)
+                .map(|(_, default, _)| *default)
+                .unwrap()
+        };
+        assert_eq!(default_of(TIMEOUT_ENV), DEFAULT_TIMEOUT_SECONDS.to_string());
+        assert_eq!(
+            default_of(API_CACHE_TTL_ENV),
+            DEFAULT_API_CACHE_TTL_SECONDS.to_string()
+        );
+        assert_eq!(default_of(GITHUB_API_URL_ENV), GITHUB_API_URL);
+        assert_eq!(default_of(GITLAB_API_URL_ENV), GITLAB_API_URL);
+    }
+}
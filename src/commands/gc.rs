@@ -0,0 +1,201 @@
+//! Main file handling 'gc' command
+
+use anyhow::{Context, Result};
+use log::{debug, error, info};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::cli::GcArgs;
+use crate::commands::list::list_installed_spells;
+use crate::commands::repair::recorded_bin_dirs;
+use crate::commands::status::{collect_statuses, BinaryStatus, LinkStatus};
+use crate::commands::uninstall::clean_broken_symlinks;
+use crate::files::{datadirs, filesys, pins};
+use crate::models::spell::Spell;
+use crate::utils::prompt::confirm;
+use crate::utils::semver::{SemverSort, SemverStringConversion};
+
+/// Returns the `(repo, version)` pairs some symlink in `rows` points to.
+pub(crate) fn linked_versions(rows: &[BinaryStatus]) -> HashSet<(String, String)> {
+    rows.iter()
+        .filter(|r| matches!(r.status, LinkStatus::Ok | LinkStatus::Broken))
+        .map(|r| (r.repo.clone(), r.version.clone()))
+        .collect()
+}
+
+/// Returns the `(repo, version)` pairs to remove: versions that are not linked,
+/// not pinned, and not among the `keep_latest` newest versions of their repository.
+pub(crate) fn plan_gc(
+    data_dir: &Path,
+    spells: &[Spell],
+    linked: &HashSet<(String, String)>,
+    keep_latest: usize,
+) -> Vec<(String, String)> {
+    let mut stale: Vec<(String, String)> = Vec::new();
+    for spell in spells {
+        let repo = spell.get_name();
+        let pinned = pins::pinned_version(data_dir, repo);
+        let mut versions = spell.get_versions().to_string_vec();
+        versions.sort_semver();
+        let candidates = versions.len().saturating_sub(keep_latest);
+        for version in versions.into_iter().take(candidates) {
+            if linked.contains(&(repo.to_string(), version.clone()))
+                || pinned.as_ref() == Some(&version)
+            {
+                continue;
+            }
+            stale.push((repo.to_string(), version));
+        }
+    }
+    stale
+}
+
+/// Delete `dir`, refusing anything outside of `data_dir`.
+/// Returns `true` if the directory was deleted, `false` if it was refused.
+pub(crate) fn remove_version_dir(dir: &Path, data_dir: &Path) -> Result<bool> {
+    // Canonicalize both paths to handle symlinked data directories consistently.
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let data_dir = data_dir
+        .canonicalize()
+        .unwrap_or_else(|_| data_dir.to_path_buf());
+
+    if !dir.starts_with(&data_dir) || dir == data_dir {
+        debug!("Refusing to delete non-data path: {}", dir.display());
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(&dir)
+        .with_context(|| format!("Cannot delete directory {}", dir.display()))?;
+    Ok(true)
+}
+
+/// Returns `bin_dir` and the other bin directories `spells` are linked into,
+/// along with the status of the symlinks found in all of them.
+pub(crate) fn scan_bin_dirs(
+    data_dir: &Path,
+    bin_dir: &Path,
+    spells: &[Spell],
+) -> (Vec<PathBuf>, Vec<BinaryStatus>) {
+    let mut bin_dirs = vec![bin_dir.to_path_buf()];
+    bin_dirs.extend(recorded_bin_dirs(data_dir, bin_dir, spells));
+    let rows = bin_dirs
+        .iter()
+        .flat_map(|dir| collect_statuses(dir, data_dir, &[]))
+        .collect();
+    (bin_dirs, rows)
+}
+
+/// Outcome of [`remove_versions`].
+#[derive(Debug, Default)]
+pub(crate) struct Removal {
+    /// The `(repo, version)` pairs whose directory has been deleted.
+    pub removed: Vec<(String, String)>,
+    /// How many version directories could not be deleted.
+    pub failed: usize,
+    /// Total size in bytes of the deleted directories.
+    pub reclaimed: u64,
+}
+
+/// Delete the directories of the `(repo, version)` pairs in `stale`.
+/// Failures are logged and counted, the remaining versions are still removed.
+pub(crate) fn remove_versions(data_dir: &Path, stale: &[(String, String)]) -> Removal {
+    let mut removal = Removal::default();
+    for (repo, version) in stale {
+        let dir: PathBuf = datadirs::get_binary_nest(data_dir, repo, version);
+        let size = filesys::dir_total_size(&dir).unwrap_or(0);
+        match remove_version_dir(&dir, data_dir) {
+            Ok(true) => {
+                debug!("Removed {}", dir.display());
+                removal.removed.push((repo.clone(), version.clone()));
+                removal.reclaimed += size;
+            }
+            Ok(false) => {
+                error!("Refusing to delete {}", dir.display());
+                removal.failed += 1;
+            }
+            Err(e) => {
+                error!("{:#}", e);
+                removal.failed += 1;
+            }
+        }
+    }
+    removal
+}
+
+/// Remove the oldest installed versions of `spell`, keeping the `keep` newest ones.
+///
+/// Like `gc`, versions a symlink points to and the pinned version are never removed,
+/// so more than `keep` versions may be left. Returns the removed versions.
+pub(crate) fn prune_versions(spell: &Spell, keep: usize) -> Result<Vec<String>> {
+    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory path")?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let spells = std::slice::from_ref(spell);
+    let (bin_dirs, rows) = scan_bin_dirs(&data_dir, &bin_dir, spells);
+    let stale = plan_gc(&data_dir, spells, &linked_versions(&rows), keep);
+    let removal = remove_versions(&data_dir, &stale);
+    for dir in &bin_dirs {
+        clean_broken_symlinks(dir)?;
+    }
+    Ok(removal
+        .removed
+        .into_iter()
+        .map(|(_, version)| version)
+        .collect())
+}
+
+/// Remove the installed versions that no symlink points to.
+///
+/// The `--keep-latest` newest versions of each repository, and pinned versions,
+/// are always kept. The versions to remove are listed and the user is prompted
+/// for confirmation, unless `--yes` is set. With `--dry-run` they are only listed.
+///
+/// Returns exit code 1 when some version cannot be removed.
+pub fn run_gc(args: &GcArgs) -> Result<ExitCode> {
+    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory path")?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let spells = list_installed_spells();
+    let (_, rows) = scan_bin_dirs(&data_dir, &bin_dir, &spells);
+    let stale = plan_gc(
+        &data_dir,
+        &spells,
+        &linked_versions(&rows),
+        args.keep_latest,
+    );
+
+    if stale.is_empty() {
+        info!("No unused versions found. Nothing to do.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let verb = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removing"
+    };
+    info!("{} {} unused version(s):", verb, stale.len());
+    for (repo, version) in &stale {
+        info!("  {} {}", repo, version);
+    }
+    if args.dry_run {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // Skip confirmation if -y flag is set
+    if !args.yes && !confirm()? {
+        info!("Garbage collection cancelled.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let removal = remove_versions(&data_dir, &stale);
+    if removal.failed > 0 {
+        error!("{} version(s) could not be removed.", removal.failed);
+        return Ok(ExitCode::FAILURE);
+    }
+    info!("Removed {} unused version(s).", stale.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn spell(repo: &str, versions: &[&str]) -> Spell {
+    Spell::new_as_string(
+        repo.to_string(),
+        versions.iter().map(|v| v.to_string()).collect(),
+    )
+}
+
+fn linked(pairs: &[(&str, &str)]) -> HashSet<(String, String)> {
+    pairs
+        .iter()
+        .map(|(r, v)| (r.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_plan_gc_keeps_linked_and_latest() {
+    let data_dir = TempDir::new().unwrap();
+    let spells = vec![spell("owner/tool", &["1.0.0", "1.10.0", "1.2.0", "2.0.0"])];
+
+    let stale = plan_gc(
+        data_dir.path(),
+        &spells,
+        &linked(&[("owner/tool", "1.2.0")]),
+        1,
+    );
+    assert_eq!(
+        stale,
+        vec![
+            ("owner/tool".to_string(), "1.0.0".to_string()),
+            ("owner/tool".to_string(), "1.10.0".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_gc_keep_latest() {
+    let data_dir = TempDir::new().unwrap();
+    let spells = vec![spell("owner/tool", &["1.0.0", "2.0.0", "3.0.0"])];
+
+    let stale = plan_gc(data_dir.path(), &spells, &HashSet::new(), 2);
+    assert_eq!(stale, vec![("owner/tool".to_string(), "1.0.0".to_string())]);
+
+    let stale = plan_gc(data_dir.path(), &spells, &HashSet::new(), 0);
+    assert_eq!(stale.len(), 3);
+
+    let stale = plan_gc(data_dir.path(), &spells, &HashSet::new(), 5);
+    assert!(stale.is_empty());
+}
+
+#[test]
+fn test_plan_gc_keeps_pinned() {
+    let data_dir = TempDir::new().unwrap();
+    pins::save_pin(data_dir.path(), "owner/tool", "1.0.0").unwrap();
+    let spells = vec![spell("owner/tool", &["1.0.0", "2.0.0"])];
+
+    let stale = plan_gc(data_dir.path(), &spells, &HashSet::new(), 0);
+    assert_eq!(stale, vec![("owner/tool".to_string(), "2.0.0".to_string())]);
+}
+
+#[test]
+fn test_remove_version_dir_refuses_outside_data_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let data_dir = temp_dir.path().join("data");
+    let inside = data_dir.join("owner/tool/1.0.0");
+    let outside = temp_dir.path().join("elsewhere");
+    fs::create_dir_all(&inside).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+
+    assert!(!remove_version_dir(&outside, &data_dir).unwrap());
+    assert!(outside.exists());
+    assert!(!remove_version_dir(&data_dir, &data_dir).unwrap());
+    assert!(remove_version_dir(&inside, &data_dir).unwrap());
+    assert!(!inside.exists());
+}
+
+#[test]
+fn test_remove_versions_reports_removed_and_reclaimed() {
+    let temp_dir = TempDir::new().unwrap();
+    let data_dir = temp_dir.path().join("data");
+    let version_dir = data_dir.join("github.com/owner/tool/1.0.0");
+    fs::create_dir_all(&version_dir).unwrap();
+    fs::write(version_dir.join("tool"), "12345").unwrap();
+
+    let stale = vec![("owner/tool".to_string(), "1.0.0".to_string())];
+    let removal = remove_versions(&data_dir, &stale);
+    assert_eq!(removal.removed, stale);
+    assert_eq!(removal.failed, 0);
+    assert_eq!(removal.reclaimed, 5);
+    assert!(!version_dir.exists());
+}
+
+#[test]
+fn test_plan_gc_keep_versions_spares_active_old_version() {
+    let data_dir = TempDir::new().unwrap();
+    // a 4th version installed with --no-symlink, the oldest one is still in use
+    let spells = vec![spell("owner/tool", &["1.0.0", "2.0.0", "3.0.0", "4.0.0"])];
+
+    let stale = plan_gc(
+        data_dir.path(),
+        &spells,
+        &linked(&[("owner/tool", "1.0.0")]),
+        2,
+    );
+    assert_eq!(stale, vec![("owner/tool".to_string(), "2.0.0".to_string())]);
+}
@@ -0,0 +1,308 @@
+//! Main file handling 'history' command, and the log of operations it displays
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::cli::HistoryArgs;
+use crate::files::datadirs;
+use crate::output;
+use crate::utils::prompt::confirm;
+use crate::utils::time::format_system_time;
+
+/// Commands whose operations are recorded in the history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryCommand {
+    Install,
+    Update,
+    Uninstall,
+    Use,
+}
+
+impl fmt::Display for HistoryCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HistoryCommand::Install => "install",
+            HistoryCommand::Update => "update",
+            HistoryCommand::Uninstall => "uninstall",
+            HistoryCommand::Use => "use",
+        };
+        f.pad(name)
+    }
+}
+
+/// Outcome of a recorded operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryStatus {
+    Success,
+    Failure,
+}
+
+/// An operation that changed the installed binaries, one line of the history log.
+/// Field names are part of poof's interface.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEvent {
+    /// When the operation ended, in RFC 3339 format.
+    pub timestamp: String,
+    pub command: HistoryCommand,
+    pub repo: String,
+    /// Version the operation replaced or removed, if any.
+    pub from_version: Option<String>,
+    /// Version the operation installed or activated, if any.
+    pub to_version: Option<String>,
+    pub status: HistoryStatus,
+    /// Why the operation failed, with the errors that caused it.
+    pub error_message: Option<String>,
+}
+
+impl HistoryEvent {
+    /// Creates the event of `command` on `repo` ending now, with the outcome of `result`.
+    pub fn new<T>(
+        command: HistoryCommand,
+        repo: &str,
+        from_version: Option<&str>,
+        to_version: Option<&str>,
+        result: &Result<T>,
+    ) -> Self {
+        HistoryEvent {
+            timestamp: format_system_time(SystemTime::now()),
+            command,
+            repo: repo.to_string(),
+            from_version: from_version.map(String::from),
+            to_version: to_version.map(String::from),
+            status: if result.is_ok() {
+                HistoryStatus::Success
+            } else {
+                HistoryStatus::Failure
+            },
+            error_message: result.as_ref().err().map(|e| format!("{:#}", e)),
+        }
+    }
+}
+
+/// Append `event` to the history log, creating it if needed.
+pub fn append_event(event: HistoryEvent) -> Result<()> {
+    let path = datadirs::get_history_file().context("Cannot determine history file path")?;
+    append_event_to(&path, &event)
+}
+
+/// Append `event` as a JSON line to the log at `path`.
+fn append_event_to(path: &Path, event: &HistoryEvent) -> Result<()> {
+    let line = serde_json::to_string(event).context("Cannot serialize history event")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Cannot open history file {}", path.display()))?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("Cannot write history file {}", path.display()))
+}
+
+/// Record the outcome of `command` on `repo` in the history.
+///
+/// A history that cannot be written is only warned about, the operation itself
+/// has already succeeded or failed.
+pub fn record<T>(
+    command: HistoryCommand,
+    repo: &str,
+    from_version: Option<&str>,
+    to_version: Option<&str>,
+    result: &Result<T>,
+) {
+    let event = HistoryEvent::new(command, repo, from_version, to_version, result);
+    if let Err(e) = append_event(event) {
+        warn!(
+            "Cannot record '{}' of {} in the history: {:#}",
+            command, repo, e
+        );
+    }
+}
+
+/// Returns the events of the log at `path`, oldest first. A missing log has no
+/// events, lines that cannot be parsed are skipped.
+fn read_events(path: &Path) -> Result<Vec<HistoryEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Cannot read history file {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                debug!("Skipping unreadable history line '{}': {}", line, e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Returns the versions an event went from and to, e.g. `1.0.0 -> 1.1.0`.
+fn versions_of(event: &HistoryEvent) -> String {
+    match (&event.from_version, &event.to_version) {
+        (Some(from), Some(to)) => format!("{} -> {}", from, to),
+        (Some(version), None) | (None, Some(version)) => version.clone(),
+        (None, None) => "-".to_string(),
+    }
+}
+
+/// Print the recorded operations, newest first, keeping those on `args.repo`
+/// and at most `args.limit` of them. With `--clear` the log is emptied instead,
+/// after confirmation unless `--yes` is given.
+pub fn run_history(args: &HistoryArgs) -> Result<()> {
+    let path = datadirs::get_history_file().context("Cannot determine history file path")?;
+    if args.clear {
+        return clear_history(&path, args.yes);
+    }
+
+    let mut events = read_events(&path)?;
+    if let Some(repo) = &args.repo {
+        events.retain(|event| &event.repo == repo);
+    }
+    events.reverse();
+    if let Some(limit) = args.limit {
+        events.truncate(limit);
+    }
+    if events.is_empty() {
+        info!("No operations recorded.");
+        return Ok(());
+    }
+
+    output!("");
+    output!(
+        "{:<20}\t{:<9}\t{:<40}\t{:<24}\t{}",
+        "Timestamp",
+        "Command",
+        "Repository",
+        "Versions",
+        "Status"
+    );
+    output!(
+        "{:<20}\t{:<9}\t{:<40}\t{:<24}\t{}",
+        "---------",
+        "-------",
+        "----------",
+        "--------",
+        "------"
+    );
+    for event in &events {
+        let status = match (&event.status, &event.error_message) {
+            (HistoryStatus::Success, _) => "success".to_string(),
+            (HistoryStatus::Failure, Some(message)) => format!("failure: {}", message),
+            (HistoryStatus::Failure, None) => "failure".to_string(),
+        };
+        output!(
+            "{:<20}\t{:<9}\t{:<40}\t{:<24}\t{}",
+            event.timestamp,
+            event.command,
+            event.repo,
+            versions_of(event),
+            status
+        );
+    }
+    Ok(())
+}
+
+/// Empty the log at `path`, asking for confirmation unless `yes` is set.
+fn clear_history(path: &Path, yes: bool) -> Result<()> {
+    let count = read_events(path)?.len();
+    if count == 0 {
+        info!("History is already empty.");
+        return Ok(());
+    }
+    info!("About to delete the history of {} operations.", count);
+    if !yes && !confirm()? {
+        info!("History left untouched.");
+        return Ok(());
+    }
+    fs::write(path, "").with_context(|| format!("Cannot clear history file {}", path.display()))?;
+    info!("History cleared.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_event_records_outcome() {
+        let ok: Result<()> = Ok(());
+        let event = HistoryEvent::new(HistoryCommand::Install, "a/b", None, Some("1.0.0"), &ok);
+        assert_eq!(event.status, HistoryStatus::Success);
+        assert_eq!(event.error_message, None);
+
+        let failed: Result<()> = Err(anyhow!("no network").context("Cannot download"));
+        let event = HistoryEvent::new(
+            HistoryCommand::Update,
+            "a/b",
+            Some("1.0.0"),
+            Some("1.1.0"),
+            &failed,
+        );
+        assert_eq!(event.status, HistoryStatus::Failure);
+        assert_eq!(
+            event.error_message.as_deref(),
+            Some("Cannot download: no network")
+        );
+    }
+
+    #[test]
+    fn test_append_and_read_events() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(crate::constants::HISTORY_FILE);
+        assert!(read_events(&path).unwrap().is_empty());
+
+        let ok: Result<()> = Ok(());
+        let first = HistoryEvent::new(HistoryCommand::Install, "a/b", None, Some("1.0.0"), &ok);
+        let second = HistoryEvent::new(HistoryCommand::Uninstall, "a/b", Some("1.0.0"), None, &ok);
+        append_event_to(&path, &first).unwrap();
+        // unreadable lines do not hide the others
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "not json").unwrap();
+        append_event_to(&path, &second).unwrap();
+
+        assert_eq!(read_events(&path).unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_event_json_fields() {
+        let ok: Result<()> = Ok(());
+        let event = HistoryEvent::new(
+            HistoryCommand::Use,
+            "a/b",
+            Some("1.0.0"),
+            Some("2.0.0"),
+            &ok,
+        );
+        let json: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["command"], "use");
+        assert_eq!(json["status"], "success");
+        assert_eq!(json["from_version"], "1.0.0");
+        assert_eq!(json["to_version"], "2.0.0");
+        assert!(json["error_message"].is_null());
+    }
+
+    #[test]
+    fn test_versions_of() {
+        let ok: Result<()> = Ok(());
+        let event = |from, to| HistoryEvent::new(HistoryCommand::Update, "a/b", from, to, &ok);
+        assert_eq!(
+            versions_of(&event(Some("1.0.0"), Some("1.1.0"))),
+            "1.0.0 -> 1.1.0"
+        );
+        assert_eq!(versions_of(&event(None, Some("1.1.0"))), "1.1.0");
+        assert_eq!(versions_of(&event(Some("1.0.0"), None)), "1.0.0");
+        assert_eq!(versions_of(&event(None, None)), "-");
+    }
+}
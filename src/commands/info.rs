@@ -1,11 +1,66 @@
+use crate::commands::list::{active_versions, list_installed_versions_per_slug};
 use crate::constants::*;
 use crate::core::platform_info::*;
-use crate::files::datadirs;
+use crate::files::{datadirs, metadata};
+use crate::models::slug::Slug;
+use crate::models::source::Source;
+use crate::output;
+use crate::utils::semver::{SemverStringConversion, SemverStringPrefix, Version};
+use crate::utils::time::format_system_time;
 use anyhow::{Context, Result};
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 
 /// Print platform information useful for debug purposes.
-pub fn show_info() -> Result<()> {
+///
+/// Directories are resolved the same way the other commands resolve them, so any
+/// `XDG_*` or `POOF_*` override is reflected in the output.
+/// With `json` the same data is printed as a JSON object, whose field names
+/// are part of poof's interface: fields may be added, never renamed or removed.
+pub fn show_info(json: bool) -> Result<()> {
+    let kernel = std::process::Command::new("uname")
+        .arg("-a")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|_| UNKNOWN.to_string());
+    let executable = std::env::current_exe().unwrap_or_default();
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let bin_dir = datadirs::get_bin_dir().context("Cannot locate bin directory")?;
+    let path_status = match check_dir_in_path(bin_dir.to_str().unwrap()) {
+        -1 => "Not in PATH",
+        0 => "In PATH at the beginning",
+        _ => "In PATH, but NOT at the beginning",
+    };
+
+    let cache_dir = datadirs::get_cache_dir().unwrap_or_default();
+    let data_dir = datadirs::get_data_dir().unwrap_or_default();
+
+    if json {
+        let info = serde_json::json!({
+            "poof_version": VERSION,
+            "commit": COMMIT,
+            "build_date": BUILD_DATE,
+            "os_family": std::env::consts::FAMILY,
+            "os_type": std::env::consts::OS,
+            "os_version": get_os_version(),
+            "arch": std::env::consts::ARCH,
+            "endianness": get_platform_endianness(),
+            "kernel": kernel,
+            "executable_path": executable,
+            "cwd": cwd,
+            "bin_dir": bin_dir,
+            "data_dir": data_dir,
+            "cache_dir": cache_dir,
+            "path_status": path_status,
+        });
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&info).context("Cannot serialize info to JSON")?
+        );
+        return Ok(());
+    }
+
     let stdout = io::stdout();
     let mut handle = stdout.lock();
 
@@ -26,18 +81,12 @@ pub fn show_info() -> Result<()> {
     output.push_str(&format!("  OS version: {}\n", get_os_version()));
     output.push_str(&format!("  Arch      : {}\n", std::env::consts::ARCH));
     output.push_str(&format!("  Endianness: {}\n", get_platform_endianness()));
-
-    let kernel = std::process::Command::new("uname")
-        .arg("-a")
-        .output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-        .unwrap_or_else(|_| UNKNOWN.to_string());
+    if let Some(translated) = is_rosetta_translated() {
+        let rosetta = if translated { "translated" } else { "native" };
+        output.push_str(&format!("  Rosetta   : {}\n", rosetta));
+    }
     output.push_str(&format!("  Kernel    : {}\n", kernel));
-
-    let executable = std::env::current_exe().unwrap_or_default();
     output.push_str(&format!("  Executable: {}\n", executable.display()));
-
-    let cwd = std::env::current_dir().unwrap_or_default();
     output.push_str(&format!("  Cwd       : {}\n", cwd.display()));
 
     // Environment variables
@@ -45,32 +94,150 @@ pub fn show_info() -> Result<()> {
     output.push_str(&format!("  SHELL: {}\n", get_shell_info()));
     output.push_str(&format!("  USER : {}\n", get_env_var("USER")));
     output.push_str(&format!("  HOME : {}\n", get_env_var("HOME")));
-
-    let bin_dir = datadirs::get_bin_dir().context("Cannot locate bin directory")?;
-    let path_status = match check_dir_in_path(bin_dir.to_str().unwrap()) {
-        -1 => "Not in PATH",
-        0 => "In PATH at the beginning",
-        _ => "In PATH, but NOT at the beginning",
-    };
     output.push_str(&format!("  PATH : {}\n", path_status));
 
     // Directories
     output.push_str("\nDirectories:\n");
+    output.push_str(&format!("  Cache dir : {}\n", cache_dir.display()));
+    output.push_str(&format!("  Data dir  : {}\n", data_dir.display()));
+    output.push_str(&format!("  Bin dir   : {}\n", bin_dir.display()));
 
-    let cache_dir = datadirs::get_cache_dir().unwrap_or_default();
-    output.push_str(&format!("  Cache dir: {}\n", cache_dir.display()));
-
-    let data_dir = datadirs::get_data_dir().unwrap_or_default();
-    //TODO: remove .parent() when poof will be updated to support different services apart from GitHub.
+    // the config file is optional, tell whether it is there
+    let config_file = datadirs::get_config_file().unwrap_or_default();
+    let config_status = if config_file.is_file() {
+        "found"
+    } else {
+        "not found"
+    };
     output.push_str(&format!(
-        "  Data dir : {}\n",
-        data_dir.parent().unwrap_or(&data_dir).display()
+        "  Config dir: {}\n",
+        config_file.parent().unwrap_or(&config_file).display()
+    ));
+    output.push_str(&format!(
+        "  Config    : {} ({})\n",
+        config_file.display(),
+        config_status
     ));
-
-    output.push_str(&format!("  Bin dir  : {}\n", bin_dir.display()));
 
     // Write everything at once
     handle.write_all(output.as_bytes())?;
     handle.flush()?;
     Ok(())
 }
+
+/// Print the latest release of `repo` along with its locally installed versions.
+///
+/// The release is fetched from the service the newest installed version came from,
+/// or from GitHub when `repo` is not installed. With `json` the same data is printed
+/// as a JSON object, whose field names are part of poof's interface.
+pub fn show_repo_info(repo: &str, json: bool) -> Result<()> {
+    let slug = Slug::new(repo)?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+    let installed: Vec<String> = list_installed_versions_per_slug(&slug)?
+        .map(|spell| {
+            let mut versions = spell.get_versions().clone();
+            versions.sort();
+            versions.to_string_vec()
+        })
+        .unwrap_or_default();
+    let active = active_versions().remove(slug.as_str());
+    let installed_at: BTreeMap<&String, Option<String>> = installed
+        .iter()
+        .map(|version| {
+            let version_dir = datadirs::get_binary_nest(&data_dir, slug.as_str(), version);
+            (
+                version,
+                metadata::load_installed_at(&version_dir).map(format_system_time),
+            )
+        })
+        .collect();
+
+    let source = match installed.last() {
+        Some(newest) => {
+            metadata::load_source(&datadirs::get_binary_nest(&data_dir, slug.as_str(), newest))
+        }
+        None => Source::GitHub,
+    };
+    let release = source
+        .get_release(slug.as_str(), None, true)
+        .with_context(|| format!("Cannot get latest release information for {}", slug))?;
+    let latest = release.tag_name().strip_v();
+    let update_available = installed
+        .last()
+        .is_some_and(|newest| Version::from(latest.as_str()) > Version::from(newest.as_str()));
+    let assets: Vec<&String> = release.assets().iter().map(|asset| asset.name()).collect();
+
+    if json {
+        let info = serde_json::json!({
+            "repo": slug.as_str(),
+            "source": source.to_string(),
+            "latest_tag": release.tag_name(),
+            "published_at": release.published_at(),
+            "assets": assets,
+            "installed_versions": installed,
+            "installed_at": installed_at,
+            "active_version": active,
+            "update_available": update_available,
+        });
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&info).context("Cannot serialize info to JSON")?
+        );
+        return Ok(());
+    }
+
+    let none = "none".to_string();
+    output!("Repository      : {} ({})", slug, source);
+    output!("Latest release  : {}", release.tag_name());
+    output!("Published at    : {}", release.published_at());
+    output!("Assets          :");
+    for asset in &assets {
+        output!("  {}", asset);
+    }
+    let installed_list = if installed.is_empty() {
+        none.clone()
+    } else {
+        installed
+            .iter()
+            .map(|version| match &installed_at[version] {
+                Some(time) => format!("{} (installed at {})", version, time),
+                None => version.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    output!("Installed       : {}", installed_list);
+    output!("Active          : {}", active.as_ref().unwrap_or(&none));
+    if update_available {
+        output!(
+            "Update available: run 'poof update {}' to install {}",
+            slug,
+            latest
+        );
+    }
+    Ok(())
+}
+
+/// Print the version information of poof.
+///
+/// With `json` the same data is printed as a JSON object, whose field names
+/// are part of poof's interface: fields may be added, never renamed or removed.
+pub fn show_version(json: bool) -> Result<()> {
+    if !json {
+        output!("{}", long_version());
+        return Ok(());
+    }
+    let version = serde_json::json!({
+        "version": VERSION,
+        "commit": COMMIT,
+        "build_date": BUILD_DATE,
+        "compile_c_lib": COMPILE_C_LIB,
+        "linking_type": linking_type(),
+        "glibc_version": get_glibc_version(),
+    });
+    output!(
+        "{}",
+        serde_json::to_string_pretty(&version).context("Cannot serialize version to JSON")?
+    );
+    Ok(())
+}
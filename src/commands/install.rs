@@ -1,8 +1,11 @@
 //! Main file handling 'install' command
 
 use std::{
+    collections::HashSet,
     ffi::OsString,
     path::{Path, PathBuf},
+    process::ExitCode,
+    time::SystemTime,
 };
 
 // we use which::which in place of which::which_all, as it's up to the user
@@ -10,46 +13,269 @@ use std::{
 // So only the first entry actually matters.
 use which::which;
 
+#[cfg(feature = "cosign")]
+use crate::files::cosign;
 use crate::{
-    commands::{self, download::download_asset},
-    core::selector::platforms_strings,
+    commands::{
+        self,
+        download::{download_asset, extract_asset, fetch_text, log_asset_checksum},
+        history::{self, HistoryCommand},
+        list::list_installed_versions_per_slug,
+    },
+    constants::ARCH_PACKAGE_INFO_FILE,
+    core::{config, selector::platforms_strings},
     files::{
-        archives, datadirs, filesys,
-        magic::is_exec_for_current_arch,
+        archives, checksum, datadirs, filesys,
+        magic::{is_exec_appimage, is_exec_for_current_arch},
+        metadata,
         utils::{clean_up_filename, get_stem_name_trimmed_at_first_separator},
     },
     github::{
-        client::{get_assets, get_release},
+        client::get_assets,
         models::{Release, ReleaseAsset},
     },
-    models::slug::Slug,
-    utils::semver::SemverStringPrefix,
+    models::{asset_triple::AssetTriple, slug::Slug, source::Source},
+    output,
+    utils::{
+        env,
+        semver::{newest_matching, SemverStringPrefix},
+    },
 };
 use anyhow::{anyhow, bail, Context, Result};
-use log::{debug, info, warn};
+use log::{debug, error, info, warn};
+
+/// Optional install behaviours, set from CLI flags.
+#[derive(Default)]
+pub struct InstallOptions {
+    /// Name of the symlink to create in the bin directory, in place of the binary's own name.
+    pub rename: Option<String>,
+    /// Name of the only executable of the archive to install, the others are skipped.
+    pub binary: Option<String>,
+    /// Always query the GitHub API, bypassing cached responses.
+    pub no_cache: bool,
+    /// Install the binaries without creating their symlinks in the bin directory.
+    pub no_symlink: bool,
+    /// Directory to download and extract assets to, in place of the cache directory.
+    /// It is kept after installation.
+    pub extract_dir: Option<PathBuf>,
+    /// Directory to create the symlinks in, in place of the bin directory.
+    pub bin_dir: Option<PathBuf>,
+    /// Number of versions of the repository to keep, older ones are removed after install.
+    pub keep_versions: Option<usize>,
+    /// Do not warn when running as root.
+    pub allow_root: bool,
+    /// Print where the binaries go and what gets downloaded, before installing.
+    pub print_path: Option<PrintPath>,
+    /// Only download and extract the assets to `extract_dir`, or the current directory.
+    pub extract_only: bool,
+    /// Install the version again even if it is already installed.
+    pub force: bool,
+    /// Version being updated from, the install is recorded in the history as its update.
+    pub update_from: Option<String>,
+    /// Verify the assets against their sigstore bundles before installing them.
+    #[cfg(feature = "cosign")]
+    pub verify_cosign: bool,
+}
+
+/// What `install` does after printing the paths it would use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrintPath {
+    /// Go on with the installation.
+    Proceed,
+    /// Stop before downloading anything.
+    Only,
+}
 
-/// Download and install a GitHub release binary for `repo`.
+/// Warn about environments where installed binaries may not end up where the user expects.
+fn check_environment(options: &InstallOptions) {
+    if !options.allow_root && env::is_root() {
+        warn!("Running as root; binaries will be installed into root's home directory. Use --allow-root to suppress this warning.");
+    }
+    if env::is_ci() {
+        debug!("CI environment detected, poof's bin directory may not persist across CI steps.");
+    }
+}
+
+/// Download and install a release binary for `repo`.
+///
+/// `repo` is either a GitHub `user/repo` slug or a `gitlab:group/project` one.
+/// Binaries are stored by their `user/repo` path whatever their source, and the
+/// source is recorded next to them so that 'update' checks the right service.
 ///
-/// When `tag` is `None` the latest release is fetched. The function selects
-/// platform-compatible assets, downloads them to the cache directory, extracts
+/// When `tag` is given and that version is already installed, nothing is fetched
+/// at all. When `tag` is `None` the latest release is fetched. The function selects
+/// assets compatible with `triple`, downloads them to the cache directory, extracts
 /// or copies the executables to the data directory, and performs a post-install
 /// PATH check. On Unix-like platforms a symlink is also created in the bin
-/// directory so the binary is available in `PATH`.
-pub fn install(repo: &str, tag: Option<&str>) -> Result<()> {
-    let (release, assets) = select_assets(repo, tag)?;
+/// directory so the binary is available in `PATH`, named after `options.rename`
+/// when set. The symlink goes to `options.bin_dir` when set, which is recorded
+/// for later commands. No symlink is created when `options.no_symlink` is set.
+/// With `options.extract_only` the assets are only downloaded and extracted.
+/// With `options.force` an installed version is downloaded and installed again.
+pub fn install(
+    repo: &str,
+    tag: Option<&str>,
+    triple: &AssetTriple,
+    options: &InstallOptions,
+) -> Result<()> {
+    check_environment(options);
+    let (source, _) = Source::split(repo);
+    // installs are stored per host, under the source-qualified name
+    let qualified = source.qualify(repo);
+    let skip_lookup = options.print_path.is_none() && !options.extract_only && !options.force;
+    if let (Some(tag), true) = (tag, skip_lookup) {
+        // the version is known, no need to ask the API about it
+        let version = tag.strip_v();
+        if check_if_installed(&get_install_dir(&qualified, version)?)? {
+            info!(
+                "Version {} is already installed (skipping download)",
+                version
+            );
+            return Ok(());
+        }
+    }
+    let (release, assets) = select_assets(repo, tag, triple, !options.no_cache)?;
     let version: String = release.tag_name().strip_v();
+    install_assets(
+        &qualified,
+        &version,
+        &assets,
+        source,
+        Some(&release),
+        options,
+    )
+}
+
+/// Download and install the asset at `url`, without looking up any release.
+///
+/// The binaries are stored under `name` when given, or under a slug derived from
+/// the URL host and file name otherwise (e.g. `example.com/tool` for
+/// `https://example.com/nightly/tool-linux-x86_64.tar.gz`). `tag` is used as
+/// version, defaulting to `latest`.
+pub fn install_from_url(
+    url: &str,
+    name: Option<&str>,
+    tag: Option<&str>,
+    options: &InstallOptions,
+) -> Result<()> {
+    check_environment(options);
+    let asset_name =
+        url_file_name(url).ok_or_else(|| anyhow!("Cannot get a file name from URL {}", url))?;
+    let repo = match name {
+        Some(name) => name.to_string(),
+        None => slug_from_url(url)
+            .ok_or_else(|| anyhow!("Cannot derive a name from URL {}, use --name", url))?,
+    };
+    let version = tag.map(|t| t.strip_v()).unwrap_or("latest");
+    debug!("Installing {} as {} version {}", url, repo, version);
+    let asset = ReleaseAsset::new(asset_name.to_string(), url.to_string());
+    install_assets(&repo, version, &[asset], Source::Url, None, options)
+}
+
+/// Returns the last path segment of `url`, ignoring any query or fragment.
+fn url_file_name(url: &str) -> Option<&str> {
+    let path = url.split(['?', '#']).next()?;
+    let (_, rest) = path.split_once("://")?;
+    let (_, file_name) = rest.rsplit_once('/')?;
+    if file_name.is_empty() {
+        None
+    } else {
+        Some(file_name)
+    }
+}
 
-    let install_dir = get_install_dir(repo, &version)?;
-    if check_if_installed(&install_dir)? {
+/// Derives a `host/name` slug from `url`, where name is the file name stripped of
+/// its version, platform and extension parts. Characters that are not allowed in
+/// slugs are replaced with hyphens.
+fn slug_from_url(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    // drop credentials and port
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    let file_name = url_file_name(url)?;
+    let stem = get_stem_name_trimmed_at_first_separator(std::ffi::OsStr::new(file_name));
+    let sanitize = |s: &str| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    };
+    let (host, stem) = (sanitize(host), sanitize(&stem.to_string_lossy()));
+    if host.is_empty() || stem.is_empty() {
+        return None;
+    }
+    Some(format!("{}/{}", host, stem))
+}
+
+/// Download `assets` and install their binaries as `version` of `repo`.
+///
+/// `release` is the release the assets belong to, if any. It is used to look up
+/// published checksums when checksum verification is enabled.
+fn install_assets(
+    repo: &str,
+    version: &str,
+    assets: &[ReleaseAsset],
+    source: Source,
+    release: Option<&Release>,
+    options: &InstallOptions,
+) -> Result<()> {
+    if options.extract_only {
+        return extract_only(repo, version, assets, options.extract_dir.as_deref());
+    }
+    if let Some(bin_dir) = &options.bin_dir {
+        datadirs::ensure_writable_dir(bin_dir)
+            .with_context(|| format!("Cannot use {} as bin directory", bin_dir.display()))?;
+    }
+    let install_dir = get_install_dir(repo, version)?;
+    if let Some(print_path) = options.print_path {
+        print_paths(repo, &install_dir, assets, options)?;
+        if print_path == PrintPath::Only {
+            return Ok(());
+        }
+    }
+    // the installed files are only replaced once the first asset has been downloaded,
+    // not to lose a working installation to a failed download
+    let reinstall = check_if_installed(&install_dir)?;
+    if reinstall && !options.force {
         info!(
-            "Skipping installation as version {} for {} seems already installed.",
-            version, repo
+            "Version {} is already installed (skipping download)",
+            version
         );
         return Ok(());
-    } else {
-        // installation should proceed, prepare install directory
-        prepare_install_dir(&install_dir)?;
     }
+    if reinstall {
+        warn!("Forcing reinstall of {} {}", repo, version);
+    } else if has_other_versions(&install_dir) {
+        info!("A different version is installed; reinstalling");
+    }
+    let result = download_and_install(repo, version, assets, source, release, options, reinstall);
+    let (command, from_version) = match &options.update_from {
+        Some(from_version) => (HistoryCommand::Update, Some(from_version.as_str())),
+        None => (HistoryCommand::Install, None),
+    };
+    history::record(command, repo, from_version, Some(version), &result);
+    result
+}
+
+/// Download `assets` and install their binaries as `version` of `repo`, replacing
+/// the installed files of that version when `reinstall` is set.
+fn download_and_install(
+    repo: &str,
+    version: &str,
+    assets: &[ReleaseAsset],
+    source: Source,
+    release: Option<&Release>,
+    options: &InstallOptions,
+    mut reinstall: bool,
+) -> Result<()> {
+    let install_dir = get_install_dir(repo, version)?;
+    // installation should proceed, prepare install directory
+    prepare_install_dir(&install_dir)?;
 
     // create slug from repo
     let slug = Slug::new(repo)?;
@@ -58,14 +284,28 @@ pub fn install(repo: &str, tag: Option<&str>) -> Result<()> {
     let cache_dir: PathBuf =
         datadirs::get_cache_dir().context("Cannot determine cache directory")?;
     debug!("Cache directory: {}", cache_dir.display());
+    if let Some(extract_dir) = &options.extract_dir {
+        // warn early, before downloading anything
+        if let Err(e) = datadirs::ensure_writable_dir(extract_dir) {
+            warn!(
+                "Extraction directory {} is not writable: {}",
+                extract_dir.display(),
+                e
+            );
+        }
+    }
 
     let mut i = 1;
     for asset in assets {
         // if not installed, download release assets.
         // we use a counter to name the assets differently to avoid conflicts in case of multiple assets,
         // which themselves may contain multiple executables.
-        let download_to =
-            datadirs::get_binary_nest(&cache_dir, repo, &version).join(format!("asset_{}", i));
+        let download_to = match &options.extract_dir {
+            Some(extract_dir) => extract_dir.join(format!("asset_{}", i)),
+            None => {
+                datadirs::get_binary_nest(&cache_dir, repo, version).join(format!("asset_{}", i))
+            }
+        };
         let downloaded_file =
             match download_asset(asset.name(), asset.browser_download_url(), &download_to)
                 .with_context(|| format!("Cannot download asset for {} version {}", repo, version))
@@ -76,28 +316,171 @@ pub fn install(repo: &str, tag: Option<&str>) -> Result<()> {
                 }
             };
         i += 1;
+        let asset_hash = log_asset_checksum(asset.name(), &downloaded_file)?;
+
+        // verify the download when asked to, before anything gets installed
+        let verified = config::get().verify_checksums
+            && match release {
+                Some(release) => verify_asset_checksum(release, asset.name(), &downloaded_file)?,
+                None => {
+                    warn!(
+                        "No release to get checksums from for {}, skipping verification.",
+                        asset.name()
+                    );
+                    false
+                }
+            };
+        #[cfg(feature = "cosign")]
+        if options.verify_cosign {
+            let trusted_root = datadirs::get_cosign_trusted_root_file()
+                .context("Cannot determine the sigstore trusted root path")?;
+            verify_asset_signature(release, asset.name(), &downloaded_file, &trusted_root)?;
+        }
+        if reinstall {
+            clean_install_dir(&install_dir)?;
+            reinstall = false;
+        }
+        let installed_before = metadata::list_binaries(&install_dir);
 
         process_install(
             &slug,
-            &version,
+            version,
             &downloaded_file,
             &download_to,
             &install_dir,
             asset.name(),
+            options,
         )
         .with_context(|| format!("Cannot install {} version {}", repo, version))?;
+        metadata::save_asset_checksum(&install_dir, asset.name(), &asset_hash)?;
+
+        // record the checksums of the binaries coming from a verified asset,
+        // so that they can be checked again later by the 'verify' command
+        if verified {
+            for binary in metadata::list_binaries(&install_dir) {
+                if installed_before.contains(&binary) {
+                    continue;
+                }
+                let hash = checksum::sha256_file(&install_dir.join(&binary))?;
+                metadata::save_checksum(&install_dir, &binary, &hash)?;
+            }
+        }
 
-        if clean_cache_dir(&download_to, &cache_dir)? {
+        if options.extract_dir.is_some() {
+            // left in place for inspection
+            info!("Extracted files kept in {}", download_to.display());
+        } else if clean_cache_dir(&download_to, &cache_dir)? {
             debug!("Cleaned up cache directory: {}", download_to.display());
         }
     }
-    info!("{} {} installed successfully.\n", repo, &version);
+    metadata::save_source(&install_dir, &source)?;
+    if let Some(bin_dir) = &options.bin_dir {
+        metadata::save_bin_dir(&install_dir, bin_dir)?;
+    }
+    metadata::save_installed_at(&install_dir, SystemTime::now())?;
+    info!("{} {} installed successfully.\n", repo, version);
+    if let Some(keep) = options.keep_versions {
+        prune_old_versions(repo, keep);
+    }
+    if options.no_symlink {
+        info!(
+            "Binaries have not been added to PATH. Run 'poof link {} {}' to add them.",
+            repo, version
+        );
+        return Ok(());
+    }
 
-    // check if the binaries are in the PATH by checking if poof's bin directory is in PATH
-    commands::check::check_if_bin_in_path()?;
+    // check if the binaries are in the PATH by checking if their bin directory is in PATH
+    match &options.bin_dir {
+        Some(bin_dir) => commands::check::check_if_dir_in_path(bin_dir)?,
+        None => commands::check::check_if_bin_in_path()?,
+    };
+    Ok(())
+}
+
+/// Download `assets` to `extract_dir`, or the current directory, and extract the
+/// archives among them there. Nothing is installed nor linked, and the files are
+/// left in place.
+fn extract_only(
+    repo: &str,
+    version: &str,
+    assets: &[ReleaseAsset],
+    extract_dir: Option<&Path>,
+) -> Result<()> {
+    let extract_dir: PathBuf = match extract_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir().context("Cannot determine current directory")?,
+    };
+    for asset in assets {
+        let downloaded = download_asset(asset.name(), asset.browser_download_url(), &extract_dir)
+            .with_context(|| {
+            format!("Cannot download asset for {} version {}", repo, version)
+        })?;
+        log_asset_checksum(asset.name(), &downloaded)?;
+        extract_asset(&downloaded, &extract_dir)?;
+    }
+    info!("Extracted to {}", extract_dir.display());
+    Ok(())
+}
+
+/// Print the install directory, the symlink and the assets `install_assets` would use.
+///
+/// The symlink is named after the binary, which is only known for bare executables
+/// before extraction. Archives are assumed to ship a binary named after `repo`.
+fn print_paths(
+    repo: &str,
+    install_dir: &Path,
+    assets: &[ReleaseAsset],
+    options: &InstallOptions,
+) -> Result<()> {
+    output!("Install dir: {}", install_dir.display());
+    if options.no_symlink {
+        output!("Symlink: none (--no-symlink)");
+    } else {
+        let bin_dir = match &options.bin_dir {
+            Some(bin_dir) => bin_dir.clone(),
+            None => datadirs::get_bin_dir().context("Cannot get bin directory path")?,
+        };
+        let name = match (&options.rename, assets) {
+            (Some(rename), _) => rename.clone(),
+            (None, [asset]) if !archives::is_archive_name(asset.name()) => {
+                get_stem_name_trimmed_at_first_separator(std::ffi::OsStr::new(asset.name()))
+                    .to_string_lossy()
+                    .to_string()
+            }
+            _ => repo.rsplit('/').next().unwrap_or(repo).to_string(),
+        };
+        output!("Symlink: {}", bin_dir.join(name).display());
+    }
+    for asset in assets {
+        output!("Asset: {} ({})", asset.name(), asset.browser_download_url());
+    }
     Ok(())
 }
 
+/// Removes the versions of `repo` older than the `keep` newest ones.
+/// Failures are only reported, as the installation itself succeeded.
+fn prune_old_versions(repo: &str, keep: usize) {
+    let spell = match Slug::new(repo).and_then(|slug| list_installed_versions_per_slug(&slug)) {
+        Ok(Some(spell)) => spell,
+        Ok(None) => return,
+        Err(e) => {
+            warn!("Cannot list installed versions of {}: {:#}", repo, e);
+            return;
+        }
+    };
+    match commands::gc::prune_versions(&spell, keep) {
+        Ok(removed) if removed.is_empty() => {}
+        Ok(removed) => info!(
+            "Removed {} old version(s) of {}: {}",
+            removed.len(),
+            repo,
+            removed.join(", ")
+        ),
+        Err(e) => warn!("Cannot remove old versions of {}: {:#}", repo, e),
+    }
+}
+
 /// Installs a single downloaded asset: extracts archives or copies bare executables into `install_dir`.
 fn process_install(
     slug: &Slug,
@@ -106,10 +489,24 @@ fn process_install(
     download_to: &PathBuf,
     install_dir: &Path,
     asset_name: &String,
+    options: &InstallOptions,
 ) -> Result<()> {
     // check if downloaded binary is an archive or an executable
-    // and proceed accordingly.
-    if is_exec_for_current_arch(downloaded_file)? {
+    // and proceed accordingly. AppImages are self-contained executables
+    // and must never reach the extraction step, even when built for another
+    // architecture.
+    // The content type declared by the server is deliberately ignored: servers
+    // label bare binaries as application/octet-stream, application/x-executable,
+    // application/x-mach-binary or application/x-msdownload, while magic bytes
+    // always tell the truth.
+    let is_exec = is_exec_for_current_arch(downloaded_file)?;
+    if !is_exec && is_exec_appimage(downloaded_file) {
+        bail!(
+            "AppImage {} is not built for the current architecture",
+            asset_name
+        );
+    }
+    if is_exec {
         debug!("Downloaded file {} is an executable binary.", asset_name);
         let file_name = &downloaded_file
             .file_name()
@@ -118,7 +515,7 @@ fn process_install(
         // This is useful to avoid installing files with names like "mytool-1.0.0" or "mytool-linux-x86_64"
         // and instead use just "mytool", which is how the binary will be used when in PATH.
         let exec_name = get_stem_name_trimmed_at_first_separator(file_name);
-        install_binary(slug, downloaded_file, install_dir, &exec_name)
+        install_binary(slug, downloaded_file, install_dir, &exec_name, options)
             .with_context(|| format!("Cannot install executable {}", asset_name))?;
     } else {
         // extract executables
@@ -127,20 +524,116 @@ fn process_install(
         debug!("Extracted {} to {}", asset_name, download_to.display());
 
         // install executables
-        install_binaries(slug, version, download_to, install_dir)
+        install_binaries(slug, version, download_to, install_dir, options)
             .with_context(|| format!("Cannot extract executables from archive {}", asset_name))?;
     }
     Ok(())
 }
 
+/// Look up the SHA-256 checksum published in `release` for the asset named `asset_name`.
+/// Checksum files dedicated to the asset (e.g. `<asset>.sha256`) are preferred over lists.
+fn published_checksum(release: &Release, asset_name: &str) -> Result<Option<String>> {
+    let mut checksum_assets: Vec<&ReleaseAsset> = release
+        .assets()
+        .iter()
+        .filter(|a| checksum::is_sha256_checksum_file(a.name()))
+        .collect();
+    checksum_assets.sort_by_key(|a| !a.name().starts_with(asset_name));
+    for checksum_asset in checksum_assets {
+        let dedicated = checksum_asset.name().starts_with(asset_name);
+        let content = fetch_text(checksum_asset.browser_download_url())
+            .with_context(|| format!("Cannot fetch checksum file {}", checksum_asset.name()))?;
+        let entries = checksum::parse_checksums(&content);
+        if let Some(hash) = checksum::find_checksum(&entries, asset_name, dedicated) {
+            debug!(
+                "Checksum of {} found in {}",
+                asset_name,
+                checksum_asset.name()
+            );
+            return Ok(Some(hash));
+        }
+    }
+    Ok(None)
+}
+
+/// Compare the SHA-256 checksum of `downloaded_file` with the one published in `release`.
+///
+/// Returns `true` when the checksums match and `false`, with a warning, when the
+/// release publishes no checksum for the asset. A mismatch is an error.
+fn verify_asset_checksum(
+    release: &Release,
+    asset_name: &str,
+    downloaded_file: &Path,
+) -> Result<bool> {
+    let Some(expected) = published_checksum(release, asset_name)? else {
+        warn!(
+            "No SHA-256 checksum published for {}, skipping verification.",
+            asset_name
+        );
+        return Ok(false);
+    };
+    let actual = checksum::sha256_file(downloaded_file)?;
+    if actual != expected {
+        bail!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            asset_name,
+            expected,
+            actual
+        );
+    }
+    info!("✓ Checksum verified for {}", asset_name);
+    Ok(true)
+}
+
+/// Verify `downloaded_file` against the sigstore bundle published for `asset_name`
+/// in `release`, trusting the signers vouched for by the trusted root at `trusted_root`.
+/// A missing bundle is an error, like a signature that does not verify.
+#[cfg(feature = "cosign")]
+fn verify_asset_signature(
+    release: Option<&Release>,
+    asset_name: &str,
+    downloaded_file: &Path,
+    trusted_root: &Path,
+) -> Result<()> {
+    let release = release.ok_or_else(|| {
+        anyhow!(
+            "No release to get the sigstore bundle of {} from",
+            asset_name
+        )
+    })?;
+    let bundle_asset = cosign::bundle_names(asset_name)
+        .iter()
+        .find_map(|name| release.assets().iter().find(|a| a.name() == name))
+        .ok_or_else(|| anyhow!("No sigstore bundle published for {}", asset_name))?;
+    let bundle = fetch_text(bundle_asset.browser_download_url())
+        .with_context(|| format!("Cannot fetch sigstore bundle {}", bundle_asset.name()))?;
+    let root = cosign::TrustedRoot::load(trusted_root)?;
+    let artifact = std::fs::read(downloaded_file)
+        .with_context(|| format!("Cannot read {}", downloaded_file.display()))?;
+    if let Err(e) = cosign::verify_bundle(&bundle, &artifact, &root) {
+        bail!("Cosign verification failed for {}: {}", asset_name, e);
+    }
+    info!("✓ Cosign signature verified for {}", asset_name);
+    Ok(())
+}
+
 /// Select the assets to download for the requested software.
+/// Assets are scored against `triple`, which describes the target platform.
+/// Release information may come from the API response cache when `use_cache` is `true`.
 /// Returns a tuple of the release and the asset.
 /// Returns an error if the release or asset cannot be selected.
-pub fn select_assets(repo: &str, tag: Option<&str>) -> Result<(Release, Vec<ReleaseAsset>)> {
+pub fn select_assets(
+    repo: &str,
+    tag: Option<&str>,
+    triple: &AssetTriple,
+    use_cache: bool,
+) -> Result<(Release, Vec<ReleaseAsset>)> {
     // select assets to download
-    let release: Release = get_release(repo, tag)
+    let (source, repo) = Source::split(repo);
+    let release: Release = source
+        .get_release(repo, tag, use_cache)
         .with_context(|| format!("Cannot get release information for {}", repo))?;
-    let assets: Vec<ReleaseAsset> = get_assets(&release).with_context(|| {
+    let assets: Vec<ReleaseAsset> = get_assets(&release, triple).with_context(|| {
         format!(
             "Cannot find any compatible asset from release {} for current platform.",
             release.tag_name()
@@ -149,6 +642,73 @@ pub fn select_assets(repo: &str, tag: Option<&str>) -> Result<(Release, Vec<Rele
     Ok((release, assets))
 }
 
+/// Exit code of `install --check-only` when the release has no compatible asset.
+const NO_COMPATIBLE_ASSET: u8 = 2;
+
+/// Looks up the compatible assets of a release of `repo` without downloading them.
+///
+/// Prints each asset name and download URL, or a JSON array of name, URL and
+/// content type when `json` is `true`. Nothing is written to disk, not even the
+/// API response cache.
+///
+/// Returns exit code 2 when the release has no asset compatible with `triple`.
+pub fn check_only(
+    repo: &str,
+    tag: Option<&str>,
+    triple: &AssetTriple,
+    json: bool,
+) -> Result<ExitCode> {
+    let (source, repo) = Source::split(repo);
+    let release: Release = source
+        .get_release(repo, tag, false)
+        .with_context(|| format!("Cannot get release information for {}", repo))?;
+    let assets: Vec<ReleaseAsset> = match get_assets(&release, triple) {
+        Ok(assets) => assets,
+        Err(e) => {
+            error!("{}", e);
+            return Ok(ExitCode::from(NO_COMPATIBLE_ASSET));
+        }
+    };
+
+    if json {
+        let items: Vec<serde_json::Value> = assets
+            .iter()
+            .map(|asset| {
+                serde_json::json!({
+                    "name": asset.name(),
+                    "url": asset.browser_download_url(),
+                    "content_type": asset.content_type(),
+                })
+            })
+            .collect();
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&items).context("Cannot serialize assets to JSON")?
+        );
+    } else {
+        for asset in &assets {
+            output!(
+                "Compatible asset found: {} ({})",
+                asset.name(),
+                asset.browser_download_url()
+            );
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the tag of the newest release of `repo` whose version satisfies `req`.
+pub fn resolve_version_constraint(repo: &str, req: &semver::VersionReq) -> Result<String> {
+    let (source, repo) = Source::split(repo);
+    let tags = source
+        .get_release_tags(repo)
+        .with_context(|| format!("Cannot list releases of {}", repo))?;
+    let tag = newest_matching(&tags, req)
+        .ok_or_else(|| anyhow!("No release of {} satisfies the constraint '{}'", repo, req))?;
+    info!("Release {} is the newest satisfying '{}'", tag, req);
+    Ok(tag.to_string())
+}
+
 /// Get the installation directory for the requested software.
 /// based on repo slug and version.
 fn get_install_dir(repo: &str, version: &str) -> Result<PathBuf> {
@@ -172,6 +732,15 @@ fn prepare_install_dir(install_dir: &PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Empty `install_dir` of a previous installation of the same version, so that
+/// no stale files are left behind by a reinstall.
+fn clean_install_dir(install_dir: &PathBuf) -> Result<()> {
+    debug!("Cleaning install directory: {}", install_dir.display());
+    std::fs::remove_dir_all(install_dir)
+        .with_context(|| format!("Cannot clean install directory {}", install_dir.display()))?;
+    prepare_install_dir(install_dir)
+}
+
 /// Check if the requested software is already installed to data directory.
 /// Returns true if the software is already installed, false if it should be installed.
 /// Returns an error if the installation directory cannot be checked.
@@ -200,7 +769,7 @@ fn check_if_installed(install_dir: &Path) -> Result<bool> {
                 Ok(false) // installation should proceed
             } else {
                 // directory exists and is not empty: we assume it's already installed
-                warn!(
+                debug!(
                     "Version already installed. Check content in {}. Skipping installation.",
                     install_dir.display()
                 );
@@ -218,68 +787,172 @@ fn check_if_installed(install_dir: &Path) -> Result<bool> {
     }
 }
 
+/// Returns `true` when versions of the repository other than the one in `install_dir` are installed.
+fn has_other_versions(install_dir: &Path) -> bool {
+    let Some(versions_dir) = install_dir.parent() else {
+        return false;
+    };
+    std::fs::read_dir(versions_dir).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            entry.path() != install_dir
+                && entry.file_type().is_ok_and(|t| t.is_dir())
+                && !entry.file_name().to_string_lossy().starts_with('.')
+        })
+    })
+}
+
+/// Returns the directory to look for executables in, within the extracted archive at
+/// `extracted_path`: `usr/bin` for Arch Linux packages, whose other executables are
+/// helpers, `extracted_path` itself otherwise.
+fn executables_dir(extracted_path: &Path) -> PathBuf {
+    let bin_dir = extracted_path.join("usr").join("bin");
+    if extracted_path.join(ARCH_PACKAGE_INFO_FILE).is_file() && bin_dir.is_dir() {
+        debug!("Arch Linux package, installing the executables in usr/bin");
+        return bin_dir;
+    }
+    extracted_path.to_path_buf()
+}
+
 /// Finds all executables within an extracted archive and installs each one into `install_dir`.
 fn install_binaries(
     slug: &Slug,
     version: &str,
     extracted_path: &Path,
     install_dir: &Path,
+    options: &InstallOptions,
 ) -> Result<()> {
     // TODO: ensure filesys::find_exec_files_from_extracted_archive returns Result if needed
     // assuming for now it returns Vec<PathBuf> and handles its own errors internally or doesn't fail often
-    let execs_to_install: Vec<PathBuf> = filesys::find_exec_files_in_dir(extracted_path, true);
+    let mut execs_to_install: Vec<PathBuf> =
+        filesys::find_exec_files_in_dir(&executables_dir(extracted_path), true);
+    // sort for a predictable install order, directory listings are not
+    execs_to_install.sort();
 
     if execs_to_install.is_empty() {
         // we interpret this as an error
         bail!("No executables found to install. Please check the archive contents.");
     }
 
-    for exec in execs_to_install {
+    // get the platform aliases and clean up the executable names
+    // this to avoid installing files with names like "mytool-1.0.0-linux-x86_64" or "mytool-1.0.0-macos-arm64"
+    let mut platform_aliases: Vec<String> = platforms_strings();
+    platform_aliases.push(version.to_string());
+    let mut execs_to_install: Vec<(PathBuf, String)> = execs_to_install
+        .into_iter()
+        .map(|exec| {
+            let file_name = exec
+                .file_name()
+                .ok_or_else(|| anyhow!("Cannot get filename from {}", exec.display()))?
+                .to_string_lossy()
+                .to_string();
+            let mut exec_name = clean_up_filename(&file_name, platform_aliases.clone());
+            if exec_name.is_empty() {
+                // the whole name was made of platform tokens, keep it as it is
+                exec_name = file_name;
+            }
+            Ok((exec, exec_name))
+        })
+        .collect::<Result<_>>()?;
+
+    // only the requested executable is installed, matched by its own or its cleaned up name
+    if let Some(binary) = &options.binary {
+        let available: Vec<String> = execs_to_install
+            .iter()
+            .map(|(_, exec_name)| exec_name.clone())
+            .collect();
+        execs_to_install.retain(|(exec, exec_name)| {
+            exec_name == binary || exec.file_name().is_some_and(|name| name == binary.as_str())
+        });
+        if execs_to_install.is_empty() {
+            error!(
+                "No executable named '{}' found in the archive. Available: {}",
+                binary,
+                available.join(", ")
+            );
+            bail!("No executable named '{}' found in the archive", binary);
+        }
+    }
+
+    // a custom name can only be given to a single binary
+    if let Some(rename) = &options.rename {
+        if execs_to_install.len() > 1 {
+            bail!(
+                "Cannot rename to '{}': the archive contains {} executables, --rename needs exactly one. \
+                Use --binary to pick one.",
+                rename,
+                execs_to_install.len()
+            );
+        }
+    }
+
+    // names already taken by previous executables of the same archive
+    let mut installed_names: HashSet<String> = HashSet::new();
+    for (exec, exec_name) in execs_to_install {
         debug!("Installing executable: {}", exec.display());
         // if we have multiple executables, we install each one.
         // we assume that to have multiple executables, those were in an archive.
-        let exec_name = exec
-            .file_name()
-            .ok_or_else(|| anyhow!("Cannot get filename from {}", exec.display()))?;
-
-        // get the platform aliases and clean up the executable name
-        // this to avoid installing files with names like "mytool-1.0.0-linux-x86_64" or "mytool-1.0.0-macos-arm64"
-        let mut platform_aliases: Vec<String> = platforms_strings();
-        platform_aliases.push(version.to_string());
-        let exec_name = clean_up_filename(&exec_name.to_string_lossy(), platform_aliases);
+        // two executables of the same archive must not overwrite each other
+        if !installed_names.insert(exec_name.clone()) {
+            warn!(
+                "Skipping {}: another executable of the archive is already installed as '{}'.",
+                exec.display(),
+                exec_name
+            );
+            continue;
+        }
 
         // install the binary
-        install_binary(slug, &exec, install_dir, &OsString::from(exec_name))
-            .with_context(|| format!("Cannot install executable {}", exec.display()))?;
+        install_binary(
+            slug,
+            &exec,
+            install_dir,
+            &OsString::from(exec_name),
+            options,
+        )
+        .with_context(|| format!("Cannot install executable {}", exec.display()))?;
     }
     Ok(())
 }
 
 /// Install a binary to the install directory.
+/// The symlink in the bin directory is named `options.rename` when set, `exec_name` otherwise,
+/// and it is not created at all when `options.no_symlink` is set.
 /// Returns an error if the binary cannot be installed.
 fn install_binary(
     slug: &Slug,
     exec: &PathBuf,
     install_dir: &Path,
     exec_name: &OsString,
+    options: &InstallOptions,
 ) -> Result<()> {
+    let rename = options.rename.as_deref();
     let installed_exec = install_dir.join(exec_name);
 
-    let bin_dir: PathBuf = datadirs::get_bin_dir().context("Cannot determine bin directory")?;
-    let symlink_path = bin_dir.join(exec_name);
+    let bin_dir: PathBuf = match &options.bin_dir {
+        Some(bin_dir) => bin_dir.clone(),
+        None => datadirs::get_bin_dir().context("Cannot determine bin directory")?,
+    };
+    let link_name: OsString = rename.map(OsString::from).unwrap_or(exec_name.clone());
+    let symlink_path = bin_dir.join(&link_name);
 
     // none of these checks should bail, they should only warn
     // if the binary is already installed and points to the wrong place, we warn the user
     // and proceed with the installation.
     let mut skip_symlink = false;
-    if let Err(e) = check_for_same_named_binary_in_bin_dir(slug, &symlink_path) {
+    if options.no_symlink {
+        // nothing is added to the bin directory, there is nothing to clash with
+        debug!(
+            "Not linking '{}' as requested.",
+            link_name.to_string_lossy()
+        );
+    } else if let Err(e) = check_for_same_named_binary_in_bin_dir(slug, &symlink_path) {
         warn!("{}", e);
         skip_symlink = true;
-    } else if binary_in_path_is_not_managed_by_poof(exec_name, &bin_dir) {
+    } else if binary_in_path_is_not_managed_by_poof(&link_name, &bin_dir) {
         // proceed with installation anyway, but warn the user
         warn!(
             "A third-party managed binary named '{}' is already installed in PATH.",
-            exec_name.to_string_lossy()
+            link_name.to_string_lossy()
         );
         warn!("Installation may shadow/be shadowed by it. Please check your PATH.\n");
         skip_symlink = false;
@@ -295,13 +968,27 @@ fn install_binary(
         )
     })?;
 
+    // record the custom name, so that other commands (e.g. 'use') can find it
+    if let Some(rename) = rename {
+        metadata::save_rename(install_dir, &exec_name.to_string_lossy(), rename)?;
+    }
+
+    if options.no_symlink {
+        filesys::make_executable(&installed_exec);
+        info!(
+            "✓ '{}' installed without adding it to PATH\n",
+            exec_name.to_string_lossy()
+        );
+        return Ok(());
+    }
+
     // We skip symlink creation in bin dir (where files are added in PATH) if a
     // binary with the same name is already installed in bin dir or if the user has
     // a binary with the same name in PATH. We warn the user to force
     if skip_symlink {
         warn!(
             "Skipping creation of symlink '{}' -> '{}'.",
-            exec_name.to_string_lossy(),
+            link_name.to_string_lossy(),
             installed_exec.display()
         );
         return Ok(());
@@ -310,26 +997,21 @@ fn install_binary(
     // make them executable
     // Set executable permissions, platform-specific
     // Note: Windows does not require setting executable permissions
-    // TODO: below we have the same issue as in line 166, Result that should
-    // be handled. we use the same workaround to ignore the warning
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Make the file executable on Unix-like systems
-        filesys::make_executable(&installed_exec);
-        // Create a symlink in the bin directory, overwriting existing to default
-        // using the new version. This is a UX feature to save the user from having to
-        // manually set the default version after installation (most cases).
-        match filesys::create_symlink(&installed_exec, &symlink_path, true) {
-            Ok(()) => {
-                info!("✓ '{}' command installed\n", exec_name.to_string_lossy());
-            }
-            Err(e) => {
-                warn!(
-                    "Cannot create symlink for {}: {}. You may need to manually set the default version.",
-                    exec_name.to_string_lossy(),
-                    e
-                );
-            }
+    filesys::make_executable(&installed_exec);
+    // Create a symlink in the bin directory, overwriting existing to default
+    // using the new version. This is a UX feature to save the user from having to
+    // manually set the default version after installation (most cases).
+    // On Windows the binary is copied when symlinks cannot be created.
+    match filesys::create_symlink(&installed_exec, &symlink_path, true) {
+        Ok(()) => {
+            info!("✓ '{}' command installed\n", link_name.to_string_lossy());
+        }
+        Err(e) => {
+            warn!(
+                "Cannot create symlink for {}: {}. You may need to manually set the default version.",
+                link_name.to_string_lossy(),
+                e
+            );
         }
     }
     Ok(())
@@ -363,38 +1045,113 @@ fn clean_cache_dir(dir: &Path, cache_root: &Path) -> Result<bool> {
 
 /// Check if a binary with the same name is in the bin directory and it's not something managed by poof.
 /// Returns an error if the binary is already installed in the bin directory or if something not managed by poof is found in its bin directory.
+/// The error names the other installed repositories providing a binary with the same name, if any.
 /// Returns Ok(()) otherwise.
-fn check_for_same_named_binary_in_bin_dir(slug: &Slug, exec_in_bin: &Path) -> Result<()> {
-    if exec_in_bin.exists() {
-        if exec_in_bin.is_symlink() {
-            // we have a symlink and we need to check what the target is.
-            let symlink_target = std::fs::read_link(exec_in_bin)?;
-            // if it's a symlink we check that the target contains the same slug of the requested software.
-            // convert it to string first.
-            let symlink_target = symlink_target.to_string_lossy();
-            let exec_in_bin = exec_in_bin.to_string_lossy().to_string();
-            let data_dir = datadirs::get_data_dir()
-                .map(|d| d.to_string_lossy().to_string())
-                .unwrap_or_default();
-
-            if symlink_target.contains(&data_dir) && symlink_target.contains(&slug.to_string()) {
-                // the symlink target contains the same slug of the requested software,
-                // so it's either a version change or an upgrade.
-                Ok(())
-            } else {
-                bail!(
-                    "A binary named '{}' is already installed and points to {}.",
-                    exec_in_bin,
-                    symlink_target
-                );
-            }
+pub(crate) fn check_for_same_named_binary_in_bin_dir(
+    slug: &Slug,
+    exec_in_bin: &Path,
+) -> Result<()> {
+    if !exec_in_bin.exists() {
+        // no file with the same name found in bin directory, so we can proceed.
+        return Ok(());
+    }
+    let data_dir = datadirs::get_data_dir();
+    let binary_name = exec_in_bin
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let other_repos: Vec<String> = data_dir
+        .as_deref()
+        .map(|data_dir| filesys::find_repos_providing_binary(&binary_name, data_dir))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|repo| repo != slug.as_str())
+        .collect();
+    let hint = if other_repos.is_empty() {
+        format!(
+            "It may come from a system package manager: remove it, or use 'poof install {} --rename <NEW_NAME>' to install it under another name.",
+            slug
+        )
+    } else {
+        format!(
+            "Use 'poof install {} --rename <NEW_NAME>' to avoid the collision.",
+            slug
+        )
+    };
+
+    if exec_in_bin.is_symlink() {
+        // we have a symlink and we need to check what the target is.
+        let symlink_target = std::fs::read_link(exec_in_bin)?;
+        // if it's a symlink we check that the target contains the same slug of the requested software.
+        // convert it to string first.
+        let symlink_target = symlink_target.to_string_lossy();
+        let data_dir = data_dir
+            .map(|d| d.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if symlink_target.contains(&data_dir) && symlink_target.contains(&slug.to_string()) {
+            // the symlink target contains the same slug of the requested software,
+            // so it's either a version change or an upgrade.
+            Ok(())
+        } else if let Some((owner, version)) =
+            symlink_owner(Path::new(symlink_target.as_ref()), Path::new(&data_dir))
+        {
+            bail!(
+                "A binary named '{}' is already installed and points to {} (from {} version {}). \
+                Run 'poof uninstall {} --version {}' to remove it, 'poof link {} --force' to point it to {} instead, \
+                or use 'poof install {} --rename <NEW_NAME>' to avoid the collision.",
+                exec_in_bin.display(),
+                symlink_target,
+                owner,
+                version,
+                owner,
+                version,
+                slug,
+                slug,
+                slug
+            );
         } else {
-            // it's not a symlink, so it's likely a foreign binary
-            bail!("An unrecognized binary named '{}' found in bin directory. Please remove it and try again.", exec_in_bin.to_string_lossy());
+            let owners = if other_repos.is_empty() {
+                String::new()
+            } else {
+                format!(" (installed by {})", other_repos.join(", "))
+            };
+            bail!(
+                "A binary named '{}' is already installed and points to {}{}. {}",
+                exec_in_bin.display(),
+                symlink_target,
+                owners,
+                hint
+            );
         }
+    } else if other_repos.is_empty() {
+        // it's not a symlink, so it's likely a foreign binary
+        bail!(
+            "An unrecognized binary named '{}' found in bin directory. {}",
+            exec_in_bin.display(),
+            hint
+        );
     } else {
-        // no file with the same name found in bin directory, so we can proceed.
-        Ok(())
+        bail!(
+            "A binary named '{}' provided by {} is already in the bin directory. {}",
+            exec_in_bin.display(),
+            other_repos.join(", "),
+            hint
+        );
+    }
+}
+
+/// Returns the slug and version of the installation `symlink_target` points into,
+/// when it is a binary installed in `data_dir` (i.e. `data_dir/host/username/reponame/version/...`).
+fn symlink_owner(symlink_target: &Path, data_dir: &Path) -> Option<(String, String)> {
+    if data_dir.as_os_str().is_empty() {
+        return None;
+    }
+    let (slug, parts) = datadirs::split_repo_path(data_dir, symlink_target)?;
+    match parts.as_slice() {
+        // the binary itself comes after the version
+        [version, _, ..] => Some((slug, version.clone())),
+        _ => None,
     }
 }
 
@@ -178,7 +178,7 @@ impl TestEnv {
     ) -> Result<PathBuf> {
         self.with_test_env(|| {
             let data_dir = datadirs::get_data_dir().unwrap();
-            let install_dir = data_dir.join(slug).join(version);
+            let install_dir = datadirs::get_binary_nest(&data_dir, slug, version);
             fs::create_dir_all(&install_dir).unwrap();
 
             let target_binary = install_dir.join(binary_name);
@@ -525,7 +525,13 @@ mod install_binary_tests {
 
         let slug = TestEnv::test_slug();
         let exec_stem = OsString::from("mybinary");
-        let result = install_binary(&slug, &source_exec, &install_dir, &exec_stem);
+        let result = install_binary(
+            &slug,
+            &source_exec,
+            &install_dir,
+            &exec_stem,
+            &InstallOptions::default(),
+        );
         // If bin_dir cannot be determined, skip the assertion
         if let Err(e) = &result {
             if format!("{:?}", e).contains("Cannot determine") {
@@ -572,7 +578,13 @@ mod install_binary_tests {
         let slug = TestEnv::test_slug();
         let exec_stem = OsString::from("tool");
         // Handle expected failures due to bin_dir issues in test environment
-        if let Err(e) = install_binary(&slug, &source_exec, &install_dir, &exec_stem) {
+        if let Err(e) = install_binary(
+            &slug,
+            &source_exec,
+            &install_dir,
+            &exec_stem,
+            &InstallOptions::default(),
+        ) {
             if !format!("{:?}", e).contains("Cannot determine") {
                 return Err(e);
             } else {
@@ -603,7 +615,13 @@ mod install_binary_tests {
 
         let slug = TestEnv::test_slug();
         let exec_stem = OsString::from("executable");
-        let _ = install_binary(&slug, &source_exec, &install_dir, &exec_stem);
+        let _ = install_binary(
+            &slug,
+            &source_exec,
+            &install_dir,
+            &exec_stem,
+            &InstallOptions::default(),
+        );
 
         let installed = install_dir.join("executable");
         if installed.exists() {
@@ -615,6 +633,107 @@ mod install_binary_tests {
 
         Ok(())
     }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_install_binary_uses_bin_dir_override() -> Result<()> {
+        let env = TestEnv::new()?;
+        let source_exec = env.home_dir.join("source/mybinary");
+        let install_dir = env.create_dir("install")?;
+        let custom_bin_dir = env.home_dir.join("opt/bin");
+        env.create_mock_executable(&source_exec)?;
+
+        let slug = TestEnv::test_slug();
+        let exec_stem = OsString::from("mybinary");
+        env.with_test_env(|| {
+            temp_env::with_var("POOF_BIN_DIR", Some(&custom_bin_dir), || {
+                install_binary(
+                    &slug,
+                    &source_exec,
+                    &install_dir,
+                    &exec_stem,
+                    &InstallOptions::default(),
+                )
+            })
+        })?;
+
+        let link = fs::read_link(custom_bin_dir.join("mybinary"))?;
+        assert_eq!(link, install_dir.join("mybinary"));
+        // nothing is linked in the default bin directory
+        let default_bin_dir = env.with_test_env(datadirs::get_bin_dir).unwrap();
+        assert!(fs::symlink_metadata(default_bin_dir.join("mybinary")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_install_binary_with_rename() -> Result<()> {
+        let env = TestEnv::new()?;
+        let source_exec = env.home_dir.join("source/mybinary");
+        let install_dir = env.create_dir("install")?;
+        env.create_mock_executable(&source_exec)?;
+
+        let slug = TestEnv::test_slug();
+        let exec_stem = OsString::from("mybinary");
+        env.with_test_env(|| {
+            install_binary(
+                &slug,
+                &source_exec,
+                &install_dir,
+                &exec_stem,
+                &InstallOptions {
+                    rename: Some("mb".to_string()),
+                    ..Default::default()
+                },
+            )
+        })?;
+
+        // the binary keeps its own name in the install directory...
+        assert!(install_dir.join("mybinary").exists());
+        // ...while the symlink in PATH uses the custom name
+        let bin_dir = env.with_test_env(datadirs::get_bin_dir).unwrap();
+        assert!(fs::symlink_metadata(bin_dir.join("mb")).is_ok());
+        assert!(fs::symlink_metadata(bin_dir.join("mybinary")).is_err());
+        assert_eq!(metadata::link_name_for(&install_dir, "mybinary"), "mb");
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_install_binary_with_no_symlink() -> Result<()> {
+        let env = TestEnv::new()?;
+        let source_exec = env.home_dir.join("source/mybinary");
+        let install_dir = env.create_dir("install")?;
+        env.create_mock_executable(&source_exec)?;
+        let bin_dir = env.with_test_env(datadirs::get_bin_dir).unwrap();
+        // a same-named foreign file would normally prevent linking
+        fs::write(bin_dir.join("mybinary"), b"foreign")?;
+
+        let slug = TestEnv::test_slug();
+        let exec_stem = OsString::from("mybinary");
+        env.with_test_env(|| {
+            install_binary(
+                &slug,
+                &source_exec,
+                &install_dir,
+                &exec_stem,
+                &InstallOptions {
+                    no_symlink: true,
+                    ..Default::default()
+                },
+            )
+        })?;
+
+        let installed = install_dir.join("mybinary");
+        assert!(filesys::is_executable(&installed));
+        // the bin directory is left untouched
+        assert!(!bin_dir.join("mybinary").is_symlink());
+        assert_eq!(fs::read(bin_dir.join("mybinary"))?, b"foreign");
+
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -652,7 +771,7 @@ mod public_api_tests {
         let result = temp_env::with_vars(
             vec![("POOF_GITHUB_API_URL", Some(server.url().as_str()))],
             || {
-                let result = select_assets(repo, None);
+                let result = select_assets(repo, None, &AssetTriple::default(), false);
                 mock.assert();
                 result
             },
@@ -687,7 +806,7 @@ mod public_api_tests {
         let result_empty = temp_env::with_vars(
             vec![("POOF_GITHUB_API_URL", Some(server.url().as_str()))],
             || {
-                let result = select_assets("", None);
+                let result = select_assets("", None, &AssetTriple::default(), false);
                 mock_empty.assert();
                 result
             },
@@ -714,7 +833,7 @@ mod public_api_tests {
         let result_invalid = temp_env::with_vars(
             vec![("POOF_GITHUB_API_URL", Some(server.url().as_str()))],
             || {
-                let result = select_assets(invalid_repo, None);
+                let result = select_assets(invalid_repo, None, &AssetTriple::default(), false);
                 mock_invalid.assert();
                 result
             },
@@ -753,6 +872,7 @@ mod process_install_tests {
             &download_to,
             &install_dir,
             &asset_name,
+            &InstallOptions::default(),
         );
 
         // Note: This may fail if bin_dir cannot be created, but the copy should work
@@ -781,6 +901,49 @@ mod process_install_tests {
         Ok(())
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_install_rejects_appimage_for_other_arch() -> Result<()> {
+        let env = TestEnv::new()?;
+        let downloaded_file = env.home_dir.join("downloaded/mytool-1.0.0-x86_64.AppImage");
+        let download_to = env.create_dir("download")?;
+        let install_dir = env.create_dir("install")?;
+
+        // ELF header with AppImage type 2 magic, built for a foreign machine (EM_68K)
+        fs::create_dir_all(downloaded_file.parent().unwrap())?;
+        let mut header = [0u8; 20];
+        header[0..4].copy_from_slice(&crate::files::magic::ELF_MAGIC);
+        header[0x05] = 1;
+        header[8..11].copy_from_slice(&[0x41, 0x49, 0x02]);
+        header[0x12] = 0x04;
+        fs::write(&downloaded_file, header)?;
+
+        let slug = TestEnv::test_slug();
+        let asset_name = String::from("mytool-1.0.0-x86_64.AppImage");
+        let result = process_install(
+            &slug,
+            "1.0.0",
+            &downloaded_file,
+            &download_to,
+            &install_dir,
+            &asset_name,
+            &InstallOptions::default(),
+        );
+
+        let err_msg = format!("{:?}", result.unwrap_err());
+        assert!(
+            err_msg.contains("not built for the current architecture"),
+            "AppImage must be rejected: {}",
+            err_msg
+        );
+        assert!(
+            !install_dir.join("mytool").exists(),
+            "AppImage for another architecture must not be installed"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_process_install_archive_path() -> Result<()> {
         let env = TestEnv::new()?;
@@ -813,6 +976,7 @@ mod process_install_tests {
             &download_to,
             &install_dir,
             &asset_name,
+            &InstallOptions::default(),
         );
 
         // The archive should be extracted and executables installed
@@ -871,7 +1035,13 @@ mod install_binaries_tests {
         fs::write(&archive_path, b"dummy archive")?;
 
         let slug = TestEnv::test_slug();
-        let result = install_binaries(&slug, "1.0.0", temp_extract.path(), &install_dir);
+        let result = install_binaries(
+            &slug,
+            "1.0.0",
+            temp_extract.path(),
+            &install_dir,
+            &InstallOptions::default(),
+        );
 
         // Note: This may fail if bin_dir cannot be created
         match result {
@@ -921,7 +1091,13 @@ mod install_binaries_tests {
         fs::write(&archive_path, b"dummy archive")?;
 
         let slug = TestEnv::test_slug();
-        let result = install_binaries(&slug, "1.0.0", &archive_path, &install_dir);
+        let result = install_binaries(
+            &slug,
+            "1.0.0",
+            &archive_path,
+            &install_dir,
+            &InstallOptions::default(),
+        );
 
         assert!(
             result.is_err(),
@@ -936,6 +1112,111 @@ mod install_binaries_tests {
 
         Ok(())
     }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_process_install_symlinks_every_executable_of_archive() -> Result<()> {
+        let env = TestEnv::new()?;
+        let temp_extract = TempDir::new()?;
+        let install_dir = env.create_dir("install")?;
+
+        // build an archive bundling the main tool plus a helper binary
+        let staging = env.create_dir("staging")?;
+        env.create_platform_executable(&staging.join("foo"))?;
+        env.create_platform_executable(&staging.join("foo-helper"))?;
+        let downloaded_file = env.home_dir.join("foo-1.0.0-linux-x86_64.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&downloaded_file)?,
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_path_with_name(staging.join("foo"), "foo-1.0.0/bin/foo")?;
+        builder.append_path_with_name(staging.join("foo-helper"), "foo-1.0.0/bin/foo-helper")?;
+        builder.into_inner()?.finish()?;
+
+        let slug = TestEnv::test_slug();
+        env.with_test_env(|| {
+            process_install(
+                &slug,
+                "1.0.0",
+                &downloaded_file,
+                &temp_extract.path().to_path_buf(),
+                &install_dir,
+                &String::from("foo-1.0.0-linux-x86_64.tar.gz"),
+                &InstallOptions::default(),
+            )
+        })?;
+
+        let bin_dir = env.with_test_env(datadirs::get_bin_dir).unwrap();
+        for name in ["foo", "foo-helper"] {
+            assert!(
+                install_dir.join(name).exists(),
+                "{} should be installed",
+                name
+            );
+            let link = fs::read_link(bin_dir.join(name))?;
+            assert_eq!(link, install_dir.join(name), "{} should be symlinked", name);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_install_binaries_skips_same_named_executables() -> Result<()> {
+        let env = TestEnv::new()?;
+        let temp_extract = TempDir::new()?;
+        let install_dir = env.create_dir("install")?;
+
+        // the same name in two subdirectories of the archive
+        env.create_platform_executable(&temp_extract.path().join("a/tool"))?;
+        env.create_platform_executable(&temp_extract.path().join("b/tool"))?;
+
+        let slug = TestEnv::test_slug();
+        env.with_test_env(|| {
+            install_binaries(
+                &slug,
+                "1.0.0",
+                temp_extract.path(),
+                &install_dir,
+                &InstallOptions::default(),
+            )
+        })?;
+
+        assert!(install_dir.join("tool").exists());
+        assert_eq!(fs::read_dir(&install_dir)?.count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_install_binaries_rename_rejects_multiple_executables() -> Result<()> {
+        let env = TestEnv::new()?;
+        let temp_extract = TempDir::new()?;
+        let install_dir = env.create_dir("install")?;
+
+        let extracted_dir = temp_extract.path().join("archive");
+        fs::create_dir_all(&extracted_dir)?;
+        env.create_platform_executable(&extracted_dir.join("tool1"))?;
+        env.create_platform_executable(&extracted_dir.join("tool2"))?;
+
+        let slug = TestEnv::test_slug();
+        let options = InstallOptions {
+            rename: Some("tool".to_string()),
+            ..Default::default()
+        };
+        let result = install_binaries(&slug, "1.0.0", temp_extract.path(), &install_dir, &options);
+
+        assert!(
+            result.is_err(),
+            "--rename must fail with multiple executables"
+        );
+        assert!(
+            !install_dir.join("tool1").exists() && !install_dir.join("tool2").exists(),
+            "Nothing should be installed when --rename is ambiguous"
+        );
+
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -996,7 +1277,7 @@ mod select_assets_success_tests {
         let result = temp_env::with_vars(
             vec![("POOF_GITHUB_API_URL", Some(server.url().as_str()))],
             || {
-                let result = select_assets(repo, None);
+                let result = select_assets(repo, None, &AssetTriple::default(), false);
                 mock.assert();
                 result
             },
@@ -1153,11 +1434,55 @@ mod check_for_same_named_binary_in_bin_dir_tests {
                 "Error should mention already installed: {}",
                 err_msg
             );
+            assert!(
+                err_msg.contains("(from otheruser/othertool version 1.0.0)"),
+                "Error should name the conflicting repository and version: {}",
+                err_msg
+            );
+            assert!(
+                err_msg.contains("poof uninstall otheruser/othertool --version 1.0.0"),
+                "Error should suggest uninstalling the conflicting version: {}",
+                err_msg
+            );
+            assert!(
+                err_msg.contains("--rename"),
+                "Error should suggest --rename: {}",
+                err_msg
+            );
         });
 
         Ok(())
     }
 
+    #[test]
+    fn test_symlink_owner() {
+        let data_dir = Path::new("/data");
+        assert_eq!(
+            symlink_owner(
+                Path::new("/data/github.com/owner/tool/1.2.3/tool"),
+                data_dir
+            ),
+            Some(("owner/tool".to_string(), "1.2.3".to_string()))
+        );
+        assert_eq!(
+            symlink_owner(
+                Path::new("/data/gitlab.com/owner/tool/1.2.3/tool"),
+                data_dir
+            ),
+            Some(("gitlab:owner/tool".to_string(), "1.2.3".to_string()))
+        );
+        // not a binary inside a version directory
+        assert_eq!(
+            symlink_owner(Path::new("/data/github.com/owner/tool/1.2.3"), data_dir),
+            None
+        );
+        assert_eq!(symlink_owner(Path::new("/usr/bin/tool"), data_dir), None);
+        assert_eq!(
+            symlink_owner(Path::new("/usr/bin/tool"), Path::new("")),
+            None
+        );
+    }
+
     #[test]
     fn test_non_symlink_file_foreign_binary() -> Result<()> {
         let env = TestEnv::new()?;
@@ -1180,6 +1505,11 @@ mod check_for_same_named_binary_in_bin_dir_tests {
             "Error should mention unrecognized binary: {}",
             err_msg
         );
+        assert!(
+            err_msg.contains("system package manager"),
+            "Error should hint at a system package manager: {}",
+            err_msg
+        );
 
         Ok(())
     }
@@ -1362,3 +1692,533 @@ mod binary_in_path_is_not_managed_by_poof_tests {
         Ok(())
     }
 }
+
+// =============================================================================
+// Tests for install
+// =============================================================================
+
+#[cfg(test)]
+mod install_tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+
+    #[test]
+    fn test_install_keeps_custom_extract_dir() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+        let repo = "testuser/testrepo";
+        let tag = "v1.0.0";
+
+        // Note: we only support x86_64 and aarch64 linux and macOS platform for tests.
+        #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+        let asset_name = "testrepo-linux-x86_64.tar.gz";
+        #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+        let asset_name = "testrepo-linux-aarch64.tar.gz";
+        #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+        let asset_name = "testrepo-darwin-x86_64.tar.gz";
+        #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+        let asset_name = "testrepo-darwin-aarch64.tar.gz";
+
+        // build the archive served as release asset
+        let staging = env.create_dir("staging")?;
+        env.create_platform_executable(&staging.join("testrepo"))?;
+        let archive = env.home_dir.join(asset_name);
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&archive)?,
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_path_with_name(staging.join("testrepo"), "testrepo")?;
+        builder.into_inner()?.finish()?;
+
+        let download_path = format!("/download/{}", asset_name);
+        let release_mock = server
+            .mock("GET", format!("/{}/releases/latest", repo).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "tag_name": tag,
+                    "published_at": "2024-01-01T00:00:00Z",
+                    "assets": [
+                        {
+                            "name": asset_name,
+                            "browser_download_url": format!("{}{}", server.url(), download_path),
+                            "content_type": "application/gzip",
+                        }
+                    ],
+                })
+                .to_string(),
+            )
+            .create();
+        let asset_mock = server
+            .mock("GET", download_path.as_str())
+            .with_status(200)
+            .with_body(fs::read(&archive)?)
+            .create();
+
+        let extract_dir = env.home_dir.join("inspect");
+        let options = InstallOptions {
+            no_cache: true,
+            no_symlink: true,
+            extract_dir: Some(extract_dir.clone()),
+            ..Default::default()
+        };
+        env.with_test_env(|| {
+            temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+                install(repo, None, &AssetTriple::default(), &options)
+            })
+        })?;
+        release_mock.assert();
+        asset_mock.assert();
+
+        // the extracted files are left in the provided directory
+        assert!(extract_dir.join("asset_1").join(asset_name).exists());
+        assert!(extract_dir.join("asset_1").join("testrepo").exists());
+        // and the binary is installed as usual
+        let data_dir = env.with_test_env(datadirs::get_data_dir).unwrap();
+        assert!(datadirs::get_binary_nest(&data_dir, repo, "1.0.0")
+            .join("testrepo")
+            .exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_install_from_url_uses_synthetic_slug() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+
+        // build the archive served at the URL
+        let staging = env.create_dir("staging")?;
+        env.create_platform_executable(&staging.join("tool"))?;
+        let archive = env.home_dir.join("tool.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&archive)?,
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_path_with_name(staging.join("tool"), "tool")?;
+        builder.into_inner()?.finish()?;
+
+        let asset_mock = server
+            .mock("GET", "/nightly/tool-nightly-linux.tar.gz")
+            .with_status(200)
+            .with_body(fs::read(&archive)?)
+            .create();
+        let url = format!("{}/nightly/tool-nightly-linux.tar.gz", server.url());
+
+        let options = InstallOptions {
+            no_symlink: true,
+            ..Default::default()
+        };
+        env.with_test_env(|| install_from_url(&url, None, None, &options))?;
+        asset_mock.assert();
+
+        // mockito listens on 127.0.0.1
+        let slug = "127.0.0.1/tool";
+        let data_dir = env.with_test_env(datadirs::get_data_dir).unwrap();
+        let install_dir = datadirs::get_binary_nest(&data_dir, slug, "latest");
+        assert!(install_dir.join("tool").exists());
+        assert_eq!(metadata::load_source(&install_dir), Source::Url);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_install_from_url_with_name_and_tag() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+
+        let staging = env.create_dir("staging")?;
+        let binary = staging.join("tool-linux");
+        env.create_platform_executable(&binary)?;
+        let asset_mock = server
+            .mock("GET", "/tool-linux")
+            .with_status(200)
+            .with_body(fs::read(&binary)?)
+            .create();
+        let url = format!("{}/tool-linux", server.url());
+
+        let options = InstallOptions {
+            no_symlink: true,
+            ..Default::default()
+        };
+        env.with_test_env(|| install_from_url(&url, Some("acme/tool"), Some("v2.0.0"), &options))?;
+        asset_mock.assert();
+
+        let data_dir = env.with_test_env(datadirs::get_data_dir).unwrap();
+        assert!(datadirs::get_binary_nest(&data_dir, "acme/tool", "2.0.0")
+            .join("tool")
+            .exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_install_bare_binary_ignores_declared_content_type() -> Result<()> {
+        for content_type in [
+            "application/octet-stream",
+            "application/x-executable",
+            "application/x-mach-binary",
+            "application/x-msdownload",
+        ] {
+            let env = TestEnv::new()?;
+            let mut server = Server::new();
+
+            // real ELF (Linux) or Mach-O (macOS) magic bytes
+            let staging = env.create_dir("staging")?;
+            let binary = staging.join("tool");
+            env.create_platform_executable(&binary)?;
+            let asset_mock = server
+                .mock("GET", "/tool")
+                .with_status(200)
+                .with_header("content-type", content_type)
+                .with_body(fs::read(&binary)?)
+                .create();
+            let url = format!("{}/tool", server.url());
+
+            let options = InstallOptions {
+                no_symlink: true,
+                ..Default::default()
+            };
+            env.with_test_env(|| install_from_url(&url, Some("acme/tool"), None, &options))
+                .map_err(|e| anyhow!("{}: {:#}", content_type, e))?;
+            asset_mock.assert();
+
+            let data_dir = env.with_test_env(datadirs::get_data_dir).unwrap();
+            assert!(
+                datadirs::get_binary_nest(&data_dir, "acme/tool", "latest")
+                    .join("tool")
+                    .exists(),
+                "Binary served as {} should be installed",
+                content_type
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_install_links_into_custom_bin_dir() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+
+        let staging = env.create_dir("staging")?;
+        let binary = staging.join("tool-linux");
+        env.create_platform_executable(&binary)?;
+        let asset_mock = server
+            .mock("GET", "/tool-linux")
+            .with_status(200)
+            .with_body(fs::read(&binary)?)
+            .create();
+        let url = format!("{}/tool-linux", server.url());
+        let team_bin = env.home_dir.join("team-bin");
+
+        let options = InstallOptions {
+            bin_dir: Some(team_bin.clone()),
+            ..Default::default()
+        };
+        env.with_test_env(|| install_from_url(&url, Some("acme/tool"), Some("1.0.0"), &options))?;
+        asset_mock.assert();
+
+        let data_dir = env.with_test_env(datadirs::get_data_dir).unwrap();
+        let install_dir = datadirs::get_binary_nest(&data_dir, "acme/tool", "1.0.0");
+        assert_eq!(
+            fs::read_link(team_bin.join("tool"))?,
+            install_dir.join("tool")
+        );
+        assert_eq!(metadata::load_bin_dir(&install_dir), Some(team_bin.clone()));
+        let default_bin = env.with_test_env(datadirs::get_bin_dir).unwrap();
+        assert!(!default_bin.join("tool").exists());
+
+        // 'use' links the version into the recorded directory again
+        fs::remove_file(team_bin.join("tool"))?;
+        env.with_test_env(|| commands::make_default::set_default("acme/tool", None))?;
+        assert!(team_bin.join("tool").is_symlink());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_version_constraint_picks_newest_match() -> Result<()> {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/testuser/testrepo/releases")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!([
+                    {"tag_name": "v2.0.0", "published_at": "2024-04-01T00:00:00Z", "assets": []},
+                    {"tag_name": "v1.4.1", "published_at": "2024-03-01T00:00:00Z", "assets": []},
+                    {"tag_name": "v1.10.0", "published_at": "2024-02-01T00:00:00Z", "assets": []},
+                    {"tag_name": "v1.1.0", "published_at": "2024-01-01T00:00:00Z", "assets": []},
+                ])
+                .to_string(),
+            )
+            .expect(2)
+            .create();
+
+        temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+            let req = semver::VersionReq::parse("^1.2").unwrap();
+            assert_eq!(
+                resolve_version_constraint("testuser/testrepo", &req).unwrap(),
+                "v1.10.0"
+            );
+            let req = semver::VersionReq::parse(">=3").unwrap();
+            let err = resolve_version_constraint("testuser/testrepo", &req).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("No release of testuser/testrepo satisfies"));
+        });
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slug_from_url() {
+        assert_eq!(
+            slug_from_url("https://example.com/nightly/tool-linux-x86_64.tar.gz").as_deref(),
+            Some("example.com/tool")
+        );
+        assert_eq!(
+            slug_from_url("http://user@downloads.example.org:8080/tool_v1.zip?x=1").as_deref(),
+            Some("downloads.example.org/tool")
+        );
+        assert_eq!(slug_from_url("https://example.com/"), None);
+        assert_eq!(slug_from_url("https://example.com"), None);
+    }
+
+    #[test]
+    fn test_url_file_name() {
+        assert_eq!(
+            url_file_name("https://example.com/a/tool.tar.gz#top"),
+            Some("tool.tar.gz")
+        );
+        assert_eq!(url_file_name("https://example.com/a/"), None);
+    }
+}
+
+// =============================================================================
+// Tests for checksum verification
+// =============================================================================
+
+#[cfg(test)]
+mod checksum_tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+
+    const HELLO_SHA256: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+
+    /// Build a release with `tool.tar.gz` and the given checksum assets, served by `server`.
+    fn release_with_checksums(server: &Server, checksum_files: &[&str]) -> Release {
+        let mut assets = vec![json!({
+            "name": "tool.tar.gz",
+            "browser_download_url": format!("{}/tool.tar.gz", server.url()),
+        })];
+        for name in checksum_files {
+            assets.push(json!({
+                "name": name,
+                "browser_download_url": format!("{}/{}", server.url(), name),
+            }));
+        }
+        serde_json::from_value(json!({
+            "tag_name": "v1.0.0",
+            "published_at": "2024-01-01T00:00:00Z",
+            "assets": assets,
+        }))
+        .unwrap()
+    }
+
+    fn downloaded_file(env: &TestEnv) -> Result<PathBuf> {
+        let file = env.create_dir("download")?.join("tool.tar.gz");
+        fs::write(&file, b"hello\n")?;
+        Ok(file)
+    }
+
+    #[test]
+    fn test_verify_asset_checksum_from_list() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/checksums.txt")
+            .with_body(format!(
+                "{}  other.tar.gz\n{}  tool.tar.gz\n",
+                "0".repeat(64),
+                HELLO_SHA256
+            ))
+            .create();
+        let release = release_with_checksums(&server, &["checksums.txt"]);
+
+        assert!(verify_asset_checksum(
+            &release,
+            "tool.tar.gz",
+            &downloaded_file(&env)?
+        )?);
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_asset_checksum_prefers_dedicated_file() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+        let dedicated = server
+            .mock("GET", "/tool.tar.gz.sha256")
+            .with_body(format!("{}\n", HELLO_SHA256))
+            .create();
+        let list = server.mock("GET", "/checksums.txt").expect(0).create();
+        let release = release_with_checksums(&server, &["checksums.txt", "tool.tar.gz.sha256"]);
+
+        assert!(verify_asset_checksum(
+            &release,
+            "tool.tar.gz",
+            &downloaded_file(&env)?
+        )?);
+        dedicated.assert();
+        list.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_asset_checksum_mismatch() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/checksums.txt")
+            .with_body(format!("{}  tool.tar.gz\n", "0".repeat(64)))
+            .create();
+        let release = release_with_checksums(&server, &["checksums.txt"]);
+
+        let err = verify_asset_checksum(&release, "tool.tar.gz", &downloaded_file(&env)?)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Checksum mismatch"),
+            "Unexpected error: {}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_asset_checksum_not_published() -> Result<()> {
+        let env = TestEnv::new()?;
+        let server = Server::new();
+        let release = release_with_checksums(&server, &[]);
+
+        assert!(!verify_asset_checksum(
+            &release,
+            "tool.tar.gz",
+            &downloaded_file(&env)?
+        )?);
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests for cosign verification
+// =============================================================================
+
+#[cfg(all(test, feature = "cosign"))]
+mod cosign_tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+
+    const FIXTURES: &str = "tests/fixtures/cosign";
+
+    /// Build a release with `artifact.txt` and the given bundle assets, served by `server`.
+    fn release_with_bundles(server: &Server, bundle_files: &[&str]) -> Release {
+        let assets: Vec<serde_json::Value> = std::iter::once("artifact.txt")
+            .chain(bundle_files.iter().copied())
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "browser_download_url": format!("{}/{}", server.url(), name),
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "tag_name": "v1.0.0",
+            "published_at": "2024-01-01T00:00:00Z",
+            "assets": assets,
+        }))
+        .unwrap()
+    }
+
+    fn serve_bundle(server: &mut Server) -> mockito::Mock {
+        server
+            .mock("GET", "/artifact.txt.sigstore.json")
+            .with_body(
+                fs::read_to_string(Path::new(FIXTURES).join("artifact.txt.sigstore.json")).unwrap(),
+            )
+            .create()
+    }
+
+    #[test]
+    fn test_verify_asset_signature_valid() -> Result<()> {
+        let mut server = Server::new();
+        let mock = serve_bundle(&mut server);
+        let release = release_with_bundles(&server, &["artifact.txt.sigstore.json"]);
+
+        verify_asset_signature(
+            Some(&release),
+            "artifact.txt",
+            &Path::new(FIXTURES).join("artifact.txt"),
+            &Path::new(FIXTURES).join("trusted_root.json"),
+        )?;
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_asset_signature_tampered_asset() -> Result<()> {
+        let env = TestEnv::new()?;
+        let mut server = Server::new();
+        let _mock = serve_bundle(&mut server);
+        let release = release_with_bundles(&server, &["artifact.txt.sigstore.json"]);
+        let tampered = env.create_dir("download")?.join("artifact.txt");
+        fs::write(&tampered, b"tampered\n")?;
+
+        let err = verify_asset_signature(
+            Some(&release),
+            "artifact.txt",
+            &tampered,
+            &Path::new(FIXTURES).join("trusted_root.json"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("Cosign verification failed for artifact.txt"),
+            "Unexpected error: {}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_asset_signature_bundle_not_published() {
+        let server = Server::new();
+        let release = release_with_bundles(&server, &[]);
+
+        let err = verify_asset_signature(
+            Some(&release),
+            "artifact.txt",
+            &Path::new(FIXTURES).join("artifact.txt"),
+            &Path::new(FIXTURES).join("trusted_root.json"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("No sigstore bundle published"),
+            "Unexpected error: {}",
+            err
+        );
+    }
+}
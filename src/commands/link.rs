@@ -0,0 +1,88 @@
+//! Main file handling 'link' command
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info, warn};
+
+use crate::cli::LinkArgs;
+use crate::commands::install::check_for_same_named_binary_in_bin_dir;
+use crate::commands::make_default::{get_installed_dir, get_latest_version};
+use crate::files::datadirs;
+use crate::files::filesys;
+use crate::files::metadata;
+use crate::models::slug::Slug;
+
+/// Create the bin directory symlinks of an installed version of a repository.
+///
+/// This adds back to `PATH` the binaries installed with `--no-symlink` or removed
+/// with 'unlink'. When no version is given the newest installed one is linked.
+/// Symlinks of other repositories with the same name are only replaced when
+/// `--force` is set, and regular files are never replaced.
+pub fn run_link(args: &LinkArgs) -> Result<()> {
+    let repo = &args.target.repo;
+    let version = match &args.target.version {
+        Some(version) => version.clone(),
+        None => get_latest_version(repo).with_context(|| {
+            format!("Failed to find the newest installed version for '{}'", repo)
+        })?,
+    };
+    let install_dir = get_installed_dir(repo, &version)?;
+    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory")?;
+    let slug = Slug::new(repo)?;
+
+    let mut execs: Vec<_> = filesys::find_exec_files_in_dir(&install_dir, false)
+        .into_iter()
+        .filter(filesys::is_executable)
+        .collect();
+    execs.sort();
+    if execs.is_empty() {
+        bail!(
+            "No executables found for version {} of '{}' in {}",
+            version,
+            repo,
+            install_dir.display()
+        );
+    }
+
+    let mut linked: Vec<String> = Vec::new();
+    for path in execs {
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        // honour the custom name the binary was installed with, if any
+        let link_name = metadata::link_name_for(&install_dir, &file_name.to_string_lossy());
+        let symlink_path = bin_dir.join(&link_name);
+
+        if let Err(e) = check_for_same_named_binary_in_bin_dir(&slug, &symlink_path) {
+            if !(args.force && symlink_path.is_symlink()) {
+                if symlink_path.is_symlink() {
+                    info!("Use --force to replace it.");
+                }
+                return Err(e);
+            }
+            warn!("{} Replacing it as requested.", e);
+        }
+        // broken symlinks do not count as existing files, remove them first
+        if filesys::is_broken_symlink(&symlink_path).unwrap_or(false) {
+            debug!("Removing broken symlink {}", symlink_path.display());
+            std::fs::remove_file(&symlink_path)
+                .with_context(|| format!("Cannot remove {}", symlink_path.display()))?;
+        }
+
+        filesys::create_symlink(&path, &symlink_path, true)
+            .map_err(anyhow::Error::msg)
+            .with_context(|| {
+                format!(
+                    "Cannot create symlink from {} to {}",
+                    path.display(),
+                    symlink_path.display()
+                )
+            })?;
+        linked.push(link_name);
+    }
+
+    info!("Version {} of {} linked:", version, repo);
+    for binary in linked {
+        info!("✓ {}", binary);
+    }
+    Ok(())
+}
@@ -1,16 +1,401 @@
 //! Main file handling 'list' command
 
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use anyhow::{anyhow, Context, Result};
+use log::{debug, info, log_enabled, warn, Level};
 
-use crate::files::datadirs::{get_data_dir, get_versions_nest};
+use crate::cli::SortField;
+use crate::files::datadirs::{
+    get_bin_dir, get_binary_nest, get_data_dir, get_versions_nest, split_repo_path,
+};
+use crate::files::{filesys, metadata, pins};
 use crate::models::slug::Slug;
+use crate::models::source::Source;
 use crate::models::spell::Spell;
-use crate::utils::semver::Version;
+use crate::output;
+use crate::utils::semver::{SemverStringConversion, SemverStringPrefix, Version};
+use crate::utils::template::Template;
+use crate::utils::time::format_system_time;
+
+/// Placeholders accepted by `list --format`.
+const LIST_FORMAT_TOKENS: &[&str] = &[
+    "repo",
+    "active_version",
+    "versions",
+    "latest_version",
+    "count",
+];
+
+/// How `list` prints the installed spells.
+pub enum ListFormat {
+    /// The default human-readable table, with the install time and the checksums
+    /// of the installed assets of each version when verbose.
+    Table {
+        /// Show the install time and the checksums of the installed assets.
+        verbose: bool,
+        /// Add the latest release of each spell, fetched waiting this long between
+        /// two requests.
+        outdated: Option<Duration>,
+    },
+    /// One line per spell rendered from a user-provided template.
+    Template(Template),
+    /// A JSON array of objects.
+    Json,
+}
+
+impl ListFormat {
+    /// Build the output format from the `list` CLI options.
+    /// Templates are validated here, before anything is printed.
+    pub fn from_args(
+        format: Option<&str>,
+        json: bool,
+        verbose: bool,
+        outdated: Option<Duration>,
+    ) -> Result<Self> {
+        match format {
+            Some(format) => Ok(Self::Template(Template::parse(format, LIST_FORMAT_TOKENS)?)),
+            None if json => Ok(Self::Json),
+            None => Ok(Self::Table { verbose, outdated }),
+        }
+    }
+}
+
+/// Print `spells` to stdout using `format`.
+pub fn print_spells(spells: &[Spell], format: &ListFormat) -> Result<()> {
+    match format {
+        ListFormat::Table { verbose, outdated } => {
+            if spells.is_empty() {
+                info!("No installed binaries found.");
+                return Ok(());
+            }
+            if let Some(delay) = outdated {
+                return print_with_latest(spells, *delay);
+            }
+            let active = active_versions();
+            output!("");
+            output!("{:<40}\t{}", "Repository", "Versions");
+            output!("{:<40}\t{}", "----------", "--------");
+            for spell in spells {
+                // spells without any symlink in the bin directory are not in PATH
+                let name = if active.contains_key(spell.get_name()) {
+                    spell.get_name().to_string()
+                } else {
+                    format!("{} (unlinked)", spell.get_name())
+                };
+                let mut versions = mark_active(
+                    &spell.get_versions().to_string_vec(),
+                    active.get(spell.get_name()),
+                );
+                if let Some(pinned) = pins::is_pinned(spell.get_name()) {
+                    versions.push_str(&format!(" [pinned {}]", pinned));
+                }
+                output!("{:<40}\t{}", name, versions);
+                if *verbose {
+                    print_version_details(spell);
+                }
+            }
+        }
+        ListFormat::Template(template) => {
+            let active = active_versions();
+            for spell in spells {
+                let active_version = active.get(spell.get_name()).cloned().unwrap_or_default();
+                output!(
+                    "{}",
+                    template.render(|token| spell_field(spell, &active_version, token))
+                );
+            }
+        }
+        ListFormat::Json => {
+            let active = active_versions();
+            let items: Vec<serde_json::Value> = spells
+                .iter()
+                .map(|spell| {
+                    serde_json::json!({
+                        "repo": spell.get_name(),
+                        "active_version": active.get(spell.get_name()),
+                        "versions": spell.get_versions().to_string_vec(),
+                        "latest_version": spell.get_latest_version(),
+                        "count": spell.get_versions().len(),
+                        "pinned": pins::is_pinned(spell.get_name()),
+                        "installed_at": spell
+                            .get_versions()
+                            .to_string_vec()
+                            .into_iter()
+                            .map(|version| {
+                                let installed_at = installed_at(spell, &version);
+                                (version, installed_at)
+                            })
+                            .collect::<BTreeMap<_, _>>(),
+                    })
+                })
+                .collect();
+            output!(
+                "{}",
+                serde_json::to_string_pretty(&items).context("Cannot serialize list to JSON")?
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Join `versions` for the table output, marking the one the symlinks point to.
+fn mark_active(versions: &[String], active: Option<&String>) -> String {
+    versions
+        .iter()
+        .map(|version| {
+            if Some(version) == active {
+                format!("{} (active)", version)
+            } else {
+                version.clone()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Fetch the version of the latest release of `spell` from the service it was installed from,
+/// without its `v` prefix. Returns `None` when it cannot be fetched, e.g. because of rate limits.
+fn latest_version_of(data_dir: &Path, spell: &Spell, installed: &str) -> Option<String> {
+    let source = metadata::load_source(&get_binary_nest(data_dir, spell.get_name(), installed));
+    match source.get_release(spell.get_name(), None, true) {
+        Ok(release) => Some(release.tag_name().strip_v()),
+        Err(e) => {
+            warn!(
+                "Cannot get the latest release of {}: {:#}",
+                spell.get_name(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Fetch the latest release version of each of `spells`, in order, `None` for those
+/// whose release cannot be fetched.
+///
+/// Releases are fetched one repository at a time, waiting `delay` between two requests
+/// to go easy on rate limits. Progress is shown on stderr when it is a terminal.
+fn fetch_latest_versions(spells: &[Spell], delay: Duration) -> Result<Vec<Option<String>>> {
+    let data_dir = get_data_dir().context("Cannot get data directory")?;
+    let show_progress = io::stderr().is_terminal() && log_enabled!(Level::Info);
+    let mut latest: Vec<Option<String>> = Vec::with_capacity(spells.len());
+    for (i, spell) in spells.iter().enumerate() {
+        if show_progress {
+            eprint!("\rChecking {}/{} repos...", i + 1, spells.len());
+        } else {
+            debug!("Checking {}/{} repos...", i + 1, spells.len());
+        }
+        if i > 0 && !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        latest.push(
+            spell
+                .get_latest_version()
+                .and_then(|installed| latest_version_of(&data_dir, spell, &installed)),
+        );
+    }
+    if show_progress {
+        // clear the progress line
+        eprint!("\r{:width$}\r", "", width = 40);
+    }
+    Ok(latest)
+}
+
+/// Print every one of `spells` with its installed versions and its latest release,
+/// marked when it is newer than the highest installed version. A latest release
+/// that cannot be fetched is shown as `unknown`. See [`fetch_latest_versions`].
+fn print_with_latest(spells: &[Spell], delay: Duration) -> Result<()> {
+    let latest_versions = fetch_latest_versions(spells, delay)?;
+    let active = active_versions();
+    output!("");
+    output!("{:<40}\t{:<32}\t{}", "Repository", "Versions", "Latest");
+    output!("{:<40}\t{:<32}\t{}", "----------", "--------", "------");
+    for (spell, latest) in spells.iter().zip(latest_versions) {
+        let versions = mark_active(
+            &spell.get_versions().to_string_vec(),
+            active.get(spell.get_name()),
+        );
+        let latest = match (latest, spell.get_latest_version()) {
+            (Some(latest), Some(installed))
+                if Version::from(latest.as_str()) > Version::from(installed.as_str()) =>
+            {
+                format!("{} (update available)", latest)
+            }
+            (Some(latest), _) => latest,
+            (None, _) => "unknown".to_string(),
+        };
+        output!("{:<40}\t{:<32}\t{}", spell.get_name(), versions, latest);
+    }
+    Ok(())
+}
+
+/// Print the installed repositories with a newer release available, along with the
+/// highest installed version and the latest one.
+///
+/// Releases are fetched as described in [`fetch_latest_versions`]. Repositories whose
+/// latest release cannot be fetched are listed with `?` as latest version, as they
+/// cannot be told up to date.
+pub fn print_outdated(spells: &[Spell], delay: Duration) -> Result<()> {
+    let latest_versions = fetch_latest_versions(spells, delay)?;
+    let mut outdated: Vec<(&str, String, String)> = Vec::new();
+    for (spell, latest) in spells.iter().zip(latest_versions) {
+        let Some(installed) = spell.get_latest_version() else {
+            continue;
+        };
+        match latest {
+            Some(latest) => {
+                if Version::from(latest.as_str()) > Version::from(installed.as_str()) {
+                    outdated.push((spell.get_name(), installed, latest));
+                }
+            }
+            None => outdated.push((spell.get_name(), installed, "?".to_string())),
+        }
+    }
+
+    if outdated.is_empty() {
+        info!("All installed binaries are up to date.");
+        return Ok(());
+    }
+    output!("");
+    output!("{:<40}\t{:<16}\t{}", "Repository", "Installed", "Latest");
+    output!("{:<40}\t{:<16}\t{}", "----------", "---------", "------");
+    for (repo, installed, latest) in outdated {
+        output!("{:<40}\t{:<16}\t{}", repo, installed, latest);
+    }
+    Ok(())
+}
+
+/// Returns when `version` of `spell` has been installed, formatted for display.
+fn installed_at(spell: &Spell, version: &str) -> Option<String> {
+    let data_dir = get_data_dir()?;
+    metadata::load_installed_at(&get_binary_nest(&data_dir, spell.get_name(), version))
+        .map(format_system_time)
+}
+
+/// Print when each version of `spell` has been installed, and the checksums of
+/// the assets it has been installed from.
+fn print_version_details(spell: &Spell) {
+    let Some(data_dir) = get_data_dir() else {
+        return;
+    };
+    for version in spell.get_versions().to_string_vec() {
+        let version_dir = get_binary_nest(&data_dir, spell.get_name(), &version);
+        let installed_at = metadata::load_installed_at(&version_dir).map(format_system_time);
+        output!(
+            "  {} installed at {}",
+            version,
+            installed_at.as_deref().unwrap_or("unknown")
+        );
+        for (asset_name, hash) in metadata::load_asset_checksums(&version_dir) {
+            output!("  {} {}  sha256:{}", version, asset_name, hash);
+        }
+    }
+}
+
+/// Returns the time the newest version of `spell` was installed at.
+fn last_install_time(data_dir: &Path, spell: &Spell) -> SystemTime {
+    spell
+        .get_versions()
+        .iter()
+        .filter_map(|version| {
+            let dir = get_binary_nest(data_dir, spell.get_name(), &version.to_string());
+            metadata::load_installed_at(&dir)
+        })
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Returns the disk space used by all the installed versions of `spell`.
+fn installed_size(data_dir: &Path, spell: &Spell) -> u64 {
+    spell
+        .get_versions()
+        .iter()
+        .filter_map(|version| {
+            let dir = get_binary_nest(data_dir, spell.get_name(), &version.to_string());
+            filesys::dir_total_size(&dir).ok()
+        })
+        .sum()
+}
+
+/// Sort `spells` by `field`, in descending order when `reverse` is set.
+///
+/// Spells with the same key keep their alphabetical order.
+pub fn sort_spells(spells: &mut [Spell], field: SortField, reverse: bool) {
+    spells.sort();
+    if let Some(data_dir) = get_data_dir() {
+        match field {
+            SortField::Repo => {}
+            SortField::Version => {
+                spells.sort_by_cached_key(|spell| spell.get_versions().iter().max().cloned())
+            }
+            SortField::Date => {
+                spells.sort_by_cached_key(|spell| last_install_time(&data_dir, spell))
+            }
+            SortField::Size => spells.sort_by_cached_key(|spell| installed_size(&data_dir, spell)),
+        }
+    }
+    if reverse {
+        spells.reverse();
+    }
+}
+
+/// Returns the value of the `list --format` placeholder `token` for `spell`.
+fn spell_field(spell: &Spell, active_version: &str, token: &str) -> String {
+    match token {
+        "repo" => spell.get_name().to_string(),
+        "active_version" => active_version.to_string(),
+        "versions" => spell.get_versions().to_string_vec().join(", "),
+        "latest_version" => spell.get_latest_version().unwrap_or_default(),
+        "count" => spell.get_versions().len().to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Returns the version currently in use for each installed slug.
+///
+/// The version in use is the one the symlinks in the bin directory point to.
+/// Slugs without any symlink in the bin directory are not included.
+pub fn active_versions() -> HashMap<String, String> {
+    let mut result: HashMap<String, String> = HashMap::new();
+    let (Some(bin_dir), Some(data_dir)) = (get_bin_dir(), get_data_dir()) else {
+        return result;
+    };
+    let Ok(entries) = fs::read_dir(&bin_dir) else {
+        return result;
+    };
+    for entry in entries.flatten() {
+        let Ok(target) = fs::read_link(entry.path()) else {
+            continue;
+        };
+        // symlinks point to data_dir/host/username/reponame/version/binary
+        let Some((repo, parts)) = split_repo_path(&data_dir, &target) else {
+            continue;
+        };
+        if let [version, ..] = parts.as_slice() {
+            result.insert(repo, version.clone());
+        }
+    }
+    result
+}
+
+/// Returns `true` when `version_dir` holds an installed version: a directory, not
+/// a dot-directory like the pins one, with at least one executable inside.
+/// Directories left behind by failed installs are not versions.
+fn is_installed_version(version_dir: &Path) -> bool {
+    version_dir.is_dir()
+        && !version_dir
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        && metadata::list_binaries(version_dir)
+            .iter()
+            .any(|binary| filesys::is_executable(&version_dir.join(binary)))
+}
 
 /// List all installed spells in the data directory.
 pub fn list_installed_spells() -> Vec<Spell> {
@@ -30,18 +415,29 @@ pub fn list_installed_spells() -> Vec<Spell> {
         .ok_or_else(|| anyhow!("Cannot get data directory"))
         .unwrap();
 
-    // Look through each subdirectory in data_dir for any installed spells.
+    // Look through each host subdirectory in data_dir for any installed spells.
     // Read user directories in parallel.
 
     let entries = match fs::read_dir(&data_dir) {
-        Ok(entries) => entries.flatten().collect::<Vec<_>>(),
+        Ok(entries) => entries
+            .flatten()
+            .filter(|host| host.path().is_dir())
+            .flat_map(|host| {
+                let source = Source::from_data_subdir(&host.file_name().to_string_lossy());
+                fs::read_dir(host.path())
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(move |user| (source.clone(), user))
+            })
+            .collect::<Vec<_>>(),
         Err(_) => return Vec::new(),
     };
 
     let spells: Vec<(String, String)> = entries
         .into_par_iter()
-        .filter(|user| user.path().is_dir())
-        .flat_map(|user| {
+        .filter(|(_, user)| user.path().is_dir())
+        .flat_map(|(source, user)| {
             let username = user.file_name().into_string().unwrap_or_default();
             fs::read_dir(user.path())
                 .ok()
@@ -51,7 +447,7 @@ pub fn list_installed_spells() -> Vec<Spell> {
                 .filter(|repo| repo.path().is_dir())
                 .flat_map(move |repo| {
                     let repo_name = repo.file_name().into_string().unwrap_or_default();
-                    let slug = format!("{}/{}", username, repo_name);
+                    let slug = source.qualify(&format!("{}/{}", username, repo_name));
 
                     fs::read_dir(repo.path())
                         .ok()
@@ -59,13 +455,7 @@ pub fn list_installed_spells() -> Vec<Spell> {
                         .flatten()
                         .flatten()
                         .filter_map(move |version| {
-                            let version_path = version.path();
-                            if version_path.is_dir()
-                                && version_path
-                                    .read_dir()
-                                    .map(|mut d| d.next().is_some())
-                                    .unwrap_or(false)
-                            {
+                            if is_installed_version(&version.path()) {
                                 let version_name =
                                     version.file_name().into_string().unwrap_or_default();
                                 Some((slug.clone(), version_name))
@@ -94,6 +484,15 @@ pub fn list_installed_spells() -> Vec<Spell> {
     result
 }
 
+/// List the installed spells of the given GitHub user or organisation.
+pub fn list_installed_spells_per_owner(owner: &str) -> Vec<Spell> {
+    let prefix = format!("{}/", owner);
+    list_installed_spells()
+        .into_iter()
+        .filter(|spell| Source::split(spell.get_name()).1.starts_with(&prefix))
+        .collect()
+}
+
 /// List all installed versions of a spell for a given slug in the data directory.
 pub fn list_installed_versions_per_slug(slug: &Slug) -> Result<Option<Spell>> {
     let data_dir: PathBuf = get_data_dir().context("Cannot get data directory")?;
@@ -112,15 +511,7 @@ pub fn list_installed_versions_per_slug(slug: &Slug) -> Result<Option<Spell>> {
         // not going parallel here because it's unlikely the user has that many versions.
         // to go parallel we should implement FromParallelIterator for Spell.
         .into_iter()
-        .filter(|version| {
-            version.path().is_dir()
-                // assure the directory is not empty
-                && version
-                    .path()
-                    .read_dir()
-                    .map(|mut d| d.next().is_some())
-                    .unwrap_or(false)
-        })
+        .filter(|version| is_installed_version(&version.path()))
         .map(|version| Version::new(version.file_name().into_string().unwrap_or_default()))
         .collect::<Vec<_>>();
 
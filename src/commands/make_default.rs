@@ -6,7 +6,8 @@ use log::{error, info};
 
 use crate::files::datadirs;
 use crate::files::filesys;
-use crate::files::utils::find_similar_repo;
+use crate::files::metadata;
+use crate::files::utils::similar_repos_hint;
 use crate::utils::semver::SemverSort;
 
 /// Check if a repository is installed, providing helpful error messages if not.
@@ -17,11 +18,8 @@ fn check_repo_installed(repo: &str) -> Result<PathBuf> {
 
     if !versions_dir.exists() {
         // Try fuzzy finding a similar named installed repository
-        if let Some(similar_repo) = find_similar_repo(&data_dir, repo) {
-            error!(
-                "It looks like '{}' is not installed. Did you mean: {}",
-                repo, similar_repo
-            );
+        if let Some(hint) = similar_repos_hint(&data_dir, repo) {
+            error!("It looks like '{}' is not installed. {}", repo, hint);
         } else {
             error!("It looks like '{}' is not installed. Typo?", repo);
         }
@@ -47,7 +45,9 @@ pub(crate) fn get_latest_version(repo: &str) -> Result<String> {
     for entry in entries.flatten() {
         if let Ok(file_type) = entry.file_type() {
             if file_type.is_dir() {
-                if let Some(version_name) = entry.file_name().to_str() {
+                if let Some(version_name) =
+                    entry.file_name().to_str().filter(|n| !n.starts_with('.'))
+                {
                     versions.push(version_name.to_string());
                 }
             }
@@ -77,7 +77,7 @@ pub(crate) fn get_latest_version(repo: &str) -> Result<String> {
 }
 
 /// Returns the data-directory path for the given repo/version, checking that it exists.
-fn get_installed_dir(repo: &str, version: &str) -> Result<PathBuf> {
+pub(crate) fn get_installed_dir(repo: &str, version: &str) -> Result<PathBuf> {
     // Check repository exists
     check_repo_installed(repo).with_context(|| {
         error!("Check installed binaries using 'list' command.");
@@ -101,9 +101,11 @@ fn get_installed_dir(repo: &str, version: &str) -> Result<PathBuf> {
 /// Set a specific (or the latest) installed version of `repo` as the default.
 ///
 /// Updates the symlinks in the bin directory to point to the requested version.
+/// Versions installed with `--bin-dir` are linked into the recorded directory instead.
 /// When `version` is `None`, the highest semantically-versioned installed release
 /// is selected automatically via [`get_latest_version`].
-pub fn set_default(repo: &str, version: Option<&str>) -> Result<()> {
+/// Returns the version set as default.
+pub fn set_default(repo: &str, version: Option<&str>) -> Result<String> {
     // Resolve version: use provided version or get latest
     let resolved_version = match version {
         Some(v) => v.to_string(),
@@ -118,8 +120,8 @@ pub fn set_default(repo: &str, version: Option<&str>) -> Result<()> {
 
     // Get the installed directory for the specified repo and version
     let install_dir = get_installed_dir(repo, &resolved_version)?;
-    // Get the bin directory
-    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory")?;
+    // Get the bin directory the version is linked into
+    let bin_dir = metadata::bin_dir_for(&install_dir).context("Cannot get bin directory")?;
 
     // List of binaries to set as default
     let mut binaries: Vec<String> = Vec::new();
@@ -127,35 +129,33 @@ pub fn set_default(repo: &str, version: Option<&str>) -> Result<()> {
     for path in filesys::find_exec_files_in_dir(&install_dir, false) {
         // Skip non-executable files (they all should be since they have
         // been installed, but just in case).
-        // Note: Windows does not require setting executable permissions
-        #[cfg(not(target_os = "windows"))]
-        {
-            if !filesys::is_executable(&path) {
-                continue;
-            }
-            // Get exec filename
-            let Some(file_name) = path.file_name() else {
-                continue;
-            };
-            binaries.push(file_name.to_string_lossy().to_string());
-            // make exec available in PATH, overwriting any existing symlink
-            let symlink_path = bin_dir.join(file_name);
-            filesys::create_symlink(&path, &symlink_path, true)
-                .map_err(anyhow::Error::msg)
-                .with_context(|| {
-                    format!(
-                        "Cannot create symlink from {} to {}",
-                        path.display(),
-                        symlink_path.display()
-                    )
-                })?;
+        if !filesys::is_executable(&path) {
+            continue;
         }
+        // Get exec filename
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        // honour the custom name the binary was installed with, if any
+        let link_name = metadata::link_name_for(&install_dir, &file_name.to_string_lossy());
+        // make exec available in PATH, overwriting any existing symlink
+        let symlink_path = bin_dir.join(&link_name);
+        binaries.push(link_name);
+        filesys::create_symlink(&path, &symlink_path, true)
+            .map_err(anyhow::Error::msg)
+            .with_context(|| {
+                format!(
+                    "Cannot create symlink from {} to {}",
+                    path.display(),
+                    symlink_path.display()
+                )
+            })?;
     }
     info!("Version {} set as default for:", resolved_version);
     for binary in binaries {
         info!("✓ {}", binary);
     }
-    Ok(())
+    Ok(resolved_version)
 }
 
 #[cfg(test)]
@@ -1,29 +1,57 @@
+/// Displays the release notes of a GitHub release.
+pub mod changelog;
 /// Verifies that the poof bin directory is present in PATH.
 pub mod check;
 /// Empties the poof cache directory.
 pub mod clean;
 /// Generates shell completion scripts.
 pub mod completions;
+/// Removes the poof bin directory from a shell's PATH configuration.
+pub mod disable;
 /// Downloads a GitHub release asset to the current directory.
 pub mod download;
+/// Reports the disk space used by installed repositories.
+pub mod du;
 /// Persistently adds the poof bin directory to a shell's PATH configuration.
 pub mod enable;
+/// Documents the environment variables poof recognises.
+pub mod env_vars;
+/// Removes installed versions no symlink points to.
+pub mod gc;
+/// Displays the log of past install, update, uninstall and use operations.
+pub mod history;
 /// Displays poof installation and environment information.
 pub mod info;
 /// Generates a shell-specific init script for PATH setup.
 pub mod init;
 /// Downloads and installs a GitHub release binary.
 pub mod install;
+/// Adds the binaries of an installed version to the PATH directory.
+pub mod link;
 /// Lists installed binaries and their versions.
 pub mod list;
 /// Sets a specific installed version as the default symlink in PATH.
 pub mod make_default;
+/// Pins a repository to an installed version, or removes its pin.
+pub mod pin;
+/// Removes the installed versions of a repository other than the active one.
+pub mod prune;
+/// Fixes broken symlinks in the PATH directory.
+pub mod repair;
+/// Searches GitHub for repositories to install.
+pub mod search;
+/// Displays the installed versions and the latest release of a repository.
+pub mod show;
+/// Reports the health of every binary managed by poof.
+pub mod status;
 /// Removes an installed binary and its symlinks.
 pub mod uninstall;
 /// Removes a binary symlink from the PATH directory.
 pub mod unlink;
 /// Updates installed binaries to their latest GitHub release.
 pub mod update;
+/// Checks installed binaries against their recorded checksums.
+pub mod verify;
 /// Shows which binaries are provided by an installed repository.
 pub mod what;
 /// Shows which repository provides a given binary name.
@@ -0,0 +1,31 @@
+//! Main file handling 'pin' and 'unpin' commands
+
+use anyhow::{Context, Result};
+use log::info;
+
+use crate::cli::{PinArgs, UnpinArgs};
+use crate::commands::make_default::get_installed_dir;
+use crate::files::{datadirs, pins};
+
+/// Pin a repository to one of its installed versions.
+///
+/// Pinned repositories are skipped by `update`, and the pinned version
+/// cannot be uninstalled without `--force`.
+pub fn run_pin(args: &PinArgs) -> Result<()> {
+    get_installed_dir(&args.repo, &args.version)?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+    pins::save_pin(&data_dir, &args.repo, &args.version)?;
+    info!("Pinned {} to version {}.", args.repo, args.version);
+    Ok(())
+}
+
+/// Remove the pin of a repository.
+pub fn run_unpin(args: &UnpinArgs) -> Result<()> {
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+    if pins::remove_pin(&data_dir, &args.repo)? {
+        info!("Unpinned {}.", args.repo);
+    } else {
+        info!("{} is not pinned. Nothing to do.", args.repo);
+    }
+    Ok(())
+}
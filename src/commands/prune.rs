@@ -0,0 +1,117 @@
+//! Main file handling 'prune' command
+
+use anyhow::{Context, Result};
+use log::{debug, error, info, warn};
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::cli::PruneArgs;
+use crate::commands::gc::{linked_versions, plan_gc, remove_versions, scan_bin_dirs};
+use crate::commands::list::{list_installed_spells, list_installed_versions_per_slug};
+use crate::commands::status::BinaryStatus;
+use crate::commands::uninstall::clean_broken_symlinks;
+use crate::files::datadirs;
+use crate::models::slug::Slug;
+use crate::models::spell::Spell;
+use crate::utils::prompt::confirm;
+use crate::utils::speed::format_bytes;
+
+/// Returns the spells to prune: the one of `args.repo`, or all installed ones with `--all`.
+fn spells_to_prune(args: &PruneArgs) -> Result<Vec<Spell>> {
+    if args.all {
+        return Ok(list_installed_spells());
+    }
+    let repo = args.repo.as_deref().unwrap_or_default();
+    let slug = Slug::new(repo)?;
+    Ok(list_installed_versions_per_slug(&slug)?
+        .into_iter()
+        .collect())
+}
+
+/// Returns the `(repo, version)` pairs to remove: every installed version of `spells`
+/// that no symlink points to. Repositories without an active version are left untouched,
+/// as pruning them would remove every version.
+fn plan_prune(data_dir: &Path, spells: &[Spell], rows: &[BinaryStatus]) -> Vec<(String, String)> {
+    let linked = linked_versions(rows);
+    let is_active = |repo: &str| linked.iter().any(|(linked_repo, _)| linked_repo == repo);
+    for spell in spells.iter().filter(|spell| !is_active(spell.get_name())) {
+        warn!(
+            "{} has no active version, skipping it. Run 'poof use {}' first.",
+            spell.get_name(),
+            spell.get_name()
+        );
+    }
+    plan_gc(data_dir, spells, &linked, 0)
+        .into_iter()
+        .filter(|(repo, _)| is_active(repo))
+        .collect()
+}
+
+/// Remove every installed version of a repository, or of all repositories,
+/// except the active one the bin directory symlinks point to.
+///
+/// Pinned versions are kept. Broken symlinks left behind are removed and the
+/// reclaimed space is printed. The user is prompted for confirmation, unless
+/// `--yes` is set. With `--dry-run` the versions to remove are only listed.
+///
+/// Returns exit code 1 when some version cannot be removed.
+pub fn run_prune(args: &PruneArgs) -> Result<ExitCode> {
+    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory path")?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let spells = spells_to_prune(args)?;
+    if spells.is_empty() {
+        info!("Nothing installed to prune.");
+        return Ok(ExitCode::SUCCESS);
+    }
+    let (bin_dirs, rows) = scan_bin_dirs(&data_dir, &bin_dir, &spells);
+
+    let stale = plan_prune(&data_dir, &spells, &rows);
+    if stale.is_empty() {
+        info!("No inactive versions found. Nothing to do.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let verb = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removing"
+    };
+    info!("{} {} inactive version(s):", verb, stale.len());
+    for (repo, version) in &stale {
+        info!("  {} {}", repo, version);
+    }
+    if args.dry_run {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // Skip confirmation if -y flag is set
+    if !args.yes && !confirm()? {
+        info!("Prune cancelled.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let removal = remove_versions(&data_dir, &stale);
+
+    let mut cleaned_count = 0;
+    for dir in &bin_dirs {
+        cleaned_count += clean_broken_symlinks(dir).context("Failed to clean broken symlinks")?;
+    }
+    if cleaned_count > 0 {
+        debug!("Removed {} broken symlink(s).", cleaned_count);
+    }
+
+    info!(
+        "Removed {} inactive version(s), reclaimed {}.",
+        removal.removed.len(),
+        format_bytes(removal.reclaimed)
+    );
+    if removal.failed > 0 {
+        error!("{} version(s) could not be removed.", removal.failed);
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+use crate::commands::status::LinkStatus;
+use tempfile::TempDir;
+
+fn spell(repo: &str, versions: &[&str]) -> Spell {
+    Spell::new_as_string(
+        repo.to_string(),
+        versions.iter().map(|v| v.to_string()).collect(),
+    )
+}
+
+fn row(repo: &str, version: &str, status: LinkStatus) -> BinaryStatus {
+    BinaryStatus {
+        repo: repo.to_string(),
+        version: version.to_string(),
+        symlink: None,
+        target: None,
+        status,
+    }
+}
+
+#[test]
+fn test_plan_prune_keeps_only_active_version() {
+    let data_dir = TempDir::new().unwrap();
+    let spells = vec![spell("owner/tool", &["1.0.0", "2.0.0", "3.0.0"])];
+    let rows = vec![row("owner/tool", "2.0.0", LinkStatus::Ok)];
+
+    let stale = plan_prune(data_dir.path(), &spells, &rows);
+    assert_eq!(
+        stale,
+        vec![
+            ("owner/tool".to_string(), "1.0.0".to_string()),
+            ("owner/tool".to_string(), "3.0.0".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_prune_skips_repos_without_active_version() {
+    let data_dir = TempDir::new().unwrap();
+    let spells = vec![
+        spell("owner/tool", &["1.0.0", "2.0.0"]),
+        spell("owner/other", &["1.0.0", "2.0.0"]),
+    ];
+    let rows = vec![
+        row("owner/tool", "2.0.0", LinkStatus::Ok),
+        row("-", "-", LinkStatus::Foreign),
+    ];
+
+    let stale = plan_prune(data_dir.path(), &spells, &rows);
+    assert_eq!(stale, vec![("owner/tool".to_string(), "1.0.0".to_string())]);
+}
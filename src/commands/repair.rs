@@ -0,0 +1,170 @@
+//! Main file handling 'repair' command
+
+use anyhow::{Context, Result};
+use log::{info, warn};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::RepairArgs;
+use crate::commands::list::list_installed_spells;
+use crate::commands::make_default::set_default;
+use crate::commands::status::{collect_statuses, BinaryStatus, LinkStatus};
+use crate::files::{datadirs, metadata};
+use crate::models::spell::Spell;
+use crate::utils::semver::SemverSort;
+
+/// What `repair` does with a broken symlink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RepairAction {
+    /// Re-point the symlinks of the repository to the given installed version.
+    Relink(String),
+    /// Remove the symlink, no newer version of the repository is installed.
+    Remove,
+}
+
+/// Returns the newest installed version of `repo` when it is newer than `version`.
+fn newer_installed_version(data_dir: &Path, repo: &str, version: &str) -> Option<String> {
+    let versions_dir = datadirs::get_versions_nest(data_dir, repo);
+    let mut versions: Vec<String> = fs::read_dir(versions_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    if versions.is_empty() {
+        return None;
+    }
+    versions.push(version.to_string());
+    versions.sort_semver();
+    versions.last().filter(|latest| *latest != version).cloned()
+}
+
+/// Returns the bin directories recorded by `install --bin-dir` for the installed `spells`,
+/// leaving out `bin_dir`.
+pub(crate) fn recorded_bin_dirs(data_dir: &Path, bin_dir: &Path, spells: &[Spell]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for spell in spells {
+        for version in spell.get_versions() {
+            let version_dir =
+                datadirs::get_binary_nest(data_dir, spell.get_name(), &version.to_string());
+            if let Some(dir) = metadata::load_bin_dir(&version_dir) {
+                if dir != bin_dir && !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+    }
+    dirs
+}
+
+/// Decide how to repair each broken symlink in `rows`.
+/// Symlinks whose target still exists are left to `status` to report.
+fn plan_repairs(data_dir: &Path, rows: &[BinaryStatus]) -> Vec<(PathBuf, String, RepairAction)> {
+    rows.iter()
+        .filter(|r| r.status == LinkStatus::Broken)
+        .filter(|r| r.target.as_ref().is_some_and(|t| !t.exists()))
+        .filter_map(|r| {
+            let symlink = r.symlink.clone()?;
+            let action = match newer_installed_version(data_dir, &r.repo, &r.version) {
+                Some(newer) => RepairAction::Relink(newer),
+                None => RepairAction::Remove,
+            };
+            Some((symlink, r.repo.clone(), action))
+        })
+        .collect()
+}
+
+/// Print the bin directory entries that are not managed by poof, without touching them.
+fn warn_foreign(rows: &[BinaryStatus]) {
+    for row in rows.iter().filter(|r| r.status == LinkStatus::Foreign) {
+        if let Some(ref symlink) = row.symlink {
+            warn!(
+                "{} is not managed by poof, leaving it untouched.",
+                symlink.display()
+            );
+        }
+    }
+}
+
+/// Fix the broken symlinks in the bin directory.
+///
+/// A broken symlink is re-pointed to the newest installed version of its repository
+/// when that is newer than the version it pointed to, and removed otherwise.
+/// The bin directories recorded by `install --bin-dir` for the installed versions
+/// are repaired too. With `--dry-run` the planned changes are only printed.
+pub fn run_repair(args: &RepairArgs) -> Result<()> {
+    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory path")?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let mut rows = collect_statuses(&bin_dir, &data_dir, &[]);
+    warn_foreign(&rows);
+    // custom bin directories are often shared, only poof's own symlinks matter there
+    for dir in recorded_bin_dirs(&data_dir, &bin_dir, &list_installed_spells()) {
+        rows.extend(collect_statuses(&dir, &data_dir, &[]));
+    }
+
+    let repairs = plan_repairs(&data_dir, &rows);
+    if repairs.is_empty() {
+        info!("No broken symlinks found.");
+        return Ok(());
+    }
+
+    if args.dry_run {
+        for (symlink, repo, action) in &repairs {
+            match action {
+                RepairAction::Relink(version) => info!(
+                    "Would re-point {} to version {} of {}",
+                    symlink.display(),
+                    version,
+                    repo
+                ),
+                RepairAction::Remove => info!("Would remove {}", symlink.display()),
+            }
+        }
+        return Ok(());
+    }
+
+    // broken symlinks cannot be overwritten, so they are all removed first
+    let mut removed: Vec<&PathBuf> = Vec::new();
+    for (symlink, _, _) in &repairs {
+        match fs::remove_file(symlink) {
+            Ok(()) => {
+                removed.push(symlink);
+            }
+            Err(e) => warn!("Cannot remove {}: {}", symlink.display(), e),
+        }
+    }
+
+    // then the binaries of each repository are re-pointed at once
+    let mut relinked: HashSet<&str> = HashSet::new();
+    for (_, repo, action) in &repairs {
+        if let RepairAction::Relink(version) = action {
+            if relinked.insert(repo.as_str()) {
+                if let Err(e) = set_default(repo, Some(version)) {
+                    warn!("Cannot re-point the binaries of {}: {}", repo, e);
+                }
+            }
+        }
+    }
+
+    let mut repaired = 0;
+    for symlink in &removed {
+        // the newer version may not ship a binary with the same name
+        if symlink.exists() {
+            repaired += 1;
+        } else {
+            info!("Removed {}", symlink.display());
+        }
+    }
+    let removed = removed.len() - repaired;
+    info!(
+        "Repaired {} and removed {} broken symlink(s).",
+        repaired, removed
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
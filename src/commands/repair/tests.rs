@@ -0,0 +1,105 @@
+use super::*;
+use std::os::unix::fs::{symlink, PermissionsExt};
+use tempfile::TempDir;
+
+/// Creates an executable `data_dir/repo/version/name`.
+fn create_binary(data_dir: &Path, repo: &str, version: &str, name: &str) -> PathBuf {
+    let dir = datadirs::get_binary_nest(data_dir, repo, version);
+    fs::create_dir_all(&dir).unwrap();
+    let binary = dir.join(name);
+    fs::write(&binary, b"#!/bin/sh\n").unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+    binary
+}
+
+fn setup() -> (TempDir, PathBuf, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let bin_dir = temp_dir.path().join("bin");
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(&bin_dir).unwrap();
+    fs::create_dir_all(&data_dir).unwrap();
+    (temp_dir, bin_dir, data_dir)
+}
+
+#[test]
+fn test_newer_installed_version() {
+    let (_temp_dir, _bin_dir, data_dir) = setup();
+    create_binary(&data_dir, "owner/tool", "1.2.0", "tool");
+    create_binary(&data_dir, "owner/tool", "1.10.0", "tool");
+
+    assert_eq!(
+        newer_installed_version(&data_dir, "owner/tool", "1.0.0"),
+        Some("1.10.0".to_string())
+    );
+    assert_eq!(
+        newer_installed_version(&data_dir, "owner/tool", "2.0.0"),
+        None
+    );
+    assert_eq!(
+        newer_installed_version(&data_dir, "owner/other", "1.0.0"),
+        None
+    );
+}
+
+#[test]
+fn test_plan_repairs() {
+    let (_temp_dir, bin_dir, data_dir) = setup();
+    // version 1.0.0 of tool has been deleted by hand, 2.0.0 is still installed
+    create_binary(&data_dir, "owner/tool", "2.0.0", "tool");
+    let gone = datadirs::get_binary_nest(&data_dir, "owner/tool", "1.0.0").join("tool");
+    symlink(&gone, bin_dir.join("tool")).unwrap();
+    // nothing left of other
+    let gone = datadirs::get_binary_nest(&data_dir, "owner/other", "1.0.0").join("other");
+    symlink(&gone, bin_dir.join("other")).unwrap();
+    // healthy symlinks and foreign files are not touched
+    let binary = create_binary(&data_dir, "owner/fine", "1.0.0", "fine");
+    symlink(&binary, bin_dir.join("fine")).unwrap();
+    fs::write(bin_dir.join("manual"), b"").unwrap();
+
+    let rows = collect_statuses(&bin_dir, &data_dir, &[]);
+    let repairs = plan_repairs(&data_dir, &rows);
+    assert_eq!(
+        repairs,
+        vec![
+            (
+                bin_dir.join("other"),
+                "owner/other".to_string(),
+                RepairAction::Remove
+            ),
+            (
+                bin_dir.join("tool"),
+                "owner/tool".to_string(),
+                RepairAction::Relink("2.0.0".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_recorded_bin_dirs() {
+    let (temp_dir, bin_dir, data_dir) = setup();
+    let team_bin = temp_dir.path().join("team-bin");
+    create_binary(&data_dir, "owner/tool", "1.0.0", "tool");
+    create_binary(&data_dir, "owner/tool", "2.0.0", "tool");
+    create_binary(&data_dir, "owner/other", "1.0.0", "other");
+    for (repo, version, dir) in [
+        ("owner/tool", "1.0.0", &team_bin),
+        ("owner/tool", "2.0.0", &team_bin),
+        ("owner/other", "1.0.0", &bin_dir),
+    ] {
+        let version_dir = datadirs::get_binary_nest(&data_dir, repo, version);
+        metadata::save_bin_dir(&version_dir, dir).unwrap();
+    }
+    let spells = vec![
+        Spell::new_as_string(
+            "owner/tool".to_string(),
+            vec!["1.0.0".to_string(), "2.0.0".to_string()],
+        ),
+        Spell::new_as_string("owner/other".to_string(), vec!["1.0.0".to_string()]),
+    ];
+
+    assert_eq!(
+        recorded_bin_dirs(&data_dir, &bin_dir, &spells),
+        vec![team_bin]
+    );
+}
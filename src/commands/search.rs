@@ -0,0 +1,62 @@
+//! Main file handling 'search' command
+
+use anyhow::{Context, Result};
+use log::info;
+
+use crate::cli::SearchArgs;
+use crate::github::client::search_repositories;
+use crate::github::models::Repository;
+use crate::output;
+
+/// Maximum length of the descriptions printed in the results table.
+const DESCRIPTION_MAX_LEN: usize = 80;
+
+/// Returns `description` cut at [`DESCRIPTION_MAX_LEN`] characters, `-` when there is none.
+fn short_description(description: Option<&str>) -> String {
+    match description.map(str::trim).filter(|d| !d.is_empty()) {
+        None => "-".to_string(),
+        Some(d) if d.chars().count() > DESCRIPTION_MAX_LEN => {
+            let cut: String = d.chars().take(DESCRIPTION_MAX_LEN - 3).collect();
+            format!("{}...", cut)
+        }
+        Some(d) => d.to_string(),
+    }
+}
+
+/// Formats the search results as table rows, header included.
+fn format_results(repositories: &[Repository]) -> Vec<String> {
+    let mut lines = vec![
+        format!("{:<40}\t{:>7}\t{}", "Repository", "Stars", "Description"),
+        format!("{:<40}\t{:>7}\t{}", "----------", "-----", "-----------"),
+    ];
+    for repository in repositories {
+        lines.push(format!(
+            "{:<40}\t{:>7}\t{}",
+            repository.full_name(),
+            repository.stargazers_count(),
+            short_description(repository.description())
+        ));
+    }
+    lines
+}
+
+/// Search GitHub for repositories matching `args.query` and print the best matches.
+pub fn run_search(args: &SearchArgs) -> Result<()> {
+    let repositories = search_repositories(&args.query, args.limit)
+        .with_context(|| format!("Cannot search GitHub for '{}'", args.query))?;
+    if repositories.is_empty() {
+        info!("No repositories found for '{}'.", args.query);
+        return Ok(());
+    }
+
+    output!("");
+    for line in format_results(&repositories) {
+        output!("{}", line);
+    }
+    output!("");
+    info!("Install one of them with 'poof install <Repository>'.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,34 @@
+use super::*;
+
+fn repository(full_name: &str, stars: u64, description: Option<&str>) -> Repository {
+    serde_json::from_value(serde_json::json!({
+        "full_name": full_name,
+        "stargazers_count": stars,
+        "description": description,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_format_results_lists_full_names() {
+    let lines = format_results(&[
+        repository("BurntSushi/ripgrep", 50000, Some("Fast grep")),
+        repository("someone/rg-wrapper", 12, None),
+    ]);
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("Repository"));
+    assert!(lines[2].starts_with("BurntSushi/ripgrep"));
+    assert!(lines[2].contains("50000"));
+    assert!(lines[2].ends_with("Fast grep"));
+    assert!(lines[3].starts_with("someone/rg-wrapper"));
+    assert!(lines[3].ends_with('-'));
+}
+
+#[test]
+fn test_short_description_is_truncated() {
+    let long = "a".repeat(DESCRIPTION_MAX_LEN + 10);
+    let short = short_description(Some(&long));
+    assert_eq!(short.chars().count(), DESCRIPTION_MAX_LEN);
+    assert!(short.ends_with("..."));
+    assert_eq!(short_description(Some("  ")), "-");
+}
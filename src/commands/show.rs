@@ -0,0 +1,151 @@
+//! Main file handling 'show' command
+
+use anyhow::{Context, Result};
+
+use crate::cli::ShowArgs;
+use crate::commands::list::{active_versions, list_installed_versions_per_slug};
+use crate::files::{datadirs, metadata};
+use crate::github::models::Release;
+use crate::models::slug::Slug;
+use crate::models::source::Source;
+use crate::output;
+use crate::utils::semver::{SemverStringConversion, SemverStringPrefix, Version};
+use crate::utils::time::format_system_time;
+
+/// An installed version of the repository being shown.
+struct InstalledVersion {
+    version: String,
+    /// When the version was installed, formatted for display.
+    installed_at: Option<String>,
+    /// Names of the binaries in PATH, custom names included.
+    binaries: Vec<String>,
+}
+
+/// Print everything known about `args.repo`: its installed versions, with their
+/// install date and binaries, the active one, and unless `--installed-only` is
+/// given the latest release available upstream.
+///
+/// With `--json` the same data is printed as a JSON object, whose field names
+/// are part of poof's interface.
+pub fn run_show(args: &ShowArgs) -> Result<()> {
+    let (requested_source, _) = Source::split(&args.repo);
+    let slug = Slug::new(&args.repo)?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let installed: Vec<InstalledVersion> = list_installed_versions_per_slug(&slug)?
+        .map(|spell| {
+            let mut versions = spell.get_versions().clone();
+            versions.sort();
+            versions.to_string_vec()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|version| {
+            let version_dir = datadirs::get_binary_nest(&data_dir, slug.as_str(), &version);
+            InstalledVersion {
+                installed_at: metadata::load_installed_at(&version_dir).map(format_system_time),
+                binaries: metadata::list_binaries(&version_dir)
+                    .iter()
+                    .map(|exec_name| metadata::link_name_for(&version_dir, exec_name))
+                    .collect(),
+                version,
+            }
+        })
+        .collect();
+    let active = active_versions().remove(slug.as_str());
+
+    // installed repositories are looked up where they were installed from
+    let source = match installed.last() {
+        Some(newest) => metadata::load_source(&datadirs::get_binary_nest(
+            &data_dir,
+            slug.as_str(),
+            &newest.version,
+        )),
+        None => requested_source,
+    };
+    let release: Option<Release> = if args.installed_only {
+        None
+    } else {
+        Some(
+            source
+                .get_release(slug.as_str(), None, true)
+                .with_context(|| format!("Cannot get latest release information for {}", slug))?,
+        )
+    };
+    let update_available = release.as_ref().is_some_and(|release| {
+        installed.last().is_some_and(|newest| {
+            Version::from(release.tag_name().strip_v()) > Version::from(newest.version.as_str())
+        })
+    });
+    let url = source.web_url(slug.as_str());
+
+    if args.json {
+        let info = serde_json::json!({
+            "repo": slug.as_str(),
+            "source": source.to_string(),
+            "url": url,
+            "installed": installed
+                .iter()
+                .map(|v| serde_json::json!({
+                    "version": v.version,
+                    "installed_at": v.installed_at,
+                    "binaries": v.binaries,
+                    "active": active.as_ref() == Some(&v.version),
+                }))
+                .collect::<Vec<_>>(),
+            "active_version": active,
+            "latest_tag": release.as_ref().map(|r| r.tag_name()),
+            "published_at": release.as_ref().map(|r| r.published_at()),
+            "assets": release
+                .as_ref()
+                .map(|r| r.assets().iter().map(|a| a.name()).collect::<Vec<_>>()),
+            "update_available": update_available,
+        });
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&info).context("Cannot serialize package to JSON")?
+        );
+        return Ok(());
+    }
+
+    let none = "none".to_string();
+    output!("Repository      : {} ({})", slug, source);
+    if let Some(url) = &url {
+        output!("URL             : {}", url);
+    }
+    if installed.is_empty() {
+        output!("Installed       : {}", none);
+    } else {
+        output!("Installed       :");
+        for v in &installed {
+            let marker = if active.as_ref() == Some(&v.version) {
+                " (active)"
+            } else {
+                ""
+            };
+            output!(
+                "  {}{} installed at {}, provides: {}",
+                v.version,
+                marker,
+                v.installed_at.as_ref().unwrap_or(&none),
+                v.binaries.join(", ")
+            );
+        }
+    }
+    output!("Active          : {}", active.as_ref().unwrap_or(&none));
+    if let Some(release) = &release {
+        output!("Latest release  : {}", release.tag_name());
+        output!("Published at    : {}", release.published_at());
+        output!("Assets          :");
+        for asset in release.assets() {
+            output!("  {}", asset.name());
+        }
+    }
+    if update_available {
+        output!(
+            "Update available: run 'poof update {}' to install the latest release",
+            source.qualify(slug.as_str())
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,254 @@
+//! Main file handling 'status' command
+
+use anyhow::{Context, Result};
+use log::{info, warn};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::cli::StatusArgs;
+use crate::commands::list::list_installed_spells;
+use crate::files::datadirs;
+use crate::files::filesys;
+use crate::models::spell::Spell;
+use crate::output;
+use crate::utils::prompt::confirm;
+
+/// Health of a single binary managed by poof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkStatus {
+    /// The symlink points to an executable inside the data directory.
+    Ok,
+    /// The symlink target is missing or not executable.
+    Broken,
+    /// The installed spell has no symlink in the bin directory.
+    Missing,
+    /// The bin directory entry is not a symlink into the data directory.
+    Foreign,
+}
+
+impl LinkStatus {
+    /// Label printed in the `Status` column.
+    fn label(&self) -> &'static str {
+        match self {
+            LinkStatus::Ok => "OK",
+            LinkStatus::Broken => "BROKEN",
+            LinkStatus::Missing => "MISSING",
+            LinkStatus::Foreign => "FOREIGN",
+        }
+    }
+
+    /// ANSI colour code used for the label on terminals.
+    fn color(&self) -> &'static str {
+        match self {
+            LinkStatus::Ok => "32",
+            LinkStatus::Broken => "31",
+            LinkStatus::Missing => "33",
+            LinkStatus::Foreign => "35",
+        }
+    }
+}
+
+/// One row of the `status` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryStatus {
+    /// Slug of the spell providing the binary, `-` when unknown.
+    pub repo: String,
+    /// Version the symlink points to, `-` when unknown.
+    pub version: String,
+    /// Path of the entry in the bin directory, if any.
+    pub symlink: Option<PathBuf>,
+    /// Path the symlink points to, if any.
+    pub target: Option<PathBuf>,
+    /// Health of the binary.
+    pub status: LinkStatus,
+}
+
+/// Inspect the entries of `bin_dir` and the installed `spells`, returning one row per
+/// bin directory entry plus one `MISSING` row for each spell without any symlink.
+pub fn collect_statuses(bin_dir: &Path, data_dir: &Path, spells: &[Spell]) -> Vec<BinaryStatus> {
+    let mut rows: Vec<BinaryStatus> = Vec::new();
+    let mut linked: HashSet<String> = HashSet::new();
+
+    let entries = fs::read_dir(bin_dir)
+        .map(|entries| entries.flatten().collect::<Vec<_>>())
+        .unwrap_or_default();
+    for entry in entries {
+        let path = entry.path();
+        let Ok(target) = fs::read_link(&path) else {
+            // regular files and directories have not been put there by poof
+            rows.push(BinaryStatus {
+                repo: "-".to_string(),
+                version: "-".to_string(),
+                symlink: Some(path),
+                target: None,
+                status: LinkStatus::Foreign,
+            });
+            continue;
+        };
+        // symlinks point to data_dir/host/username/reponame/version/binary
+        let (slug, parts) = datadirs::split_repo_path(data_dir, &target).unwrap_or_default();
+        let row = match parts.as_slice() {
+            [version, _, ..] => {
+                linked.insert(slug.clone());
+                let status = if filesys::is_executable(&target) {
+                    LinkStatus::Ok
+                } else {
+                    LinkStatus::Broken
+                };
+                BinaryStatus {
+                    repo: slug,
+                    version: version.clone(),
+                    symlink: Some(path),
+                    target: Some(target),
+                    status,
+                }
+            }
+            _ => BinaryStatus {
+                repo: "-".to_string(),
+                version: "-".to_string(),
+                symlink: Some(path),
+                target: Some(target),
+                status: LinkStatus::Foreign,
+            },
+        };
+        rows.push(row);
+    }
+
+    for spell in spells.iter().filter(|s| !linked.contains(s.get_name())) {
+        rows.push(BinaryStatus {
+            repo: spell.get_name().to_string(),
+            version: spell
+                .get_latest_version()
+                .unwrap_or_else(|| "-".to_string()),
+            symlink: None,
+            target: None,
+            status: LinkStatus::Missing,
+        });
+    }
+
+    rows.sort_by(|a, b| (&a.repo, &a.symlink).cmp(&(&b.repo, &b.symlink)));
+    rows
+}
+
+/// Returns the file name of `path`, or `-` when there is none.
+fn display_name(path: Option<&PathBuf>) -> String {
+    path.and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Print `rows` as a table, colouring the status column when stdout is a terminal.
+fn print_table(rows: &[BinaryStatus]) {
+    let colored = io::stdout().is_terminal();
+    output!("");
+    output!(
+        "{:<40}\t{:<15}\t{:<20}\t{:<60}\t{}",
+        "Repository",
+        "Version",
+        "Symlink",
+        "Target",
+        "Status"
+    );
+    output!(
+        "{:<40}\t{:<15}\t{:<20}\t{:<60}\t{}",
+        "----------",
+        "-------",
+        "-------",
+        "------",
+        "------"
+    );
+    for row in rows {
+        let target = row
+            .target
+            .as_ref()
+            .map(|t| t.display().to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let status = if colored {
+            format!("\x1b[{}m{}\x1b[0m", row.status.color(), row.status.label())
+        } else {
+            row.status.label().to_string()
+        };
+        output!(
+            "{:<40}\t{:<15}\t{:<20}\t{:<60}\t{}",
+            row.repo,
+            row.version,
+            display_name(row.symlink.as_ref()),
+            target,
+            status
+        );
+    }
+}
+
+/// Remove the symlinks of the `BROKEN` rows, returning how many have been removed.
+/// The user is prompted for confirmation unless `yes` is set.
+fn fix_broken(rows: &[BinaryStatus], yes: bool) -> Result<usize> {
+    let broken: Vec<&PathBuf> = rows
+        .iter()
+        .filter(|r| r.status == LinkStatus::Broken)
+        .filter_map(|r| r.symlink.as_ref())
+        .collect();
+    if broken.is_empty() {
+        info!("No broken symlinks to remove.");
+        return Ok(0);
+    }
+
+    if !yes {
+        // Show what will be deleted
+        eprintln!("This will remove {} broken symlink(s):", broken.len());
+        for link in &broken {
+            eprintln!("  {}", link.display());
+        }
+
+        if !confirm()? {
+            info!("Fix cancelled.");
+            return Ok(0);
+        }
+    }
+
+    let mut removed = 0;
+    for link in broken {
+        match fs::remove_file(link) {
+            Ok(()) => {
+                info!("Removed {}", link.display());
+                removed += 1;
+            }
+            Err(e) => warn!("Cannot remove {}: {}", link.display(), e),
+        }
+    }
+    Ok(removed)
+}
+
+/// Show the health of every binary managed by poof.
+///
+/// Returns [`ExitCode::SUCCESS`] when every binary is `OK` and [`ExitCode::FAILURE`]
+/// otherwise. With `--fix`, `BROKEN` symlinks are removed after confirmation and no
+/// longer count as failures.
+pub fn run_status(args: &StatusArgs) -> Result<ExitCode> {
+    let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory path")?;
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+
+    let spells = list_installed_spells();
+    let rows = collect_statuses(&bin_dir, &data_dir, &spells);
+    if rows.is_empty() {
+        info!("No installed binaries found.");
+        return Ok(ExitCode::SUCCESS);
+    }
+    print_table(&rows);
+
+    let mut issues = rows.iter().filter(|r| r.status != LinkStatus::Ok).count();
+    if args.fix {
+        issues -= fix_broken(&rows, args.yes)?;
+    }
+
+    if issues == 0 {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,105 @@
+use super::*;
+use std::os::unix::fs::{symlink, PermissionsExt};
+use tempfile::TempDir;
+
+/// Creates `data_dir/repo/version/name` with the given permission bits.
+fn create_binary(data_dir: &Path, repo: &str, version: &str, name: &str, mode: u32) -> PathBuf {
+    let dir = datadirs::get_binary_nest(data_dir, repo, version);
+    fs::create_dir_all(&dir).unwrap();
+    let binary = dir.join(name);
+    fs::write(&binary, b"#!/bin/sh\n").unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(mode)).unwrap();
+    binary
+}
+
+fn setup() -> (TempDir, PathBuf, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let bin_dir = temp_dir.path().join("bin");
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(&bin_dir).unwrap();
+    fs::create_dir_all(&data_dir).unwrap();
+    (temp_dir, bin_dir, data_dir)
+}
+
+#[test]
+fn test_collect_statuses_ok() {
+    let (_temp_dir, bin_dir, data_dir) = setup();
+    let binary = create_binary(&data_dir, "owner/tool", "1.0.0", "tool", 0o755);
+    symlink(&binary, bin_dir.join("tool")).unwrap();
+    let spells = vec![Spell::new_as_string(
+        "owner/tool".to_string(),
+        vec!["1.0.0".to_string()],
+    )];
+
+    let rows = collect_statuses(&bin_dir, &data_dir, &spells);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].repo, "owner/tool");
+    assert_eq!(rows[0].version, "1.0.0");
+    assert_eq!(rows[0].target.as_ref(), Some(&binary));
+    assert_eq!(rows[0].status, LinkStatus::Ok);
+}
+
+#[test]
+fn test_collect_statuses_broken() {
+    let (_temp_dir, bin_dir, data_dir) = setup();
+    // dangling symlink
+    let gone = datadirs::get_binary_nest(&data_dir, "owner/gone", "1.0.0").join("gone");
+    symlink(&gone, bin_dir.join("gone")).unwrap();
+    // target not executable
+    let binary = create_binary(&data_dir, "owner/noexec", "2.0.0", "noexec", 0o644);
+    symlink(&binary, bin_dir.join("noexec")).unwrap();
+
+    let rows = collect_statuses(&bin_dir, &data_dir, &[]);
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r.status == LinkStatus::Broken));
+    assert_eq!(rows[0].repo, "owner/gone");
+    assert_eq!(rows[1].repo, "owner/noexec");
+    assert_eq!(rows[1].version, "2.0.0");
+}
+
+#[test]
+fn test_collect_statuses_missing() {
+    let (_temp_dir, bin_dir, data_dir) = setup();
+    create_binary(&data_dir, "owner/tool", "1.0.0", "tool", 0o755);
+    let spells = vec![Spell::new_as_string(
+        "owner/tool".to_string(),
+        vec!["1.0.0".to_string()],
+    )];
+
+    let rows = collect_statuses(&bin_dir, &data_dir, &spells);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].status, LinkStatus::Missing);
+    assert_eq!(rows[0].symlink, None);
+}
+
+#[test]
+fn test_collect_statuses_foreign() {
+    let (temp_dir, bin_dir, data_dir) = setup();
+    // regular file
+    fs::write(bin_dir.join("manual"), b"").unwrap();
+    // symlink outside of the data directory
+    let outside = temp_dir.path().join("outside");
+    fs::write(&outside, b"").unwrap();
+    fs::set_permissions(&outside, fs::Permissions::from_mode(0o755)).unwrap();
+    symlink(&outside, bin_dir.join("outside")).unwrap();
+
+    let rows = collect_statuses(&bin_dir, &data_dir, &[]);
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r.status == LinkStatus::Foreign));
+    assert!(rows.iter().all(|r| r.repo == "-"));
+}
+
+#[test]
+fn test_fix_broken_removes_only_broken_symlinks() {
+    let (_temp_dir, bin_dir, data_dir) = setup();
+    let binary = create_binary(&data_dir, "owner/tool", "1.0.0", "tool", 0o755);
+    symlink(&binary, bin_dir.join("tool")).unwrap();
+    let gone = datadirs::get_binary_nest(&data_dir, "owner/gone", "1.0.0").join("gone");
+    symlink(&gone, bin_dir.join("gone")).unwrap();
+
+    let rows = collect_statuses(&bin_dir, &data_dir, &[]);
+    let removed = fix_broken(&rows, true).unwrap();
+    assert_eq!(removed, 1);
+    assert!(bin_dir.join("tool").is_symlink());
+    assert!(!bin_dir.join("gone").is_symlink());
+}
@@ -4,61 +4,156 @@ use anyhow::{bail, Context, Result};
 use log::{debug, info};
 use std::fs;
 use std::io::{stdin, stdout, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::cli::UninstallArgs;
-use crate::files::datadirs;
+use crate::commands::history::{self, HistoryCommand};
 use crate::files::filesys::is_broken_symlink;
+use crate::files::{datadirs, metadata, pins};
+use crate::utils::semver::{SemverArrayConversion, SemverStringConversion, Version};
+
+/// Returns the installed versions of `repo`, oldest first.
+fn installed_versions(data_dir: &Path, repo: &str) -> Vec<String> {
+    let versions: Vec<String> = fs::read_dir(datadirs::get_versions_nest(data_dir, repo))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .filter_map(|entry| entry.file_name().to_str().map(String::from))
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut versions = versions.to_version();
+    versions.sort();
+    versions.to_string_vec()
+}
+
+/// Returns the versions among `versions` (oldest first) that `--keep-latest`
+/// or `--older-than` select for removal.
+fn select_versions(
+    versions: &[String],
+    keep_latest: bool,
+    older_than: Option<&str>,
+) -> Vec<String> {
+    if keep_latest {
+        return versions[..versions.len().saturating_sub(1)].to_vec();
+    }
+    let Some(threshold) = older_than.map(Version::from) else {
+        return Vec::new();
+    };
+    versions
+        .iter()
+        .filter(|version| Version::from(version.as_str()) < threshold)
+        .cloned()
+        .collect()
+}
 
-/// Remove an installed version (or all versions) of a repository from the data directory.
+/// Remove an installed version (or several, or all versions) of a repository from the data directory.
 ///
 /// After deleting the requested files the function also removes any broken
-/// symlinks left behind in the bin directory. The user is prompted for
+/// symlinks left behind in the bin directory, and in the directories recorded
+/// by `install --bin-dir` for the removed versions. The user is prompted for
 /// confirmation unless the `--yes` / `-y` flag is set.
+/// The version a repository is pinned to is only removed with `--force`,
+/// which also removes the pin.
 pub fn run_uninstall(args: &UninstallArgs) -> Result<()> {
     let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
     let bin_dir = datadirs::get_bin_dir().context("Cannot get bin directory")?;
 
+    if let Some(threshold) = &args.older_than {
+        if Version::parse(threshold).is_err() {
+            bail!("Invalid version '{}' passed to --older-than.", threshold);
+        }
+    }
+
     // Determine what to delete and set a proper message for the confirmation prompt.
-    let (target_path, message) = if args.all {
-        let path = datadirs::get_versions_nest(&data_dir, &args.repo);
+    let (versions, message) = if args.all {
         let msg = format!(
             "This will delete ALL versions of '{}' and remove provided binaries from PATH.",
             args.repo
         );
-        (path, msg)
+        (installed_versions(&data_dir, &args.repo), msg)
     } else if let Some(version) = &args.version {
-        let path = datadirs::get_binary_nest(&data_dir, &args.repo, version);
         let msg = format!(
             "This will delete version '{}' of '{}' and remove provided binaries from PATH.",
             version, args.repo
         );
-        (path, msg)
+        let installed = datadirs::get_binary_nest(&data_dir, &args.repo, version).exists();
+        let versions = if installed {
+            vec![version.clone()]
+        } else {
+            Vec::new()
+        };
+        (versions, msg)
+    } else if args.keep_latest || args.older_than.is_some() {
+        let versions = select_versions(
+            &installed_versions(&data_dir, &args.repo),
+            args.keep_latest,
+            args.older_than.as_deref(),
+        );
+        let msg = format!(
+            "This will delete versions {} of '{}' and remove provided binaries from PATH.",
+            versions.join(", "),
+            args.repo
+        );
+        (versions, msg)
     } else {
         // This shouldn't happen due to clap validation, but handle it gracefully
-        bail!("Please specify either --version or --all flags.");
+        bail!("Please specify either --version, --all, --keep-latest or --older-than flags.");
     };
 
-    // Check if the target exists
-    if !target_path.exists() {
-        if args.all {
-            info!("No versions of '{}' installed. Nothing to do.", args.repo);
-        } else if let Some(version) = &args.version {
+    // Check if there is anything to delete
+    if versions.is_empty() {
+        if let Some(version) = &args.version {
             info!(
                 "Version '{}' of '{}' is not installed. Nothing to do.",
                 version, args.repo
             );
+        } else if args.all {
+            info!("No versions of '{}' installed. Nothing to do.", args.repo);
+        } else {
+            info!(
+                "No installed versions of '{}' to remove. Nothing to do.",
+                args.repo
+            );
         }
         return Ok(());
     }
 
+    // Refuse to remove the pinned version, unless forced
+    let pinned =
+        pins::pinned_version(&data_dir, &args.repo).filter(|pinned| versions.contains(pinned));
+    if let Some(ref pinned) = pinned {
+        if !args.force {
+            bail!(
+                "'{}' is pinned to version {}. Use 'poof unpin {}' first, or pass --force.",
+                args.repo,
+                pinned,
+                args.repo
+            );
+        }
+    }
+
+    // uninstalling all versions removes the whole repository directory
+    let target_paths: Vec<PathBuf> = if args.all {
+        vec![datadirs::get_versions_nest(&data_dir, &args.repo)]
+    } else {
+        versions
+            .iter()
+            .map(|version| datadirs::get_binary_nest(&data_dir, &args.repo, version))
+            .collect()
+    };
+
     // Show what will be deleted
     info!("{}", message);
-    debug!(
-        "Uninstalling '{}' by removing directory: {}",
-        args.repo,
-        target_path.display()
-    );
+    for target_path in &target_paths {
+        debug!(
+            "Uninstalling '{}' by removing directory: {}",
+            args.repo,
+            target_path.display()
+        );
+    }
 
     // Skip confirmation if -y flag is set
     if !args.yes {
@@ -79,10 +174,36 @@ pub fn run_uninstall(args: &UninstallArgs) -> Result<()> {
         }
     }
 
-    // Delete the directory
-    debug!("Deleting directory: {}", target_path.display());
-    fs::remove_dir_all(&target_path)
-        .with_context(|| format!("Cannot delete directory: {}", target_path.display()))?;
+    // remember where the binaries are linked before their metadata is gone
+    let mut bin_dirs: Vec<PathBuf> = vec![bin_dir];
+    for version in &versions {
+        let version_dir = datadirs::get_binary_nest(&data_dir, &args.repo, version);
+        if let Some(dir) = metadata::load_bin_dir(&version_dir) {
+            if !bin_dirs.contains(&dir) {
+                bin_dirs.push(dir);
+            }
+        }
+    }
+
+    // Delete the directories
+    let removed: Result<()> = target_paths.iter().try_for_each(|target_path| {
+        debug!("Deleting directory: {}", target_path.display());
+        fs::remove_dir_all(target_path)
+            .with_context(|| format!("Cannot delete directory: {}", target_path.display()))
+    });
+    for version in &versions {
+        history::record(
+            HistoryCommand::Uninstall,
+            &args.repo,
+            Some(version),
+            None,
+            &removed,
+        );
+    }
+    removed?;
+    if pinned.is_some() {
+        pins::remove_pin(&data_dir, &args.repo)?;
+    }
 
     if args.all {
         info!(
@@ -94,11 +215,20 @@ pub fn run_uninstall(args: &UninstallArgs) -> Result<()> {
             "Version '{}' of '{}' has been successfully removed.",
             version, args.repo
         );
+    } else {
+        info!(
+            "Versions {} of '{}' have been successfully removed.",
+            versions.join(", "),
+            args.repo
+        );
     }
 
     // Clean up broken symlinks
-    let cleaned_count =
-        clean_broken_symlinks(&bin_dir).context("Failed to clean broken symlinks")?;
+    let mut cleaned_count = 0;
+    for bin_dir in &bin_dirs {
+        cleaned_count +=
+            clean_broken_symlinks(bin_dir).context("Failed to clean broken symlinks")?;
+    }
 
     if cleaned_count > 0 {
         debug!(
@@ -112,7 +242,7 @@ pub fn run_uninstall(args: &UninstallArgs) -> Result<()> {
 
 /// Clean broken symlinks from the bin directory.
 /// Returns the number of symlinks that were removed.
-fn clean_broken_symlinks(bin_dir: &Path) -> Result<usize> {
+pub(crate) fn clean_broken_symlinks(bin_dir: &Path) -> Result<usize> {
     let mut count = 0;
 
     // Return early if bin_dir doesn't exist
@@ -169,6 +299,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_select_versions() {
+        let versions: Vec<String> = ["0.9.0", "1.5.0", "2.0.0", "2.1.0"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(
+            select_versions(&versions, true, None),
+            vec!["0.9.0", "1.5.0", "2.0.0"]
+        );
+        assert_eq!(
+            select_versions(&versions, false, Some("2.0.0")),
+            vec!["0.9.0", "1.5.0"]
+        );
+        assert_eq!(
+            select_versions(&versions, false, Some("v2.0.1")),
+            vec!["0.9.0", "1.5.0", "2.0.0"]
+        );
+        assert!(select_versions(&versions[..1], true, None).is_empty());
+    }
+
     #[test]
     fn test_clean_broken_symlinks_empty_dir() -> Result<()> {
         let env = TestEnv::new()?;
@@ -5,7 +5,7 @@ use log::info;
 use std::io::{self, Write};
 
 use crate::cli::UnlinkArgs;
-use crate::files::datadirs;
+use crate::files::{datadirs, filesys};
 
 /// Remove a binary's symlink from the bin directory, making it unavailable in `PATH`.
 ///
@@ -25,8 +25,9 @@ Check installed binaries using 'list' command.",
         );
     }
 
-    // Verify it's a symlink
-    if !binary_path.is_symlink() {
+    // Verify it's a symlink, or the copy made in its place on Windows
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+    if !binary_path.is_symlink() && !filesys::is_managed_copy(&binary_path, &data_dir) {
         bail!(
             "Binary '{}' exists but is not a symlink. \
             Refusing to delete regular files.\n\
@@ -69,7 +70,7 @@ Check installed binaries using 'list' command.",
     })?;
 
     info!(
-        "'{}' successfully removed from PATH. Use 'poof use' to re-add it.",
+        "'{}' successfully removed from PATH. Use 'poof link' to re-add it.",
         args.binary_name
     );
     Ok(())
@@ -1,30 +1,97 @@
 use crate::cli::UpdateArgs;
+use crate::commands::install::InstallOptions;
 use crate::commands::list::list_installed_versions_per_slug;
+use crate::constants::{APP_NAME, THIS_REPO, VERSION};
+use crate::core::{config, worker_pool::run_with_pool};
+use crate::files::magic::is_exec_for_current_arch;
+use crate::files::utils::similar_repos_hint;
+use crate::files::{datadirs, filesys, metadata, pins};
+use crate::models::asset_triple::AssetTriple;
 use crate::models::slug::Slug;
+use crate::models::source::Source;
 use crate::{
     commands::{self, list::list_installed_spells},
-    github::client::get_release,
     models::spell::Spell,
     utils::semver::{SemverStringPrefix, Version},
 };
 use anyhow::{bail, Context, Result};
-use log::{debug, error, info, warn};
-use rayon::prelude::*;
+use log::{debug, error, info, warn, LevelFilter};
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Returns the custom name the binary of `repo` was installed with at `version`, if any.
+fn installed_rename(repo: &str, version: &str) -> Option<String> {
+    let data_dir = datadirs::get_data_dir()?;
+    let version_dir = datadirs::get_binary_nest(&data_dir, repo, version);
+    let mut renames = metadata::load_renames(&version_dir);
+    // --rename only applies to single-binary installs
+    if renames.len() == 1 {
+        renames.pop_first().map(|(_, link_name)| link_name)
+    } else {
+        None
+    }
+}
+
+/// Returns the custom bin directory `repo` was installed into at `version`, if any.
+fn installed_bin_dir(repo: &str, version: &str) -> Option<std::path::PathBuf> {
+    let data_dir = datadirs::get_data_dir()?;
+    metadata::load_bin_dir(&datadirs::get_binary_nest(&data_dir, repo, version))
+}
+
+/// Returns the source `repo` was installed from at `version`.
+fn installed_source(repo: &str, version: &str) -> Source {
+    datadirs::get_data_dir()
+        .map(|data_dir| metadata::load_source(&datadirs::get_binary_nest(&data_dir, repo, version)))
+        .unwrap_or_default()
+}
+
+/// What checking a single repository for updates resulted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UpdateOutcome {
+    /// A newer version has been installed.
+    Updated,
+    /// The highest installed version is the latest one.
+    UpToDate,
+    /// The repository is pinned, it has not been checked.
+    Pinned,
+}
 
 /// Checks for and applies an update for a single installed repository (by name).
-fn update_single_repo(repo: &str) -> Result<()> {
-    update_single_repo_internal(repo, None)
+fn update_single_repo(repo: &str, use_cache: bool, keep_versions: Option<usize>) -> Result<()> {
+    update_single_repo_internal(repo, None, use_cache, keep_versions).map(|_| ())
 }
 
 /// Checks for and applies an update for a single repository using a pre-loaded [`Spell`].
-fn update_single_repo_with_spell(repo: &str, spell: &Spell) -> Result<()> {
-    update_single_repo_internal(repo, Some(spell))
+fn update_single_repo_with_spell(
+    repo: &str,
+    spell: &Spell,
+    use_cache: bool,
+    keep_versions: Option<usize>,
+) -> Result<UpdateOutcome> {
+    update_single_repo_internal(repo, Some(spell), use_cache, keep_versions)
 }
 
 /// Core update logic: compares the highest installed version against the latest GitHub release and
 /// installs the new version when one is available.
-fn update_single_repo_internal(repo: &str, spell: Option<&Spell>) -> Result<()> {
-    info!("Checking for updates for {}", repo);
+/// Cached GitHub API responses are only used when `use_cache` is `true`.
+/// Only the `keep_versions` newest versions are kept after an update, when set.
+fn update_single_repo_internal(
+    repo: &str,
+    spell: Option<&Spell>,
+    use_cache: bool,
+    keep_versions: Option<usize>,
+) -> Result<UpdateOutcome> {
+    info!("Checking {}...", repo);
+
+    if let Some(version) = pins::is_pinned(repo) {
+        info!(
+            "Skipping {} (pinned to version {}), use 'poof unpin {}' to allow updates.",
+            repo, version, repo
+        );
+        return Ok(UpdateOutcome::Pinned);
+    }
 
     // 1. find the specific asset for the requested repo
     let loaded_asset = if spell.is_none() {
@@ -35,6 +102,11 @@ fn update_single_repo_internal(repo: &str, spell: Option<&Spell>) -> Result<()>
     let asset = match spell.or(loaded_asset.as_ref()) {
         Some(asset) => asset,
         None => {
+            if let Some(hint) =
+                datadirs::get_data_dir().and_then(|dir| similar_repos_hint(&dir, repo))
+            {
+                error!("{}", hint);
+            }
             bail!(
                 "Repository '{}' not found. Check installed binaries using 'list' command.",
                 repo
@@ -50,7 +122,7 @@ fn update_single_repo_internal(repo: &str, spell: Option<&Spell>) -> Result<()>
                 "Repository '{}' found but has no versions listed. Nothing to update.",
                 repo
             );
-            return Ok(());
+            return Ok(UpdateOutcome::UpToDate);
         }
     };
 
@@ -66,9 +138,10 @@ fn update_single_repo_internal(repo: &str, spell: Option<&Spell>) -> Result<()>
         repo, highest_installed
     );
 
-    // 2. get the latest release tag from GitHub
-    // TODO: refactor get_release to return Result
-    let latest_release = get_release(repo, None) // None fetches the latest release
+    // 2. get the latest release tag from the service the binary was installed from
+    let source = installed_source(repo, &highest_installed_str);
+    let latest_release = source
+        .get_release(repo, None, use_cache) // None fetches the latest release
         .with_context(|| format!("Cannot get latest release information for {}", repo))?;
     let latest_version_str = latest_release.tag_name();
     let latest_version =
@@ -79,16 +152,34 @@ fn update_single_repo_internal(repo: &str, spell: Option<&Spell>) -> Result<()>
             )
         })?;
 
-    info!("Latest available version for {}: {}", repo, latest_version);
+    debug!("Latest available version for {}: {}", repo, latest_version);
 
     // 3. compare latest release tag with the highest installed version
     if latest_version > highest_installed {
         info!(
-            "Newer version {} found for {}. Updating from {}.",
-            latest_version, repo, highest_installed
+            "Updating {} from {} to {}...",
+            repo, highest_installed, latest_version
         );
         // 4. call process_install for the latest tag
-        commands::install::install(repo, Some(latest_version_str)).with_context(|| {
+        let triple = AssetTriple::default();
+        // keep the custom name the binary was installed with, if any
+        let options = InstallOptions {
+            rename: installed_rename(repo, &highest_installed_str),
+            bin_dir: installed_bin_dir(repo, &highest_installed_str),
+            update_from: Some(highest_installed_str.to_string()),
+            no_cache: !use_cache,
+            keep_versions,
+            // the warning is meant for explicit installs, not for every updated repository
+            allow_root: true,
+            ..Default::default()
+        };
+        commands::install::install(
+            &source.qualify(repo),
+            Some(latest_version_str),
+            &triple,
+            &options,
+        )
+        .with_context(|| {
             format!(
                 "Cannot install version {} as the default for {}",
                 latest_version_str, repo
@@ -98,19 +189,29 @@ fn update_single_repo_internal(repo: &str, spell: Option<&Spell>) -> Result<()>
             "Successfully updated {} to version {} and set it as default",
             repo, latest_version
         );
+        Ok(UpdateOutcome::Updated)
     } else {
         // 5. if no newer version, inform the user.
-        info!(
-            "{} is already up-to-date (version {}).",
-            repo, highest_installed
-        );
+        info!("{} is up-to-date (version {}).", repo, highest_installed);
+        Ok(UpdateOutcome::UpToDate)
     }
-
-    Ok(())
 }
 
-/// Checks and updates all installed repositories in parallel, reporting any failures.
-fn update_all_repos() -> Result<()> {
+/// Checks and updates all installed repositories, `jobs` of them at a time.
+///
+/// A failure for one repository does not stop the others: a summary is printed at
+/// the end, followed by the failed repositories and their errors. The exit code is
+/// 2 when only some repositories failed, an error is returned when all of them did.
+/// With `fail_fast` no repository is checked after the first failure, whose error
+/// is returned. With `summary_only` only warnings and errors are printed while the
+/// repositories are checked, the summary is printed as usual.
+fn update_all_repos(
+    use_cache: bool,
+    jobs: usize,
+    keep_versions: Option<usize>,
+    fail_fast: bool,
+    summary_only: bool,
+) -> Result<ExitCode> {
     info!("Checking for updates for all installed binaries...");
 
     // 1. get all installed assets
@@ -118,7 +219,7 @@ fn update_all_repos() -> Result<()> {
 
     if installed_assets.is_empty() {
         info!("No binaries installed yet. Nothing to update.");
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
     }
 
     info!(
@@ -126,55 +227,217 @@ fn update_all_repos() -> Result<()> {
         installed_assets.len()
     );
 
-    // 2. Use rayon::par_iter to parallelize calls to update_single_repo
-    let results: Vec<Result<()>> = installed_assets
-        .par_iter() // parallel iterator
-        .map(|asset| {
+    // 2. Run update_single_repo on a bounded worker pool.
+    // Repositories left unchecked because of --fail-fast yield `None`.
+    let log_level = log::max_level();
+    if summary_only {
+        log::set_max_level(log_level.min(LevelFilter::Warn));
+    }
+    let failed = AtomicBool::new(false);
+    let results: Vec<Result<Option<UpdateOutcome>>> =
+        run_with_pool(&installed_assets, jobs, |asset| {
+            if fail_fast && failed.load(Ordering::SeqCst) {
+                return Ok(None);
+            }
             // extract repo name for the call
             let repo_name = asset.get_name();
             // call update_single_repo for each asset using the already loaded spell
-            update_single_repo_with_spell(repo_name, asset)
+            update_single_repo_with_spell(repo_name, asset, use_cache, keep_versions)
+                .map(Some)
+                .inspect_err(|_| failed.store(true, Ordering::SeqCst))
                 // add context specific to this repo in case of failure
                 .with_context(|| format!("Cannot update {}", repo_name))
-        })
-        .collect(); // collect results
+        });
+    log::set_max_level(log_level);
 
     // 3. Collect results and report overall success/failures.
-    let mut failures = Vec::new();
-    for (index, result) in results.iter().enumerate() {
-        if let Err(e) = result {
-            // store the error along with the repo name it occurred for
-            let repo_name = installed_assets[index].get_name();
-            // use the error's context chain provided by anyhow
-            error!("Update failed for {}: {:?}", repo_name, e);
-            failures.push(format!("{}: {}", repo_name, e)); // store formatted error
+    let (mut updated, mut up_to_date, mut pinned) = (0, 0, 0);
+    let mut failures: Vec<(&str, anyhow::Error)> = Vec::new();
+    for (asset, result) in installed_assets.iter().zip(results) {
+        match result {
+            Ok(Some(UpdateOutcome::Updated)) => updated += 1,
+            Ok(Some(UpdateOutcome::UpToDate)) => up_to_date += 1,
+            Ok(Some(UpdateOutcome::Pinned)) => pinned += 1,
+            Ok(None) => {}
+            Err(e) => failures.push((asset.get_name(), e)),
         }
     }
 
+    if fail_fast && !failures.is_empty() {
+        let (_, e) = failures.swap_remove(0);
+        return Err(e.context("Update --all stopped at the first failure"));
+    }
+
+    info!(
+        "Updated: {}, Already up-to-date: {}, Skipped (pinned): {}, Failed: {}",
+        updated,
+        up_to_date,
+        pinned,
+        failures.len()
+    );
+    for (repo_name, e) in &failures {
+        // use the error's context chain provided by anyhow
+        error!("Update failed for {}: {:#}", repo_name, e);
+    }
+
     if failures.is_empty() {
         info!("All installed binaries checked successfully.");
-        Ok(()) // return Ok from the function here
+        Ok(ExitCode::SUCCESS)
+    } else if failures.len() == installed_assets.len() {
+        bail!(
+            "Update --all failed for all {} repositories.",
+            failures.len()
+        )
     } else {
         error!("{} repositories failed to update.", failures.len());
+        Ok(ExitCode::from(2))
+    }
+}
+
+/// Replaces the executable at `target` with `new_binary`.
+///
+/// The new binary is staged next to `target` and renamed over it, so that `target`
+/// always holds a complete executable. A running process keeps using the file it
+/// was started from. The previous executable is restored from a backup if the
+/// replacement fails.
+fn replace_executable(new_binary: &Path, target: &Path) -> Result<()> {
+    if !is_exec_for_current_arch(new_binary)
+        .with_context(|| format!("Cannot read {}", new_binary.display()))?
+    {
         bail!(
-            "Update --all finished with errors:\n - {}",
-            failures.join("\n - ")
-        )
+            "{} is not an executable for this platform, keeping {}",
+            new_binary.display(),
+            target.display()
+        );
+    }
+    let file_name = target
+        .file_name()
+        .with_context(|| format!("Cannot get file name of {}", target.display()))?
+        .to_string_lossy();
+    let staged = target.with_file_name(format!(".{}.new", file_name));
+    let backup = target.with_file_name(format!(".{}.bak", file_name));
+
+    fs::copy(new_binary, &staged)
+        .with_context(|| format!("Cannot copy new executable to {}", staged.display()))?;
+    filesys::make_executable(&staged);
+
+    // a running executable can be moved but not overwritten on Windows
+    #[cfg(not(target_os = "windows"))]
+    let backed_up = fs::copy(target, &backup).map(|_| ());
+    #[cfg(target_os = "windows")]
+    let backed_up = fs::rename(target, &backup);
+    if let Err(e) = backed_up {
+        let _ = fs::remove_file(&staged);
+        return Err(e).with_context(|| format!("Cannot back up {}", target.display()));
+    }
+
+    let replaced = fs::rename(&staged, target)
+        .with_context(|| format!("Cannot replace {}", target.display()))
+        .and_then(|_| match is_exec_for_current_arch(target) {
+            Ok(true) => Ok(()),
+            _ => bail!("{} is not executable after replacement", target.display()),
+        });
+    if let Err(e) = replaced {
+        let _ = fs::remove_file(&staged);
+        fs::rename(&backup, target).with_context(|| {
+            format!(
+                "Cannot restore {} from {}, copy it back manually",
+                target.display(),
+                backup.display()
+            )
+        })?;
+        return Err(e.context(format!("Restored the previous {}", target.display())));
+    }
+
+    // the backup is the running executable on Windows, it stays until next update
+    if let Err(e) = fs::remove_file(&backup) {
+        debug!("Cannot remove backup {}: {}", backup.display(), e);
+    }
+    Ok(())
+}
+
+/// Updates poof itself to its latest release.
+///
+/// The release is installed to the data directory like any other repository,
+/// without symlinks, then copied over the running executable.
+fn update_self(use_cache: bool) -> Result<()> {
+    info!("Checking for updates for {}", APP_NAME);
+    let current = Version::parse(VERSION)
+        .with_context(|| format!("Cannot parse current version '{}' as semver", VERSION))?;
+
+    let release = Source::default()
+        .get_release(THIS_REPO, None, use_cache)
+        .with_context(|| format!("Cannot get latest release information for {}", APP_NAME))?;
+    let latest_tag = release.tag_name();
+    let latest_str = latest_tag.strip_v();
+    let latest = Version::parse(&latest_str)
+        .with_context(|| format!("Cannot parse latest release tag '{}' as semver", latest_tag))?;
+    if latest <= current {
+        info!("{} is already up-to-date (version {}).", APP_NAME, current);
+        return Ok(());
     }
+
+    info!(
+        "Newer version {} found for {}. Updating from {}.",
+        latest, APP_NAME, current
+    );
+    let options = InstallOptions {
+        no_symlink: true,
+        no_cache: !use_cache,
+        allow_root: true,
+        ..Default::default()
+    };
+    commands::install::install(
+        THIS_REPO,
+        Some(latest_tag),
+        &AssetTriple::default(),
+        &options,
+    )
+    .with_context(|| format!("Cannot install version {} of {}", latest_tag, APP_NAME))?;
+
+    let data_dir = datadirs::get_data_dir().context("Cannot get data directory")?;
+    let new_binary = datadirs::get_binary_nest(&data_dir, THIS_REPO, &latest_str).join(format!(
+        "{}{}",
+        APP_NAME,
+        std::env::consts::EXE_SUFFIX
+    ));
+    // resolve symlinks, the file they point to is the one to replace
+    let current_exe = std::env::current_exe()
+        .and_then(fs::canonicalize)
+        .context("Cannot locate the running executable")?;
+    replace_executable(&new_binary, &current_exe)?;
+
+    info!(
+        "Successfully updated {} to version {} ({}).",
+        APP_NAME,
+        latest,
+        current_exe.display()
+    );
+    Ok(())
 }
 
 /// Check for newer GitHub releases and update the specified repository (or all).
 ///
-/// When `args.all` is `true` every installed repository is checked in parallel
-/// using rayon. When a specific repository is named via `args.repo`, only that
+/// When `args.all` is `true` every installed repository is checked, `args.jobs`
+/// of them at a time. When a specific repository is named via `args.repo`, only that
 /// one is updated. A non-fatal error for a single repository is collected and
-/// reported at the end without aborting the rest.
-pub fn process_update(args: &UpdateArgs) -> Result<()> {
-    if args.all {
-        update_all_repos().context("Failed during update --all")?;
-        Ok(())
+/// reported at the end without aborting the rest, unless `args.fail_fast` is set.
+/// With `args.update_self` poof updates its own executable.
+pub fn process_update(args: &UpdateArgs) -> Result<ExitCode> {
+    let keep_versions = config::get().keep_versions(args.keep_versions);
+    if args.update_self {
+        update_self(!args.no_cache).map(|_| ExitCode::SUCCESS)
+    } else if args.all {
+        update_all_repos(
+            !args.no_cache,
+            args.jobs.unwrap_or(1),
+            keep_versions,
+            args.fail_fast,
+            args.summary_only,
+        )
+        .context("Failed during update --all")
     } else if let Some(repo) = &args.repo {
-        update_single_repo(repo)
+        update_single_repo(repo, !args.no_cache, keep_versions).map(|_| ExitCode::SUCCESS)
     } else {
         bail!("No repository specified, and --all flag was not provided.");
     }
@@ -79,6 +79,7 @@ fn create_fake_installation(base_data_dir: &Path, repo: &str, version: &str) ->
     // Create a fake binary file to make the installation look valid
     let binary_path = install_dir.join(parts[1]);
     fs::write(&binary_path, b"fake binary")?;
+    filesys::make_executable(&binary_path);
     Ok(())
 }
 
@@ -120,7 +121,7 @@ fn test_update_single_repo_not_installed() -> Result<()> {
 
     temp_env::with_vars(env_vars, || {
         // Try to update a repo that's not installed
-        let result = update_single_repo("user/notinstalled");
+        let result = update_single_repo("user/notinstalled", false, None);
         // Should succeed with a message that it's not installed
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -144,7 +145,7 @@ fn test_update_all_repos_empty() -> Result<()> {
 
     temp_env::with_vars(env_vars, || {
         // Try to update all repos when nothing is installed
-        let result = update_all_repos();
+        let result = update_all_repos(false, 1, None, false, false);
         // Should succeed with a message that nothing is installed
         assert!(result.is_ok());
     });
@@ -173,7 +174,7 @@ fn test_update_single_repo_up_to_date() -> Result<()> {
 
     temp_env::with_vars(env_vars, || {
         // Update repo that's already up to date
-        let result = update_single_repo("testuser/testrepo");
+        let result = update_single_repo("testuser/testrepo", false, None);
         // Should succeed and report up-to-date
         assert!(result.is_ok());
     });
@@ -181,6 +182,48 @@ fn test_update_single_repo_up_to_date() -> Result<()> {
     Ok(())
 }
 
+/// Runs `update_single_repo` with `installed` as only installed version and `tag`
+/// as latest release tag. It succeeds only when no update is attempted, as the
+/// assets of the release are not mocked.
+fn update_with_versions(installed: &str, tag: &str) -> Result<()> {
+    let test_env = setup_test_env()?;
+    create_fake_installation(test_env.data_dir.as_path(), "testuser/testrepo", installed)?;
+
+    let mut server = Server::new();
+    let _m = mock_release_response(&mut server, "testuser/testrepo", tag, 200);
+
+    let server_url = server.url();
+    let mut env_vars: Vec<(&str, Option<&str>)> = test_env
+        .env_vars
+        .iter()
+        .map(|(k, v)| (*k, Some(v.as_str())))
+        .collect();
+    env_vars.push(("POOF_GITHUB_API_URL", Some(server_url.as_str())));
+
+    temp_env::with_vars(env_vars, || {
+        update_single_repo("testuser/testrepo", false, None)
+    })
+}
+
+#[test]
+fn test_update_single_repo_v_prefix_mismatch_is_up_to_date() {
+    assert!(update_with_versions("1.2.0", "v1.2.0").is_ok());
+    assert!(update_with_versions("1.2.0", "V1.2.0").is_ok());
+}
+
+#[test]
+fn test_update_single_repo_build_metadata_is_up_to_date() {
+    assert!(update_with_versions("1.2.0", "v1.2.0+build.7").is_ok());
+    assert!(update_with_versions("1.2.0+build.6", "1.2.0+build.7").is_ok());
+}
+
+#[test]
+fn test_update_single_repo_prerelease_is_older_than_release() {
+    // an update is attempted and fails, the assets are not mocked
+    assert!(update_with_versions("1.2.0-rc1", "v1.2.0").is_err());
+    assert!(update_with_versions("1.2.0", "v1.2.0-rc1").is_ok());
+}
+
 #[test]
 fn test_update_single_repo_on_error_with_newer_version() -> Result<()> {
     let test_env = setup_test_env()?;
@@ -201,7 +244,7 @@ fn test_update_single_repo_on_error_with_newer_version() -> Result<()> {
     env_vars.push(("POOF_GITHUB_API_URL", Some(server_url.as_str())));
 
     temp_env::with_vars(env_vars, || {
-        let result = update_single_repo("testuser/testrepo");
+        let result = update_single_repo("testuser/testrepo", false, None);
         // install() should fail since we haven't mocked download assets
         assert!(result.is_err(), "Expected error when install() fails");
         let err_msg = result.unwrap_err().to_string();
@@ -239,14 +282,53 @@ fn test_update_all_repos_with_multiple_installations() -> Result<()> {
         .collect();
     env_vars.push(("POOF_GITHUB_API_URL", Some(server_url.as_str())));
 
+    temp_env::with_vars(env_vars.clone(), || {
+        // repo3 failed while the others are up to date: partial failure
+        let result = update_all_repos(false, 2, None, false, false);
+        assert_eq!(result.unwrap(), ExitCode::from(2));
+    });
+
     temp_env::with_vars(env_vars, || {
-        let result = update_all_repos();
-        // Should fail because repo3 failed
-        assert!(result.is_err());
+        // with --fail-fast the error of repo3 is returned
+        let result = update_all_repos(false, 1, None, true, false);
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("user3/repo3"), "{}", err_msg);
+        assert!(
+            err_msg.contains("stopped at the first failure"),
+            "{}",
+            err_msg
+        );
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_update_all_repos_total_failure() -> Result<()> {
+    let test_env = setup_test_env()?;
+    create_fake_installation(test_env.data_dir.as_path(), "user1/repo1", "1.0.0")?;
+    create_fake_installation(test_env.data_dir.as_path(), "user2/repo2", "1.0.0")?;
+
+    let mut server = Server::new();
+    let _m1 = mock_release_response(&mut server, "user1/repo1", "v1.0.0", 500);
+    let _m2 = mock_release_response(&mut server, "user2/repo2", "v1.0.0", 500);
+
+    let server_url = server.url();
+    let mut env_vars: Vec<(&str, Option<&str>)> = test_env
+        .env_vars
+        .iter()
+        .map(|(k, v)| (*k, Some(v.as_str())))
+        .collect();
+    env_vars.push(("POOF_GITHUB_API_URL", Some(server_url.as_str())));
+
+    temp_env::with_vars(env_vars, || {
+        let result = update_all_repos(false, 1, None, false, false);
         let err_msg = result.unwrap_err().to_string();
-        // Should mention the failed repo
-        assert!(err_msg.contains("user3/repo3"));
-        assert!(err_msg.contains("Update --all finished with errors"));
+        assert!(
+            err_msg.contains("failed for all 2 repositories"),
+            "{}",
+            err_msg
+        );
     });
 
     Ok(())
@@ -277,7 +359,7 @@ fn test_update_single_repo_invalid_semver_installed() -> Result<()> {
 
     temp_env::with_vars(env_vars, || {
         // Update repo with invalid semver should fail
-        let result = update_single_repo("testuser/testrepo");
+        let result = update_single_repo("testuser/testrepo", false, None);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("Cannot parse") || err_msg.contains("semver"));
@@ -320,7 +402,7 @@ fn test_update_single_repo_invalid_semver_from_github() -> Result<()> {
 
     temp_env::with_vars(env_vars, || {
         // Update repo should fail due to invalid semver from GitHub
-        let result = update_single_repo("testuser/testrepo");
+        let result = update_single_repo("testuser/testrepo", false, None);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("Cannot parse") || err_msg.contains("semver"));
@@ -355,7 +437,7 @@ fn test_update_single_repo_github_api_failure() -> Result<()> {
 
     temp_env::with_vars(env_vars, || {
         // Update repo should fail due to GitHub API error
-        let result = update_single_repo("testuser/testrepo");
+        let result = update_single_repo("testuser/testrepo", false, None);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("Cannot get latest release") || err_msg.contains("500"));
@@ -386,7 +468,7 @@ fn test_update_single_repo_with_spell_uses_provided_versions() -> Result<()> {
             "testuser/testrepo".to_string(),
             vec!["invalid-version".to_string()],
         );
-        let result = update_single_repo_with_spell("testuser/testrepo", &spell);
+        let result = update_single_repo_with_spell("testuser/testrepo", &spell, false, None);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("Cannot parse") || err_msg.contains("semver"));
@@ -402,6 +484,12 @@ fn test_process_update_with_no_arguments() -> Result<()> {
     let args = UpdateArgs {
         repo: None,
         all: false,
+        update_self: false,
+        no_cache: true,
+        jobs: None,
+        keep_versions: None,
+        fail_fast: false,
+        summary_only: false,
     };
 
     let result = process_update(&args);
@@ -421,6 +509,12 @@ fn test_process_update_with_all_flag() -> Result<()> {
     let args = UpdateArgs {
         repo: None,
         all: true,
+        update_self: false,
+        no_cache: true,
+        jobs: None,
+        keep_versions: None,
+        fail_fast: false,
+        summary_only: false,
     };
 
     let env_vars: Vec<(&str, Option<&str>)> = test_env
@@ -447,6 +541,12 @@ fn test_process_update_with_repo_name() -> Result<()> {
     let args = UpdateArgs {
         repo: Some("user/repo".to_string()),
         all: false,
+        update_self: false,
+        no_cache: true,
+        jobs: None,
+        keep_versions: None,
+        fail_fast: false,
+        summary_only: false,
     };
 
     let env_vars: Vec<(&str, Option<&str>)> = test_env
@@ -466,3 +566,75 @@ fn test_process_update_with_repo_name() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(not(target_os = "windows"))]
+fn write_script(path: &Path, content: &str) {
+    fs::write(path, content).unwrap();
+    filesys::make_executable(path);
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_replace_executable() {
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("poof");
+    let new_binary = dir.path().join("poof-new");
+    write_script(&target, "#!/bin/sh\necho old\n");
+    write_script(&new_binary, "#!/bin/sh\necho new\n");
+
+    replace_executable(&new_binary, &target).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&target).unwrap(),
+        "#!/bin/sh\necho new\n"
+    );
+    assert!(filesys::is_executable(&target));
+    // neither the staged file nor the backup are left behind
+    let mut names: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["poof", "poof-new"]);
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_replace_executable_rejects_non_executable() {
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("poof");
+    let new_binary = dir.path().join("poof-new");
+    write_script(&target, "#!/bin/sh\necho old\n");
+    fs::write(&new_binary, "<html>not found</html>").unwrap();
+
+    let err = replace_executable(&new_binary, &target).unwrap_err();
+    assert!(err.to_string().contains("not an executable"), "{}", err);
+    assert_eq!(
+        fs::read_to_string(&target).unwrap(),
+        "#!/bin/sh\necho old\n"
+    );
+}
+
+#[test]
+fn test_update_self_up_to_date() -> Result<()> {
+    for tag in [format!("v{}", VERSION), "v0.0.1".to_string()] {
+        let test_env = setup_test_env()?;
+        let mut server = Server::new();
+        let m = mock_release_response(&mut server, THIS_REPO, &tag, 200);
+
+        let server_url = server.url();
+        let mut env_vars: Vec<(&str, Option<&str>)> = test_env
+            .env_vars
+            .iter()
+            .map(|(k, v)| (*k, Some(v.as_str())))
+            .collect();
+        env_vars.push(("POOF_GITHUB_API_URL", Some(server_url.as_str())));
+
+        // nothing is installed, as the release has no assets to install
+        temp_env::with_vars(env_vars, || update_self(false))?;
+        m.assert();
+        assert!(!test_env.data_dir.join("pirafrank").exists());
+    }
+    Ok(())
+}
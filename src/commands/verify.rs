@@ -0,0 +1,197 @@
+//! Main file handling 'verify' command
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::cli::VerifyArgs;
+use crate::commands::download::fetch_text;
+use crate::commands::list::list_installed_spells;
+use crate::commands::make_default::{get_installed_dir, get_latest_version};
+use crate::files::{checksum, datadirs, metadata};
+use crate::output;
+
+/// Outcome of the verification of a single binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// The binary matches its checksum.
+    Pass,
+    /// The binary does not match its checksum.
+    Fail,
+    /// No checksum is known for the binary.
+    Unknown,
+}
+
+impl Outcome {
+    /// Label printed in the `Result` column.
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Pass => "PASS",
+            Outcome::Fail => "FAIL",
+            Outcome::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// Returns the installed versions of `repo`, as named in the data directory.
+fn installed_versions(repo: &str) -> Vec<String> {
+    let Some(data_dir) = datadirs::get_data_dir() else {
+        return Vec::new();
+    };
+    let mut versions: Vec<String> = std::fs::read_dir(datadirs::get_versions_nest(&data_dir, repo))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    versions.sort();
+    versions
+}
+
+/// Fetch every SHA-256 checksum published with the release `version` of `repo`.
+///
+/// Releases are looked up by tag, with and without the `v` prefix, from the
+/// service the version has been installed from.
+fn published_checksums(repo: &str, version: &str, version_dir: &Path) -> Result<HashSet<String>> {
+    let source = metadata::load_source(version_dir);
+    let release = source
+        .get_release(repo, Some(&format!("v{}", version)), true)
+        .or_else(|_| source.get_release(repo, Some(version), true))
+        .with_context(|| format!("Cannot find release {} of {}", version, repo))?;
+    let mut hashes = HashSet::new();
+    for asset in release
+        .assets()
+        .iter()
+        .filter(|a| checksum::is_sha256_checksum_file(a.name()))
+    {
+        let content = fetch_text(asset.browser_download_url())
+            .with_context(|| format!("Cannot fetch checksum file {}", asset.name()))?;
+        hashes.extend(
+            checksum::parse_checksums(&content)
+                .into_iter()
+                .map(|(hash, _)| hash),
+        );
+    }
+    Ok(hashes)
+}
+
+/// Verify the binaries of `version` of `repo`, printing one line per binary.
+fn verify_version(repo: &str, version: &str, redownload: bool) -> Result<Vec<Outcome>> {
+    let version_dir = get_installed_dir(repo, version)?;
+    // fetched at most once per version, only when needed
+    let mut published: Option<HashSet<String>> = None;
+    let mut outcomes = Vec::new();
+    for binary in metadata::list_binaries(&version_dir) {
+        let actual = checksum::sha256_file(&version_dir.join(&binary))?;
+        let outcome = match metadata::load_checksum(&version_dir, &binary) {
+            Some(expected) if expected == actual => Outcome::Pass,
+            Some(_) => Outcome::Fail,
+            None if redownload => {
+                if published.is_none() {
+                    published = Some(
+                        published_checksums(repo, version, &version_dir).unwrap_or_else(|e| {
+                            warn!("{:#}", e);
+                            HashSet::new()
+                        }),
+                    );
+                }
+                // only bare executable assets can be matched against published checksums,
+                // binaries extracted from archives are not listed there
+                if published.as_ref().is_some_and(|p| p.contains(&actual)) {
+                    metadata::save_checksum(&version_dir, &binary, &actual)?;
+                    debug!("Recorded checksum of {}", binary);
+                    Outcome::Pass
+                } else {
+                    Outcome::Unknown
+                }
+            }
+            None => Outcome::Unknown,
+        };
+        output!(
+            "{:<40}\t{:<15}\t{:<20}\t{}",
+            repo,
+            version,
+            binary,
+            outcome.label()
+        );
+        outcomes.push(outcome);
+    }
+    Ok(outcomes)
+}
+
+/// Re-check the integrity of installed binaries against their recorded checksums.
+///
+/// Checksums are recorded at install time when the `verify_checksums` config
+/// setting is enabled. Returns [`ExitCode::FAILURE`] when any binary does not
+/// match its checksum. Binaries without a known checksum are reported as
+/// `UNKNOWN` and do not make the command fail.
+pub fn run_verify(args: &VerifyArgs) -> Result<ExitCode> {
+    let targets: Vec<(String, String)> = if args.all {
+        list_installed_spells()
+            .iter()
+            .flat_map(|spell| {
+                let repo = spell.get_name().to_string();
+                installed_versions(&repo)
+                    .into_iter()
+                    .map(move |version| (repo.clone(), version))
+            })
+            .collect()
+    } else if let Some(repo) = &args.repo {
+        let version = match &args.version {
+            Some(version) => version.clone(),
+            None => get_latest_version(repo)?,
+        };
+        vec![(repo.clone(), version)]
+    } else {
+        anyhow::bail!("No repository specified, and --all flag was not provided.");
+    };
+
+    if targets.is_empty() {
+        info!("No installed binaries found.");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    output!("");
+    output!(
+        "{:<40}\t{:<15}\t{:<20}\t{}",
+        "Repository",
+        "Version",
+        "Binary",
+        "Result"
+    );
+    output!(
+        "{:<40}\t{:<15}\t{:<20}\t{}",
+        "----------",
+        "-------",
+        "------",
+        "------"
+    );
+    let mut outcomes = Vec::new();
+    for (repo, version) in &targets {
+        outcomes.extend(verify_version(repo, version, args.redownload_checksums)?);
+    }
+
+    let unknown = outcomes.iter().filter(|o| **o == Outcome::Unknown).count();
+    if unknown > 0 {
+        warn!(
+            "{} binaries have no known checksum. Enable 'verify_checksums' in the config file to record them at install time{}.",
+            unknown,
+            if args.redownload_checksums {
+                ""
+            } else {
+                ", or try --redownload-checksums"
+            }
+        );
+    }
+    if outcomes.contains(&Outcome::Fail) {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
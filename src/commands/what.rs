@@ -2,11 +2,14 @@
 
 use anyhow::{bail, Context, Result};
 use log::error;
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::cli::WhatArgs;
 use crate::files::datadirs;
 use crate::files::filesys;
+use crate::files::metadata;
 use crate::files::utils::find_similar_repo;
 use crate::models::slug::Slug;
 use crate::output;
@@ -16,7 +19,8 @@ use crate::utils::semver::SemverSort;
 ///
 /// Validates that `args.repo` is an installed slug, resolves the latest version
 /// via semver sorting, and prints all executable files found in that version's
-/// install directory.
+/// install directory. With `--verbose` or `--json` the binaries of every installed
+/// version are listed instead, see [`print_all_versions`].
 pub fn run_what(args: &WhatArgs) -> Result<()> {
     // Validate slug
     let slug = Slug::new(&args.repo)?;
@@ -49,7 +53,9 @@ pub fn run_what(args: &WhatArgs) -> Result<()> {
     for entry in entries.flatten() {
         if let Ok(file_type) = entry.file_type() {
             if file_type.is_dir() {
-                if let Some(version_name) = entry.file_name().to_str() {
+                if let Some(version_name) =
+                    entry.file_name().to_str().filter(|n| !n.starts_with('.'))
+                {
                     versions.push(version_name.to_string());
                 }
             }
@@ -68,6 +74,10 @@ pub fn run_what(args: &WhatArgs) -> Result<()> {
     // Sort versions using semantic versioning
     versions.sort_semver();
 
+    if args.verbose || args.json {
+        return print_all_versions(&slug, &data_dir, &versions, args.json);
+    }
+
     // Get the latest version (last element after sorting)
     let latest_version = versions
         .last()
@@ -93,9 +103,80 @@ pub fn run_what(args: &WhatArgs) -> Result<()> {
     output!("{} (version {}) provides:", slug, latest_version);
     for binary_path in binaries {
         if let Some(binary_name) = binary_path.file_name() {
-            output!("- {}", binary_name.to_string_lossy());
+            let binary_name = binary_name.to_string_lossy();
+            let link_name = metadata::link_name_for(&latest_version_dir, &binary_name);
+            if link_name == binary_name {
+                output!("- {}", binary_name);
+            } else {
+                output!("- {} (renamed from {})", link_name, binary_name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the binaries provided by any of `versions` of `slug`, sorted by name,
+/// each with the versions providing it and the path it is installed at in each.
+/// Binaries are named as they are in PATH, custom names included.
+fn print_all_versions(slug: &Slug, data_dir: &Path, versions: &[String], json: bool) -> Result<()> {
+    // binary name -> (version, install path), in version order
+    let mut binaries: BTreeMap<String, Vec<(&str, PathBuf)>> = BTreeMap::new();
+    for version in versions {
+        let version_dir = datadirs::get_binary_nest(data_dir, slug.as_str(), version);
+        for binary_path in filesys::find_exec_files_in_dir(&version_dir, false) {
+            let Some(binary_name) = binary_path.file_name() else {
+                continue;
+            };
+            let link_name = metadata::link_name_for(&version_dir, &binary_name.to_string_lossy());
+            binaries
+                .entry(link_name)
+                .or_default()
+                .push((version.as_str(), binary_path));
         }
     }
 
+    if json {
+        let binaries: Vec<serde_json::Value> = binaries
+            .iter()
+            .map(|(name, provided_by)| {
+                serde_json::json!({
+                    "name": name,
+                    "versions": provided_by
+                        .iter()
+                        .map(|(version, path)| serde_json::json!({
+                            "version": version,
+                            "path": path.display().to_string(),
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let what = serde_json::json!({
+            "repo": slug.as_str(),
+            "binaries": binaries,
+        });
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&what).context("Cannot serialize binaries to JSON")?
+        );
+        return Ok(());
+    }
+
+    if binaries.is_empty() {
+        error!(
+            "No binaries found in any version of '{}'. Installation may be corrupted.",
+            slug
+        );
+        bail!("No binaries found for '{}'", slug);
+    }
+    output!("{} provides:", slug);
+    for (name, provided_by) in &binaries {
+        let versions: Vec<&str> = provided_by.iter().map(|(version, _)| *version).collect();
+        output!("- {} (versions {})", name, versions.join(", "));
+        for (version, path) in provided_by {
+            output!("    {:<16}\t{}", version, path.display());
+        }
+    }
     Ok(())
 }
@@ -1,51 +1,192 @@
 //! Main file handling 'which' command
 
 use anyhow::{anyhow, Context, Result};
+use log::error;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::cli::WhichArgs;
 use crate::commands::list::list_installed_spells;
-use crate::files::{datadirs, magic};
+use crate::files::{datadirs, magic, metadata};
 use crate::models::spell::Spell;
 use crate::output;
+use crate::utils::string::levenshtein_distance;
+
+/// Maximum number of binary names suggested when a lookup finds nothing.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// An installed version providing the looked up binary.
+struct Provider {
+    slug: String,
+    version: String,
+    /// `true` if the binary symlink in the bin directory points to this version.
+    active: bool,
+}
 
 /// Find which installed repository (and version) provides a given binary name.
 ///
 /// Searches across all installed spells for an executable matching
 /// `args.binary_name`, taking both the file system layout and the current
-/// active symlink target into account.
+/// active symlink target into account. Every providing version is listed,
+/// the one the bin directory symlink points to is marked as active.
+///
+/// With `--all` every binary provided by the installed repositories is listed
+/// instead, see [`list_all_binaries`].
 pub fn run_which(args: &WhichArgs) -> Result<()> {
     let data_dir = datadirs::get_data_dir().context("Cannot get data directory path")?;
     let spells = list_installed_spells();
 
+    if args.all {
+        return list_all_binaries(&spells, &data_dir, args.json);
+    }
+    let binary_name = args
+        .binary_name
+        .as_deref()
+        .context("A binary name is required unless --all is given")?;
+
     // Find all binaries matching the requested name across all installed repositories.
-    let matches = find_binary_providers(&spells, &data_dir, &args.binary_name);
+    let matches = find_binary_providers(&spells, &data_dir, binary_name);
 
     if matches.is_empty() {
+        if let Some(hint) = similar_binaries_hint(&spells, &data_dir, binary_name) {
+            error!("{}", hint);
+        }
         return Err(anyhow!(
             "'{}' not found in any installed repositories.",
-            args.binary_name
+            binary_name
         ));
     }
 
+    if args.json {
+        let items: Vec<serde_json::Value> = matches
+            .iter()
+            .map(|provider| {
+                serde_json::json!({
+                    "repo": provider.slug,
+                    "version": provider.version,
+                    "active": provider.active,
+                })
+            })
+            .collect();
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&items).context("Cannot serialize providers to JSON")?
+        );
+        return Ok(());
+    }
+
     // Display results
-    output!("{} is provided by:", args.binary_name);
-    for (slug, version) in matches {
-        output!("{} {}", slug, version);
+    output!("{} is provided by:", binary_name);
+    output!("");
+    output!("{:<40}\t{:<16}\t{}", "Repository", "Version", "Active");
+    output!("{:<40}\t{:<16}\t{}", "----------", "-------", "------");
+    for provider in &matches {
+        output!(
+            "{:<40}\t{:<16}\t{}",
+            provider.slug,
+            provider.version,
+            if provider.active { "*" } else { "" }
+        );
+    }
+    if !matches.iter().any(|provider| provider.active) {
+        output!("");
+        output!(
+            "'{}' is installed but not linked in the bin directory. Run 'poof link {}' to add it to PATH.",
+            binary_name,
+            matches[0].slug
+        );
     }
 
     Ok(())
 }
 
-/// Searches all installed spells for versions that contain an executable named `binary_name`.
-fn find_binary_providers(
+/// Print every binary provided by the installed `spells` with its repository,
+/// and the version the bin directory symlink points to, if any.
+fn list_all_binaries(spells: &[Spell], data_dir: &Path, json: bool) -> Result<()> {
+    let inventory = binary_inventory(spells, data_dir);
+
+    if json {
+        let items: Vec<serde_json::Value> = inventory
+            .iter()
+            .map(|((binary, slug), active_version)| {
+                serde_json::json!({
+                    "binary": binary,
+                    "repo": slug,
+                    "active_version": active_version,
+                })
+            })
+            .collect();
+        output!(
+            "{}",
+            serde_json::to_string_pretty(&items).context("Cannot serialize binaries to JSON")?
+        );
+        return Ok(());
+    }
+
+    if inventory.is_empty() {
+        output!("No binaries installed.");
+        return Ok(());
+    }
+    output!("");
+    output!(
+        "{:<24}\t{:<40}\t{}",
+        "Binary",
+        "Repository",
+        "Active version"
+    );
+    output!(
+        "{:<24}\t{:<40}\t{}",
+        "------",
+        "----------",
+        "--------------"
+    );
+    for ((binary, slug), active_version) in &inventory {
+        output!(
+            "{:<24}\t{:<40}\t{}",
+            binary,
+            slug,
+            active_version.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+/// Maps each `(binary name, slug)` pair found across all installed versions of
+/// `spells` to the version the binary symlink points to, `None` if unlinked.
+/// Binaries installed with a custom name are listed under that name.
+fn binary_inventory(
     spells: &[Spell],
     data_dir: &Path,
-    binary_name: &str,
-) -> Vec<(String, String)> {
+) -> BTreeMap<(String, String), Option<String>> {
+    let mut inventory: BTreeMap<(String, String), Option<String>> = BTreeMap::new();
+    for spell in spells {
+        let slug = spell.get_name();
+        let versions_nest = datadirs::get_versions_nest(data_dir, slug);
+        for version in spell.get_versions() {
+            let version_str = version.to_string();
+            let version_dir = versions_nest.join(&version_str);
+            for exec_name in metadata::list_binaries(&version_dir) {
+                if !is_executable_file(&version_dir.join(&exec_name)) {
+                    continue;
+                }
+                let binary = metadata::link_name_for(&version_dir, &exec_name);
+                let is_active = resolve_managed_symlink_target(&binary)
+                    .is_some_and(|target| target.starts_with(&version_dir));
+                let active_version = inventory.entry((binary, slug.clone())).or_insert(None);
+                if is_active {
+                    *active_version = Some(version_str.clone());
+                }
+            }
+        }
+    }
+    inventory
+}
+
+/// Searches all installed spells for versions that contain an executable named `binary_name`.
+fn find_binary_providers(spells: &[Spell], data_dir: &Path, binary_name: &str) -> Vec<Provider> {
     let symlink_target = resolve_managed_symlink_target(binary_name);
-    let mut matches: Vec<(String, String)> = Vec::new();
+    let mut matches: Vec<Provider> = Vec::new();
 
     for spell in spells {
         let slug = spell.get_name().to_owned();
@@ -55,12 +196,19 @@ fn find_binary_providers(
             let version_str = version.to_string();
             let version_dir = versions_nest.join(&version_str);
             let has_exact_binary = has_executable_named(&version_dir, binary_name);
+            let has_renamed_binary = metadata::load_renames(&version_dir)
+                .values()
+                .any(|link_name| link_name == binary_name);
             let has_symlink_for_version = symlink_target
                 .as_ref()
                 .is_some_and(|target| target.starts_with(&version_dir));
 
-            if has_exact_binary || has_symlink_for_version {
-                matches.push((slug.clone(), version_str));
+            if has_exact_binary || has_renamed_binary || has_symlink_for_version {
+                matches.push(Provider {
+                    slug: slug.clone(),
+                    version: version_str,
+                    active: has_symlink_for_version,
+                });
             }
         }
     }
@@ -68,6 +216,30 @@ fn find_binary_providers(
     matches
 }
 
+/// Returns a `Did you mean: ...?` hint listing the installed binary names most
+/// similar to `binary_name`, or `None` when there are none.
+fn similar_binaries_hint(spells: &[Spell], data_dir: &Path, binary_name: &str) -> Option<String> {
+    let mut names: BTreeSet<String> = BTreeSet::new();
+    for spell in spells {
+        let versions_nest = datadirs::get_versions_nest(data_dir, spell.get_name());
+        for version in spell.get_versions() {
+            let version_dir = versions_nest.join(version.to_string());
+            for exec_name in metadata::list_binaries(&version_dir) {
+                names.insert(metadata::link_name_for(&version_dir, &exec_name));
+            }
+        }
+    }
+    let similar: Vec<String> = names
+        .into_iter()
+        .filter(|name| levenshtein_distance(binary_name, name) <= 2)
+        .take(MAX_SUGGESTIONS)
+        .collect();
+    if similar.is_empty() {
+        return None;
+    }
+    Some(format!("Did you mean: {}?", similar.join(", ")))
+}
+
 /// Returns `true` if `version_dir` contains an executable file named `binary_name`.
 fn has_executable_named(version_dir: &Path, binary_name: &str) -> bool {
     let direct_candidate = version_dir.join(binary_name);
@@ -8,6 +8,8 @@ pub const DESCRIPTION: &str = "magic manager of pre-built software";
 pub const AUTHOR: &str = env!("CARGO_PKG_AUTHORS");
 /// The canonical URL of the upstream repository.
 pub const THIS_REPO_URL: &str = env!("CARGO_PKG_REPOSITORY");
+/// The GitHub slug poof itself is released from.
+pub const THIS_REPO: &str = "pirafrank/poof";
 
 /// The current application version, sourced from `Cargo.toml`.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -24,14 +26,229 @@ pub const DATA_SUBDIR: &str = "data";
 pub const BIN_SUBDIR: &str = "bin";
 /// Sub-directory name used to namespace GitHub-hosted repositories inside the data root.
 pub const GITHUB_SUBDIR: &str = "github.com";
+/// Sub-directory name used to namespace GitLab-hosted repositories inside the data root.
+pub const GITLAB_SUBDIR: &str = "gitlab.com";
+
+/// Sub-directory name under the cache root holding cached GitHub API responses.
+pub const API_CACHE_SUBDIR: &str = "api";
+/// Default lifetime, in seconds, of a cached GitHub API response.
+pub const DEFAULT_API_CACHE_TTL_SECONDS: u64 = 300;
+
+/// Default timeout, in seconds, of network operations.
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 60;
+
+/// Environment variable overriding the directory poof keeps its data in.
+pub const DATA_DIR_ENV: &str = "POOF_DATA_DIR";
+/// Environment variable overriding the directory poof keeps its cache in.
+pub const CACHE_DIR_ENV: &str = "POOF_CACHE_DIR";
+/// Environment variable overriding the directory the binaries are symlinked into.
+pub const BIN_DIR_ENV: &str = "POOF_BIN_DIR";
+/// Environment variable setting the timeout, in seconds, of network operations.
+pub const TIMEOUT_ENV: &str = "POOF_TIMEOUT";
+/// Environment variable overriding the lifetime, in seconds, of cached API responses.
+/// A value of `0` disables the cache.
+pub const API_CACHE_TTL_ENV: &str = "POOF_API_CACHE_TTL_SECONDS";
+/// Environment variable forcing the preference for MUSL-linked assets.
+pub const PREFER_MUSL_ENV: &str = "POOF_PREFER_MUSL";
+/// Environment variable holding a regular expression that selects the assets to
+/// install or download, like `--asset-pattern`.
+pub const ASSET_PATTERN_ENV: &str = "POOF_ASSET_PATTERN";
+/// Environment variable printing errors as JSON when set to `1`, like `--json`.
+pub const JSON_ERRORS_ENV: &str = "POOF_JSON_ERRORS";
+/// Environment variable overriding the base URL of the GitHub API.
+pub const GITHUB_API_URL_ENV: &str = "POOF_GITHUB_API_URL";
+/// Environment variable holding the token used to authenticate to GitHub.
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+/// Environment variable overriding the base URL of the GitLab API.
+pub const GITLAB_API_URL_ENV: &str = "POOF_GITLAB_API_URL";
+/// Environment variable holding the token used to authenticate to GitLab.
+pub const GITLAB_TOKEN_ENV: &str = "GITLAB_TOKEN";
+/// Environment variable overriding the base URL of the Gitea and Forgejo API.
+pub const GITEA_API_URL_ENV: &str = "POOF_GITEA_API_URL";
+/// Environment variable holding the token used to authenticate to Gitea and Forgejo.
+pub const GITEA_TOKEN_ENV: &str = "GITEA_TOKEN";
+
+/// Base URL for the GitHub REST API.
+pub const GITHUB_API_URL: &str = "https://api.github.com/repos";
+/// Base URL for the GitLab REST API.
+pub const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Every environment variable poof recognises, as `(name, default, description)`.
+/// An empty default means the variable is unset unless the user sets it.
+/// This is what `poof env-vars` prints, keep it in sync when reading a new variable.
+pub const ENV_VARS: &[(&str, &str, &str)] = &[
+    (
+        DATA_DIR_ENV,
+        "",
+        "Directory poof keeps installed versions in, replacing the platform default",
+    ),
+    (
+        CACHE_DIR_ENV,
+        "",
+        "Directory poof keeps downloads and API responses in, replacing the platform default",
+    ),
+    (
+        BIN_DIR_ENV,
+        "",
+        "Directory the installed binaries are symlinked into",
+    ),
+    (
+        TIMEOUT_ENV,
+        "60",
+        "Timeout, in seconds, of network operations",
+    ),
+    (
+        API_CACHE_TTL_ENV,
+        "300",
+        "Lifetime, in seconds, of cached API responses, 0 disables the cache",
+    ),
+    (
+        PREFER_MUSL_ENV,
+        "",
+        "Set to 1 to prefer MUSL-linked assets, to 0 to prefer glibc ones",
+    ),
+    (
+        ASSET_PATTERN_ENV,
+        "",
+        "Regular expression selecting the assets to install or download, same as --asset-pattern",
+    ),
+    (
+        JSON_ERRORS_ENV,
+        "",
+        "Set to 1 to print errors to stderr as JSON, same as --json",
+    ),
+    (
+        GITHUB_API_URL_ENV,
+        GITHUB_API_URL,
+        "Base URL of the GitHub API",
+    ),
+    (
+        GITHUB_TOKEN_ENV,
+        "",
+        "Token authenticating GitHub API requests",
+    ),
+    (
+        GITLAB_API_URL_ENV,
+        GITLAB_API_URL,
+        "Base URL of the GitLab API",
+    ),
+    (
+        GITLAB_TOKEN_ENV,
+        "",
+        "Token authenticating GitLab API requests",
+    ),
+    (
+        GITEA_API_URL_ENV,
+        "https://<host>/api/v1/repos",
+        "Base URL of the Gitea and Forgejo API",
+    ),
+    (
+        GITEA_TOKEN_ENV,
+        "",
+        "Token authenticating Gitea and Forgejo API requests",
+    ),
+    (
+        "XDG_DATA_HOME",
+        "~/.local/share",
+        "Base directory of poof's data and of the default bin directory (Linux)",
+    ),
+    (
+        "XDG_CACHE_HOME",
+        "~/.cache",
+        "Base directory of poof's cache (Linux)",
+    ),
+    (
+        "XDG_CONFIG_HOME",
+        "~/.config",
+        "Base directory of poof's configuration file (Linux)",
+    ),
+    ("RUST_LOG", "", "Per-module log filter, e.g. poof=debug"),
+];
+
+/// Name of the metadata file at the root of Arch Linux packages, whose executables
+/// are in `usr/bin`.
+pub const ARCH_PACKAGE_INFO_FILE: &str = ".PKGINFO";
+
+/// Name of the user configuration file, inside the application's config directory.
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Name of the sigstore trusted root used by `install --verify-cosign`, inside the
+/// application's config directory.
+#[cfg(feature = "cosign")]
+pub const COSIGN_TRUSTED_ROOT_FILE: &str = "trusted_root.json";
+
+/// Name of the log of past install, update, uninstall and use operations, one JSON
+/// object per line, in the parent of the data directory.
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// Name of the metadata file, inside a version directory, recording custom binary names.
+pub const RENAMES_FILE: &str = ".poof-renames";
+
+/// Name of the metadata file, inside a version directory, recording the release source
+/// of binaries not installed from GitHub.
+pub const SOURCE_FILE: &str = ".poof-source";
+
+/// Name of the metadata file, inside a version directory, recording the bin directory
+/// its binaries have been linked into, when not the default one.
+pub const BIN_DIR_FILE: &str = ".poof-bin-dir";
+
+/// Name of the metadata file, inside a version directory, recording the SHA-256 checksum
+/// of the release assets the binaries have been installed from, in `sha256sum` format.
+pub const ASSET_CHECKSUMS_FILE: &str = ".poof-assets.sha256";
+
+/// Name of the metadata file, inside a version directory, recording when it has been
+/// installed, in seconds since the Unix epoch.
+pub const INSTALLED_AT_FILE: &str = ".poof-installed-at";
+
+/// Name of the directory, next to the version directories of a repository, holding its pin.
+/// The pin is an empty file named after the pinned version.
+pub const PINS_DIR: &str = ".pinned";
+
+/// Extension of the files, next to installed binaries, recording their SHA-256 checksum.
+pub const CHECKSUM_EXTENSION: &str = "sha256";
 
 /// All archive and compression extensions recognised by the asset selector.
 ///
 /// Multi-part extensions (e.g. `.tar.gz`) **must** appear before their single-part
 /// counterparts (e.g. `.gz`) so that the longest match wins during extension stripping.
-pub const SUPPORTED_EXTENSIONS: [&str; 15] = [
-    ".tar.gz", ".tgz", ".tar.xz", ".txz", ".tar.bz2", ".tbz", ".tbz2", ".zip", ".tar", ".gz",
-    ".xz", ".bz2", ".tar.zst", ".tzst", ".zst",
+pub const SUPPORTED_EXTENSIONS: [&str; 20] = [
+    ".tar.gz",
+    ".tgz",
+    ".tar.xz",
+    ".txz",
+    ".tar.bz2",
+    ".tbz",
+    ".tbz2",
+    ".zip",
+    ".tar",
+    ".gz",
+    ".xz",
+    ".bz2",
+    ".tar.zst",
+    ".tzst",
+    ".zst",
+    ".tar.lz4",
+    ".lz4",
+    ".tar.lzma",
+    ".tar.lz",
+    ".lzma",
+];
+
+/// CPU architecture identifiers accepted by the `--arch` override.
+///
+/// These mirror the architectures known to the asset selector, plus `arm`
+/// which the selector treats as an alias for `armv7`.
+pub const SUPPORTED_ARCHS: [&str; 10] = [
+    "x86",
+    "x86_64",
+    "arm",
+    "armv7",
+    "aarch64",
+    "powerpc",
+    "powerpc64",
+    "riscv64",
+    "s390x",
+    "loongarch64",
 ];
 
 /// Sentinel string returned when a value cannot be determined at runtime.
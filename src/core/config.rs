@@ -0,0 +1,89 @@
+//! User configuration loaded from the `config.toml` file.
+//!
+//! Every setting is optional and acts as a default: CLI flags and environment
+//! variables always take precedence over the values found in the file.
+
+use anyhow::{Context, Result};
+use log::debug;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::files::datadirs;
+
+/// Configuration loaded at startup, see [`init`] and [`get`].
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Defaults read from the user's config file.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// Token used for GitHub API requests when `GITHUB_TOKEN` is not set.
+    pub github_token: Option<String>,
+    /// Prefer musl (`true`) or glibc (`false`) assets unless `--musl`/`--gnu` is given.
+    pub prefer_musl: Option<bool>,
+    /// Consider pre-releases when looking for the latest release of a repository.
+    pub include_prereleases: bool,
+    /// Verify downloaded assets against their published checksums.
+    pub verify_checksums: bool,
+    /// Directory holding the symlinks to installed binaries, unless `POOF_BIN_DIR` is set.
+    pub bin_dir: Option<PathBuf>,
+    /// Number of versions of a repository to keep after install and update,
+    /// unless `--keep-versions` is given.
+    pub keep_versions: Option<usize>,
+    /// Sigstore trusted root used by `install --verify-cosign`, in place of the
+    /// `trusted_root.json` file next to the config file.
+    #[cfg(feature = "cosign")]
+    pub cosign_trusted_root: Option<PathBuf>,
+}
+
+impl Config {
+    /// Load the configuration from the default config file.
+    ///
+    /// A missing file is not an error and yields the default configuration.
+    pub fn load() -> Result<Self> {
+        match datadirs::get_config_file() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load the configuration from the file at `path`, tolerating a missing file.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            debug!("No config file found at {}", path.display());
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read config file {}", path.display()))?;
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Cannot parse config file {}", path.display()))?;
+        debug!("Loaded config file {}", path.display());
+        Ok(config)
+    }
+
+    /// Returns the libc preference, giving precedence to the `--musl`/`--gnu` CLI flags.
+    pub fn musl_preference(&self, cli: Option<bool>) -> Option<bool> {
+        cli.or(self.prefer_musl)
+    }
+
+    /// Returns the number of versions to keep, giving precedence to `--keep-versions`.
+    /// A value of 0 in the config file disables pruning.
+    pub fn keep_versions(&self, cli: Option<usize>) -> Option<usize> {
+        cli.or(self.keep_versions.filter(|n| *n > 0))
+    }
+}
+
+/// Make `config` available to the whole program via [`get`].
+/// Only the first call has an effect.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// Returns the configuration set by [`init`], or the default one if none was set.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,100 @@
+//! Unit tests for the config file loader
+
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_all_keys() {
+    let config: Config = toml::from_str(
+        r#"
+        github_token = "ghp_secret"
+        prefer_musl = true
+        include_prereleases = true
+        verify_checksums = true
+        bin_dir = "/opt/poof/bin"
+        keep_versions = 3
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            github_token: Some("ghp_secret".to_string()),
+            prefer_musl: Some(true),
+            include_prereleases: true,
+            verify_checksums: true,
+            bin_dir: Some(PathBuf::from("/opt/poof/bin")),
+            keep_versions: Some(3),
+            #[cfg(feature = "cosign")]
+            cosign_trusted_root: None,
+        }
+    );
+}
+
+#[cfg(feature = "cosign")]
+#[test]
+fn test_parse_cosign_trusted_root() {
+    let config: Config =
+        toml::from_str(r#"cosign_trusted_root = "/etc/sigstore/root.json""#).unwrap();
+    assert_eq!(
+        config.cosign_trusted_root,
+        Some(PathBuf::from("/etc/sigstore/root.json"))
+    );
+}
+
+#[test]
+fn test_parse_partial_file_keeps_defaults() {
+    let config: Config = toml::from_str("prefer_musl = false").unwrap();
+    assert_eq!(config.prefer_musl, Some(false));
+    assert_eq!(config.github_token, None);
+    assert!(!config.include_prereleases);
+    assert!(!config.verify_checksums);
+}
+
+#[test]
+fn test_load_missing_file_returns_defaults() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config::load_from(&temp_dir.path().join("config.toml")).unwrap();
+    assert_eq!(config, Config::default());
+}
+
+#[test]
+fn test_load_from_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    std::fs::write(&path, "include_prereleases = true\n").unwrap();
+    let config = Config::load_from(&path).unwrap();
+    assert!(config.include_prereleases);
+}
+
+#[test]
+fn test_load_invalid_file_is_an_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    std::fs::write(&path, "prefer_musl = \"maybe\"\n").unwrap();
+    let err = Config::load_from(&path).unwrap_err();
+    assert!(format!("{:?}", err).contains("Cannot parse config file"));
+}
+
+#[test]
+fn test_cli_flags_take_precedence_over_config() {
+    let config = Config {
+        prefer_musl: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(config.musl_preference(Some(false)), Some(false));
+    assert_eq!(config.musl_preference(None), Some(true));
+    assert_eq!(Config::default().musl_preference(None), None);
+
+    let config = Config {
+        keep_versions: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(config.keep_versions(Some(1)), Some(1));
+    assert_eq!(config.keep_versions(None), Some(3));
+    let disabled = Config {
+        keep_versions: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(disabled.keep_versions(None), None);
+}
@@ -0,0 +1,126 @@
+//! HTTP client setup shared by every network operation.
+//!
+//! Requests give up after a configurable timeout, so that a stalled connection
+//! cannot leave poof hanging. The timeout is read from [`TIMEOUT_ENV`], which the
+//! global `--timeout` flag sets, and defaults to [`DEFAULT_TIMEOUT_SECONDS`].
+
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::constants::{DEFAULT_TIMEOUT_SECONDS, TIMEOUT_ENV};
+
+/// Returns the configured timeout of network operations, in seconds.
+/// Invalid and zero values fall back to the default.
+pub fn timeout_seconds() -> u64 {
+    std::env::var(TIMEOUT_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .unwrap_or(DEFAULT_TIMEOUT_SECONDS)
+}
+
+/// Build an HTTP client whose connect and read operations time out after
+/// [`timeout_seconds`].
+pub fn client() -> Result<Client> {
+    let timeout = Duration::from_secs(timeout_seconds());
+    Client::builder()
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .build()
+        .context("Cannot create HTTP client")
+}
+
+/// Error of a network operation that did not complete within [`timeout_seconds`].
+///
+/// It is attached as context to the underlying error, so that it can be found
+/// with [`anyhow::Error::downcast_ref`] under any further context.
+#[derive(Debug)]
+pub struct TimeoutError {
+    /// Timeout that expired, in seconds.
+    seconds: u64,
+}
+
+impl TimeoutError {
+    /// Returns the error for the currently configured timeout.
+    pub fn new() -> Self {
+        Self {
+            seconds: timeout_seconds(),
+        }
+    }
+}
+
+impl Display for TimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Connection timed out after {} seconds. Try increasing --timeout.",
+            self.seconds
+        )
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// Returns `true` when `e`, raised while reading a response body, is a timeout.
+pub fn is_timeout_io(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::TimedOut
+        || e.get_ref()
+            .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+            .is_some_and(|inner| inner.is_timeout())
+}
+
+/// Error of an API request answered with an unsuccessful HTTP status.
+#[derive(Debug)]
+pub struct StatusError {
+    pub url: String,
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl Display for StatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Request to {} failed with status: {}. Response: {}",
+            self.url, self.status, self.body
+        )
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// Returns `true` when the request that failed with `e` may succeed later without
+/// any change on the user's side: the server could not be reached or did not answer
+/// in time, had an internal error, or rate limited the request.
+///
+/// Other errors, e.g. a missing release or a rejected token, are definitive.
+pub fn is_transient(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        cause.is::<TimeoutError>()
+            || cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|e| e.is_connect() || e.is_timeout())
+            || cause.downcast_ref::<StatusError>().is_some_and(|e| {
+                e.status.is_server_error()
+                    || e.status == StatusCode::TOO_MANY_REQUESTS
+                    // GitHub answers 403 when the rate limit is exceeded
+                    || (e.status == StatusCode::FORBIDDEN
+                        && e.body.to_lowercase().contains("rate limit"))
+            })
+    })
+}
+
+/// Wrap the error of a request to `url`, marking timeouts with [`TimeoutError`].
+pub fn request_error(e: reqwest::Error, url: &str) -> anyhow::Error {
+    if e.is_timeout() {
+        anyhow!(e).context(TimeoutError::new())
+    } else {
+        anyhow!(e).context(format!("Cannot send request to {}", url))
+    }
+}
+
+#[cfg(test)]
+mod tests;
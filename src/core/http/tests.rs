@@ -0,0 +1,97 @@
+//! Unit tests for the HTTP client setup
+
+use super::*;
+use mockito::Server;
+use std::thread::sleep;
+use tempfile::TempDir;
+
+use crate::commands::download::download_asset;
+
+#[test]
+fn test_timeout_defaults() {
+    temp_env::with_var_unset(TIMEOUT_ENV, || {
+        assert_eq!(timeout_seconds(), DEFAULT_TIMEOUT_SECONDS);
+    });
+    for invalid in ["0", "-5", "soon", ""] {
+        temp_env::with_var(TIMEOUT_ENV, Some(invalid), || {
+            assert_eq!(timeout_seconds(), DEFAULT_TIMEOUT_SECONDS, "{}", invalid);
+        });
+    }
+}
+
+#[test]
+fn test_timeout_from_env() {
+    temp_env::with_var(TIMEOUT_ENV, Some(" 5 "), || {
+        assert_eq!(timeout_seconds(), 5);
+    });
+}
+
+#[test]
+fn test_timeout_message() {
+    let message = temp_env::with_var(TIMEOUT_ENV, Some("7"), || TimeoutError::new().to_string());
+    assert_eq!(
+        message,
+        "Connection timed out after 7 seconds. Try increasing --timeout."
+    );
+}
+
+#[test]
+fn test_slow_response_times_out() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/slow")
+        .with_body_from_request(|_| {
+            sleep(Duration::from_secs(3));
+            b"late".to_vec()
+        })
+        .create();
+    let url = format!("{}/slow", server.url());
+
+    let result = temp_env::with_var(TIMEOUT_ENV, Some("1"), || {
+        client()
+            .unwrap()
+            .get(&url)
+            .send()
+            .map_err(|e| request_error(e, &url))
+    });
+    let err = result.unwrap_err();
+    assert!(
+        err.downcast_ref::<TimeoutError>().is_some(),
+        "unexpected error: {:#}",
+        err
+    );
+    assert!(err.to_string().contains("timed out after 1 seconds"));
+    mock.assert();
+}
+
+#[test]
+fn test_stalled_download_times_out() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut server = Server::new();
+    let _mock = server
+        .mock("GET", "/tool.tar.gz")
+        .with_chunked_body(|w| {
+            w.write_all(b"partial")?;
+            w.flush()?;
+            sleep(Duration::from_secs(3));
+            w.write_all(b"rest")
+        })
+        .create();
+    let url = format!("{}/tool.tar.gz", server.url());
+
+    let result = temp_env::with_var(TIMEOUT_ENV, Some("1"), || {
+        download_asset(
+            &"tool.tar.gz".to_string(),
+            &url,
+            &temp_dir.path().to_path_buf(),
+        )
+    });
+    let err = result.unwrap_err();
+    // the timeout is found under the context added by callers
+    let err = err.context("Cannot download asset for owner/tool version 1.0.0");
+    assert!(
+        err.downcast_ref::<TimeoutError>().is_some(),
+        "unexpected error: {:#}",
+        err
+    );
+}
@@ -1,4 +1,10 @@
+/// User configuration file loading.
+pub mod config;
+/// HTTP client setup, with the network timeout.
+pub mod http;
 pub mod musl;
 pub mod platform_info;
 /// Asset-selection logic: scores release assets by platform compatibility.
 pub mod selector;
+/// Bounded thread pool for commands working on several repositories at once.
+pub mod worker_pool;
@@ -6,12 +6,14 @@
 
 use std::sync::OnceLock;
 
+use crate::constants::PREFER_MUSL_ENV;
+
 /// One-time initialisation cell that caches the musl-preference result.
 static CELL: OnceLock<bool> = OnceLock::new();
 
 /// Computes the default musl preference: respects `POOF_PREFER_MUSL` env var, then auto-detects.
 fn get_default() -> bool {
-    let user_value = std::env::var("POOF_PREFER_MUSL")
+    let user_value = std::env::var(PREFER_MUSL_ENV)
         .ok()
         .and_then(|v| match v.as_str() {
             "1" | "true" | "TRUE" | "True" => Some(true),
@@ -18,14 +18,19 @@ pub fn env_path_separator() -> &'static str {
     ENV_PATH_SEPARATOR
 }
 
-/// Returns a static string containing the version information.
-/// It uses Box::leak to convert a String into a &'static str.
-/// This is a workaround to avoid using a global static variable.
-pub fn long_version() -> &'static str {
+/// Returns how the C library is linked into this build of poof.
+pub fn linking_type() -> &'static str {
     #[cfg(static_linking)]
     let linking_type = "statically linked";
     #[cfg(dynamic_linking)]
     let linking_type = "dynamically linked";
+    linking_type
+}
+
+/// Returns a static string containing the version information.
+/// It uses Box::leak to convert a String into a &'static str.
+/// This is a workaround to avoid using a global static variable.
+pub fn long_version() -> &'static str {
     Box::leak(
         format!(
             "Version   : {}\nCommit    : {}\nBuild Date: {}\nBuilt with: {} ({}){}\n{}",
@@ -33,7 +38,7 @@ pub fn long_version() -> &'static str {
             COMMIT,
             BUILD_DATE,
             COMPILE_C_LIB,
-            linking_type,
+            linking_type(),
             get_glibc_version_string(),
             release_url()
         )
@@ -88,7 +93,8 @@ pub fn get_env_var(var: &str) -> String {
 /// Return a human-readable string describing the OS name and version.
 ///
 /// On Linux, attempts `lsb_release -ds` or `/etc/os-release`. On macOS uses
-/// `sw_vers`. On Windows uses `cmd /c ver`. Returns [`UNKNOWN`] on failure.
+/// `sw_vers`. On FreeBSD uses `freebsd-version`. On Windows uses `cmd /c ver`.
+/// Returns [`UNKNOWN`] on failure.
 pub fn get_os_version() -> String {
     if cfg!(target_os = "linux") {
         // Try to detect Linux distribution and version
@@ -115,6 +121,19 @@ pub fn get_os_version() -> String {
                 }
             })
             .unwrap_or(UNKNOWN.to_string())
+    } else if cfg!(target_os = "freebsd") {
+        // Get FreeBSD userland version
+        std::process::Command::new("freebsd-version")
+            .output()
+            .map(|o| {
+                let result = String::from_utf8_lossy(&o.stdout).trim().to_string();
+                if result.is_empty() {
+                    UNKNOWN.to_string()
+                } else {
+                    format!("FreeBSD {}", result)
+                }
+            })
+            .unwrap_or(UNKNOWN.to_string())
     } else if cfg!(target_os = "windows") {
         // Get Windows version
         std::process::Command::new("cmd")
@@ -127,6 +146,25 @@ pub fn get_os_version() -> String {
     }
 }
 
+/// Return whether poof runs under Rosetta 2 translation on an Apple Silicon Mac.
+///
+/// Returns `None` on other operating systems, and on Intel Macs where the
+/// `sysctl.proc_translated` key does not exist.
+pub fn is_rosetta_translated() -> Option<bool> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let output = std::process::Command::new("sysctl")
+        .args(["-n", "sysctl.proc_translated"])
+        .output()
+        .ok()?;
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
 /// Return a string describing the platform byte order (`"Little Endian"`, `"Big Endian"`, or `"Unknown Endian"`).
 #[cfg(target_endian = "little")]
 pub fn get_platform_endianness() -> String {
@@ -236,6 +274,12 @@ mod tests {
         assert!(!os_version.is_empty(), "OS version should not be empty");
     }
 
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn test_is_rosetta_translated_only_on_macos() {
+        assert_eq!(is_rosetta_translated(), None);
+    }
+
     #[test]
     fn test_get_platform_endianness() {
         let endianness = get_platform_endianness();
@@ -3,18 +3,19 @@ use crate::{
     models::asset_triple::AssetTriple,
 };
 use lazy_static::lazy_static;
-use std::{cmp::max, collections::HashMap};
+use std::collections::HashMap;
 
 use crate::constants::SUPPORTED_EXTENSIONS;
 
 lazy_static! {
     static ref OPERATING_SYSTEM: HashMap<&'static str, Vec<&'static str>> = {
         let mut m = HashMap::new();
-        //m.insert("windows", vec!["windows", "win"]);
+        // Rust triples first, the matched alias is used to spot bare executables
+        m.insert("windows", vec!["pc-windows-msvc", "pc-windows-gnu", "windows", "win"]);
         m.insert("macos", vec!["macos", "darwin", "osx", "mac"]);
         m.insert("linux", vec!["linux"]);
+        m.insert("freebsd", vec!["freebsd", "bsd"]);
         //m.insert("openbsd", vec!["openbsd"]);
-        //m.insert("freebsd", vec!["freebsd"]);
         //m.insert("netbsd", vec!["netbsd"]);
         m
     };
@@ -34,18 +35,35 @@ lazy_static! {
         m.insert("aarch64", vec!["aarch64", "arm64"]);
         // powerpc64le support
         m.insert("powerpc", vec!["powerpcle", "ppcle"]);
-        m.insert("powerpc64", vec!["powerpc64le", "ppc64le"]);
+        // ppc64el is the Debian spelling
+        m.insert("powerpc64", vec!["powerpc64le", "ppc64le", "ppc64el"]);
         // note: de-facto are all riscv64 are riscv64gc if they run can Linux,
-        // as linux needs the gc extensions.
-        m.insert("riscv64", vec!["riscv64gc", "riscv64"]);
+        // as linux needs the gc extensions. rv64 is the shorthand used by some projects.
+        m.insert("riscv64", vec!["riscv64gc", "riscv64", "rv64gc", "rv64"]);
         // s390x 64bit support
         m.insert("s390x", vec!["s390x"]);
-        // loongarch64 support
-        m.insert("loongarch64", vec!["loongarch64"]);
+        // loongarch64 support, loong64 is the GOARCH name used by Go projects
+        m.insert("loongarch64", vec!["loongarch64", "loong64"]);
         m
     };
 }
 
+/// Tags of macOS universal binaries, which hold both the x86_64 and the arm64 slices.
+const UNIVERSAL_TAGS: &[&str] = &["universal", "universal2", "all"];
+
+/// Returns `true` if `item` names any of the known architectures, not only the current one.
+fn has_any_arch_tag(item: &str) -> bool {
+    CPU_ARCH
+        .values()
+        .flatten()
+        .any(|alias| contains_alias_token(item, alias))
+}
+
+/// Returns `true` if `item` is an Arch Linux package, e.g. `tool-1.0.0-1-x86_64.pkg.tar.zst`.
+fn is_arch_linux_package(item: &str) -> bool {
+    item.ends_with(".pkg.tar.zst") || contains_alias_token(item, "archlinux")
+}
+
 /// Returns `true` if `item` has what looks like a real file extension (non-empty, ≤4 chars, not all digits).
 fn has_extension(item: &str) -> bool {
     // going case insensitive to avoid false positives for AppImage assets
@@ -84,51 +102,58 @@ fn has_extension(item: &str) -> bool {
     true
 }
 
-/// Returns the most compatible assets from the given list of assets
-pub fn get_env_compatible_assets<T, F>(assets: &[T], extractor_fn: F) -> Option<Vec<T>>
-where
-    T: Clone,
-    F: Fn(&T) -> &str,
-{
-    let t = AssetTriple::default();
-    get_triple_compatible_assets(assets, &t, &extractor_fn)
-}
-
-/// Returns the most compatible asset from the given list of assets
+/// Returns the compatible assets from the given list of assets, most compatible first
 ///
 /// # Arguments
 ///
-/// * `assets` - The list of assets to select from.
+/// * `assets` - The list of assets to score.
 /// * `t` - The asset triple to use for scoring.
 /// * `extractor_fn` - The function to extract the asset name from the asset.
 ///
 /// # Returns
 ///
-/// A vector of the most compatible assets.
+/// A vector of `(asset, confidence)` pairs sorted by descending confidence.
+/// Assets with the same confidence keep their order in `assets`.
 /// If no compatible assets are found, returns `None`.
 pub fn get_triple_compatible_assets<T, F>(
     assets: &[T],
     t: &AssetTriple,
     extractor_fn: F,
-) -> Option<Vec<T>>
+) -> Option<Vec<(T, u32)>>
 where
     T: Clone,
     F: Fn(&T) -> &str,
 {
-    let mut map: HashMap<i32, Vec<T>> = HashMap::new();
-    let mut max_score: i32 = 0;
-    for asset in assets {
-        let score = get_triple_score(extractor_fn(asset), t);
-        if score > 0 {
-            max_score = max(max_score, score);
-            map.entry(score).or_default().push(asset.clone());
-        }
-    }
-    if max_score > 0 {
-        map.get(&max_score).cloned()
-    } else {
-        None
+    let mut scored: Vec<(T, u32)> = assets
+        .iter()
+        .filter_map(|asset| {
+            let score = get_triple_score(extractor_fn(asset), t);
+            u32::try_from(score)
+                .ok()
+                .filter(|score| *score > 0)
+                .map(|score| (asset.clone(), score))
+        })
+        .collect();
+    if scored.is_empty() {
+        return None;
     }
+    // stable sort, so that ties keep the release order
+    scored.sort_by(|(_, a), (_, b)| b.cmp(a));
+    Some(scored)
+}
+
+/// Keeps the assets with the highest confidence from a list sorted by
+/// [`get_triple_compatible_assets`].
+///
+/// More than one asset is returned when several share the highest confidence,
+/// e.g. releases shipping one archive per tool.
+pub fn best_scored<T>(scored: Vec<(T, u32)>) -> Vec<T> {
+    let best = scored.first().map(|(_, score)| *score).unwrap_or_default();
+    scored
+        .into_iter()
+        .take_while(|(_, score)| *score == best)
+        .map(|(asset, _)| asset)
+        .collect()
 }
 
 /// Get score for the input string
@@ -151,8 +176,9 @@ fn get_triple_score(input: &str, t: &AssetTriple) -> i32 {
     // AssetTriple defaults to the operating system poof is running on.
     let current_os = t.get_os().as_str();
 
-    // tiny helper
+    // tiny helpers
     let is_linux: bool = current_os == "linux";
+    let is_windows: bool = current_os == "windows";
 
     // MUSL
     if is_linux && t.is_musl() && item.contains("musl") {
@@ -163,6 +189,12 @@ fn get_triple_score(input: &str, t: &AssetTriple) -> i32 {
         score -= 2;
     }
 
+    // MSVC
+    if is_windows && item.contains("msvc") {
+        // bonus point for MSVC builds on Windows, they don't need the MinGW runtime.
+        score += 1;
+    }
+
     // OPERATING_SYSTEM
     let Some(os_aliases) = OPERATING_SYSTEM.get(current_os) else {
         // If current operating system is not in the OPERATING_SYSTEM hashmap, return -1
@@ -178,7 +210,12 @@ fn get_triple_score(input: &str, t: &AssetTriple) -> i32 {
         .iter()
         .find(|alias| contains_alias_token(&item, alias))
         .map(|alias| alias.to_string());
-    let found_os: bool = matching_os.is_some();
+    // Arch Linux packages seldom name the operating system, but only run on Linux
+    let is_arch_package = is_arch_linux_package(&item);
+    if is_arch_package && !is_linux {
+        return -1;
+    }
+    let found_os: bool = matching_os.is_some() || is_arch_package;
     if found_os {
         score += 5;
     }
@@ -222,6 +259,31 @@ fn get_triple_score(input: &str, t: &AssetTriple) -> i32 {
         });
     let matching_arch: Option<String> = matching_arch.map(|(_, s)| s);
     let found_arch: bool = matching_arch.is_some();
+    // x86_64 is the only architecture Arch Linux supports, packages for the
+    // others come from ports naming their architecture. 'any' packages hold scripts
+    if is_arch_package
+        && !found_arch
+        && current_arch != "x86_64"
+        && !contains_alias_token(&item, "any")
+    {
+        return -1;
+    }
+
+    // UNIVERSAL BINARIES
+    // On macOS, assets tagged as universal, or tagged for macOS with no architecture
+    // at all, run on both Intel and Apple Silicon. They get the base bonus of an
+    // architecture match, without the alias bonus, so an asset built for the
+    // current architecture still ranks higher.
+    let is_universal = current_os == "macos"
+        && !found_arch
+        && (found_os
+            || UNIVERSAL_TAGS
+                .iter()
+                .any(|tag| contains_alias_token(&item, tag)))
+        && !has_any_arch_tag(&item);
+    if is_universal {
+        score += 5;
+    }
 
     // ADDITIONAL CHECKS
     // fix to avoid mismatch between the asset and the target architecture
@@ -250,10 +312,17 @@ fn get_triple_score(input: &str, t: &AssetTriple) -> i32 {
         // if the executable name ends with the matching architecture, we give it a lower bonus point.
         // this is likely a binary that is released as an executable without an archive.
         score += 2;
-    } else if found_os && item.ends_with(matching_os.as_ref().unwrap()) {
+    } else if matching_os
+        .as_ref()
+        .is_some_and(|matching_os| item.ends_with(matching_os))
+    {
         // if the executable name ends with the matching operating system, we give it a lower bonus point.
         // this is likely a binary that is released as an executable without an archive.
         score += 2;
+    } else if is_windows && item.ends_with(".exe") {
+        // on Windows executables are released with their extension,
+        // they are as good as a supported archive.
+        score += 5;
     } else if has_extension(&item)
         && SUPPORTED_EXTENSIONS
             .iter()
@@ -1,2 +1,4 @@
 pub mod test_assets;
 pub mod test_incompatible_platforms;
+pub mod test_overrides;
+pub mod test_scores;
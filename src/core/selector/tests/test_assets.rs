@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
 
-    use crate::core::selector::get_triple_compatible_assets;
+    use crate::core::selector::{best_scored, get_triple_compatible_assets};
     use crate::models::asset_triple::AssetTriple;
 
     #[test]
@@ -9,7 +9,8 @@ mod tests {
         let assets: Vec<String> =
             ron::from_str(include_str!("assets/jesseduffield@lazygit.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -20,7 +21,8 @@ mod tests {
     fn test_linux_x86_64_musl() {
         let assets: Vec<String> = ron::from_str(include_str!("assets/lsd-rs@lsd.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), true);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -31,7 +33,8 @@ mod tests {
     fn test_linux_aarch64_glibc() {
         let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -42,7 +45,8 @@ mod tests {
     fn test_linux_aarch64_musl() {
         let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "aarch64".to_string(), true);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -54,7 +58,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/sharkdp@fd.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -66,7 +71,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/sharkdp@fd.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86".to_string(), true);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -79,7 +85,8 @@ mod tests {
             ron::from_str(include_str!("assets/ClementTsang@bottom.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -92,7 +99,8 @@ mod tests {
             ron::from_str(include_str!("assets/ClementTsang@bottom.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), true);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -104,7 +112,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/sxyazi@yazi.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "riscv64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -116,7 +125,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/gokcehan@lf.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "s390x".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -128,7 +138,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/gokcehan@lf.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "powerpc64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -142,7 +153,8 @@ mod tests {
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple =
             AssetTriple::new("linux".to_string(), "loongarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -155,7 +167,8 @@ mod tests {
             ron::from_str(include_str!("assets/helix-editor@helix.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("macos".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -168,13 +181,178 @@ mod tests {
             ron::from_str(include_str!("assets/helix-editor@helix.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("macos".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
         assert!(binaries[0].contains("helix-25.07.1-x86_64-macos.tar.xz"));
     }
 
+    #[test]
+    fn test_freebsd_x86_64() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/syncthing@syncthing.ron")).unwrap();
+        let platform_triple = AssetTriple::new("freebsd".to_string(), "x86_64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("syncthing-freebsd-amd64-v1.27.12.tar.gz"));
+    }
+
+    #[test]
+    fn test_freebsd_aarch64() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/syncthing@syncthing.ron")).unwrap();
+        let platform_triple = AssetTriple::new("freebsd".to_string(), "aarch64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("syncthing-freebsd-arm64-v1.27.12.tar.gz"));
+    }
+
+    #[test]
+    fn test_freebsd_ignores_other_bsds() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/muesli@duf.ron")).unwrap();
+        let platform_triple = AssetTriple::new("freebsd".to_string(), "x86_64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("duf_0.9.1_freebsd_x86_64.tar.gz"));
+    }
+
+    #[test]
+    fn test_freebsd_x86_64_rust_triple() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/pirafrank@rust_exif_renamer.ron")).unwrap();
+        let platform_triple = AssetTriple::new("freebsd".to_string(), "x86_64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("exif_renamer-0.2.2-x86_64-unknown-freebsd.tar.gz"));
+    }
+
+    #[test]
+    fn test_freebsd_aarch64_go_style() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/charmbracelet@glow.ron")).unwrap();
+        let platform_triple = AssetTriple::new("freebsd".to_string(), "aarch64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("glow_2.1.1_Freebsd_arm64.tar.gz"));
+    }
+
+    #[test]
+    fn test_freebsd_ignores_musl() {
+        // musl only matters on Linux, asking for it on FreeBSD changes nothing
+        for (file, expected) in [
+            (
+                include_str!("assets/syncthing@syncthing.ron"),
+                "syncthing-freebsd-amd64-v1.27.12.tar.gz",
+            ),
+            (
+                include_str!("assets/pirafrank@rust_exif_renamer.ron"),
+                "exif_renamer-0.2.2-x86_64-unknown-freebsd.tar.gz",
+            ),
+        ] {
+            let assets: Vec<String> = ron::from_str(file).unwrap();
+            let platform_triple =
+                AssetTriple::new("freebsd".to_string(), "x86_64".to_string(), true);
+            let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset)
+                .map(best_scored);
+            assert!(binaries.is_some());
+            let binaries = binaries.unwrap();
+            assert!(!binaries.is_empty() && binaries.len() == 1);
+            assert!(binaries[0].contains(expected));
+        }
+    }
+
+    #[test]
+    fn test_windows_x86_64_prefers_msvc() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/sharkdp@fd.ron")).unwrap();
+        let platform_triple = AssetTriple::new("windows".to_string(), "x86_64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("fd-v10.3.0-x86_64-pc-windows-msvc.zip"));
+    }
+
+    #[test]
+    fn test_windows_aarch64_msvc() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/sharkdp@fd.ron")).unwrap();
+        let platform_triple = AssetTriple::new("windows".to_string(), "aarch64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("fd-v10.3.0-aarch64-pc-windows-msvc.zip"));
+    }
+
+    #[test]
+    fn test_windows_go_style() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/jesseduffield@lazygit.ron")).unwrap();
+        for (arch, expected) in [
+            ("x86_64", "lazygit_0.58.1_windows_x86_64.zip"),
+            ("aarch64", "lazygit_0.58.1_windows_arm64.zip"),
+        ] {
+            let platform_triple = AssetTriple::new("windows".to_string(), arch.to_string(), false);
+            let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset)
+                .map(best_scored);
+            assert!(binaries.is_some());
+            let binaries = binaries.unwrap();
+            assert!(!binaries.is_empty() && binaries.len() == 1);
+            assert!(binaries[0].contains(expected));
+        }
+    }
+
+    #[test]
+    fn test_windows_bare_exe() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/shshemi@tabiew.ron")).unwrap();
+        let platform_triple = AssetTriple::new("windows".to_string(), "x86_64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("tw-x86_64-pc-windows-msvc.exe"));
+    }
+
+    #[test]
+    fn test_linux_arch_alias_spellings() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/fantauser@fantarepo_arch_aliases.ron")).unwrap();
+        for (arch, expected) in [
+            ("riscv64", "fantarepo_1.0.0_linux_rv64.tar.gz"),
+            ("powerpc64", "fantarepo_1.0.0_linux_ppc64el.tar.gz"),
+            ("loongarch64", "fantarepo_1.0.0_linux_loong64.tar.gz"),
+            ("s390x", "fantarepo_1.0.0_linux_s390x.tar.gz"),
+            ("x86_64", "fantarepo_1.0.0_linux_amd64.tar.gz"),
+        ] {
+            let platform_triple = AssetTriple::new("linux".to_string(), arch.to_string(), false);
+            let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset)
+                .map(best_scored);
+            assert!(binaries.is_some(), "{}", arch);
+            let binaries = binaries.unwrap();
+            assert!(!binaries.is_empty() && binaries.len() == 1, "{}", arch);
+            assert!(binaries[0].contains(expected), "{}: {:?}", arch, binaries);
+        }
+    }
+
     //
     // more tests for uncommon and edge cases follows.
     //
@@ -183,7 +361,8 @@ mod tests {
     fn test_linux_x86_64_compatible_binary_without_extension() {
         let assets: Vec<String> = ron::from_str(include_str!("assets/shshemi@tabiew.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -195,7 +374,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -207,7 +387,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), true);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -220,7 +401,8 @@ mod tests {
             ron::from_str(include_str!("assets/jesseduffield@lazygit.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -232,7 +414,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/gokcehan@lf.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -244,7 +427,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/direnv@direnv.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "s390x".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -256,7 +440,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/direnv@direnv.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "powerpc64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -269,7 +454,8 @@ mod tests {
             ron::from_str(include_str!("assets/jesseduffield@lazygit.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -282,7 +468,8 @@ mod tests {
             ron::from_str(include_str!("assets/charmbracelet@freeze.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -295,7 +482,8 @@ mod tests {
             ron::from_str(include_str!("assets/charmbracelet@freeze.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -308,7 +496,8 @@ mod tests {
             ron::from_str(include_str!("assets/charmbracelet@freeze.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -320,7 +509,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/sharkdp@fd.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -332,7 +522,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/jwt-rs@jwt-ui.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -344,7 +535,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/jwt-rs@jwt-ui.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -356,7 +548,8 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/jwt-rs@jwt-ui.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), true);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -369,7 +562,8 @@ mod tests {
             ron::from_str(include_str!("assets/jedisct1@minisign.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -382,7 +576,8 @@ mod tests {
             ron::from_str(include_str!("assets/jedisct1@minisign.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), true);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -395,7 +590,8 @@ mod tests {
             ron::from_str(include_str!("assets/jedisct1@minisign.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("macos".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -408,7 +604,8 @@ mod tests {
             ron::from_str(include_str!("assets/jedisct1@minisign.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("macos".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -421,7 +618,8 @@ mod tests {
             ron::from_str(include_str!("assets/vitor-mariano@regex-tui.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -434,7 +632,8 @@ mod tests {
             ron::from_str(include_str!("assets/vitor-mariano@regex-tui.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("macos".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -447,7 +646,8 @@ mod tests {
             ron::from_str(include_str!("assets/fantauser@fantarepo_ends_in_os.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -460,7 +660,8 @@ mod tests {
             ron::from_str(include_str!("assets/fantauser@fantarepo_ends_in_os.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("macos".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
@@ -471,7 +672,8 @@ mod tests {
     fn test_linux_x86_64_multiple_assets() {
         let assets: Vec<String> = ron::from_str(include_str!("assets/ahmetb@kubectx.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 2);
@@ -487,7 +689,8 @@ mod tests {
     fn test_linux_armv7_multiple_assets() {
         let assets: Vec<String> = ron::from_str(include_str!("assets/ahmetb@kubectx.ron")).unwrap();
         let platform_triple = AssetTriple::new("linux".to_string(), "arm".to_string(), false);
-        let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 2);
@@ -504,10 +707,70 @@ mod tests {
         let assets: Vec<String> = ron::from_str(include_str!("assets/muesli@duf.ron")).unwrap();
         let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
         let platform_triple = AssetTriple::new("linux".to_string(), "aarch64".to_string(), false);
-        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
         assert!(binaries.is_some());
         let binaries = binaries.unwrap();
         assert!(!binaries.is_empty() && binaries.len() == 1);
         assert!(binaries[0].contains("duf_0.9.1_linux_arm64.tar.gz"));
     }
+
+    #[test]
+    fn test_macos_universal_on_both_archs() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/some_org@some_tool_universal.ron")).unwrap();
+        let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
+        for arch in ["aarch64", "x86_64"] {
+            let platform_triple = AssetTriple::new("macos".to_string(), arch.to_string(), false);
+            let binaries =
+                get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+                    .map(best_scored);
+            assert!(binaries.is_some(), "{}", arch);
+            let binaries = binaries.unwrap();
+            assert_eq!(
+                binaries,
+                vec!["some_tool-1.2.0-universal-apple-darwin.tar.gz"],
+                "{}",
+                arch
+            );
+        }
+    }
+
+    #[test]
+    fn test_linux_ignores_macos_universal() {
+        let assets: Vec<String> =
+            ron::from_str(include_str!("assets/some_org@some_tool_universal.ron")).unwrap();
+        let asset_refs: Vec<&str> = assets.iter().map(|s| s.as_str()).collect();
+        let platform_triple = AssetTriple::new("linux".to_string(), "aarch64".to_string(), false);
+        let binaries = get_triple_compatible_assets(&asset_refs, &platform_triple, |asset| asset)
+            .map(best_scored);
+        assert_eq!(
+            binaries,
+            Some(vec!["some_tool-1.2.0-aarch64-unknown-linux-gnu.tar.gz"])
+        );
+    }
+
+    #[test]
+    fn test_macos_thin_binary_preferred_over_universal() {
+        let assets = vec![
+            "tool_darwin_all.tar.gz",
+            "tool_darwin_arm64.tar.gz",
+            "tool_darwin_amd64.tar.gz",
+        ];
+        let platform_triple = AssetTriple::new("macos".to_string(), "aarch64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert_eq!(binaries, Some(vec!["tool_darwin_arm64.tar.gz"]));
+    }
+
+    #[test]
+    fn test_macos_no_arch_tag_beats_other_arch() {
+        // an asset for macOS with no arch tag is likely universal,
+        // an x86_64 one would need Rosetta on Apple Silicon
+        let assets = vec!["tool-macos-x86_64.tar.gz", "tool-macos.tar.gz"];
+        let platform_triple = AssetTriple::new("macos".to_string(), "aarch64".to_string(), false);
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert_eq!(binaries, Some(vec!["tool-macos.tar.gz"]));
+    }
 }
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::core::selector::{best_scored, get_triple_compatible_assets};
+    use crate::models::asset_triple::AssetTriple;
+
+    #[test]
+    fn test_musl_override_selects_musl_asset() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
+        // whatever the detected preference is, the override wins
+        for detected in [true, false] {
+            let platform_triple =
+                AssetTriple::new("linux".to_string(), "x86_64".to_string(), detected)
+                    .with_musl(Some(true));
+            let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset)
+                .map(best_scored);
+            assert!(binaries.is_some());
+            let binaries = binaries.unwrap();
+            assert!(!binaries.is_empty() && binaries.len() == 1);
+            assert!(binaries[0].contains("dust-v1.2.4-x86_64-unknown-linux-musl.tar.gz"));
+        }
+    }
+
+    #[test]
+    fn test_gnu_override_selects_gnu_asset() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
+        // whatever the detected preference is, the override wins
+        for detected in [true, false] {
+            let platform_triple =
+                AssetTriple::new("linux".to_string(), "x86_64".to_string(), detected)
+                    .with_musl(Some(false));
+            let binaries = get_triple_compatible_assets(&assets, &platform_triple, |asset| asset)
+                .map(best_scored);
+            assert!(binaries.is_some());
+            let binaries = binaries.unwrap();
+            assert!(!binaries.is_empty() && binaries.len() == 1);
+            assert!(binaries[0].contains("dust-v1.2.4-x86_64-unknown-linux-gnu.tar.gz"));
+        }
+    }
+
+    #[test]
+    fn test_no_override_keeps_detected_preference() {
+        let platform_triple =
+            AssetTriple::new("linux".to_string(), "x86_64".to_string(), true).with_musl(None);
+        assert!(platform_triple.is_musl());
+        let platform_triple =
+            AssetTriple::new("linux".to_string(), "x86_64".to_string(), false).with_musl(None);
+        assert!(!platform_triple.is_musl());
+    }
+
+    #[test]
+    fn test_arch_override_selects_other_arch_asset() {
+        let assets: Vec<String> = ron::from_str(include_str!("assets/bootandy@dust.ron")).unwrap();
+        // simulate an x86_64 host asking for aarch64 assets
+        let platform_triple = AssetTriple::new("linux".to_string(), "x86_64".to_string(), false)
+            .with_arch(Some("aarch64"));
+        let binaries =
+            get_triple_compatible_assets(&assets, &platform_triple, |asset| asset).map(best_scored);
+        assert!(binaries.is_some());
+        let binaries = binaries.unwrap();
+        assert!(!binaries.is_empty() && binaries.len() == 1);
+        assert!(binaries[0].contains("dust-v1.2.4-aarch64-unknown-linux-gnu.tar.gz"));
+    }
+
+    #[test]
+    fn test_no_arch_override_keeps_detected_arch() {
+        let platform_triple =
+            AssetTriple::new("linux".to_string(), "x86_64".to_string(), false).with_arch(None);
+        assert_eq!(platform_triple.get_arch(), "x86_64");
+    }
+}
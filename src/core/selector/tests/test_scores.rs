@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::core::selector::{best_scored, get_triple_compatible_assets};
+    use crate::models::asset_triple::AssetTriple;
+
+    fn linux(arch: &str, musl: bool) -> AssetTriple {
+        AssetTriple::new("linux".to_string(), arch.to_string(), musl)
+    }
+
+    #[test]
+    fn test_scores_sorted_descending() {
+        let assets = vec![
+            "tool-linux-x86_64",
+            "tool-linux-x86_64.tar.gz",
+            "tool-darwin-arm64.dmg",
+            "checksums.txt",
+        ];
+        let scored = get_triple_compatible_assets(&assets, &linux("x86_64", false), |a| a).unwrap();
+        assert_eq!(scored.len(), 2);
+        assert!(scored.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(scored[0].0, "tool-linux-x86_64.tar.gz");
+        assert_eq!(scored[1].0, "tool-linux-x86_64");
+    }
+
+    #[test]
+    fn test_archive_preferred_over_bare_binary() {
+        // listed first, the bare binary must not win the tie-break by release order
+        let assets = vec!["tool-linux-x86_64", "tool-linux-x86_64.tar.gz"];
+        let binaries = get_triple_compatible_assets(&assets, &linux("x86_64", false), |a| a)
+            .map(best_scored)
+            .unwrap();
+        assert_eq!(binaries, vec!["tool-linux-x86_64.tar.gz"]);
+    }
+
+    #[test]
+    fn test_musl_preferred_when_requested() {
+        let assets = vec![
+            "tool-x86_64-unknown-linux-gnu.tar.gz",
+            "tool-x86_64-unknown-linux-musl.tar.gz",
+        ];
+        let scored = get_triple_compatible_assets(&assets, &linux("x86_64", true), |a| a).unwrap();
+        assert_eq!(scored[0].0, "tool-x86_64-unknown-linux-musl.tar.gz");
+        assert!(scored[0].1 > scored[1].1);
+
+        let scored = get_triple_compatible_assets(&assets, &linux("x86_64", false), |a| a).unwrap();
+        assert_eq!(scored[0].0, "tool-x86_64-unknown-linux-gnu.tar.gz");
+        assert!(scored[0].1 > scored[1].1);
+    }
+
+    #[test]
+    fn test_exact_arch_preferred_over_fallback() {
+        let assets = vec!["tool-linux-arm.tar.gz", "tool-linux-armv7.tar.gz"];
+        let scored = get_triple_compatible_assets(&assets, &linux("armv7", false), |a| a).unwrap();
+        assert_eq!(scored[0].0, "tool-linux-armv7.tar.gz");
+        assert!(scored[0].1 > scored[1].1);
+    }
+
+    #[test]
+    fn test_ties_keep_release_order() {
+        let assets = vec!["alpha-linux-x86_64.tar.gz", "bravo-linux-x86_64.tar.gz"];
+        let scored = get_triple_compatible_assets(&assets, &linux("x86_64", false), |a| a).unwrap();
+        assert_eq!(scored[0].1, scored[1].1);
+        assert_eq!(best_scored(scored), assets);
+    }
+
+    #[test]
+    fn test_no_compatible_assets() {
+        let assets = vec!["tool-darwin-arm64.dmg", "checksums.txt"];
+        assert!(get_triple_compatible_assets(&assets, &linux("x86_64", false), |a| a).is_none());
+        assert!(best_scored(Vec::<(&str, u32)>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_arch_linux_packages_are_linux_only() {
+        let assets = vec!["tool-1.0.0-1-x86_64.pkg.tar.zst", "tool-archlinux.tar.gz"];
+        let scored = get_triple_compatible_assets(&assets, &linux("x86_64", false), |a| a).unwrap();
+        assert_eq!(scored.len(), 2);
+
+        let macos = AssetTriple::new("macos".to_string(), "x86_64".to_string(), false);
+        assert!(get_triple_compatible_assets(&assets, &macos, |a| a).is_none());
+    }
+
+    #[test]
+    fn test_arch_linux_package_without_arch_is_x86_64() {
+        let assets = vec!["tool-archlinux.tar.gz"];
+        assert!(get_triple_compatible_assets(&assets, &linux("aarch64", false), |a| a).is_none());
+        let assets = vec![
+            "tool-1.0.0-1-aarch64.pkg.tar.zst",
+            "tool-1.0.0-1-x86_64.pkg.tar.zst",
+        ];
+        let binaries = get_triple_compatible_assets(&assets, &linux("aarch64", false), |a| a)
+            .map(best_scored)
+            .unwrap();
+        assert_eq!(binaries, vec!["tool-1.0.0-1-aarch64.pkg.tar.zst"]);
+        let assets = vec!["tool-1.0.0-1-any.pkg.tar.zst"];
+        assert!(get_triple_compatible_assets(&assets, &linux("aarch64", false), |a| a).is_some());
+    }
+}
@@ -0,0 +1,86 @@
+//!
+//! Bounded thread pool to run independent jobs concurrently.
+//!
+
+use anyhow::Result;
+use log::warn;
+use rayon::prelude::*;
+
+/// Upper bound for the number of concurrent jobs: the number of available CPUs.
+pub fn max_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Runs `f` on every item using at most `jobs` threads, capped at [`max_jobs`].
+///
+/// Results are returned in the same order as `items`, an error for one item
+/// does not stop the others.
+pub fn run_with_pool<I, F, R>(items: I, jobs: usize, f: F) -> Vec<Result<R>>
+where
+    I: IntoIterator,
+    I::Item: Send,
+    F: Fn(I::Item) -> Result<R> + Send + Sync,
+    R: Send,
+{
+    let items: Vec<I::Item> = items.into_iter().collect();
+    let jobs = jobs.clamp(1, max_jobs());
+    if jobs == 1 {
+        return items.into_iter().map(f).collect();
+    }
+    match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool.install(|| items.into_par_iter().map(f).collect()),
+        Err(e) => {
+            warn!(
+                "Cannot start {} worker threads ({}), running jobs one at a time.",
+                jobs, e
+            );
+            items.into_iter().map(f).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_run_with_pool_keeps_order() {
+        for jobs in [0, 1, 2, 64] {
+            let results = run_with_pool(0..20, jobs, |i| Ok(i * 2));
+            let values: Vec<i32> = results.into_iter().map(|r| r.unwrap()).collect();
+            assert_eq!(values, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_run_with_pool_reports_errors_per_item() {
+        let results = run_with_pool(vec!["a", "b", "c"], 2, |item| {
+            if item == "b" {
+                bail!("cannot process {}", item);
+            }
+            Ok(item.to_uppercase())
+        });
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), "A");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap(), "C");
+    }
+
+    #[test]
+    fn test_run_with_pool_bounds_concurrency() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        run_with_pool(0..16, 2, |_| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        });
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
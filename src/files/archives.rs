@@ -4,18 +4,24 @@ use crate::models::binary_container::BinaryContainer;
 
 use bzip2::read::BzDecoder;
 use flate2::read::GzDecoder;
-use log::debug;
+use log::{debug, warn};
+use lz4_flex::frame::FrameDecoder;
 use std::fs::File;
-use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::io::{Chain, Cursor, Read, Seek};
+use std::path::{Component, Path, PathBuf};
 use tar::Archive;
 use xz2::read::XzDecoder;
+use xz2::stream::Stream;
 use zip::read::ZipArchive;
 
 use anyhow::{bail, Context, Result};
 
 /// Fallback subdirectory name used when an archive's own directory cannot be determined.
 const OUTPUT_DIR: &str = "output";
+/// Size in bytes of an lzip member header (magic, version, coded dictionary size).
+const LZIP_HEADER_SIZE: usize = 6;
+/// LZMA properties byte used by lzip (lc=3, lp=0, pb=2).
+const LZIP_LZMA_PROPERTIES: u8 = 0x5D;
 
 /// Validates an archive file's magic bytes against its expected format.
 ///
@@ -45,6 +51,9 @@ const OUTPUT_DIR: &str = "output";
 /// - **XZ** (XZ, TAR.XZ): Checks for XZ magic bytes at the start
 /// - **BZIP2** (BZ2, TAR.BZ2): Checks for BZIP2 magic bytes at the start
 /// - **ZSTD** (ZST, TAR.ZST): Checks for ZSTD magic bytes at the start
+/// - **LZ4** (LZ4, TAR.LZ4): Checks for LZ4 frame magic bytes at the start
+/// - **LZIP** (TAR.LZ): Checks for "LZIP" signature at the start
+/// - **LZMA** (LZMA, TAR.LZMA): Checks for the LZMA-alone header at the start
 /// - **TAR**: Checks for "ustar" signature at offset 257 (POSIX tar format)
 /// - **7Z**: Checks for 7-Zip signature at the start
 ///
@@ -80,6 +89,9 @@ fn validate_format_against_magic_bytes(
         BinaryContainer::TarXz | BinaryContainer::Xz => buffer.starts_with(XZ_MAGIC),
         BinaryContainer::TarBz2 | BinaryContainer::Bz2 => buffer.starts_with(BZIP2_MAGIC),
         BinaryContainer::TarZstd | BinaryContainer::Zstd => buffer.starts_with(ZSTD_MAGIC),
+        BinaryContainer::TarLz4 | BinaryContainer::Lz4 => buffer.starts_with(LZ4_MAGIC),
+        BinaryContainer::TarLz => buffer.starts_with(LZIP_MAGIC),
+        BinaryContainer::TarLzma | BinaryContainer::Lzma => buffer.starts_with(LZMA_MAGIC),
         BinaryContainer::Tar => {
             // Check for tar magic at offset 257
             bytes_read > TAR_MAGIC_OFFSET + TAR_MAGIC.len()
@@ -108,10 +120,15 @@ fn validate_format_against_magic_bytes(
 /// - `BinaryContainer::TarXz` for `.tar.xz` or `.txz` files
 /// - `BinaryContainer::TarBz2` for `.tar.bz2`, `.tbz`, or `.tbz2` files
 /// - `BinaryContainer::TarZstd` for `.tar.zst` or `.tzst` files
+/// - `BinaryContainer::TarLz4` for `.tar.lz4` files
+/// - `BinaryContainer::TarLz` for `.tar.lz` files
+/// - `BinaryContainer::TarLzma` for `.tar.lzma` files
 /// - `BinaryContainer::Gz` for standalone `.gz` files
 /// - `BinaryContainer::Xz` for standalone `.xz` files
 /// - `BinaryContainer::Bz2` for standalone `.bz2` files
 /// - `BinaryContainer::Zstd` for standalone `.zst` files
+/// - `BinaryContainer::Lz4` for standalone `.lz4` files
+/// - `BinaryContainer::Lzma` for standalone `.lzma` files
 /// - `BinaryContainer::Tar` for `.tar` files
 /// - `BinaryContainer::SevenZ` for `.7z` files
 /// - `BinaryContainer::Unknown` for unrecognized extensions
@@ -136,12 +153,17 @@ fn get_archive_format_from_extension(archive_path: &Path) -> BinaryContainer {
         "tar.xz" | "txz" => BinaryContainer::TarXz,
         "tar.bz2" | "tbz" | "tbz2" => BinaryContainer::TarBz2,
         "tar.zst" | "tzst" => BinaryContainer::TarZstd,
+        "tar.lz4" => BinaryContainer::TarLz4,
+        "tar.lz" => BinaryContainer::TarLz,
+        "tar.lzma" => BinaryContainer::TarLzma,
         // Single extensions
         "zip" => BinaryContainer::Zip,
         "gz" => BinaryContainer::Gz,
         "xz" => BinaryContainer::Xz,
         "bz2" => BinaryContainer::Bz2,
         "zst" => BinaryContainer::Zstd,
+        "lz4" => BinaryContainer::Lz4,
+        "lzma" => BinaryContainer::Lzma,
         "tar" => BinaryContainer::Tar,
         "7z" => BinaryContainer::SevenZ,
         _ => BinaryContainer::Unknown,
@@ -183,10 +205,15 @@ fn get_archive_format_from_extension(archive_path: &Path) -> BinaryContainer {
 /// - **TAR.XZ/TXZ** (`.tar.xz`, `.txz`): XZ-compressed TAR archive
 /// - **TAR.BZ2/TBZ/TBZ2** (`.tar.bz2`, `.tbz`, `.tbz2`): BZip2-compressed TAR archive
 /// - **TAR.ZSTD/TZST** (`.tar.zst`, `.tzst`): Zstandard-compressed TAR archive
+/// - **TAR.LZ4** (`.tar.lz4`): LZ4-compressed TAR archive
+/// - **TAR.LZ** (`.tar.lz`): Lzip-compressed TAR archive
+/// - **TAR.LZMA** (`.tar.lzma`): LZMA-compressed TAR archive
 /// - **GZ** (`.gz`): Standalone GZip-compressed file (not commonly used for distribution)
 /// - **XZ** (`.xz`): Standalone XZ-compressed file (not commonly used for distribution)
 /// - **BZ2** (`.bz2`): Standalone BZip2-compressed file (not commonly used for distribution)
 /// - **ZSTD** (`.zst`): Standalone Zstandard-compressed file (not commonly used for distribution)
+/// - **LZ4** (`.lz4`): Standalone LZ4-compressed file (not commonly used for distribution)
+/// - **LZMA** (`.lzma`): Standalone LZMA-compressed file (not commonly used for distribution)
 /// - **7Z** (`.7z`): 7-Zip archive format
 ///
 /// # Arguments
@@ -262,6 +289,12 @@ pub fn get_validated_archive_format(archive_path: &Path) -> Result<BinaryContain
     }
 }
 
+/// Returns `true` when `file_name` has the extension of a supported archive format.
+/// Only the name is checked, the file does not need to exist.
+pub fn is_archive_name(file_name: &str) -> bool {
+    get_archive_format_from_extension(Path::new(file_name)) != BinaryContainer::Unknown
+}
+
 /// Extracts an archive file to a specified directory with format validation.
 ///
 /// This function provides a unified interface for extracting various archive formats.
@@ -278,10 +311,15 @@ pub fn get_validated_archive_format(archive_path: &Path) -> Result<BinaryContain
 /// - **TAR.XZ/TXZ** (`.tar.xz`, `.txz`): XZ-compressed TAR archives
 /// - **TAR.BZ2/TBZ/TBZ2** (`.tar.bz2`, `.tbz`, `.tbz2`): BZip2-compressed TAR archives
 /// - **TAR.ZST/TZST** (`.tar.zst`, `.tzst`): Zstandard-compressed TAR archives
+/// - **TAR.LZ4** (`.tar.lz4`): LZ4-compressed TAR archives
+/// - **TAR.LZ** (`.tar.lz`): Lzip-compressed TAR archives
+/// - **TAR.LZMA** (`.tar.lzma`): LZMA-compressed TAR archives
 /// - **GZ** (`.gz`): Standalone GZip-compressed files (uncommon for distribution)
 /// - **XZ** (`.xz`): Standalone XZ-compressed files (uncommon for distribution)
 /// - **BZ2** (`.bz2`): Standalone BZip2-compressed files (uncommon for distribution)
 /// - **ZST** (`.zst`): Standalone Zstandard-compressed files (uncommon for distribution)
+/// - **LZ4** (`.lz4`): Standalone LZ4-compressed files (uncommon for distribution)
+/// - **LZMA** (`.lzma`): Standalone LZMA-compressed files (uncommon for distribution)
 /// - **7Z** (`.7z`): 7-Zip archives using the `sevenz-rust2` crate
 ///
 /// # Arguments
@@ -312,7 +350,8 @@ pub fn get_validated_archive_format(archive_path: &Path) -> Result<BinaryContain
 ///
 /// ## Directory Creation
 /// - For TAR and ZIP archives, the target directory should exist or be creatable
-/// - For standalone compressed files (GZ, XZ, BZ2), the function creates the target directory
+/// - For standalone compressed files (GZ, XZ, BZ2, ZST, LZ4, LZMA), the function creates the
+///   target directory
 ///
 /// ## Output File Naming
 /// - **TAR/ZIP archives**: Preserves the internal directory structure
@@ -329,7 +368,8 @@ pub fn get_validated_archive_format(archive_path: &Path) -> Result<BinaryContain
 /// - **Format Validation**: All archives are validated via `get_validated_archive_format`
 ///   before extraction to prevent format spoofing attacks
 /// - **Magic Byte Verification**: Ensures the file content matches its claimed format
-/// - **Path Traversal**: Archive extraction libraries handle path traversal protection
+/// - **Path Traversal**: ZIP and TAR entries are checked before extraction and the whole
+///   archive is refused if any entry is absolute or escapes `extract_to` (Zip Slip)
 ///
 /// # Examples
 ///
@@ -366,7 +406,10 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
             debug!("Extracting zip archive: {}", archive_path.display());
             let zip_file = File::open(archive_path)?;
             let mut archive = ZipArchive::new(zip_file)?;
+            validate_zip_entries(&mut archive)?;
             archive.extract(extract_to)?;
+            #[cfg(not(target_os = "windows"))]
+            drop_zip_special_mode_bits(&mut archive, extract_to)?;
             debug!(
                 "Successfully extracted zip archive to {}",
                 extract_to.display()
@@ -374,6 +417,7 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
         }
         BinaryContainer::TarGz => {
             debug!("Extracting tar.gz archive: {}", archive_path.display());
+            validate_tar_entries(GzDecoder::new(File::open(archive_path)?))?;
             let tar_gz_file = File::open(archive_path)?;
             let tar = GzDecoder::new(tar_gz_file);
             let mut archive = Archive::new(tar);
@@ -385,6 +429,7 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
         }
         BinaryContainer::TarXz => {
             debug!("Extracting tar.xz archive: {}", archive_path.display());
+            validate_tar_entries(XzDecoder::new(File::open(archive_path)?))?;
             let tar_xz_file = File::open(archive_path)?;
             let tar = XzDecoder::new(tar_xz_file);
             let mut archive = Archive::new(tar);
@@ -396,6 +441,7 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
         }
         BinaryContainer::TarBz2 => {
             debug!("Extracting tar.bz2 archive: {}", archive_path.display());
+            validate_tar_entries(BzDecoder::new(File::open(archive_path)?))?;
             let tar_bz2_file = File::open(archive_path)?;
             let tar = BzDecoder::new(tar_bz2_file);
             let mut archive = Archive::new(tar);
@@ -407,6 +453,7 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
         }
         BinaryContainer::TarZstd => {
             debug!("Extracting tar.zst archive: {}", archive_path.display());
+            validate_tar_entries(zstd::stream::read::Decoder::new(File::open(archive_path)?)?)?;
             let tar_zstd_file = File::open(archive_path)?;
             let tar = zstd::stream::read::Decoder::new(tar_zstd_file)?;
             let mut archive = Archive::new(tar);
@@ -416,8 +463,45 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
                 extract_to.display()
             );
         }
+        BinaryContainer::TarLz4 => {
+            debug!("Extracting tar.lz4 archive: {}", archive_path.display());
+            validate_tar_entries(FrameDecoder::new(File::open(archive_path)?))?;
+            let tar_lz4_file = File::open(archive_path)?;
+            let tar = FrameDecoder::new(tar_lz4_file);
+            let mut archive = Archive::new(tar);
+            archive.unpack(extract_to)?;
+            debug!(
+                "Successfully extracted tar.lz4 archive to {}",
+                extract_to.display()
+            );
+        }
+        BinaryContainer::TarLz => {
+            debug!("Extracting tar.lz archive: {}", archive_path.display());
+            validate_tar_entries(lzip_decoder(File::open(archive_path)?)?)?;
+            let tar_lz_file = File::open(archive_path)?;
+            let tar = lzip_decoder(tar_lz_file)?;
+            let mut archive = Archive::new(tar);
+            archive.unpack(extract_to)?;
+            debug!(
+                "Successfully extracted tar.lz archive to {}",
+                extract_to.display()
+            );
+        }
+        BinaryContainer::TarLzma => {
+            debug!("Extracting tar.lzma archive: {}", archive_path.display());
+            validate_tar_entries(lzma_decoder(File::open(archive_path)?)?)?;
+            let tar_lzma_file = File::open(archive_path)?;
+            let tar = lzma_decoder(tar_lzma_file)?;
+            let mut archive = Archive::new(tar);
+            archive.unpack(extract_to)?;
+            debug!(
+                "Successfully extracted tar.lzma archive to {}",
+                extract_to.display()
+            );
+        }
         BinaryContainer::Tar => {
             debug!("Extracting tar archive: {}", archive_path.display());
+            validate_tar_entries(File::open(archive_path)?)?;
             let tar_file = File::open(archive_path)?;
             let mut archive = Archive::new(tar_file);
             archive.unpack(extract_to)?;
@@ -501,6 +585,43 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
                 output_path.display()
             );
         }
+        BinaryContainer::Lz4 => {
+            debug!("Extracting lz4 archive: {}", archive_path.display());
+            let lz4_file = File::open(archive_path)?;
+            let mut decoder = FrameDecoder::new(lz4_file);
+            let output_path = extract_to.join(
+                archive_path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(OUTPUT_DIR),
+            );
+            std::fs::create_dir_all(extract_to)?;
+            let mut output_file = File::create(&output_path)?;
+            std::io::copy(&mut decoder, &mut output_file)?;
+            debug!(
+                "Successfully extracted lz4 archive to {}",
+                output_path.display()
+            );
+        }
+        BinaryContainer::Lzma => {
+            // Plain lzma file (not tar.lzma) - not really used for software distribution
+            debug!("Extracting lzma archive: {}", archive_path.display());
+            let lzma_file = File::open(archive_path)?;
+            let mut decoder = lzma_decoder(lzma_file)?;
+            let output_path = extract_to.join(
+                archive_path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(OUTPUT_DIR),
+            );
+            std::fs::create_dir_all(extract_to)?;
+            let mut output_file = File::create(&output_path)?;
+            std::io::copy(&mut decoder, &mut output_file)?;
+            debug!(
+                "Successfully extracted lzma archive to {}",
+                output_path.display()
+            );
+        }
         BinaryContainer::SevenZ => {
             debug!("Extracting 7z archive: {}", archive_path.display());
             sevenz_rust2::decompress_file(archive_path, extract_to).expect("complete");
@@ -514,5 +635,179 @@ pub fn extract_to_dir(archive_path: &PathBuf, extract_to: &PathBuf) -> Result<()
     Ok(())
 }
 
+/// Returns `true` if `entry` would be written outside the extraction directory.
+///
+/// An entry escapes when it is an absolute path or when, once normalized,
+/// its `..` components climb above the extraction root (e.g. `../../etc/foo`).
+fn is_escaping_path(entry: &Path) -> bool {
+    let mut depth: usize = 0;
+    for component in entry.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return true,
+            Component::ParentDir => {
+                if depth == 0 {
+                    return true;
+                }
+                depth -= 1;
+            }
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+        }
+    }
+    false
+}
+
+/// Rejects an archive entry whose path escapes the extraction directory (Zip Slip).
+fn ensure_safe_entry_path(entry: &Path) -> Result<()> {
+    if is_escaping_path(entry) {
+        warn!(
+            "Archive entry '{}' points outside the extraction directory",
+            entry.display()
+        );
+        bail!(
+            "Refusing to extract archive: entry '{}' escapes the extraction directory",
+            entry.display()
+        );
+    }
+    Ok(())
+}
+
+/// Rejects a symlink entry whose `target` points outside the extraction directory.
+///
+/// The target is resolved against the directory holding the `link` entry, so
+/// absolute targets and targets climbing above the extraction root are refused.
+fn ensure_safe_link_target(link: &Path, target: &Path) -> Result<()> {
+    let resolved = link.parent().unwrap_or(Path::new("")).join(target);
+    if is_escaping_path(&resolved) {
+        warn!(
+            "Archive symlink '{}' points to '{}', outside the extraction directory",
+            link.display(),
+            target.display()
+        );
+        bail!(
+            "Refusing to extract archive: symlink '{}' escapes the extraction directory",
+            link.display()
+        );
+    }
+    Ok(())
+}
+
+/// Checks every entry of a zip archive before extraction.
+///
+/// Poof extracts untrusted third-party archives, so the whole archive is
+/// refused when any entry, or the target of any symlink entry, is absolute
+/// or escapes the extraction directory.
+fn validate_zip_entries<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<()> {
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        let name = PathBuf::from(entry.name());
+        ensure_safe_entry_path(&name)?;
+        if entry.is_symlink() {
+            drop(entry);
+            let mut target = String::new();
+            archive
+                .by_index(i)?
+                .read_to_string(&mut target)
+                .with_context(|| format!("Cannot read symlink target of {}", name.display()))?;
+            ensure_safe_link_target(&name, Path::new(&target))?;
+        }
+    }
+    Ok(())
+}
+
+/// Drops the setuid, setgid and sticky bits that extraction restored from the
+/// zip entries of an untrusted archive (Unix only).
+///
+/// The other mode bits are restored by [`ZipArchive::extract`] itself. Only
+/// regular files are touched: symlink entries are skipped and the extracted
+/// path is checked without following links, so a crafted archive cannot have
+/// the permissions of a file outside `extract_to` changed.
+#[cfg(not(target_os = "windows"))]
+fn drop_zip_special_mode_bits<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    extract_to: &Path,
+) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        if entry.is_dir() || entry.is_symlink() {
+            continue;
+        }
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let extracted = extract_to.join(name);
+        let Ok(metadata) = std::fs::symlink_metadata(&extracted) else {
+            continue;
+        };
+        let mode = metadata.permissions().mode();
+        if metadata.file_type().is_file() && mode & 0o7000 != 0 {
+            std::fs::set_permissions(&extracted, std::fs::Permissions::from_mode(mode & 0o777))
+                .with_context(|| format!("Cannot set permissions on {}", extracted.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks every entry of a (decompressed) tar stream before extraction.
+///
+/// Reads the whole stream, so callers must open a fresh reader for the actual
+/// unpacking. The archive is refused when any entry is absolute or escapes the
+/// extraction directory.
+fn validate_tar_entries<R: Read>(reader: R) -> Result<()> {
+    let mut archive = Archive::new(reader);
+    for entry in archive.entries()? {
+        let entry = entry?;
+        ensure_safe_entry_path(&entry.path()?)?;
+    }
+    Ok(())
+}
+
+/// Wraps `reader` in a decoder for the legacy LZMA-alone (`.lzma`) format.
+fn lzma_decoder<R: Read>(reader: R) -> Result<XzDecoder<R>> {
+    let stream = Stream::new_lzma_decoder(u64::MAX).context("Cannot initialise LZMA decoder")?;
+    Ok(XzDecoder::new_stream(reader, stream))
+}
+
+/// Wraps an lzip `reader` in a decoder for its LZMA payload.
+///
+/// An lzip member is a raw LZMA stream terminated by an end-of-stream marker,
+/// preceded by a 6-byte header and followed by a CRC/size trailer. The header is
+/// translated into the equivalent LZMA-alone header so that liblzma can decode
+/// the payload. Only the first member is decoded and the trailer is not verified.
+fn lzip_decoder<R: Read>(mut reader: R) -> Result<XzDecoder<Chain<Cursor<Vec<u8>>, R>>> {
+    let mut header = [0u8; LZIP_HEADER_SIZE];
+    reader
+        .read_exact(&mut header)
+        .context("Cannot read lzip header")?;
+    if !header.starts_with(LZIP_MAGIC) {
+        bail!("Not an lzip file");
+    }
+    if header[4] != 1 {
+        bail!("Unsupported lzip version: {}", header[4]);
+    }
+    let dict_size = lzip_dict_size(header[5])?;
+
+    let mut lzma_header = Vec::with_capacity(13);
+    lzma_header.push(LZIP_LZMA_PROPERTIES);
+    lzma_header.extend_from_slice(&dict_size.to_le_bytes());
+    // unknown uncompressed size, decoding stops at the end-of-stream marker
+    lzma_header.extend_from_slice(&u64::MAX.to_le_bytes());
+    lzma_decoder(Cursor::new(lzma_header).chain(reader))
+}
+
+/// Decodes the coded dictionary size byte of an lzip header.
+///
+/// Bits 4-0 hold the base-2 logarithm of the base size (12 to 29) and bits 7-5
+/// the number of sixteenths of the base size to subtract from it.
+fn lzip_dict_size(coded: u8) -> Result<u32> {
+    let exponent = u32::from(coded & 0x1F);
+    if !(12..=29).contains(&exponent) {
+        bail!("Invalid lzip dictionary size: {:#04x}", coded);
+    }
+    let base = 1u32 << exponent;
+    Ok(base - (base / 16) * u32::from(coded >> 5))
+}
+
 #[cfg(test)]
 mod tests;
@@ -4,6 +4,7 @@
 use std::path::PathBuf;
 use tempfile::TempDir;
 
+use super::common::create_file_with_magic;
 use crate::files::archives::extract_to_dir;
 
 /// Get the path to the fixtures directory
@@ -232,6 +233,69 @@ fn test_extract_tzst_archive() {
     assert!(extract_path.join("README").exists());
 }
 
+#[test]
+fn test_extract_tar_lz4_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = fixtures_dir().join("archive.tar.lz4");
+    let extract_path = temp_dir.path().join("extracted");
+
+    // Extract the fixture tar.lz4 archive
+    let result = extract_to_dir(&archive_path, &extract_path);
+
+    assert!(
+        result.is_ok(),
+        "Extraction failed for .tar.lz4: {:?}",
+        result.err()
+    );
+
+    // Verify extracted files exist
+    assert!(extract_path.join("file.txt").exists());
+    assert!(extract_path.join("text.txt").exists());
+    assert!(extract_path.join("README").exists());
+}
+
+#[test]
+fn test_extract_tar_lz_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = fixtures_dir().join("archive.tar.lz");
+    let extract_path = temp_dir.path().join("extracted");
+
+    // Extract the fixture tar.lz (lzip) archive
+    let result = extract_to_dir(&archive_path, &extract_path);
+
+    assert!(
+        result.is_ok(),
+        "Extraction failed for .tar.lz: {:?}",
+        result.err()
+    );
+
+    // Verify extracted files exist
+    assert!(extract_path.join("file.txt").exists());
+    assert!(extract_path.join("text.txt").exists());
+    assert!(extract_path.join("README").exists());
+}
+
+#[test]
+fn test_extract_tar_lzma_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = fixtures_dir().join("archive.tar.lzma");
+    let extract_path = temp_dir.path().join("extracted");
+
+    // Extract the fixture tar.lzma archive
+    let result = extract_to_dir(&archive_path, &extract_path);
+
+    assert!(
+        result.is_ok(),
+        "Extraction failed for .tar.lzma: {:?}",
+        result.err()
+    );
+
+    // Verify extracted files exist
+    assert!(extract_path.join("file.txt").exists());
+    assert!(extract_path.join("text.txt").exists());
+    assert!(extract_path.join("README").exists());
+}
+
 #[test]
 fn test_extract_7z_archive() {
     let temp_dir = TempDir::new().unwrap();
@@ -329,6 +393,44 @@ fn test_extract_zstd_compressed_file() {
     assert!(extract_path.join("file.txt").exists());
 }
 
+#[test]
+fn test_extract_lz4_compressed_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = fixtures_dir().join("file.txt.lz4");
+    let extract_path = temp_dir.path().join("extracted");
+
+    // Extract the fixture lz4 compressed file (not tar.lz4, just lz4)
+    let result = extract_to_dir(&archive_path, &extract_path);
+
+    assert!(
+        result.is_ok(),
+        "Extraction failed for .lz4: {:?}",
+        result.err()
+    );
+
+    // Verify extracted file exists (file.* contains just file.txt)
+    assert!(extract_path.join("file.txt").exists());
+}
+
+#[test]
+fn test_extract_lzma_compressed_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = fixtures_dir().join("file.txt.lzma");
+    let extract_path = temp_dir.path().join("extracted");
+
+    // Extract the fixture lzma compressed file (not tar.lzma, just lzma)
+    let result = extract_to_dir(&archive_path, &extract_path);
+
+    assert!(
+        result.is_ok(),
+        "Extraction failed for .lzma: {:?}",
+        result.err()
+    );
+
+    // Verify extracted file exists (file.* contains just file.txt)
+    assert!(extract_path.join("file.txt").exists());
+}
+
 // ============================================================================
 // Tests for non existent files and directories
 // ============================================================================
@@ -371,3 +473,212 @@ fn test_extract_to_nonexistent_directory() {
     assert!(extract_path.exists());
     assert!(extract_path.join("file.txt").exists());
 }
+
+#[test]
+fn test_extract_tar_lz_unsupported_version_returns_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("bad.tar.lz");
+    let extract_path = temp_dir.path().join("extracted");
+
+    // Valid lzip magic but version 0 and an invalid dictionary size
+    create_file_with_magic(&archive_path, crate::files::magic::LZIP_MAGIC).unwrap();
+
+    let result = extract_to_dir(&archive_path, &extract_path);
+    assert!(
+        result.is_err(),
+        "Extraction should fail for a bad lzip header"
+    );
+}
+
+// ============================================================================
+// Tests for path traversal (Zip Slip)
+// ============================================================================
+
+#[test]
+fn test_is_escaping_path() {
+    use crate::files::archives::is_escaping_path;
+    use std::path::Path;
+
+    assert!(!is_escaping_path(Path::new("file.txt")));
+    assert!(!is_escaping_path(Path::new("./dir/file.txt")));
+    assert!(!is_escaping_path(Path::new("dir/../file.txt")));
+    assert!(is_escaping_path(Path::new("../file.txt")));
+    assert!(is_escaping_path(Path::new("dir/../../file.txt")));
+    assert!(is_escaping_path(Path::new("/etc/passwd")));
+}
+
+/// Assert that extracting `fixture` is refused and nothing escapes `extract_path`.
+fn assert_traversal_refused(fixture: &str) {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = fixtures_dir().join(fixture);
+    let extract_path = temp_dir.path().join("extracted");
+
+    let result = extract_to_dir(&archive_path, &extract_path);
+
+    assert!(
+        result.is_err(),
+        "Extraction of {} should be refused",
+        fixture
+    );
+    let err_msg = format!("{:?}", result.err().unwrap());
+    assert!(
+        err_msg.contains("escapes the extraction directory"),
+        "Error should name the offending entry: {}",
+        err_msg
+    );
+    // the validation pass runs before anything is written
+    assert!(!temp_dir.path().join("evil.txt").exists());
+    assert!(!extract_path.join("file.txt").exists());
+}
+
+#[test]
+fn test_extract_zip_with_parent_dir_entry_is_refused() {
+    assert_traversal_refused("traversal.zip");
+}
+
+#[test]
+fn test_extract_tar_with_parent_dir_entry_is_refused() {
+    assert_traversal_refused("traversal.tar");
+}
+
+#[test]
+fn test_extract_tar_gz_with_parent_dir_entry_is_refused() {
+    assert_traversal_refused("traversal.tar.gz");
+}
+
+#[test]
+fn test_extract_tar_with_absolute_entry_is_refused() {
+    assert_traversal_refused("absolute.tar");
+    assert!(!std::path::Path::new("/tmp/poof-absolute-evil.txt").exists());
+}
+
+#[test]
+fn test_extract_zip_with_escaping_symlink_is_refused() {
+    assert_traversal_refused("symlink_escape.zip");
+}
+
+#[test]
+fn test_extract_zip_with_absolute_symlink_is_refused() {
+    assert_traversal_refused("symlink_absolute.zip");
+}
+
+#[test]
+fn test_ensure_safe_link_target() {
+    use crate::files::archives::ensure_safe_link_target;
+    use std::path::Path;
+
+    assert!(ensure_safe_link_target(Path::new("bin/tool"), Path::new("../lib/tool")).is_ok());
+    assert!(ensure_safe_link_target(Path::new("link"), Path::new("file.txt")).is_ok());
+    assert!(ensure_safe_link_target(Path::new("link"), Path::new("../evil.txt")).is_err());
+    assert!(ensure_safe_link_target(Path::new("bin/link"), Path::new("../../evil.txt")).is_err());
+    assert!(ensure_safe_link_target(Path::new("bin/link"), Path::new("/etc/passwd")).is_err());
+}
+
+/// Sets `bits` in the Unix mode stored in the central directory entry of `name`
+/// in the zip archive at `path`.
+#[cfg(not(target_os = "windows"))]
+fn set_zip_entry_mode_bits(path: &std::path::Path, name: &str, bits: u32) {
+    const CENTRAL_HEADER_SIGNATURE: &[u8] = b"PK\x01\x02";
+    let mut bytes = std::fs::read(path).unwrap();
+    let start = (0..bytes.len() - CENTRAL_HEADER_SIGNATURE.len())
+        .filter(|&i| bytes[i..].starts_with(CENTRAL_HEADER_SIGNATURE))
+        .find(|&i| {
+            let name_len = u16::from_le_bytes([bytes[i + 28], bytes[i + 29]]) as usize;
+            &bytes[i + 46..i + 46 + name_len] == name.as_bytes()
+        })
+        .expect("entry not found in the central directory");
+    // the mode is in the upper half of the external file attributes
+    let attributes = &mut bytes[start + 38..start + 42];
+    let value = u32::from_le_bytes(attributes.try_into().unwrap()) | (bits << 16);
+    attributes.copy_from_slice(&value.to_le_bytes());
+    std::fs::write(path, bytes).unwrap();
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_extract_zip_preserves_unix_permissions() {
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+    use zip::write::SimpleFileOptions;
+
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("exec.zip");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+    writer
+        .start_file(
+            "bin/mytool",
+            SimpleFileOptions::default().unix_permissions(0o755),
+        )
+        .unwrap();
+    writer.write_all(b"#!/bin/sh\necho hello\n").unwrap();
+    writer
+        .start_file(
+            "README",
+            SimpleFileOptions::default().unix_permissions(0o644),
+        )
+        .unwrap();
+    writer.write_all(b"readme").unwrap();
+    writer.finish().unwrap();
+    // the zip writer masks the mode to 0o777, set the setuid bit of mytool by hand
+    set_zip_entry_mode_bits(&archive_path, "bin/mytool", 0o4000);
+
+    let extract_path = temp_dir.path().join("extracted");
+    extract_to_dir(&archive_path, &extract_path).unwrap();
+
+    let mode = |p: &str| {
+        std::fs::metadata(extract_path.join(p))
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o7777
+    };
+    assert_eq!(
+        mode("bin/mytool"),
+        0o755,
+        "Execute bits should be preserved, the setuid bit dropped"
+    );
+    assert_eq!(
+        mode("README"),
+        0o644,
+        "Non-executables should stay as stored"
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_drop_zip_special_mode_bits_does_not_follow_symlinks() {
+    use crate::files::archives::drop_zip_special_mode_bits;
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+    use zip::write::SimpleFileOptions;
+
+    let temp_dir = TempDir::new().unwrap();
+    let outside = temp_dir.path().join("outside");
+    std::fs::write(&outside, "outside").unwrap();
+    std::fs::set_permissions(&outside, std::fs::Permissions::from_mode(0o4755)).unwrap();
+    // a link left where the regular file entry is expected
+    let extract_path = temp_dir.path().join("extracted");
+    std::fs::create_dir(&extract_path).unwrap();
+    std::os::unix::fs::symlink(&outside, extract_path.join("mytool")).unwrap();
+
+    let archive_path = temp_dir.path().join("exec.zip");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+    writer
+        .start_file(
+            "mytool",
+            SimpleFileOptions::default().unix_permissions(0o755),
+        )
+        .unwrap();
+    writer.write_all(b"#!/bin/sh\n").unwrap();
+    writer.finish().unwrap();
+    set_zip_entry_mode_bits(&archive_path, "mytool", 0o4000);
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&archive_path).unwrap()).unwrap();
+    drop_zip_special_mode_bits(&mut archive, &extract_path).unwrap();
+
+    let mode = std::fs::metadata(&outside).unwrap().permissions().mode() & 0o7777;
+    assert_eq!(
+        mode, 0o4755,
+        "Files outside the extraction directory must be left alone"
+    );
+}
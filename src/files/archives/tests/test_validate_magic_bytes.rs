@@ -2,8 +2,8 @@
 //! Tests archive format detection and validation
 
 use crate::files::magic::{
-    BZIP2_MAGIC, GZIP_MAGIC, SEVENZ_MAGIC, TAR_MAGIC, TAR_MAGIC_OFFSET, XZ_MAGIC, ZIP_MAGIC,
-    ZSTD_MAGIC,
+    BZIP2_MAGIC, GZIP_MAGIC, LZ4_MAGIC, LZIP_MAGIC, LZMA_MAGIC, SEVENZ_MAGIC, TAR_MAGIC,
+    TAR_MAGIC_OFFSET, XZ_MAGIC, ZIP_MAGIC, ZSTD_MAGIC,
 };
 use crate::models::binary_container::BinaryContainer;
 use std::fs::File;
@@ -12,7 +12,7 @@ use std::path::PathBuf;
 use tempfile::TempDir;
 
 use super::common::*;
-use crate::files::archives::get_validated_archive_format;
+use crate::files::archives::{get_validated_archive_format, is_archive_name};
 
 // ============================================================================
 // Tests for valid archives with matching extension and magic bytes
@@ -88,6 +88,26 @@ fn test_valid_tzst_archive() {
     assert_eq!(format, BinaryContainer::TarZstd);
 }
 
+#[test]
+fn test_valid_lz4_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.lz4");
+    create_file_with_magic(&file_path, LZ4_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path).unwrap();
+    assert_eq!(format, BinaryContainer::Lz4);
+}
+
+#[test]
+fn test_valid_tar_lz4_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.tar.lz4");
+    create_file_with_magic(&file_path, LZ4_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path).unwrap();
+    assert_eq!(format, BinaryContainer::TarLz4);
+}
+
 #[test]
 fn test_valid_xz_archive() {
     let temp_dir = TempDir::new().unwrap();
@@ -118,6 +138,36 @@ fn test_valid_txz_archive() {
     assert_eq!(format, BinaryContainer::TarXz);
 }
 
+#[test]
+fn test_valid_tar_lz_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.tar.lz");
+    create_file_with_magic(&file_path, LZIP_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path).unwrap();
+    assert_eq!(format, BinaryContainer::TarLz);
+}
+
+#[test]
+fn test_valid_lzma_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.lzma");
+    create_file_with_magic(&file_path, LZMA_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path).unwrap();
+    assert_eq!(format, BinaryContainer::Lzma);
+}
+
+#[test]
+fn test_valid_tar_lzma_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.tar.lzma");
+    create_file_with_magic(&file_path, LZMA_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path).unwrap();
+    assert_eq!(format, BinaryContainer::TarLzma);
+}
+
 #[test]
 fn test_valid_bzip2_archive() {
     let temp_dir = TempDir::new().unwrap();
@@ -232,6 +282,46 @@ fn test_bz2_extension_with_7z_magic() {
     assert!(format.is_err());
 }
 
+#[test]
+fn test_tar_lz4_extension_with_zstd_magic() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("fake.tar.lz4");
+    create_file_with_magic(&file_path, ZSTD_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path);
+    assert!(format.is_err());
+}
+
+#[test]
+fn test_tar_lz_extension_with_xz_magic() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("fake.tar.lz");
+    create_file_with_magic(&file_path, XZ_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path);
+    assert!(format.is_err());
+}
+
+#[test]
+fn test_tar_lzma_extension_with_lzip_magic() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("fake.tar.lzma");
+    create_file_with_magic(&file_path, LZIP_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path);
+    assert!(format.is_err());
+}
+
+#[test]
+fn test_lzma_extension_with_gzip_magic() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("fake.lzma");
+    create_file_with_magic(&file_path, GZIP_MAGIC).unwrap();
+
+    let format = get_validated_archive_format(&file_path);
+    assert!(format.is_err());
+}
+
 // ============================================================================
 // Tests for unsupported extensions
 // ============================================================================
@@ -543,3 +633,11 @@ fn test_executable_with_archive_extension() {
     // Should detect this is not a zip and return Unknown
     assert!(format.is_err());
 }
+
+#[test]
+fn test_is_archive_name() {
+    assert!(is_archive_name("tool-linux-x86_64.tar.gz"));
+    assert!(is_archive_name("tool-windows-x86_64.ZIP"));
+    assert!(!is_archive_name("tool-linux-x86_64"));
+    assert!(!is_archive_name("tool-1.2.3-darwin-arm64"));
+}
@@ -0,0 +1,93 @@
+//! SHA-256 checksum computation and parsing of published checksum files.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Compute the SHA-256 digest of the file at `path`, as a lowercase hex string.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Returns `true` if `name` looks like a file publishing SHA-256 checksums.
+pub fn is_sha256_checksum_file(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name == "checksum.txt"
+        || name == "checksums.txt"
+        || name.ends_with(".sha256")
+        || name.ends_with(".sha256sum")
+        || name.contains("sha256sums")
+}
+
+/// Returns `true` if `s` is a hex encoded SHA-256 digest.
+fn is_sha256_hex(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parse the content of a checksum file.
+///
+/// Both the `sha256sum` output format (`<hash>  <file>`, binary mode `*<file>`
+/// included) and files holding a bare hash are supported. Returns the lowercase
+/// hashes, paired with the file name they refer to when one is given. Lines not
+/// holding a SHA-256 hash are ignored.
+pub fn parse_checksums(content: &str) -> Vec<(String, Option<String>)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let hash = parts.next()?;
+            if !is_sha256_hex(hash) {
+                return None;
+            }
+            let name = parts.next().map(|n| {
+                let n = n.trim_start_matches('*');
+                // some tools prefix the path of the file
+                n.rsplit('/').next().unwrap_or(n).to_string()
+            });
+            Some((hash.to_lowercase(), name))
+        })
+        .collect()
+}
+
+/// Find the hash published for `file_name` in the parsed `entries` of a checksum file.
+///
+/// When the checksum file is dedicated to `file_name` (e.g. `tool.tar.gz.sha256`),
+/// pass `dedicated = true` so that a single bare hash is accepted too.
+pub fn find_checksum(
+    entries: &[(String, Option<String>)],
+    file_name: &str,
+    dedicated: bool,
+) -> Option<String> {
+    entries
+        .iter()
+        .find(|(_, name)| name.as_deref() == Some(file_name))
+        .or_else(|| {
+            if dedicated && entries.len() == 1 {
+                entries.first()
+            } else {
+                None
+            }
+        })
+        .map(|(hash, _)| hash.clone())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+use tempfile::TempDir;
+
+const HELLO_SHA256: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+
+#[test]
+fn test_sha256_file() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("hello");
+    std::fs::write(&file, b"hello\n").unwrap();
+    assert_eq!(sha256_file(&file).unwrap(), HELLO_SHA256);
+}
+
+#[test]
+fn test_sha256_file_missing() {
+    let dir = TempDir::new().unwrap();
+    assert!(sha256_file(&dir.path().join("missing")).is_err());
+}
+
+#[test]
+fn test_is_sha256_checksum_file() {
+    assert!(is_sha256_checksum_file("checksums.txt"));
+    assert!(is_sha256_checksum_file("tool.tar.gz.sha256"));
+    assert!(is_sha256_checksum_file("tool_1.0_SHA256SUMS"));
+    assert!(!is_sha256_checksum_file("tool.tar.gz.sha512"));
+    assert!(!is_sha256_checksum_file("tool.tar.gz"));
+}
+
+#[test]
+fn test_parse_checksums_sha256sum_format() {
+    let content = format!(
+        "{}  tool-linux.tar.gz\n{} *dist/tool-macos.tar.gz\nnot a checksum line\n",
+        HELLO_SHA256,
+        HELLO_SHA256.to_uppercase()
+    );
+    let entries = parse_checksums(&content);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        entries[0],
+        (
+            HELLO_SHA256.to_string(),
+            Some("tool-linux.tar.gz".to_string())
+        )
+    );
+    assert_eq!(
+        entries[1],
+        (
+            HELLO_SHA256.to_string(),
+            Some("tool-macos.tar.gz".to_string())
+        )
+    );
+}
+
+#[test]
+fn test_find_checksum() {
+    let entries = parse_checksums(&format!("{}  tool.tar.gz\n", HELLO_SHA256));
+    assert_eq!(
+        find_checksum(&entries, "tool.tar.gz", false),
+        Some(HELLO_SHA256.to_string())
+    );
+    assert_eq!(find_checksum(&entries, "other.tar.gz", false), None);
+}
+
+#[test]
+fn test_find_checksum_bare_hash() {
+    let entries = parse_checksums(&format!("{}\n", HELLO_SHA256));
+    // a bare hash is only trusted when the file is dedicated to the asset
+    assert_eq!(find_checksum(&entries, "tool.tar.gz", false), None);
+    assert_eq!(
+        find_checksum(&entries, "tool.tar.gz", true),
+        Some(HELLO_SHA256.to_string())
+    );
+}
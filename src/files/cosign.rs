@@ -0,0 +1,359 @@
+//! Verification of release assets signed with cosign, from their sigstore bundle.
+//!
+//! A bundle holds the short-lived Fulcio certificate of the signer, the signature of
+//! the asset and the Rekor transparency log entry recording it. An asset is trusted
+//! when the certificate chains to a certificate authority of the trusted root at the
+//! time the entry was logged, the signature matches the asset, and the entry is
+//! promised by a log of the trusted root through its signed entry timestamp.
+//!
+//! The Merkle inclusion proof of the entry is not checked.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use log::debug;
+use rustls_pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::Duration;
+use webpki::{EndEntityCert, KeyUsage, RawPublicKeyEntity};
+
+/// Extensions of the sigstore bundle published next to a signed asset, most common first.
+const BUNDLE_EXTENSIONS: &[&str] = &[".sigstore.json", ".sigstore", ".bundle"];
+
+/// DER encoded value of the code signing extended key usage OID (1.3.6.1.5.5.7.3.3),
+/// which Fulcio certificates carry.
+const CODE_SIGNING: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03];
+
+/// Signature algorithms accepted for certificate chains.
+const CHAIN_ALGORITHMS: &[&dyn SignatureVerificationAlgorithm] = &[
+    webpki::ring::ECDSA_P256_SHA256,
+    webpki::ring::ECDSA_P256_SHA384,
+    webpki::ring::ECDSA_P384_SHA256,
+    webpki::ring::ECDSA_P384_SHA384,
+    webpki::ring::RSA_PKCS1_2048_8192_SHA256,
+    webpki::ring::ED25519,
+];
+
+/// Signature algorithms accepted for the signature of the asset, matching the keys
+/// cosign generates.
+const SIGNATURE_ALGORITHMS: &[&dyn SignatureVerificationAlgorithm] = &[
+    webpki::ring::ECDSA_P256_SHA256,
+    webpki::ring::ECDSA_P384_SHA384,
+    webpki::ring::ED25519,
+];
+
+/// Returns the names the sigstore bundle of the asset named `asset_name` may be
+/// published under, most common first.
+pub fn bundle_names(asset_name: &str) -> Vec<String> {
+    BUNDLE_EXTENSIONS
+        .iter()
+        .map(|ext| format!("{}{}", asset_name, ext))
+        .collect()
+}
+
+/// Base64 encoded bytes, as found in sigstore JSON documents.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBytes {
+    raw_bytes: String,
+}
+
+/// A sigstore `trusted_root.json` file, of which only the certificate authorities
+/// and transparency logs are used.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrustedRootFile {
+    #[serde(default)]
+    certificate_authorities: Vec<CertificateAuthorityFile>,
+    #[serde(default)]
+    tlogs: Vec<TransparencyLogFile>,
+}
+
+/// A certificate authority of a `trusted_root.json` file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CertificateAuthorityFile {
+    cert_chain: CertificateChainFile,
+}
+
+/// DER encoded certificates, the end-most first.
+#[derive(Deserialize)]
+struct CertificateChainFile {
+    certificates: Vec<RawBytes>,
+}
+
+/// A transparency log of a `trusted_root.json` file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransparencyLogFile {
+    public_key: RawBytes,
+    log_id: LogId,
+}
+
+/// Identifier of a transparency log, the SHA-256 digest of its public key.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogId {
+    key_id: String,
+}
+
+/// Certificate authorities and transparency logs trusted to vouch for signers,
+/// read from a sigstore `trusted_root.json` file.
+pub struct TrustedRoot {
+    /// Certificate chain of each authority, from the intermediates to the root.
+    chains: Vec<Vec<CertificateDer<'static>>>,
+    /// Key id and DER encoded public key of each log.
+    logs: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TrustedRoot {
+    /// Load the trusted root from the sigstore `trusted_root.json` file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read trusted root {}", path.display()))?;
+        Self::from_json(&content)
+            .with_context(|| format!("Cannot parse trusted root {}", path.display()))
+    }
+
+    /// Parse the content of a sigstore `trusted_root.json` file.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: TrustedRootFile = serde_json::from_str(json)?;
+        let chains = file
+            .certificate_authorities
+            .iter()
+            .map(|ca| {
+                ca.cert_chain
+                    .certificates
+                    .iter()
+                    .map(|cert| Ok(CertificateDer::from(decode(&cert.raw_bytes)?)))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let logs = file
+            .tlogs
+            .iter()
+            .map(|log| {
+                Ok((
+                    decode(&log.log_id.key_id)?,
+                    decode(&log.public_key.raw_bytes)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if chains.iter().all(|chain| chain.is_empty()) || logs.is_empty() {
+            bail!("The trusted root needs a certificate authority and a transparency log");
+        }
+        Ok(TrustedRoot { chains, logs })
+    }
+}
+
+/// A sigstore bundle, of which only what a message signature needs is used.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bundle {
+    verification_material: VerificationMaterial,
+    message_signature: MessageSignature,
+}
+
+/// What a bundle provides to verify its signature with.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationMaterial {
+    /// The signing certificate, in v0.3 bundles.
+    certificate: Option<RawBytes>,
+    /// The signing certificate first, in older bundles.
+    x509_certificate_chain: Option<CertificateChainFile>,
+    #[serde(default)]
+    tlog_entries: Vec<TlogEntry>,
+}
+
+/// The transparency log entry recording a signature.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TlogEntry {
+    log_index: String,
+    log_id: LogId,
+    integrated_time: String,
+    inclusion_promise: Option<InclusionPromise>,
+    canonicalized_body: String,
+}
+
+/// The promise of a transparency log to include an entry.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InclusionPromise {
+    signed_entry_timestamp: String,
+}
+
+/// The signature of an artifact, with the digest it was computed over.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageSignature {
+    message_digest: Option<MessageDigest>,
+    signature: String,
+}
+
+/// Base64 encoded digest of a signed artifact.
+#[derive(Deserialize)]
+struct MessageDigest {
+    digest: String,
+}
+
+/// Verify `artifact` against the sigstore `bundle` JSON, trusting the authorities
+/// and logs of `root`. Returns an error naming the first check that failed.
+pub fn verify_bundle(bundle: &str, artifact: &[u8], root: &TrustedRoot) -> Result<()> {
+    let bundle: Bundle = serde_json::from_str(bundle).context("Cannot parse sigstore bundle")?;
+    let material = &bundle.verification_material;
+    let certificate = match (&material.certificate, &material.x509_certificate_chain) {
+        (Some(certificate), _) => &certificate.raw_bytes,
+        (None, Some(chain)) => {
+            &chain
+                .certificates
+                .first()
+                .ok_or_else(|| anyhow!("The bundle has an empty certificate chain"))?
+                .raw_bytes
+        }
+        (None, None) => bail!("The bundle has no signing certificate"),
+    };
+    let certificate = CertificateDer::from(decode(certificate)?);
+    let signature = decode(&bundle.message_signature.signature)?;
+    let entry = material
+        .tlog_entries
+        .first()
+        .ok_or_else(|| anyhow!("The bundle has no transparency log entry"))?;
+
+    let digest = Sha256::digest(artifact);
+    if let Some(message_digest) = &bundle.message_signature.message_digest {
+        if decode(&message_digest.digest)? != digest.as_slice() {
+            bail!("The artifact does not match the digest in the bundle");
+        }
+    }
+
+    let integrated_time: u64 = entry
+        .integrated_time
+        .parse()
+        .context("Invalid integrated time in the bundle")?;
+    verify_log_entry(entry, integrated_time, root)?;
+    verify_logged_signature(entry, &digest, &signature)?;
+
+    let leaf = EndEntityCert::try_from(&certificate).context("Invalid signing certificate")?;
+    verify_chain(&leaf, root, integrated_time)?;
+    SIGNATURE_ALGORITHMS
+        .iter()
+        .find(|alg| leaf.verify_signature(**alg, artifact, &signature).is_ok())
+        .ok_or_else(|| anyhow!("The signature does not match the artifact"))?;
+    Ok(())
+}
+
+/// Check that the signing certificate was valid and chained to an authority of `root`
+/// at `integrated_time`, the time its signature was logged.
+fn verify_chain(leaf: &EndEntityCert, root: &TrustedRoot, integrated_time: u64) -> Result<()> {
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(integrated_time));
+    let mut last_error = None;
+    for chain in &root.chains {
+        let Some((anchor, intermediates)) = chain.split_last() else {
+            continue;
+        };
+        let anchor = webpki::anchor_from_trusted_cert(anchor)
+            .map_err(|e| anyhow!("Invalid certificate authority in the trusted root: {}", e))?;
+        match leaf.verify_for_usage(
+            CHAIN_ALGORITHMS,
+            &[anchor],
+            intermediates,
+            time,
+            KeyUsage::required(CODE_SIGNING),
+            None,
+            None,
+        ) {
+            Ok(_) => return Ok(()),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    match last_error {
+        Some(e) => bail!("The signing certificate is not trusted: {}", e),
+        None => bail!("The trusted root has no certificate authority"),
+    }
+}
+
+/// Check the signed entry timestamp of `entry`, logged at `integrated_time`, the
+/// promise of a log of `root` to include the entry.
+fn verify_log_entry(entry: &TlogEntry, integrated_time: u64, root: &TrustedRoot) -> Result<()> {
+    let key_id = decode(&entry.log_id.key_id)?;
+    let (_, public_key) = root
+        .logs
+        .iter()
+        .find(|(id, _)| *id == key_id)
+        .ok_or_else(|| {
+            anyhow!("The bundle was logged by a transparency log that is not trusted")
+        })?;
+    let promise = entry
+        .inclusion_promise
+        .as_ref()
+        .ok_or_else(|| anyhow!("The bundle has no signed entry timestamp"))?;
+    let log_index: u64 = entry
+        .log_index
+        .parse()
+        .context("Invalid log index in the bundle")?;
+    // canonical JSON, with the keys sorted, as signed by Rekor
+    let payload = format!(
+        r#"{{"body":{},"integratedTime":{},"logID":{},"logIndex":{}}}"#,
+        serde_json::to_string(&entry.canonicalized_body)?,
+        integrated_time,
+        serde_json::to_string(&hex(&key_id))?,
+        log_index
+    );
+    let public_key = rustls_pki_types::SubjectPublicKeyInfoDer::from(public_key.as_slice());
+    let log = RawPublicKeyEntity::try_from(&public_key)
+        .map_err(|e| anyhow!("Invalid transparency log key in the trusted root: {}", e))?;
+    log.verify_signature(
+        webpki::ring::ECDSA_P256_SHA256,
+        payload.as_bytes(),
+        &decode(&promise.signed_entry_timestamp)?,
+    )
+    .map_err(|_| anyhow!("The signed entry timestamp of the bundle is not valid"))?;
+    debug!("Transparency log entry {} verified", log_index);
+    Ok(())
+}
+
+/// Check that the log entry records `signature` over the artifact hashed to `digest`.
+fn verify_logged_signature(entry: &TlogEntry, digest: &[u8], signature: &[u8]) -> Result<()> {
+    let body = decode(&entry.canonicalized_body)?;
+    // a hashedrekord entry, see https://github.com/sigstore/rekor/tree/main/pkg/types/hashedrekord
+    let rekord: serde_json::Value =
+        serde_json::from_slice(&body).context("Cannot parse the transparency log entry")?;
+    if rekord["kind"] != "hashedrekord" {
+        bail!(
+            "Unsupported transparency log entry kind: {}",
+            rekord["kind"]
+        );
+    }
+    let logged_digest = rekord["spec"]["data"]["hash"]["value"]
+        .as_str()
+        .unwrap_or_default();
+    if !logged_digest.eq_ignore_ascii_case(&hex(digest)) {
+        bail!("The transparency log entry is for another artifact");
+    }
+    let logged_signature = rekord["spec"]["signature"]["content"]
+        .as_str()
+        .unwrap_or_default();
+    if decode(logged_signature)? != signature {
+        bail!("The transparency log entry is for another signature");
+    }
+    Ok(())
+}
+
+/// Decode the standard base64 string `s`.
+fn decode(s: &str) -> Result<Vec<u8>> {
+    BASE64
+        .decode(s.trim())
+        .context("Invalid base64 data in sigstore document")
+}
+
+/// Encode `bytes` as a lowercase hex string.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests;
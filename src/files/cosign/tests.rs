@@ -0,0 +1,91 @@
+use super::*;
+
+const FIXTURES: &str = "tests/fixtures/cosign";
+
+fn fixture(name: &str) -> String {
+    std::fs::read_to_string(Path::new(FIXTURES).join(name)).expect("Cannot read fixture file")
+}
+
+fn trusted_root() -> TrustedRoot {
+    TrustedRoot::load(&Path::new(FIXTURES).join("trusted_root.json")).unwrap()
+}
+
+#[test]
+fn test_bundle_names() {
+    assert_eq!(
+        bundle_names("tool.tar.gz"),
+        vec![
+            "tool.tar.gz.sigstore.json",
+            "tool.tar.gz.sigstore",
+            "tool.tar.gz.bundle"
+        ]
+    );
+}
+
+#[test]
+fn test_verify_bundle_valid_signature() {
+    let bundle = fixture("artifact.txt.sigstore.json");
+    let artifact = fixture("artifact.txt");
+    verify_bundle(&bundle, artifact.as_bytes(), &trusted_root()).unwrap();
+}
+
+#[test]
+fn test_verify_bundle_tampered_artifact() {
+    let bundle = fixture("artifact.txt.sigstore.json");
+    let artifact = fixture("artifact.txt").replace("poof", "evil");
+    let err = verify_bundle(&bundle, artifact.as_bytes(), &trusted_root()).unwrap_err();
+    assert!(
+        err.to_string().contains("does not match the digest"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_verify_bundle_tampered_signature() {
+    let mut bundle: serde_json::Value =
+        serde_json::from_str(&fixture("artifact.txt.sigstore.json")).unwrap();
+    let signature = &mut bundle["messageSignature"]["signature"];
+    let mut bytes = decode(signature.as_str().unwrap()).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    *signature = BASE64.encode(bytes).into();
+    let artifact = fixture("artifact.txt");
+    assert!(verify_bundle(&bundle.to_string(), artifact.as_bytes(), &trusted_root()).is_err());
+}
+
+#[test]
+fn test_verify_bundle_untrusted_certificate() {
+    // without the intermediate, the signing certificate does not chain to the root
+    let mut root: serde_json::Value = serde_json::from_str(&fixture("trusted_root.json")).unwrap();
+    root["certificateAuthorities"][0]["certChain"]["certificates"]
+        .as_array_mut()
+        .unwrap()
+        .remove(0);
+    let root = TrustedRoot::from_json(&root.to_string()).unwrap();
+    let bundle = fixture("artifact.txt.sigstore.json");
+    let artifact = fixture("artifact.txt");
+    let err = verify_bundle(&bundle, artifact.as_bytes(), &root).unwrap_err();
+    assert!(err.to_string().contains("not trusted"), "{}", err);
+}
+
+#[test]
+fn test_verify_bundle_tampered_log_entry() {
+    let mut bundle: serde_json::Value =
+        serde_json::from_str(&fixture("artifact.txt.sigstore.json")).unwrap();
+    bundle["verificationMaterial"]["tlogEntries"][0]["integratedTime"] = "1704067800".into();
+    let artifact = fixture("artifact.txt");
+    let err = verify_bundle(&bundle.to_string(), artifact.as_bytes(), &trusted_root()).unwrap_err();
+    assert!(
+        err.to_string().contains("signed entry timestamp"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_trusted_root_needs_a_log() {
+    let mut root: serde_json::Value = serde_json::from_str(&fixture("trusted_root.json")).unwrap();
+    root["tlogs"] = serde_json::json!([]);
+    assert!(TrustedRoot::from_json(&root.to_string()).is_err());
+}
@@ -1,6 +1,9 @@
+use anyhow::{bail, Result};
 use std::path::{Path, PathBuf};
 
 use crate::constants::*;
+use crate::core::config;
+use crate::models::source::Source;
 
 /// This function returns the path to the config directory for the application.
 /// It creates the directory if it doesn't exist.
@@ -19,8 +22,51 @@ pub fn _get_config_dir() -> Option<PathBuf> {
     Some(config_dir)
 }
 
+/// This function returns the path to the user configuration file.
+/// The file itself may not exist.
+///
+/// Linux: ~/.config/APPNAME/config.toml
+///
+/// macOS: ~/Library/Application Support/APPNAME/config.toml
+///
+/// Windows: %APPDATA%/APPNAME/config.toml
+///
+pub fn get_config_file() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join(APP_NAME).join(CONFIG_FILE))
+}
+
+/// Returns the path to the sigstore trusted root used to verify cosign signatures,
+/// set in the config file or next to it otherwise.
+#[cfg(feature = "cosign")]
+pub fn get_cosign_trusted_root_file() -> Option<PathBuf> {
+    match &config::get().cosign_trusted_root {
+        Some(path) => Some(PathBuf::from(
+            shellexpand::tilde(&path.to_string_lossy()).as_ref(),
+        )),
+        None => Some(
+            dirs::config_dir()?
+                .join(APP_NAME)
+                .join(COSIGN_TRUSTED_ROOT_FILE),
+        ),
+    }
+}
+
+/// Returns the directory set in the `env` environment variable, with a leading `~` expanded.
+/// Unset and empty variables yield `None`.
+fn dir_from_env(env: &str) -> Option<PathBuf> {
+    let value = std::env::var(env).ok().filter(|v| !v.is_empty())?;
+    Some(PathBuf::from(shellexpand::tilde(&value).as_ref()))
+}
+
+/// Returns the root of the application's data: the `POOF_DATA_DIR` environment
+/// variable when set, the platform data directory otherwise.
+fn get_app_data_root() -> Option<PathBuf> {
+    dir_from_env(DATA_DIR_ENV).or_else(|| Some(dirs::data_dir()?.join(APP_NAME)))
+}
+
 /// This function returns the path to the data directory for the application.
-/// It creates the directory if it doesn't exist.
+/// It creates the directory if it doesn't exist. Installed repositories are kept
+/// in a sub-directory per host, see [`get_versions_nest`].
 ///
 /// Linux: $HOME/.local/share/APPNAME/data
 ///
@@ -28,18 +74,22 @@ pub fn _get_config_dir() -> Option<PathBuf> {
 ///
 /// Windows: %LOCALAPPDATA%/APPNAME/data
 ///
+/// The `POOF_DATA_DIR` environment variable replaces the platform default
+/// `.../APPNAME` part, e.g. `POOF_DATA_DIR=~/poof` yields `~/poof/data`.
 pub fn get_data_dir() -> Option<PathBuf> {
-    //TODO: remove .join(GITHUB_SUBDIR) when poof will be updated to support different services apart from GitHub.
-    let data_dir = dirs::data_dir()?
-        .join(APP_NAME)
-        .join(DATA_SUBDIR)
-        .join(GITHUB_SUBDIR);
+    let data_dir = get_app_data_root()?.join(DATA_SUBDIR);
     if !data_dir.exists() {
         std::fs::create_dir_all(&data_dir).ok()?;
     }
     Some(data_dir)
 }
 
+/// Returns the path to the history log, at the top of the data directory.
+/// It is left out of 'clean', which only empties the cache directory.
+pub fn get_history_file() -> Option<PathBuf> {
+    Some(get_data_dir()?.join(HISTORY_FILE))
+}
+
 /// This function returns the path to the bin directory for the application.
 /// It creates the directory if it doesn't exist.
 /// This is where the binaries will be stored.
@@ -50,14 +100,54 @@ pub fn get_data_dir() -> Option<PathBuf> {
 ///
 /// Windows: %LOCALAPPDATA%/APPNAME/bin
 ///
+/// The `POOF_BIN_DIR` environment variable or, when unset, the `bin_dir` key of
+/// the config file replace the platform default. Their writability is checked
+/// once at startup by [`validate_bin_dir`]. Without them, the bin directory sits
+/// next to the data directory, under `POOF_DATA_DIR` if set.
 pub fn get_bin_dir() -> Option<PathBuf> {
-    let bin_dir = dirs::data_dir()?.join(APP_NAME).join(BIN_SUBDIR);
+    let bin_dir = match bin_dir_override() {
+        Some((bin_dir, _)) => bin_dir,
+        None => get_app_data_root()?.join(BIN_SUBDIR),
+    };
     if !bin_dir.exists() {
         std::fs::create_dir_all(&bin_dir).ok()?;
     }
     Some(bin_dir)
 }
 
+/// Returns the bin directory set via `POOF_BIN_DIR` or the config file, if any,
+/// along with where it was set.
+fn bin_dir_override() -> Option<(PathBuf, &'static str)> {
+    dir_from_env(BIN_DIR_ENV)
+        .map(|d| (d, BIN_DIR_ENV))
+        .or_else(|| config::get().bin_dir.clone().map(|d| (d, "config file")))
+}
+
+/// Checks that the bin directory set via `POOF_BIN_DIR` or the config file, if any,
+/// can be created and written to, so that a misconfiguration is reported before any
+/// command runs.
+pub fn validate_bin_dir() -> Result<()> {
+    if let Some((bin_dir, source)) = bin_dir_override() {
+        if let Err(e) = ensure_writable_dir(&bin_dir) {
+            bail!(
+                "Cannot use {} set via {} as bin directory: {}",
+                bin_dir.display(),
+                source,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Creates `dir` if missing and checks that files can be created in it.
+pub fn ensure_writable_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(format!(".{}-write-test-{}", APP_NAME, std::process::id()));
+    std::fs::File::create(&probe)?;
+    std::fs::remove_file(&probe)
+}
+
 /// This function returns the path to the cache directory for the application.
 /// It creates the directory if it doesn't exist.
 /// This is where the cache files will be stored.
@@ -68,28 +158,61 @@ pub fn get_bin_dir() -> Option<PathBuf> {
 ///
 /// Windows: %LOCALAPPDATA%/APPNAME/cache
 ///
+/// The `POOF_CACHE_DIR` environment variable replaces the platform default.
 pub fn get_cache_dir() -> Option<PathBuf> {
-    let cache_dir = dirs::cache_dir()?.join(APP_NAME);
+    let cache_dir =
+        dir_from_env(CACHE_DIR_ENV).or_else(|| Some(dirs::cache_dir()?.join(APP_NAME)))?;
     if !cache_dir.exists() {
         std::fs::create_dir_all(&cache_dir).ok()?;
     }
     Some(cache_dir)
 }
 
-/// Returns `base/username/reponame` for `repo` in `"username/reponame"` format.
+/// This function returns the path to the directory holding cached GitHub API responses.
+/// It creates the directory if it doesn't exist.
+/// It lives inside the cache directory, but it is kept by the 'clean' command.
+pub fn get_api_cache_dir() -> Option<PathBuf> {
+    let api_cache_dir = get_cache_dir()?.join(API_CACHE_SUBDIR);
+    if !api_cache_dir.exists() {
+        std::fs::create_dir_all(&api_cache_dir).ok()?;
+    }
+    Some(api_cache_dir)
+}
+
+/// Returns `base/host/username/reponame` for `repo` in `"username/reponame"` format,
+/// optionally prefixed by its source (e.g. `gitlab:username/reponame`). `host` is
+/// the [`Source::data_subdir`] of the source, so that repositories with the same
+/// name on different hosts never share a directory.
 pub fn get_versions_nest(base: &Path, repo: &str) -> PathBuf {
+    let (source, repo) = Source::split(repo);
     // Convert repo path to filesystem-friendly format by replacing '/' with OS separator
-    // Creating path as: base_dir/username/reponame
+    // Creating path as: base_dir/host/username/reponame
     let repo_path = repo.replace('/', std::path::MAIN_SEPARATOR_STR);
-    base.join(&repo_path)
+    base.join(source.data_subdir()).join(&repo_path)
 }
 
-/// Returns `base/username/reponame/version` for the given repository and version string.
+/// Returns `base/host/username/reponame/version` for the given repository and version string.
 pub fn get_binary_nest(base: &Path, repo: &str, version: &str) -> PathBuf {
-    // Creating path as: base_dir/username/reponame/version
+    // Creating path as: base_dir/host/username/reponame/version
     get_versions_nest(base, repo).join(version)
 }
 
+/// Splits `path`, pointing inside a repository directory of `base`, into the name of
+/// the repository, prefixed by its source like on the command line, and the path
+/// components below the repository directory, the version first.
+///
+/// This is the inverse of [`get_versions_nest`].
+pub fn split_repo_path(base: &Path, path: &Path) -> Option<(String, Vec<String>)> {
+    let mut parts = path
+        .strip_prefix(base)
+        .ok()?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string());
+    let (host, user, repo) = (parts.next()?, parts.next()?, parts.next()?);
+    let repo = Source::from_data_subdir(&host).qualify(&format!("{}/{}", user, repo));
+    Some((repo, parts.collect()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,20 +229,58 @@ mod tests {
         assert!(result.to_str().unwrap().contains("repo"));
 
         #[cfg(unix)]
-        assert_eq!(result, Path::new("/test/base/owner/repo"));
+        assert_eq!(result, Path::new("/test/base/github.com/owner/repo"));
 
         #[cfg(windows)]
-        assert_eq!(result, Path::new("\\test\\base\\owner\\repo"));
+        assert_eq!(result, Path::new("\\test\\base\\github.com\\owner\\repo"));
+    }
+
+    #[test]
+    fn test_get_versions_nest_per_host() {
+        let base = Path::new("/test/base");
+        assert_eq!(
+            get_versions_nest(base, "gitlab:owner/repo"),
+            base.join("gitlab.com").join("owner").join("repo")
+        );
+        assert_eq!(
+            get_versions_nest(base, "gitea:codeberg.org/owner/repo"),
+            base.join("codeberg.org").join("owner").join("repo")
+        );
+        assert_ne!(
+            get_versions_nest(base, "owner/repo"),
+            get_versions_nest(base, "gitlab:owner/repo")
+        );
+    }
+
+    #[test]
+    fn test_split_repo_path() {
+        let base = Path::new("/test/base");
+        for repo in [
+            "owner/repo",
+            "gitlab:owner/repo",
+            "gitea:codeberg.org/owner/repo",
+        ] {
+            let path = get_binary_nest(base, repo, "1.0.0").join("tool");
+            assert_eq!(
+                split_repo_path(base, &path),
+                Some((
+                    repo.to_string(),
+                    vec!["1.0.0".to_string(), "tool".to_string()]
+                ))
+            );
+        }
+        assert_eq!(split_repo_path(base, Path::new("/elsewhere/a/b/c")), None);
+        assert_eq!(split_repo_path(base, &base.join("github.com/owner")), None);
     }
 
     #[test]
     fn test_get_binary_nest_full_structure() {
-        let base = Path::new("/home/user/.local/share/poof/data/github.com");
+        let base = Path::new("/home/user/.local/share/poof/data");
         let repo = "some-tools/some-cli";
         let version = "1.2.3-beta.1";
         let result = get_binary_nest(base, repo, version);
 
-        // The path should follow: base/owner/repo/version
+        // The path should follow: base/host/owner/repo/version
         #[cfg(unix)]
         assert_eq!(
             result,
@@ -158,28 +319,19 @@ mod tests {
         // If it returns Some, it should contain the APP_NAME
         if let Some(dir) = data_dir {
             let path_str = dir.to_str().unwrap();
-            // TODO: remove GITHUB_SUBDIR when poof will be updated to support different services apart from GitHub.
             #[cfg(target_os = "linux")]
-            assert!(
-                path_str.ends_with(&format!(".local/share/{}/data/{}", APP_NAME, GITHUB_SUBDIR))
-            );
+            assert!(path_str.ends_with(&format!(".local/share/{}/data", APP_NAME)));
             #[cfg(target_os = "macos")]
-            assert!(path_str.ends_with(&format!(
-                "Library/Application Support/{}/data/{}",
-                APP_NAME, GITHUB_SUBDIR
-            )));
+            assert!(path_str.ends_with(&format!("Library/Application Support/{}/data", APP_NAME)));
             #[cfg(target_os = "windows")]
-            assert!(path_str.ends_with(&format!(
-                "AppData\\Local\\{}\\data\\{}",
-                APP_NAME, GITHUB_SUBDIR
-            )));
+            assert!(path_str.ends_with(&format!("AppData\\Local\\{}\\data", APP_NAME)));
         }
     }
 
     #[test]
     fn test_get_bin_dir_returns_some() {
         // Test that bin dir returns a value (if dirs::data_dir() works)
-        let bin_dir = get_bin_dir();
+        let bin_dir = temp_env::with_var_unset(BIN_DIR_ENV, get_bin_dir);
 
         // If it returns Some, it should contain the APP_NAME
         if let Some(dir) = bin_dir {
@@ -193,6 +345,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_bin_dir_env_override() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let custom = temp_dir.path().join("custom").join("bin");
+        let bin_dir = temp_env::with_var(BIN_DIR_ENV, Some(&custom), get_bin_dir);
+        assert_eq!(bin_dir, Some(custom.clone()));
+        assert!(custom.is_dir(), "Override directory should be created");
+    }
+
+    #[test]
+    fn test_get_bin_dir_env_override_not_writable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        // a directory cannot be created below a regular file
+        let file = temp_dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        let (validated, bin_dir) = temp_env::with_var(BIN_DIR_ENV, Some(file.join("bin")), || {
+            (validate_bin_dir(), get_bin_dir())
+        });
+        let err = validated.unwrap_err().to_string();
+        assert!(
+            err.contains(BIN_DIR_ENV),
+            "Error should name the source: {}",
+            err
+        );
+        assert!(bin_dir.is_none());
+    }
+
+    #[test]
+    fn test_validate_bin_dir_without_override() {
+        let result = temp_env::with_var_unset(BIN_DIR_ENV, validate_bin_dir);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_bin_dir_env_override_expands_tilde() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let bin_dir = temp_env::with_vars(
+            [
+                ("HOME", Some(temp_dir.path().as_os_str())),
+                (BIN_DIR_ENV, Some("~/bin".as_ref())),
+            ],
+            get_bin_dir,
+        );
+        assert_eq!(bin_dir, Some(temp_dir.path().join("bin")));
+    }
+
+    #[test]
+    fn test_data_and_cache_dir_env_overrides() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("poof");
+        let cache = temp_dir.path().join("cache");
+        let (data_dir, bin_dir, cache_dir) = temp_env::with_vars(
+            [
+                (DATA_DIR_ENV, Some(root.as_os_str())),
+                (CACHE_DIR_ENV, Some(cache.as_os_str())),
+                (BIN_DIR_ENV, None),
+            ],
+            || (get_data_dir(), get_bin_dir(), get_cache_dir()),
+        );
+        assert_eq!(data_dir, Some(root.join(DATA_SUBDIR)));
+        assert_eq!(bin_dir, Some(root.join(BIN_SUBDIR)));
+        assert_eq!(cache_dir, Some(cache.clone()));
+        assert!(cache.is_dir(), "Override directory should be created");
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_data_dir_env_override_expands_tilde() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let data_dir = temp_env::with_vars(
+            [
+                ("HOME", Some(temp_dir.path().as_os_str())),
+                (DATA_DIR_ENV, Some("~/poof".as_ref())),
+            ],
+            get_data_dir,
+        );
+        assert_eq!(
+            data_dir,
+            Some(temp_dir.path().join("poof").join(DATA_SUBDIR))
+        );
+    }
+
     #[test]
     fn test_get_cache_dir_returns_some() {
         // Test that cache dir returns a value (if dirs::cache_dir() works)
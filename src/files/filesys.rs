@@ -2,22 +2,32 @@
 
 use log::{debug, warn};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
 use crate::files::magic::{is_exec_by_magic_number, is_exec_for_current_arch};
+use crate::files::metadata;
+
+/// How many directory levels below the searched one [`find_exec_files_in_dir`] descends.
+const MAX_EXEC_SEARCH_DEPTH: usize = 5;
 
 /// Return all executable files found inside `dir` (recursively).
 ///
 /// A file is considered executable when inner checks on the file header
-/// return `true` for it. Directories and symlinks are ignored.
+/// return `true` for it. Directories and symlinks are ignored, and so are
+/// shared libraries, which share their magic number with executables.
+/// Directories nested more than [`MAX_EXEC_SEARCH_DEPTH`] levels below `dir`
+/// are not searched, archives nest their binaries a few levels at most
+/// (e.g. `tool-1.2.3/bin/tool`).
 ///
 /// If `deep` is `true`, the function will check if the file is an executable
 /// by checking the magic number AND the architecture.
 /// If `deep` is `false`, it will only check the magic number.
 pub fn find_exec_files_in_dir(dir: &Path, deep: bool) -> Vec<PathBuf> {
     let mut result: Vec<PathBuf> = Vec::new();
-    let mut stack: Vec<PathBuf> = vec![dir.to_path_buf()];
+    let mut stack: Vec<(PathBuf, usize)> = vec![(dir.to_path_buf(), 0)];
 
-    while let Some(dir) = stack.pop() {
+    while let Some((dir, depth)) = stack.pop() {
         // move to next iteration if the directory does not exist or is not a directory
         if !dir.exists() || !dir.is_dir() {
             continue;
@@ -40,8 +50,13 @@ pub fn find_exec_files_in_dir(dir: &Path, deep: bool) -> Vec<PathBuf> {
                 // 1. Check if the file is a regular file
                 // 2. Check if the file is an executable by checking the magic number
                 if file_type.is_dir() {
-                    stack.push(entry.path());
+                    if depth < MAX_EXEC_SEARCH_DEPTH {
+                        stack.push((entry.path(), depth + 1));
+                    } else {
+                        debug!("Not searching {}, too deep", entry.path().display());
+                    }
                 } else if file_type.is_file()
+                    && !is_shared_library_name(&entry.file_name().to_string_lossy())
                     && ((deep && is_exec_for_current_arch(&entry.path()).unwrap_or(false))
                         || (!deep && is_exec_by_magic_number(&entry.path())))
                 {
@@ -56,6 +71,18 @@ pub fn find_exec_files_in_dir(dir: &Path, deep: bool) -> Vec<PathBuf> {
     result
 }
 
+/// Returns `true` if `file_name` is the name of a shared library,
+/// e.g. `libfoo.so`, `libfoo.so.1.2`, `libfoo.dylib` or `foo.dll`.
+fn is_shared_library_name(file_name: &str) -> bool {
+    let name = file_name.to_lowercase();
+    name.ends_with(".dylib")
+        || name.ends_with(".dll")
+        || name.ends_with(".so")
+        || name
+            .rsplit_once(".so.")
+            .is_some_and(|(_, suffix)| suffix.split('.').all(|n| n.parse::<u32>().is_ok()))
+}
+
 /// Return `true` when `path` is a regular file with at least one executable bit set (Unix only).
 #[cfg(not(target_os = "windows"))]
 pub fn is_executable(path: &PathBuf) -> bool {
@@ -70,6 +97,16 @@ pub fn is_executable(path: &PathBuf) -> bool {
     false
 }
 
+/// Return `true` when `path` is a regular file with an `.exe` extension (Windows variant).
+#[cfg(target_os = "windows")]
+pub fn is_executable(path: &PathBuf) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
 /// Add executable permission bits to `file` (equivalent to `chmod +x`) (Unix only).
 ///
 /// Has no effect if `file` is not a regular file.
@@ -89,6 +126,37 @@ pub fn make_executable(file: &Path) {
     debug!("Set executable permissions for {}", file.display());
 }
 
+/// Windows does not require setting executable permissions, nothing to do (Windows variant).
+#[cfg(target_os = "windows")]
+pub fn make_executable(file: &Path) {
+    debug!("No permissions to set on {}", file.display());
+}
+
+/// Return the last modification time of the directory `path` itself.
+pub fn dir_modified_time(path: &Path) -> std::io::Result<SystemTime> {
+    std::fs::metadata(path)?.modified()
+}
+
+/// Return the total size in bytes of the files inside `path` (recursively).
+///
+/// Symlinks are not followed, their own size is counted instead.
+pub fn dir_total_size(path: &Path) -> std::io::Result<u64> {
+    let mut total: u64 = 0;
+    let mut stack: Vec<PathBuf> = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let metadata = entry.path().symlink_metadata()?;
+            if metadata.is_dir() {
+                stack.push(entry.path());
+            } else {
+                total += metadata.len();
+            }
+        }
+    }
+    Ok(total)
+}
+
 /// Copy `source` to `target`, returning a descriptive error string on failure.
 pub fn copy_file(source: &PathBuf, target: &PathBuf) -> Result<(), String> {
     debug!(
@@ -109,17 +177,43 @@ pub fn copy_file(source: &PathBuf, target: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
-/// Create a Unix symlink at `target` pointing to `source` (Unix only).
+/// Create the link at `target` pointing to `source` (Unix variant).
+#[cfg(not(target_os = "windows"))]
+fn make_link(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+/// Create the link at `target` pointing to `source` (Windows variant).
 ///
+/// Creating symlinks on Windows requires either administrator privileges or
+/// Developer Mode, so `source` is copied to `target` when that fails.
+#[cfg(target_os = "windows")]
+fn make_link(source: &Path, target: &Path) -> std::io::Result<()> {
+    match std::os::windows::fs::symlink_file(source, target) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            debug!(
+                "Cannot create symlink {} ({}), copying {} instead",
+                target.display(),
+                e,
+                source.display()
+            );
+            std::fs::copy(source, target).map(|_| ())
+        }
+    }
+}
+
+/// Serialises symlink creation, so that concurrent installs do not race
+/// on the same bin directory entry.
+static LINK_LOCK: Mutex<()> = Mutex::new(());
+
+/// Create a symlink at `target` pointing to `source`.
+///
+/// On Windows the file is copied instead when symlinks cannot be created.
 /// When `remove_existing` is `true` any file already at `target` is deleted
 /// before the symlink is created. When it is `false` and `target` already
 /// exists the operation is skipped with a warning.
-#[cfg(not(target_os = "windows"))]
-pub fn create_symlink(
-    source: &PathBuf,
-    target: &PathBuf,
-    remove_existing: bool,
-) -> Result<(), String> {
+pub fn create_symlink(source: &Path, target: &Path, remove_existing: bool) -> Result<(), String> {
     let msg = if remove_existing { "" } else { " NOT" };
     debug!(
         "Creating symlink {} -> {},{} removing existing",
@@ -127,6 +221,8 @@ pub fn create_symlink(
         target.display(),
         msg
     );
+    // a panic while holding the lock leaves nothing to clean up
+    let _guard = LINK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
     if target.exists() {
         if remove_existing {
             if let Err(e) = std::fs::remove_file(target) {
@@ -141,7 +237,7 @@ pub fn create_symlink(
     }
 
     // Create a symlink in the target directory pointing to the installed binary.
-    match std::os::unix::fs::symlink(source, target) {
+    match make_link(source, target) {
         Ok(_) => {
             debug!(
                 "Symlink created: {} -> {}",
@@ -162,6 +258,83 @@ pub fn create_symlink(
     Ok(())
 }
 
+/// Returns the directories directly inside `dir`, hidden ones excluded.
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .filter(|p| {
+                    p.file_name()
+                        .is_some_and(|n| !n.to_string_lossy().starts_with('.'))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns `name` without a trailing `.exe`, so that Windows binaries match their bare name.
+fn exec_stem(name: &str) -> &str {
+    let len = name.len();
+    if len > 4 && name.is_char_boundary(len - 4) && name[len - 4..].eq_ignore_ascii_case(".exe") {
+        &name[..len - 4]
+    } else {
+        name
+    }
+}
+
+/// Returns the sorted slugs of the repositories installed in `data_dir` that
+/// provide a binary named `binary_name` in any of their versions, either by
+/// file name or by the custom name it was installed with.
+pub fn find_repos_providing_binary(binary_name: &str, data_dir: &Path) -> Vec<String> {
+    let wanted = exec_stem(binary_name);
+    let mut repos: Vec<String> = Vec::new();
+    // binaries are installed in data_dir/username/reponame/version
+    for user in subdirs(data_dir) {
+        for repo in subdirs(&user) {
+            let provides = subdirs(&repo).iter().any(|version| {
+                metadata::list_binaries(version).iter().any(|exec_name| {
+                    exec_stem(exec_name) == wanted
+                        || exec_stem(&metadata::link_name_for(version, exec_name)) == wanted
+                })
+            });
+            if let (true, Some(user), Some(repo)) = (provides, user.file_name(), repo.file_name()) {
+                repos.push(format!(
+                    "{}/{}",
+                    user.to_string_lossy(),
+                    repo.to_string_lossy()
+                ));
+            }
+        }
+    }
+    repos.sort();
+    repos
+}
+
+/// Return `true` when `path` is a copy of a binary installed in `data_dir`,
+/// as made in place of a symlink when symlinks cannot be created (Windows variant).
+#[cfg(target_os = "windows")]
+pub fn is_managed_copy(path: &Path, data_dir: &Path) -> bool {
+    let (Some(file_name), Ok(content)) = (path.file_name(), std::fs::read(path)) else {
+        return false;
+    };
+    // binaries are installed in data_dir/username/reponame/version
+    subdirs(data_dir)
+        .into_iter()
+        .flat_map(|user| subdirs(&user))
+        .flat_map(|repo| subdirs(&repo))
+        .map(|version| version.join(file_name))
+        .any(|installed| std::fs::read(installed).is_ok_and(|c| c == content))
+}
+
+/// Copies are only made on Windows, where symlinks may not be available (Unix variant).
+#[cfg(not(target_os = "windows"))]
+pub fn is_managed_copy(_path: &Path, _data_dir: &Path) -> bool {
+    false
+}
+
 /// Check if a symlink is broken.
 /// Returns true if the symlink is broken, false otherwise.
 pub fn is_broken_symlink(path: &Path) -> std::io::Result<bool> {
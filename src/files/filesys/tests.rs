@@ -172,3 +172,147 @@ fn test_find_exec_files_in_dir_finds_shebang_file() {
     assert!(found.contains(&script));
     assert!(!found.contains(&dir.path().join("data.txt")));
 }
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_find_exec_files_in_dir_finds_nested_file() {
+    let dir = TempDir::new().unwrap();
+    let nested = dir.path().join("tool-1.2.3").join("bin");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(nested.join("tool"), b"#!/bin/sh\necho hi\n").unwrap();
+    let found = find_exec_files_in_dir(dir.path(), true);
+    assert_eq!(found, vec![nested.join("tool")]);
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_find_exec_files_in_dir_stops_at_max_depth() {
+    let dir = TempDir::new().unwrap();
+    let mut nested = dir.path().to_path_buf();
+    for level in 0..=MAX_EXEC_SEARCH_DEPTH {
+        nested = nested.join(format!("level{}", level));
+    }
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(nested.join("tool"), b"#!/bin/sh\necho hi\n").unwrap();
+    assert!(find_exec_files_in_dir(dir.path(), true).is_empty());
+    // one level up it is found
+    assert_eq!(
+        find_exec_files_in_dir(&dir.path().join("level0"), true),
+        vec![nested.join("tool")]
+    );
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_find_exec_files_in_dir_skips_shared_libraries() {
+    let dir = TempDir::new().unwrap();
+    let tool = write_tmp_file(&dir, "tool", b"#!/bin/sh\necho hi\n");
+    write_tmp_file(&dir, "libtool.so.1", b"#!/bin/sh\necho hi\n");
+    let found = find_exec_files_in_dir(dir.path(), true);
+    assert_eq!(found, vec![tool]);
+}
+
+#[test]
+fn test_is_shared_library_name() {
+    assert!(is_shared_library_name("libfoo.so"));
+    assert!(is_shared_library_name("libfoo.so.1"));
+    assert!(is_shared_library_name("libfoo.so.1.2.3"));
+    assert!(is_shared_library_name("libfoo.dylib"));
+    assert!(is_shared_library_name("Foo.DLL"));
+    assert!(!is_shared_library_name("tool"));
+    assert!(!is_shared_library_name("tool.exe"));
+    assert!(!is_shared_library_name("tool.so.backup"));
+    assert!(!is_shared_library_name("sops"));
+}
+
+#[cfg(target_os = "windows")]
+#[test]
+fn test_create_symlink_links_or_copies() {
+    let dir = TempDir::new().unwrap();
+    let source = write_tmp_file(&dir, "tool.exe", b"MZ binary");
+    let target = dir.path().join("bin-tool.exe");
+
+    // without privileges the binary is copied, either way it can be run from target
+    create_symlink(&source, &target, true).unwrap();
+    assert_eq!(std::fs::read(&target).unwrap(), b"MZ binary");
+
+    // the copy is replaced by later calls, like a symlink would
+    std::fs::write(&source, b"MZ binary v2").unwrap();
+    create_symlink(&source, &target, true).unwrap();
+    assert_eq!(std::fs::read(&target).unwrap(), b"MZ binary v2");
+}
+
+#[cfg(target_os = "windows")]
+#[test]
+fn test_is_managed_copy() {
+    let dir = TempDir::new().unwrap();
+    let data_dir = dir.path().join("data");
+    let version_dir = data_dir.join("owner").join("tool").join("1.0.0");
+    std::fs::create_dir_all(&version_dir).unwrap();
+    std::fs::write(version_dir.join("tool.exe"), b"MZ binary").unwrap();
+    let bin_dir = dir.path().join("bin");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+
+    std::fs::write(bin_dir.join("tool.exe"), b"MZ binary").unwrap();
+    assert!(is_managed_copy(&bin_dir.join("tool.exe"), &data_dir));
+
+    std::fs::write(bin_dir.join("tool.exe"), b"MZ other").unwrap();
+    assert!(!is_managed_copy(&bin_dir.join("tool.exe"), &data_dir));
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_is_managed_copy_never_on_unix() {
+    let dir = TempDir::new().unwrap();
+    let file = write_tmp_file(&dir, "tool", b"#!/bin/sh\n");
+    assert!(!is_managed_copy(&file, dir.path()));
+}
+
+// *** dir_total_size / dir_modified_time *********************************
+
+#[test]
+fn test_dir_total_size_counts_nested_files() {
+    let dir = TempDir::new().unwrap();
+    write_tmp_file(&dir, "a", &[0u8; 100]);
+    std::fs::create_dir_all(dir.path().join("sub").join("deeper")).unwrap();
+    std::fs::write(dir.path().join("sub").join("b"), [0u8; 20]).unwrap();
+    std::fs::write(dir.path().join("sub").join("deeper").join("c"), [0u8; 3]).unwrap();
+    assert_eq!(dir_total_size(dir.path()).unwrap(), 123);
+}
+
+#[test]
+fn test_dir_total_size_empty_and_missing() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(dir_total_size(dir.path()).unwrap(), 0);
+    assert!(dir_total_size(&dir.path().join("missing")).is_err());
+}
+
+#[test]
+fn test_dir_modified_time() {
+    let dir = TempDir::new().unwrap();
+    let modified = dir_modified_time(dir.path()).unwrap();
+    assert!(modified <= std::time::SystemTime::now());
+    assert!(dir_modified_time(&dir.path().join("missing")).is_err());
+}
+
+// *** find_repos_providing_binary ****************************************
+
+#[test]
+fn test_find_repos_providing_binary() {
+    let dir = TempDir::new().unwrap();
+    let install = |slug: &str, version: &str, exec_name: &str| {
+        let version_dir = dir.path().join(slug).join(version);
+        std::fs::create_dir_all(&version_dir).unwrap();
+        std::fs::write(version_dir.join(exec_name), b"#!/bin/sh\n").unwrap();
+    };
+    install("b-user/rg", "1.0.0", "rg");
+    install("a-user/ripgrep", "2.0.0", "rg.exe");
+    install("c-user/other", "1.0.0", "other");
+
+    assert_eq!(
+        find_repos_providing_binary("rg", dir.path()),
+        vec!["a-user/ripgrep".to_string(), "b-user/rg".to_string()]
+    );
+    assert!(find_repos_providing_binary("fd", dir.path()).is_empty());
+    assert!(find_repos_providing_binary("rg", &dir.path().join("missing")).is_empty());
+}
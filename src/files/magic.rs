@@ -20,10 +20,17 @@ pub const MACHO_MAGIC_NUMBERS: &[[u8; 4]] = &[
     [0xCA, 0xFE, 0xBA, 0xBE], // Mach-O universal ('fat') binary (big-endian)
 ];
 
-/// ELF magic number identifying Linux (and most Unix) executables (Linux only).
-#[cfg(target_os = "linux")]
+/// ELF magic number identifying Linux (and most Unix) executables (Linux and FreeBSD only).
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
 pub const ELF_MAGIC: [u8; 4] = [0x7F, 0x45, 0x4C, 0x46]; // ELF
 
+/// Byte offset within the ELF identification padding where AppImages store [`APPIMAGE_MAGIC`].
+#[cfg(target_os = "linux")]
+pub const APPIMAGE_MAGIC_OFFSET: usize = 8;
+/// AppImage magic bytes (`AI`), followed by the AppImage type byte (Linux only).
+#[cfg(target_os = "linux")]
+pub const APPIMAGE_MAGIC: &[u8] = &[0x41, 0x49]; // "AI"
+
 /// PE/MZ magic number identifying Windows executables (Windows only).
 #[cfg(target_os = "windows")]
 pub const PE_MAGIC: [u8; 2] = [0x4D, 0x5A]; // MZ
@@ -34,6 +41,13 @@ pub const ZIP_MAGIC: &[u8] = &[0x50, 0x4B, 0x03, 0x04]; // "PK\x03\x04"
 pub const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B]; // gzip
 /// Zstandard frame magic number.
 pub const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD]; // zstd
+/// LZ4 frame magic number.
+pub const LZ4_MAGIC: &[u8] = &[0x04, 0x22, 0x4D, 0x18]; // lz4 frame
+/// Lzip member magic string (`LZIP`).
+pub const LZIP_MAGIC: &[u8] = b"LZIP"; // lzip
+/// Legacy LZMA-alone header start: default properties byte followed by the
+/// low bytes of a power-of-two dictionary size.
+pub const LZMA_MAGIC: &[u8] = &[0x5D, 0x00, 0x00]; // lzma
 /// XZ stream magic number.
 pub const XZ_MAGIC: &[u8] = &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00]; // "\xfd7zXZ\x00"
 /// Bzip2 stream magic number (`BZh`).
@@ -46,7 +60,7 @@ pub const TAR_MAGIC: &[u8] = b"ustar";
 pub const SEVENZ_MAGIC: &[u8] = &[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]; // 7z signature
 
 /// Returns `true` if the first four bytes of `buffer` match the ELF magic number.
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
 fn is_exec_magic(buffer: &[u8; 4]) -> bool {
     buffer == &ELF_MAGIC
 }
@@ -56,7 +70,7 @@ fn is_exec_magic(buffer: &[u8; 4]) -> bool {
     // Windows expects PE binaries (MZ header).
     // Checking only the first two bytes because the other two may change,
     // as they depend on the DOS stub.
-    buffer[..2] == core::magic::PE_MAGIC
+    buffer[..2] == PE_MAGIC
 }
 
 #[cfg(target_os = "macos")]
@@ -69,7 +83,7 @@ fn is_exec_magic(buffer: &[u8; 4]) -> bool {
 ///
 /// Detection is based on magic bytes at the start of the file rather than
 /// file-name extensions. On non-Windows platforms both shebang scripts (`#!`)
-/// and native binary formats (ELF on Linux, Mach-O on macOS) are recognised.
+/// and native binary formats (ELF on Linux and FreeBSD, Mach-O on macOS) are recognised.
 /// On Windows only `.exe` files with a valid PE/MZ header are accepted.
 #[cfg(not(target_os = "windows"))]
 pub fn is_exec_by_magic_number(path: &Path) -> bool {
@@ -100,7 +114,7 @@ pub fn is_exec_by_magic_number(path: &Path) -> bool {
         .extension()
         .and_then(|s| s.to_str())
         .unwrap_or_default();
-    if extension != "exe" {
+    if !extension.eq_ignore_ascii_case("exe") {
         return false;
     }
     if let Ok(mut file) = File::open(path) {
@@ -112,6 +126,29 @@ pub fn is_exec_by_magic_number(path: &Path) -> bool {
     false
 }
 
+/// Return `true` when the file at `path` is an AppImage.
+///
+/// AppImages are self-contained ELF executables carrying [`APPIMAGE_MAGIC`] at
+/// [`APPIMAGE_MAGIC_OFFSET`]. They must be installed as they are, without any
+/// extraction step. AppImages only exist on Linux, other platforms always return `false`.
+#[cfg(target_os = "linux")]
+pub fn is_exec_appimage(path: &Path) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    let mut buffer = [0u8; APPIMAGE_MAGIC_OFFSET + 2];
+    if file.read_exact(&mut buffer).is_err() {
+        return false;
+    }
+    buffer[..4] == ELF_MAGIC && buffer[APPIMAGE_MAGIC_OFFSET..].starts_with(APPIMAGE_MAGIC)
+}
+
+/// Return `true` when the file at `path` is an AppImage (non-Linux variant, always `false`).
+#[cfg(not(target_os = "linux"))]
+pub fn is_exec_appimage(_path: &Path) -> bool {
+    false
+}
+
 /// Return `true` when the file at `file_path` appears to be a binary for the current architecture.
 ///
 /// The function checks the machine type of the binary to determine if it is for the current architecture.
@@ -146,7 +183,7 @@ pub fn is_exec_for_current_arch(file_path: &Path) -> Result<bool> {
     //       Data is likely to be already in memory as OS puts everything in memory
     //       on first read since its page cache is 4kb. It's zero I/O cost.
 
-    #[cfg(target_os = "linux")]
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     {
         // Check if the file is an ELF file
         if !is_exec_magic(&buffer) {
@@ -286,10 +323,9 @@ pub fn is_exec_for_current_arch(file_path: &Path) -> Result<bool> {
     }
 
     // on Windows we call the dedicated variant of is_exec_by_magic_number.
-    // TODO: here in case we ever port poof to Windows.
     #[cfg(target_os = "windows")]
     {
-        return Ok(is_exec_by_magic_number(file_path));
+        Ok(is_exec_by_magic_number(file_path))
     }
 }
 
@@ -128,6 +128,40 @@ fn test_is_exec_for_current_arch_elf_invalid_ei_data() {
     assert!(!is_exec_for_current_arch(f.path()).unwrap());
 }
 
+// *** is_exec_appimage ***************************************************
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_is_exec_appimage_type2() {
+    let mut header = elf_header_for_current_arch();
+    header[APPIMAGE_MAGIC_OFFSET..APPIMAGE_MAGIC_OFFSET + 3].copy_from_slice(&[0x41, 0x49, 0x02]);
+    let f = write_tmp(&header);
+    assert!(is_exec_appimage(f.path()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_is_exec_appimage_plain_elf() {
+    let f = write_tmp(&elf_header_for_current_arch());
+    assert!(!is_exec_appimage(f.path()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_is_exec_appimage_magic_without_elf() {
+    // AppImage magic at the right offset, but not an ELF file
+    let mut buf = vec![0u8; 20];
+    buf[APPIMAGE_MAGIC_OFFSET..APPIMAGE_MAGIC_OFFSET + 2].copy_from_slice(APPIMAGE_MAGIC);
+    let f = write_tmp(&buf);
+    assert!(!is_exec_appimage(f.path()));
+}
+
+#[test]
+fn test_is_exec_appimage_too_short() {
+    let f = write_tmp(&[0x7F, 0x45, 0x4C, 0x46]);
+    assert!(!is_exec_appimage(f.path()));
+}
+
 // *** macOS Mach-O helpers ***********************************************
 
 /// Build a minimal thin Mach-O (64-bit LE) buffer with the given cputype.
@@ -177,6 +211,21 @@ fn test_is_exec_by_magic_number_macho() {
     assert!(is_exec_by_magic_number(f.path()));
 }
 
+#[cfg(target_os = "macos")]
+#[test]
+fn test_is_exec_by_magic_number_macho_fat() {
+    // universal binaries start with the big-endian fat magic
+    let f = write_tmp(&[0xCA, 0xFE, 0xBA, 0xBE]);
+    assert!(is_exec_by_magic_number(f.path()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_is_exec_by_magic_number_macho_fat_not_on_linux() {
+    let f = write_tmp(&[0xCA, 0xFE, 0xBA, 0xBE]);
+    assert!(!is_exec_by_magic_number(f.path()));
+}
+
 // *** is_exec_for_current_arch – thin Mach-O *****************************
 
 #[cfg(target_os = "macos")]
@@ -0,0 +1,204 @@
+//! Per-version install metadata stored alongside the installed binaries.
+
+use anyhow::{Context, Result};
+use log::debug;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::constants::{
+    ASSET_CHECKSUMS_FILE, BIN_DIR_FILE, CHECKSUM_EXTENSION, INSTALLED_AT_FILE, RENAMES_FILE,
+    SOURCE_FILE,
+};
+use crate::files::checksum;
+use crate::files::datadirs;
+use crate::files::filesys;
+use crate::models::source::Source;
+
+/// Load the custom symlink names recorded for the binaries in `version_dir`.
+///
+/// Returns a map from the installed executable file name to the name of its
+/// symlink in the bin directory. Missing or unreadable metadata yields an
+/// empty map, which means every binary is linked under its own name.
+pub fn load_renames(version_dir: &Path) -> BTreeMap<String, String> {
+    let path = version_dir.join(RENAMES_FILE);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(exec_name, link_name)| (exec_name.to_string(), link_name.to_string()))
+        .collect()
+}
+
+/// Record that the executable `exec_name` in `version_dir` is linked as `link_name`.
+///
+/// Any previous entry for the same executable is replaced.
+pub fn save_rename(version_dir: &Path, exec_name: &str, link_name: &str) -> Result<()> {
+    let mut renames = load_renames(version_dir);
+    renames.insert(exec_name.to_string(), link_name.to_string());
+    let content: String = renames
+        .iter()
+        .map(|(exec_name, link_name)| format!("{}\t{}\n", exec_name, link_name))
+        .collect();
+    let path = version_dir.join(RENAMES_FILE);
+    std::fs::write(&path, content)
+        .with_context(|| format!("Cannot write rename metadata to {}", path.display()))?;
+    debug!(
+        "Recorded '{}' as the link name of '{}' in {}",
+        link_name,
+        exec_name,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Return the name under which `exec_name` from `version_dir` is linked in the bin directory.
+pub fn link_name_for(version_dir: &Path, exec_name: &str) -> String {
+    load_renames(version_dir)
+        .remove(exec_name)
+        .unwrap_or_else(|| exec_name.to_string())
+}
+
+/// Load the release source recorded for `version_dir`.
+///
+/// Binaries installed without a recorded source come from GitHub.
+pub fn load_source(version_dir: &Path) -> Source {
+    std::fs::read_to_string(version_dir.join(SOURCE_FILE))
+        .ok()
+        .and_then(|name| Source::from_name(&name))
+        .unwrap_or_default()
+}
+
+/// Record that the binaries in `version_dir` have been installed from `source`.
+///
+/// Nothing is written for GitHub, the default source.
+pub fn save_source(version_dir: &Path, source: &Source) -> Result<()> {
+    if *source == Source::GitHub {
+        return Ok(());
+    }
+    let path = version_dir.join(SOURCE_FILE);
+    std::fs::write(&path, format!("{}\n", source))
+        .with_context(|| format!("Cannot write source metadata to {}", path.display()))
+}
+
+/// Load the bin directory recorded for `version_dir` by `install --bin-dir`, if any.
+pub fn load_bin_dir(version_dir: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(version_dir.join(BIN_DIR_FILE)).ok()?;
+    let bin_dir = content.trim_end_matches('\n');
+    if bin_dir.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(bin_dir))
+    }
+}
+
+/// Record that the binaries in `version_dir` are linked into `bin_dir`.
+pub fn save_bin_dir(version_dir: &Path, bin_dir: &Path) -> Result<()> {
+    let path = version_dir.join(BIN_DIR_FILE);
+    std::fs::write(&path, format!("{}\n", bin_dir.display()))
+        .with_context(|| format!("Cannot write bin directory metadata to {}", path.display()))
+}
+
+/// Returns the bin directory the binaries in `version_dir` are linked into:
+/// the recorded one, or the default bin directory.
+pub fn bin_dir_for(version_dir: &Path) -> Option<PathBuf> {
+    load_bin_dir(version_dir).or_else(datadirs::get_bin_dir)
+}
+
+/// Load when the binaries in `version_dir` have been installed.
+///
+/// Versions installed before the time was recorded fall back to the modification
+/// time of `version_dir`, which later changes to the directory may move forward.
+pub fn load_installed_at(version_dir: &Path) -> Option<SystemTime> {
+    std::fs::read_to_string(version_dir.join(INSTALLED_AT_FILE))
+        .ok()
+        .and_then(|content| content.trim().parse::<u64>().ok())
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+        .or_else(|| filesys::dir_modified_time(version_dir).ok())
+}
+
+/// Record that the binaries in `version_dir` have been installed at `time`.
+pub fn save_installed_at(version_dir: &Path, time: SystemTime) -> Result<()> {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = version_dir.join(INSTALLED_AT_FILE);
+    std::fs::write(&path, format!("{}\n", seconds))
+        .with_context(|| format!("Cannot write install time to {}", path.display()))
+}
+
+/// Returns the sorted file names of the binaries installed in `version_dir`,
+/// leaving out the metadata files stored next to them.
+pub fn list_binaries(version_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(version_dir) else {
+        return Vec::new();
+    };
+    let checksum_suffix = format!(".{}", CHECKSUM_EXTENSION);
+    let mut binaries: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.') && !name.ends_with(&checksum_suffix))
+        .collect();
+    binaries.sort();
+    binaries
+}
+
+/// Returns the path of the checksum file of `exec_name` in `version_dir`.
+fn checksum_path(version_dir: &Path, exec_name: &str) -> std::path::PathBuf {
+    version_dir.join(format!("{}.{}", exec_name, CHECKSUM_EXTENSION))
+}
+
+/// Record the SHA-256 checksum of the installed executable `exec_name` in `version_dir`.
+///
+/// The file uses the `sha256sum` format, so it can also be checked with `sha256sum -c`.
+pub fn save_checksum(version_dir: &Path, exec_name: &str, hash: &str) -> Result<()> {
+    let path = checksum_path(version_dir, exec_name);
+    std::fs::write(&path, format!("{}  {}\n", hash, exec_name))
+        .with_context(|| format!("Cannot write checksum to {}", path.display()))
+}
+
+/// Load the SHA-256 checksum recorded for `exec_name` in `version_dir`, if any.
+pub fn load_checksum(version_dir: &Path, exec_name: &str) -> Option<String> {
+    let content = std::fs::read_to_string(checksum_path(version_dir, exec_name)).ok()?;
+    checksum::find_checksum(&checksum::parse_checksums(&content), exec_name, true)
+}
+
+/// Load the SHA-256 checksums of the release assets `version_dir` has been installed from,
+/// as `(asset name, checksum)` pairs sorted by asset name.
+pub fn load_asset_checksums(version_dir: &Path) -> Vec<(String, String)> {
+    let Ok(content) = std::fs::read_to_string(version_dir.join(ASSET_CHECKSUMS_FILE)) else {
+        return Vec::new();
+    };
+    let mut checksums: Vec<(String, String)> = content
+        .lines()
+        .filter_map(|line| line.split_once("  "))
+        .map(|(hash, asset_name)| (asset_name.to_string(), hash.to_string()))
+        .collect();
+    checksums.sort();
+    checksums
+}
+
+/// Record the SHA-256 checksum of the release asset `asset_name` installed into `version_dir`.
+///
+/// Any previous entry for the same asset is replaced. The file uses the `sha256sum`
+/// format, so the asset can be checked with `sha256sum -c` if it is downloaded again.
+pub fn save_asset_checksum(version_dir: &Path, asset_name: &str, hash: &str) -> Result<()> {
+    let mut checksums = load_asset_checksums(version_dir);
+    checksums.retain(|(name, _)| name != asset_name);
+    checksums.push((asset_name.to_string(), hash.to_string()));
+    checksums.sort();
+    let content: String = checksums
+        .iter()
+        .map(|(name, hash)| format!("{}  {}\n", hash, name))
+        .collect();
+    let path = version_dir.join(ASSET_CHECKSUMS_FILE);
+    std::fs::write(&path, content)
+        .with_context(|| format!("Cannot write asset checksums to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests;
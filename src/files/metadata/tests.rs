@@ -0,0 +1,145 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_load_renames_missing_file() {
+    let dir = TempDir::new().unwrap();
+    assert!(load_renames(dir.path()).is_empty());
+}
+
+#[test]
+fn test_save_and_load_rename() {
+    let dir = TempDir::new().unwrap();
+    save_rename(dir.path(), "rg-x86_64", "rg").unwrap();
+    let renames = load_renames(dir.path());
+    assert_eq!(renames.len(), 1);
+    assert_eq!(renames.get("rg-x86_64").map(String::as_str), Some("rg"));
+}
+
+#[test]
+fn test_save_rename_replaces_previous_entry() {
+    let dir = TempDir::new().unwrap();
+    save_rename(dir.path(), "rg-x86_64", "rg").unwrap();
+    save_rename(dir.path(), "rg-x86_64", "ripgrep").unwrap();
+    save_rename(dir.path(), "other", "tool").unwrap();
+    let renames = load_renames(dir.path());
+    assert_eq!(renames.len(), 2);
+    assert_eq!(
+        renames.get("rg-x86_64").map(String::as_str),
+        Some("ripgrep")
+    );
+    assert_eq!(renames.get("other").map(String::as_str), Some("tool"));
+}
+
+#[test]
+fn test_link_name_for() {
+    let dir = TempDir::new().unwrap();
+    save_rename(dir.path(), "rg-x86_64", "rg").unwrap();
+    assert_eq!(link_name_for(dir.path(), "rg-x86_64"), "rg");
+    assert_eq!(link_name_for(dir.path(), "not-renamed"), "not-renamed");
+}
+
+#[test]
+fn test_load_renames_ignores_malformed_lines() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join(RENAMES_FILE), "garbage\nrg-x86_64\trg\n").unwrap();
+    let renames = load_renames(dir.path());
+    assert_eq!(renames.len(), 1);
+    assert_eq!(link_name_for(dir.path(), "rg-x86_64"), "rg");
+}
+
+#[test]
+fn test_load_source_defaults_to_github() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(load_source(dir.path()), Source::GitHub);
+    save_source(dir.path(), &Source::GitHub).unwrap();
+    assert!(!dir.path().join(SOURCE_FILE).exists());
+}
+
+#[test]
+fn test_save_and_load_source() {
+    let dir = TempDir::new().unwrap();
+    save_source(dir.path(), &Source::GitLab).unwrap();
+    assert_eq!(load_source(dir.path()), Source::GitLab);
+
+    let gitea = Source::Gitea("codeberg.org".to_string());
+    save_source(dir.path(), &gitea).unwrap();
+    assert_eq!(load_source(dir.path()), gitea);
+}
+
+#[test]
+fn test_save_and_load_checksum() {
+    let dir = TempDir::new().unwrap();
+    let hash = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+    assert_eq!(load_checksum(dir.path(), "tool"), None);
+    save_checksum(dir.path(), "tool", hash).unwrap();
+    assert_eq!(load_checksum(dir.path(), "tool"), Some(hash.to_string()));
+    let content = std::fs::read_to_string(dir.path().join("tool.sha256")).unwrap();
+    assert_eq!(content, format!("{}  tool\n", hash));
+}
+
+#[test]
+fn test_list_binaries_skips_metadata() {
+    let dir = TempDir::new().unwrap();
+    for name in ["tool", "helper", "tool.sha256", RENAMES_FILE, SOURCE_FILE] {
+        std::fs::write(dir.path().join(name), b"").unwrap();
+    }
+    std::fs::create_dir(dir.path().join("subdir")).unwrap();
+    assert_eq!(list_binaries(dir.path()), vec!["helper", "tool"]);
+}
+
+#[test]
+fn test_load_bin_dir_missing_file() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(load_bin_dir(dir.path()), None);
+}
+
+#[test]
+fn test_save_and_load_bin_dir() {
+    let dir = TempDir::new().unwrap();
+    let bin_dir = dir.path().join("team bin");
+    save_bin_dir(dir.path(), &bin_dir).unwrap();
+    assert_eq!(load_bin_dir(dir.path()), Some(bin_dir.clone()));
+    assert_eq!(bin_dir_for(dir.path()), Some(bin_dir));
+}
+
+#[test]
+fn test_save_and_load_asset_checksums() {
+    let dir = TempDir::new().unwrap();
+    assert!(load_asset_checksums(dir.path()).is_empty());
+
+    save_asset_checksum(dir.path(), "tool-linux.tar.gz", "aaaa").unwrap();
+    save_asset_checksum(dir.path(), "extra-linux.tar.gz", "bbbb").unwrap();
+    // a second download of the same asset replaces its entry
+    save_asset_checksum(dir.path(), "tool-linux.tar.gz", "cccc").unwrap();
+
+    assert_eq!(
+        load_asset_checksums(dir.path()),
+        vec![
+            ("extra-linux.tar.gz".to_string(), "bbbb".to_string()),
+            ("tool-linux.tar.gz".to_string(), "cccc".to_string()),
+        ]
+    );
+    // the metadata file is not taken for a binary
+    assert!(list_binaries(dir.path()).is_empty());
+}
+
+#[test]
+fn test_save_and_load_installed_at() {
+    let dir = TempDir::new().unwrap();
+    let time = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+    save_installed_at(dir.path(), time).unwrap();
+    assert_eq!(load_installed_at(dir.path()), Some(time));
+    // not a binary
+    assert!(list_binaries(dir.path()).is_empty());
+}
+
+#[test]
+fn test_load_installed_at_falls_back_to_dir_time() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(
+        load_installed_at(dir.path()),
+        filesys::dir_modified_time(dir.path()).ok()
+    );
+    assert_eq!(load_installed_at(&dir.path().join("missing")), None);
+}
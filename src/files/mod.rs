@@ -1,10 +1,19 @@
 /// Archive extraction: tar, gz, xz, bz2, zstd, zip.
 pub mod archives;
+/// SHA-256 checksum computation and checksum file parsing.
+pub mod checksum;
+/// Verification of cosign signatures from sigstore bundles.
+#[cfg(feature = "cosign")]
+pub mod cosign;
 /// Platform-specific data, bin, cache, and config directory resolution.
 pub mod datadirs;
 /// Filesystem helpers: find executables, copy files, create symlinks.
 pub mod filesys;
 /// Binary format detection via magic-number (file-signature) inspection.
 pub mod magic;
+/// Per-version install metadata, such as custom binary names.
+pub mod metadata;
+/// Version pins that keep `update` from upgrading a repository.
+pub mod pins;
 /// Filename and extension utilities shared across the crate.
 pub mod utils;
@@ -0,0 +1,56 @@
+//! Version pins, stored as `<data_dir>/<slug>/.pinned/<version>` empty marker files.
+
+use anyhow::{Context, Result};
+use log::debug;
+use std::path::{Path, PathBuf};
+
+use crate::constants::PINS_DIR;
+use crate::files::datadirs;
+
+/// Returns the directory holding the pin of `slug`.
+fn pins_dir(data_dir: &Path, slug: &str) -> PathBuf {
+    datadirs::get_versions_nest(data_dir, slug).join(PINS_DIR)
+}
+
+/// Returns the version `slug` is pinned to in `data_dir`, if any.
+pub fn pinned_version(data_dir: &Path, slug: &str) -> Option<String> {
+    std::fs::read_dir(pins_dir(data_dir, slug))
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+        .next()
+}
+
+/// Returns the version `slug` is pinned to, if any.
+pub fn is_pinned(slug: &str) -> Option<String> {
+    pinned_version(&datadirs::get_data_dir()?, slug)
+}
+
+/// Pin `slug` to `version` in `data_dir`, replacing any previous pin.
+pub fn save_pin(data_dir: &Path, slug: &str, version: &str) -> Result<()> {
+    remove_pin(data_dir, slug)?;
+    let dir = pins_dir(data_dir, slug);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Cannot create pins directory {}", dir.display()))?;
+    let marker = dir.join(version);
+    std::fs::write(&marker, "")
+        .with_context(|| format!("Cannot write pin {}", marker.display()))?;
+    debug!("Pinned {} to version {}", slug, version);
+    Ok(())
+}
+
+/// Remove the pin of `slug` in `data_dir`.
+/// Returns `false` when `slug` was not pinned.
+pub fn remove_pin(data_dir: &Path, slug: &str) -> Result<bool> {
+    let dir = pins_dir(data_dir, slug);
+    if !dir.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(&dir)
+        .with_context(|| format!("Cannot remove pins directory {}", dir.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_pinned_version_not_pinned() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(pinned_version(dir.path(), "owner/tool"), None);
+}
+
+#[test]
+fn test_save_and_load_pin() {
+    let dir = TempDir::new().unwrap();
+    save_pin(dir.path(), "owner/tool", "1.2.3").unwrap();
+    assert_eq!(
+        pinned_version(dir.path(), "owner/tool"),
+        Some("1.2.3".to_string())
+    );
+    assert!(dir
+        .path()
+        .join("github.com/owner/tool")
+        .join(PINS_DIR)
+        .join("1.2.3")
+        .is_file());
+}
+
+#[test]
+fn test_save_pin_replaces_previous_pin() {
+    let dir = TempDir::new().unwrap();
+    save_pin(dir.path(), "owner/tool", "1.2.3").unwrap();
+    save_pin(dir.path(), "owner/tool", "2.0.0").unwrap();
+    assert_eq!(
+        pinned_version(dir.path(), "owner/tool"),
+        Some("2.0.0".to_string())
+    );
+    assert!(!dir
+        .path()
+        .join("owner/tool")
+        .join(PINS_DIR)
+        .join("1.2.3")
+        .exists());
+}
+
+#[test]
+fn test_remove_pin() {
+    let dir = TempDir::new().unwrap();
+    assert!(!remove_pin(dir.path(), "owner/tool").unwrap());
+    save_pin(dir.path(), "owner/tool", "1.2.3").unwrap();
+    assert!(remove_pin(dir.path(), "owner/tool").unwrap());
+    assert_eq!(pinned_version(dir.path(), "owner/tool"), None);
+}
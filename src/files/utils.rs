@@ -2,18 +2,19 @@
 
 use crate::constants::FILENAME_SEPARATORS;
 use crate::constants::SUPPORTED_EXTENSIONS;
+use crate::models::source::Source;
 use crate::utils::string::levenshtein_distance;
 use crate::utils::string::strip_repeated_separator;
 use std::{
     ffi::{OsStr, OsString},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 /// Return the file extension of `archive_path` as a string slice.
 ///
-/// Multi-part extensions such as `.tar.gz`, `.tar.xz`, `.tar.bz2`, and
-/// `.tar.zst` are returned whole. For all other paths the standard
-/// single-component extension is returned.
+/// Multi-part extensions such as `.tar.gz`, `.tar.xz`, `.tar.bz2`,
+/// `.tar.zst`, `.tar.lz4`, `.tar.lz`, and `.tar.lzma` are returned whole.
+/// For all other paths the standard single-component extension is returned.
 pub fn get_file_extension(archive_path: &Path) -> &str {
     let filename = archive_path
         .file_name()
@@ -30,6 +31,12 @@ pub fn get_file_extension(archive_path: &Path) -> &str {
         return "tar.bz2";
     } else if filename.ends_with(".tar.zst") {
         return "tar.zst";
+    } else if filename.ends_with(".tar.lz4") {
+        return "tar.lz4";
+    } else if filename.ends_with(".tar.lz") {
+        return "tar.lz";
+    } else if filename.ends_with(".tar.lzma") {
+        return "tar.lzma";
     }
 
     // For single extensions, use the standard method
@@ -155,47 +162,46 @@ pub fn get_stem_name_trimmed_at_first_separator(file_name: &OsStr) -> OsString {
     OsString::from(x)
 }
 
-/// Find similar repo names in the data directory based on fuzzy matching
-pub fn find_similar_repos(data_dir: &Path, target_repo: &str) -> Vec<String> {
-    let mut similar_repos = Vec::new();
-
-    if let Ok(entries) = std::fs::read_dir(data_dir) {
-        for entry in entries.flatten() {
-            if let Ok(file_type) = entry.file_type() {
-                if file_type.is_dir() {
-                    if let Some(username) = entry.file_name().to_str() {
-                        // Check subdirectories (repos) within each user directory
-                        let user_dir = data_dir.join(username);
-                        if let Ok(repo_entries) = std::fs::read_dir(user_dir) {
-                            for repo_entry in repo_entries.flatten() {
-                                if let Ok(repo_file_type) = repo_entry.file_type() {
-                                    if repo_file_type.is_dir() {
-                                        if let Some(repo_name) = repo_entry.file_name().to_str() {
-                                            let full_repo = format!("{}/{}", username, repo_name);
-
-                                            // Calculate similarity
-                                            let distance =
-                                                levenshtein_distance(target_repo, &full_repo);
-                                            let max_len =
-                                                std::cmp::max(target_repo.len(), full_repo.len());
-
-                                            // Consider repos with distance <= 3 or similarity >= 70%
-                                            if distance <= 3
-                                                || (max_len > 0
-                                                    && distance as f32 / max_len as f32 <= 0.3)
-                                            {
-                                                similar_repos.push(full_repo);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+/// Maximum number of repos suggested by [`similar_repos_hint`].
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the names of the repository directories in `data_dir`, prefixed by
+/// their source like on the command line.
+fn repo_names(data_dir: &Path) -> Vec<String> {
+    let subdirs = |dir: &Path| -> Vec<(String, PathBuf)> {
+        std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter_map(|entry| Some((entry.file_name().to_str()?.to_string(), entry.path())))
+            .collect()
+    };
+    let mut names = Vec::new();
+    for (host, host_dir) in subdirs(data_dir) {
+        let source = Source::from_data_subdir(&host);
+        for (username, user_dir) in subdirs(&host_dir) {
+            for (repo_name, _) in subdirs(&user_dir) {
+                names.push(source.qualify(&format!("{}/{}", username, repo_name)));
             }
         }
     }
+    names
+}
+
+/// Find similar repo names in the data directory based on fuzzy matching
+pub fn find_similar_repos(data_dir: &Path, target_repo: &str) -> Vec<String> {
+    let mut similar_repos: Vec<String> = repo_names(data_dir)
+        .into_iter()
+        .filter(|full_repo| {
+            // Calculate similarity
+            let distance = levenshtein_distance(target_repo, full_repo);
+            let max_len = std::cmp::max(target_repo.len(), full_repo.len());
+
+            // Consider repos with distance <= 3 or similarity >= 70%
+            distance <= 3 || (max_len > 0 && distance as f32 / max_len as f32 <= 0.3)
+        })
+        .collect();
 
     // Sort by similarity (lower distance = more similar)
     similar_repos.sort_by(|a, b| {
@@ -215,6 +221,17 @@ pub fn find_similar_repo(data_dir: &Path, target_repo: &str) -> Option<String> {
     similar_repos.into_iter().next()
 }
 
+/// Returns a `Did you mean: ...?` hint listing the installed repos most similar
+/// to `target_repo`, or `None` when there are none.
+pub fn similar_repos_hint(data_dir: &Path, target_repo: &str) -> Option<String> {
+    let mut similar_repos = find_similar_repos(data_dir, target_repo);
+    if similar_repos.is_empty() {
+        return None;
+    }
+    similar_repos.truncate(MAX_SUGGESTIONS);
+    Some(format!("Did you mean: {}?", similar_repos.join(", ")))
+}
+
 /// Returns `true` if `item` is a checksum file.
 pub fn is_checksum_file(item: &str) -> bool {
     let item = item.to_lowercase();
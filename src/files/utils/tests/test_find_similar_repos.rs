@@ -1,4 +1,5 @@
-use crate::files::utils::find_similar_repos;
+use crate::constants::{GITHUB_SUBDIR, GITLAB_SUBDIR};
+use crate::files::utils::{find_similar_repos, similar_repos_hint};
 use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -9,13 +10,23 @@ fn setup_test_dir_structure() -> TempDir {
     let data_dir = temp_dir.path();
 
     // Create user directories with repo subdirectories
-    fs::create_dir_all(data_dir.join("user1/repo1")).unwrap();
-    fs::create_dir_all(data_dir.join("user1/repo2")).unwrap();
-    fs::create_dir_all(data_dir.join("user2/test-repo")).unwrap();
-    fs::create_dir_all(data_dir.join("user2/another-repo")).unwrap();
-    fs::create_dir_all(data_dir.join("pirafrank/rust_exif_renamer")).unwrap();
-    fs::create_dir_all(data_dir.join("pirafrank/rust_exit_renamere")).unwrap();
-    fs::create_dir_all(data_dir.join("octocat/Hello-World")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user1/repo1")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user1/repo2")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user2/test-repo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user2/another-repo")).unwrap();
+    fs::create_dir_all(
+        data_dir
+            .join(GITHUB_SUBDIR)
+            .join("pirafrank/rust_exif_renamer"),
+    )
+    .unwrap();
+    fs::create_dir_all(
+        data_dir
+            .join(GITHUB_SUBDIR)
+            .join("pirafrank/rust_exit_renamere"),
+    )
+    .unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("octocat/Hello-World")).unwrap();
 
     temp_dir
 }
@@ -65,7 +76,7 @@ fn test_distance_threshold_3() {
 
     // Test with a repo name that has distance <= 3 from "user1/repo1"
     // "user1/repa1" has distance 1 from "user1/repo1"
-    fs::create_dir_all(data_dir.join("user1/repa1")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user1/repa1")).unwrap();
 
     let results = find_similar_repos(data_dir, "user1/repo1");
 
@@ -80,7 +91,7 @@ fn test_percentage_similarity_threshold() {
 
     // Create repos with 70% similarity (30% distance)
     // "user1/testing" (13 chars) vs "user1/test-repo" (15 chars)
-    fs::create_dir_all(data_dir.join("user1/testing")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user1/testing")).unwrap();
 
     let results = find_similar_repos(data_dir, "user2/test-repo");
 
@@ -126,9 +137,9 @@ fn test_multiple_users_same_repo_name() {
     let temp_dir = TempDir::new().unwrap();
     let data_dir = temp_dir.path();
 
-    fs::create_dir_all(data_dir.join("user1/myrepo")).unwrap();
-    fs::create_dir_all(data_dir.join("user2/myrepo")).unwrap();
-    fs::create_dir_all(data_dir.join("user3/myrepo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user1/myrepo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user2/myrepo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user3/myrepo")).unwrap();
 
     let results = find_similar_repos(data_dir, "user1/myrepo");
 
@@ -146,8 +157,8 @@ fn test_special_characters_in_repo_name() {
     let temp_dir = TempDir::new().unwrap();
     let data_dir = temp_dir.path();
 
-    fs::create_dir_all(data_dir.join("user/repo-name_v2.0")).unwrap();
-    fs::create_dir_all(data_dir.join("user/repo-name_v2.1")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/repo-name_v2.0")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/repo-name_v2.1")).unwrap();
 
     let results = find_similar_repos(data_dir, "user/repo-name_v2.0");
 
@@ -162,8 +173,8 @@ fn test_hyphen_vs_underscore() {
     let temp_dir = TempDir::new().unwrap();
     let data_dir = temp_dir.path();
 
-    fs::create_dir_all(data_dir.join("user/my-repo")).unwrap();
-    fs::create_dir_all(data_dir.join("user/my_repo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/my-repo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/my_repo")).unwrap();
 
     let results = find_similar_repos(data_dir, "user/my-repo");
 
@@ -179,9 +190,9 @@ fn test_short_repo_names() {
     let temp_dir = TempDir::new().unwrap();
     let data_dir = temp_dir.path();
 
-    fs::create_dir_all(data_dir.join("u/a")).unwrap();
-    fs::create_dir_all(data_dir.join("u/b")).unwrap();
-    fs::create_dir_all(data_dir.join("u/c")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("u/a")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("u/b")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("u/c")).unwrap();
 
     let results = find_similar_repos(data_dir, "u/a");
 
@@ -199,8 +210,8 @@ fn test_long_repo_names() {
     let long_name = "verylongusername/very-long-repository-name-with-many-words";
     let similar_long = "verylongusername/very-long-repository-name-with-manu-words"; // typo: manu
 
-    fs::create_dir_all(data_dir.join(long_name)).unwrap();
-    fs::create_dir_all(data_dir.join(similar_long)).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join(long_name)).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join(similar_long)).unwrap();
 
     let results = find_similar_repos(data_dir, long_name);
 
@@ -215,9 +226,17 @@ fn test_only_files_no_directories() {
     let data_dir = temp_dir.path();
 
     // Create user directory but put files instead of repo directories
-    fs::create_dir_all(data_dir.join("user")).unwrap();
-    fs::write(data_dir.join("user/file1.txt"), "content").unwrap();
-    fs::write(data_dir.join("user/file2.txt"), "content").unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user")).unwrap();
+    fs::write(
+        data_dir.join(GITHUB_SUBDIR).join("user/file1.txt"),
+        "content",
+    )
+    .unwrap();
+    fs::write(
+        data_dir.join(GITHUB_SUBDIR).join("user/file2.txt"),
+        "content",
+    )
+    .unwrap();
 
     let results = find_similar_repos(data_dir, "user/repo");
 
@@ -231,7 +250,7 @@ fn test_nested_structure_only_two_levels() {
     let data_dir = temp_dir.path();
 
     // Create deeper nested structure
-    fs::create_dir_all(data_dir.join("user/repo/subdir")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/repo/subdir")).unwrap();
 
     let results = find_similar_repos(data_dir, "user/repo");
 
@@ -245,8 +264,8 @@ fn test_username_typo() {
     let temp_dir = TempDir::new().unwrap();
     let data_dir = temp_dir.path();
 
-    fs::create_dir_all(data_dir.join("user1/myrepo")).unwrap();
-    fs::create_dir_all(data_dir.join("user2/myrepo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user1/myrepo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user2/myrepo")).unwrap();
 
     // Search with typo in username
     let results = find_similar_repos(data_dir, "user3/myrepo");
@@ -290,9 +309,9 @@ fn test_github_style_repo_names() {
     let temp_dir = TempDir::new().unwrap();
     let data_dir = temp_dir.path();
 
-    fs::create_dir_all(data_dir.join("facebook/react")).unwrap();
-    fs::create_dir_all(data_dir.join("facebook/reac")).unwrap();
-    fs::create_dir_all(data_dir.join("vercel/next.js")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("facebook/react")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("facebook/reac")).unwrap();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("vercel/next.js")).unwrap();
 
     let results = find_similar_repos(data_dir, "facebook/react");
 
@@ -311,10 +330,10 @@ fn test_sorting_order_by_distance() {
     let data_dir = temp_dir.path();
 
     // Create repos with varying distances from "user/test"
-    fs::create_dir_all(data_dir.join("user/test")).unwrap(); // distance 0
-    fs::create_dir_all(data_dir.join("user/tast")).unwrap(); // distance 1
-    fs::create_dir_all(data_dir.join("user/tost")).unwrap(); // distance 1
-    fs::create_dir_all(data_dir.join("user/toast")).unwrap(); // distance 2
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/test")).unwrap(); // distance 0
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/tast")).unwrap(); // distance 1
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/tost")).unwrap(); // distance 1
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/toast")).unwrap(); // distance 2
 
     let results = find_similar_repos(data_dir, "user/test");
 
@@ -325,3 +344,42 @@ fn test_sorting_order_by_distance() {
     // Last should have distance 2
     assert!(results.contains(&"user/toast".to_string()));
 }
+
+#[test]
+fn test_similar_repos_hint() {
+    let temp_dir = setup_test_dir_structure();
+    let data_dir = temp_dir.path();
+
+    assert_eq!(
+        similar_repos_hint(data_dir, "user2/test-rep0"),
+        Some("Did you mean: user2/test-repo?".to_string())
+    );
+    assert_eq!(
+        similar_repos_hint(data_dir, "completely/different-repository-name"),
+        None
+    );
+}
+
+#[test]
+fn test_similar_repos_hint_is_capped() {
+    let temp_dir = TempDir::new().unwrap();
+    let data_dir = temp_dir.path();
+    for repo in ["user/repo1", "user/repo2", "user/repo3", "user/repo4"] {
+        fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join(repo)).unwrap();
+    }
+
+    let hint = similar_repos_hint(data_dir, "user/repo").unwrap();
+    assert_eq!(hint.matches("user/repo").count(), 3, "{}", hint);
+}
+
+#[test]
+fn test_repos_of_other_hosts_are_qualified() {
+    let temp_dir = TempDir::new().unwrap();
+    let data_dir = temp_dir.path();
+    fs::create_dir_all(data_dir.join(GITHUB_SUBDIR).join("user/repo")).unwrap();
+    fs::create_dir_all(data_dir.join(GITLAB_SUBDIR).join("user/repo")).unwrap();
+
+    let results = find_similar_repos(data_dir, "gitlab:user/repo");
+
+    assert_eq!(results, vec!["gitlab:user/repo".to_string()]);
+}
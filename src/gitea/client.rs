@@ -0,0 +1,41 @@
+//! Gitea and Forgejo API interaction for fetching releases and assets.
+//!
+//! Gitea exposes a GitHub-compatible releases API, so requests go through
+//! [`ReleasesApi`] and responses deserialise straight into [`Release`].
+
+use anyhow::Result;
+
+use crate::constants::{GITEA_API_URL_ENV, GITEA_TOKEN_ENV};
+use crate::github::client::ReleasesApi;
+use crate::github::models::Release;
+
+/// Get the base API URL for `host` from environment or use the instance default
+fn get_base_api_url(host: &str) -> String {
+    std::env::var(GITEA_API_URL_ENV).unwrap_or_else(|_| format!("https://{}/api/v1/repos", host))
+}
+
+/// Returns the releases API of the Gitea or Forgejo instance at `host`.
+///
+/// The `GITEA_TOKEN` environment variable is sent as token when set, to access
+/// private repositories. The base API URL can be overridden via `POOF_GITEA_API_URL`.
+fn api(host: &str) -> ReleasesApi {
+    let token = std::env::var(GITEA_TOKEN_ENV)
+        .ok()
+        .filter(|token| !token.is_empty());
+    ReleasesApi::gitea(get_base_api_url(host), token)
+}
+
+/// Fetch a release for `repo` (in `owner/repo` format) from the instance at `host`.
+/// See [`ReleasesApi::get_release`] for the meaning of the other arguments.
+pub fn get_release(host: &str, repo: &str, tag: Option<&str>, use_cache: bool) -> Result<Release> {
+    api(host).get_release(repo, tag, use_cache)
+}
+
+/// Fetch the tags of the most recent releases of `repo` from the instance at `host`.
+/// See [`ReleasesApi::get_release_tags`].
+pub fn get_release_tags(host: &str, repo: &str) -> Result<Vec<String>> {
+    api(host).get_release_tags(repo)
+}
+
+#[cfg(test)]
+mod tests;
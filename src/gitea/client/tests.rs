@@ -0,0 +1,179 @@
+//! Unit tests for Gitea client functions
+//! Tests without making actual network calls
+
+use super::*;
+use mockito::Server;
+use serde_json::json;
+
+/// A trimmed down Gitea release response.
+fn release_response() -> serde_json::Value {
+    json!({
+        "id": 42,
+        "tag_name": "v0.4.0",
+        "target_commitish": "main",
+        "name": "v0.4.0",
+        "body": "## Changes\n\n- Fixes",
+        "url": "https://codeberg.org/api/v1/repos/owner/tool/releases/42",
+        "html_url": "https://codeberg.org/owner/tool/releases/tag/v0.4.0",
+        "tarball_url": "https://codeberg.org/owner/tool/archive/v0.4.0.tar.gz",
+        "zipball_url": "https://codeberg.org/owner/tool/archive/v0.4.0.zip",
+        "draft": false,
+        "prerelease": false,
+        "created_at": "2024-05-01T08:00:00Z",
+        "published_at": "2024-05-01T08:00:00Z",
+        "author": { "id": 1, "login": "owner" },
+        "assets": [
+            {
+                "id": 7,
+                "name": "tool-x86_64-unknown-linux-musl.tar.gz",
+                "size": 1024,
+                "download_count": 3,
+                "created_at": "2024-05-01T08:00:00Z",
+                "uuid": "5e2b0bd6-1c3a-4d4e-9c5e-1f2f3a4b5c6d",
+                "browser_download_url": "https://codeberg.org/owner/tool/releases/download/v0.4.0/tool-x86_64-unknown-linux-musl.tar.gz"
+            },
+            {
+                "id": 8,
+                "name": "tool-aarch64-apple-darwin.tar.gz",
+                "size": 1024,
+                "download_count": 1,
+                "created_at": "2024-05-01T08:00:00Z",
+                "uuid": "6f3c1ce7-2d4b-5e5f-ad6f-2a3b4c5d6e7f",
+                "browser_download_url": "https://codeberg.org/owner/tool/releases/download/v0.4.0/tool-aarch64-apple-darwin.tar.gz"
+            }
+        ]
+    })
+}
+
+mod get_release_url {
+    use super::*;
+
+    #[test]
+    fn test_latest_release_url() {
+        let url = temp_env::with_var_unset("POOF_GITEA_API_URL", || {
+            api("codeberg.org").release_url("owner/tool", None)
+        });
+        assert_eq!(
+            url,
+            "https://codeberg.org/api/v1/repos/owner/tool/releases/latest"
+        );
+    }
+
+    #[test]
+    fn test_specific_tag_release_url() {
+        let url = temp_env::with_var_unset("POOF_GITEA_API_URL", || {
+            api("git.example.com:3000").release_url("owner/tool", Some("v0.4.0"))
+        });
+        assert_eq!(
+            url,
+            "https://git.example.com:3000/api/v1/repos/owner/tool/releases/tags/v0.4.0"
+        );
+    }
+
+    #[test]
+    fn test_base_url_override() {
+        let url = temp_env::with_var("POOF_GITEA_API_URL", Some("http://127.0.0.1:1234"), || {
+            api("codeberg.org").release_url("owner/tool", None)
+        });
+        assert_eq!(url, "http://127.0.0.1:1234/owner/tool/releases/latest");
+    }
+}
+
+mod get_release {
+    use super::*;
+
+    #[test]
+    fn test_release_is_deserialized() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/tool/releases/latest")
+            .match_header("User-Agent", "pirafrank/poof")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(release_response().to_string())
+            .create();
+
+        let release = temp_env::with_vars(
+            [
+                ("POOF_GITEA_API_URL", Some(server.url())),
+                ("GITEA_TOKEN", None),
+            ],
+            || get_release("codeberg.org", "owner/tool", None, false),
+        )
+        .unwrap();
+        mock.assert();
+
+        assert_eq!(release.tag_name(), "v0.4.0");
+        assert_eq!(release.published_at(), "2024-05-01T08:00:00Z");
+        assert_eq!(release.body(), "## Changes\n\n- Fixes");
+        assert_eq!(release.assets().len(), 2);
+        assert_eq!(
+            release.assets()[0].name(),
+            "tool-x86_64-unknown-linux-musl.tar.gz"
+        );
+        assert_eq!(
+            release.assets()[0].browser_download_url(),
+            "https://codeberg.org/owner/tool/releases/download/v0.4.0/tool-x86_64-unknown-linux-musl.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_token_is_sent() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/tool/releases/tags/v0.4.0")
+            .match_header("Authorization", "token gitea-test")
+            .with_status(200)
+            .with_body(release_response().to_string())
+            .create();
+
+        let result = temp_env::with_vars(
+            [
+                ("POOF_GITEA_API_URL", Some(server.url())),
+                ("GITEA_TOKEN", Some("gitea-test".to_string())),
+            ],
+            || get_release("codeberg.org", "owner/tool", Some("v0.4.0"), false),
+        );
+        mock.assert();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_github_token_is_not_sent() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/tool/releases/latest")
+            .match_header("Authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_body(release_response().to_string())
+            .create();
+
+        let result = temp_env::with_vars(
+            [
+                ("POOF_GITEA_API_URL", Some(server.url())),
+                ("GITEA_TOKEN", None),
+                ("GITHUB_TOKEN", Some("ghp-secret".to_string())),
+            ],
+            || get_release("codeberg.org", "owner/tool", None, false),
+        );
+        mock.assert();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_not_found() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/missing/releases/latest")
+            .with_status(404)
+            .with_body(r#"{"message":"The target couldn't be found."}"#)
+            .create();
+
+        let result = temp_env::with_var("POOF_GITEA_API_URL", Some(server.url()), || {
+            get_release("codeberg.org", "owner/missing", None, false)
+        });
+        mock.assert();
+        let err = format!("{:#}", result.unwrap_err());
+        assert!(err.contains("404"), "unexpected error: {}", err);
+    }
+}
@@ -0,0 +1,2 @@
+/// HTTP client for the Gitea and Forgejo Releases API.
+pub mod client;
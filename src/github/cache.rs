@@ -0,0 +1,129 @@
+//! File-based cache of GitHub API responses.
+//!
+//! Each entry is stored in its own file, named after the SHA-256 of the request URL,
+//! inside [`datadirs::get_api_cache_dir`]. The first line of the file holds the
+//! expiry time as seconds since the Unix epoch, the rest is the raw JSON body.
+//!
+//! Expired entries are kept on disk, so that [`read_stale`] can serve them when
+//! the API cannot be reached.
+
+use anyhow::Result;
+use log::{debug, warn};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::constants::{API_CACHE_TTL_ENV, DEFAULT_API_CACHE_TTL_SECONDS};
+use crate::core::http;
+use crate::files::datadirs;
+use crate::utils::time::format_system_time;
+
+/// Returns the configured lifetime of cache entries, in seconds.
+fn cache_ttl() -> u64 {
+    std::env::var(API_CACHE_TTL_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_API_CACHE_TTL_SECONDS)
+}
+
+/// Returns the current time as seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Returns the path of the cache entry for `url` inside `cache_dir`, named after
+/// the SHA-256 digest of `url`, which stays the same across poof builds.
+fn entry_path(cache_dir: &Path, url: &str) -> PathBuf {
+    let digest: String = Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    cache_dir.join(format!("{}.json", digest))
+}
+
+/// Return the cached response body for `url`, if a fresh entry exists.
+///
+/// Missing, unreadable and expired entries are all treated as misses.
+pub fn read(url: &str) -> Option<String> {
+    if cache_ttl() == 0 {
+        return None;
+    }
+    let path = entry_path(&datadirs::get_api_cache_dir()?, url);
+    let content = std::fs::read_to_string(&path).ok()?;
+    let (expiry, body) = content.split_once('\n')?;
+    let expiry: u64 = expiry.parse().ok()?;
+    if now() >= expiry {
+        debug!("Cached response for {} has expired", url);
+        return None;
+    }
+    debug!("Using cached response for {} from {}", url, path.display());
+    Some(body.to_string())
+}
+
+/// Return the cached response body for `url` even if it has expired, along with
+/// the time it was stored at, formatted for display.
+///
+/// Meant as a fallback when the API cannot be reached. Nothing is returned when
+/// caching is disabled.
+pub fn read_stale(url: &str) -> Option<(String, String)> {
+    if cache_ttl() == 0 {
+        return None;
+    }
+    let path = entry_path(&datadirs::get_api_cache_dir()?, url);
+    let content = std::fs::read_to_string(&path).ok()?;
+    let (_, body) = content.split_once('\n')?;
+    let stored_at = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .map(format_system_time)
+        .unwrap_or_else(|_| "an unknown time".to_string());
+    debug!(
+        "Using stale cached response for {} from {}",
+        url,
+        path.display()
+    );
+    Some((body.to_string(), stored_at))
+}
+
+/// Fall back to the stale cached response for `url` after its request failed
+/// with `error`, warning that the data may be outdated.
+///
+/// Only failures to reach the API fall back (see [`http::is_transient`]), an
+/// answer such as a 404 is returned as it is: a deleted release must not be
+/// served from the cache. The original error is also returned when nothing is cached.
+pub fn fallback_stale(url: &str, error: anyhow::Error) -> Result<String> {
+    if !http::is_transient(&error) {
+        return Err(error);
+    }
+    match read_stale(url) {
+        Some((body, stored_at)) => {
+            warn!("Cannot query {}, using cached data from {}", url, stored_at);
+            Ok(body)
+        }
+        None => Err(error),
+    }
+}
+
+/// Store `body` as the response for `url`, valid for the configured TTL.
+///
+/// Caching is best effort: failures are logged and otherwise ignored.
+pub fn write(url: &str, body: &str) {
+    let ttl = cache_ttl();
+    if ttl == 0 {
+        return;
+    }
+    let Some(cache_dir) = datadirs::get_api_cache_dir() else {
+        debug!("Cannot determine API cache directory, not caching {}", url);
+        return;
+    };
+    let path = entry_path(&cache_dir, url);
+    let content = format!("{}\n{}", now().saturating_add(ttl), body);
+    if let Err(e) = std::fs::write(&path, content) {
+        debug!("Cannot write API cache entry {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests;
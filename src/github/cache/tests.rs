@@ -0,0 +1,137 @@
+//! Unit tests for the GitHub API response cache
+
+use super::*;
+use tempfile::TempDir;
+
+const URL: &str = "https://api.github.com/repos/owner/repo/releases/latest";
+
+/// Run `f` with the cache directory pointing inside `temp_dir` and the given TTL.
+fn with_cache_env<R>(temp_dir: &TempDir, ttl: Option<&str>, f: impl FnOnce() -> R) -> R {
+    let home = temp_dir.path().to_str().unwrap();
+    let cache_home = temp_dir.path().join(".cache");
+    temp_env::with_vars(
+        vec![
+            ("HOME", Some(home)),
+            ("XDG_CACHE_HOME", Some(cache_home.to_str().unwrap())),
+            (API_CACHE_TTL_ENV, ttl),
+        ],
+        f,
+    )
+}
+
+#[test]
+fn test_write_then_read_returns_body() {
+    let temp_dir = TempDir::new().unwrap();
+    let cached = with_cache_env(&temp_dir, None, || {
+        write(URL, r#"{"tag_name":"v1.0.0"}"#);
+        read(URL)
+    });
+    assert_eq!(cached.as_deref(), Some(r#"{"tag_name":"v1.0.0"}"#));
+}
+
+#[test]
+fn test_read_is_keyed_by_url() {
+    let temp_dir = TempDir::new().unwrap();
+    let cached = with_cache_env(&temp_dir, None, || {
+        write(URL, "{}");
+        read("https://api.github.com/repos/owner/other/releases/latest")
+    });
+    assert!(cached.is_none());
+}
+
+#[test]
+fn test_expired_entry_is_a_miss() {
+    let temp_dir = TempDir::new().unwrap();
+    let cached = with_cache_env(&temp_dir, None, || {
+        let cache_dir = datadirs::get_api_cache_dir().unwrap();
+        std::fs::write(entry_path(&cache_dir, URL), format!("{}\n{{}}", now() - 1)).unwrap();
+        read(URL)
+    });
+    assert!(cached.is_none());
+}
+
+#[test]
+fn test_zero_ttl_disables_cache() {
+    let temp_dir = TempDir::new().unwrap();
+    let cached = with_cache_env(&temp_dir, Some("0"), || {
+        write(URL, "{}");
+        read(URL)
+    });
+    assert!(cached.is_none());
+    // nothing was written either
+    let cached = with_cache_env(&temp_dir, None, || read(URL));
+    assert!(cached.is_none());
+}
+
+#[test]
+fn test_cache_ttl_falls_back_to_default() {
+    temp_env::with_var(API_CACHE_TTL_ENV, Some("not-a-number"), || {
+        assert_eq!(cache_ttl(), DEFAULT_API_CACHE_TTL_SECONDS);
+    });
+    temp_env::with_var(API_CACHE_TTL_ENV, Some("60"), || {
+        assert_eq!(cache_ttl(), 60);
+    });
+}
+
+#[test]
+fn test_entry_path_is_sha256_of_url() {
+    let path = entry_path(Path::new("/cache"), "https://example.com");
+    assert_eq!(
+        path,
+        Path::new("/cache")
+            .join("100680ad546ce6a577f42f52df33b4cfdca756859e664b8d7de329b150d09ce9.json")
+    );
+}
+
+#[test]
+fn test_read_stale_returns_expired_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let stale = with_cache_env(&temp_dir, None, || {
+        let cache_dir = datadirs::get_api_cache_dir().unwrap();
+        std::fs::write(entry_path(&cache_dir, URL), format!("{}\n{{}}", now() - 1)).unwrap();
+        read_stale(URL)
+    });
+    let (body, stored_at) = stale.unwrap();
+    assert_eq!(body, "{}");
+    assert!(stored_at.ends_with('Z'));
+}
+
+#[test]
+fn test_fallback_stale_returns_error_when_nothing_cached() {
+    let temp_dir = TempDir::new().unwrap();
+    let result = with_cache_env(&temp_dir, None, || {
+        fallback_stale(URL, anyhow::anyhow!("connection refused"))
+    });
+    assert_eq!(result.unwrap_err().to_string(), "connection refused");
+}
+
+#[test]
+fn test_fallback_stale_only_when_api_unreachable() {
+    let temp_dir = TempDir::new().unwrap();
+    let status_error = |status| {
+        anyhow::anyhow!(http::StatusError {
+            url: URL.to_string(),
+            status,
+            body: String::new(),
+        })
+    };
+    with_cache_env(&temp_dir, None, || {
+        let cache_dir = datadirs::get_api_cache_dir().unwrap();
+        std::fs::write(entry_path(&cache_dir, URL), format!("{}\n{{}}", now() - 1)).unwrap();
+
+        let served = fallback_stale(URL, status_error(reqwest::StatusCode::BAD_GATEWAY));
+        assert_eq!(served.unwrap(), "{}");
+        // the API answered, its answer wins over the cache
+        for status in [
+            reqwest::StatusCode::NOT_FOUND,
+            reqwest::StatusCode::UNAUTHORIZED,
+        ] {
+            assert!(
+                fallback_stale(URL, status_error(status)).is_err(),
+                "{}",
+                status
+            );
+        }
+        assert!(fallback_stale(URL, anyhow::anyhow!("invalid JSON")).is_err());
+    });
+}
@@ -2,146 +2,425 @@
 
 use anyhow::{anyhow, bail, Context, Result};
 use log::{debug, error, info};
+use regex::Regex;
 use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::StatusCode;
 
-use crate::core::selector::get_env_compatible_assets;
+use crate::constants::{GITHUB_API_URL, GITHUB_API_URL_ENV, GITHUB_TOKEN_ENV};
+use crate::core::config;
+use crate::core::http::{self, StatusError};
+use crate::core::platform_info::is_rosetta_translated;
+use crate::core::selector::{best_scored, get_triple_compatible_assets};
+use crate::models::asset_triple::AssetTriple;
+use crate::utils::semver::normalise_tag;
 
-use super::models::{Release, ReleaseAsset};
+use super::cache;
+use super::models::{Release, ReleaseAsset, Repository, RepositorySearch};
 
-/// Base URL for the GitHub REST API.
-const GITHUB_API_URL: &str = "https://api.github.com/repos";
 /// `User-Agent` header value sent with every GitHub API request.
 const GITHUB_API_USER_AGENT: &str = "pirafrank/poof";
 /// `Accept` header value requesting GitHub API v3 JSON responses.
 const GITHUB_API_ACCEPT: &str = "application/vnd.github.v3+json";
+/// Number of releases requested when listing the releases of a repository.
+const RELEASES_PAGE_SIZE: u32 = 100;
 
-/// Reads the `GITHUB_TOKEN` environment variable and returns it, or errors if unset/empty.
+/// Number of release tags suggested when the requested one does not exist.
+const SUGGESTED_TAGS: usize = 5;
+
+/// Returns `true` when `e` is the 404 of a request to the API.
+fn is_not_found(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<StatusError>()
+        .is_some_and(|e| e.status == StatusCode::NOT_FOUND)
+}
+
+/// Logs the status of `e` when the API answered with an unsuccessful one, and returns `e`.
+fn log_failed_status(e: anyhow::Error) -> anyhow::Error {
+    if let Some(status_error) = e.downcast_ref::<StatusError>() {
+        error!("Request failed with status: {}", status_error.status);
+    }
+    e
+}
+
+/// Reads the `GITHUB_TOKEN` environment variable and returns it, falling back to the
+/// `github_token` config setting. Errors if neither is set or both are empty.
 fn get_github_token() -> Result<String> {
-    let token = std::env::var("GITHUB_TOKEN").with_context(|| "GITHUB_TOKEN is not set")?;
-    if token.is_empty() {
-        bail!("GITHUB_TOKEN is not set");
+    let token = std::env::var(GITHUB_TOKEN_ENV)
+        .ok()
+        .filter(|t| !t.is_empty())
+        .or_else(|| config::get().github_token.clone());
+    match token {
+        Some(token) if !token.is_empty() => Ok(token),
+        _ => bail!("GITHUB_TOKEN is not set"),
     }
-    Ok(token)
 }
 
 /// Get the base API URL from environment or use the default
 fn get_base_api_url() -> String {
-    std::env::var("POOF_GITHUB_API_URL").unwrap_or_else(|_| GITHUB_API_URL.to_string())
+    std::env::var(GITHUB_API_URL_ENV).unwrap_or_else(|_| GITHUB_API_URL.to_string())
 }
 
-/// Fetch a GitHub release for `repo`.
+/// A GitHub-compatible releases API.
 ///
-/// When `tag` is `None` the latest release is retrieved. When a tag string is
-/// provided that specific release tag is fetched. Attaches a `Bearer` token
-/// from the `GITHUB_TOKEN` environment variable when available to avoid rate
-/// limiting. The base API URL can be overridden via `POOF_GITHUB_API_URL`
-/// (useful in tests with a mock server).
-pub fn get_release(repo: &str, tag: Option<&str>) -> Result<Release> {
-    let release_url = get_release_url(repo, tag);
-    info!("Release URL: {}", release_url);
-    let client: Client = Client::new();
-
-    let mut request: RequestBuilder = client
-        .get(&release_url)
-        .header("User-Agent", GITHUB_API_USER_AGENT) // Keep User-Agent header for GitHub API
-        .header("Accept", GITHUB_API_ACCEPT);
-
-    // Add Authorization header if token is available to avoid rate limiting
-    if let Ok(token) = get_github_token() {
-        request = request.header("Authorization", format!("Bearer {}", token));
-    }
-
-    // Make the request
-    match request.send() {
-        Ok(response) => {
-            debug!("Response Status: {}", response.status());
-            let status = response.status(); // we store for error case
-
-            if response.status().is_success() {
-                // Attempt to parse the JSON response into a Vec<Release>
-                match response.json::<Release>() {
-                    Ok(release) => {
-                        if let Some(tag) = tag {
-                            info!("Selected release tag: {}", tag);
-                        } else {
-                            info!("Current latest release tag: {}", release.tag_name());
-                        }
-                        debug!("Published at: {}", release.published_at());
-                        debug!("Available assets:");
-                        for asset in release.assets() {
-                            debug!("\t{}", asset.name());
-                        }
-                        // return Ok on success
-                        Ok(release)
+/// GitHub and Gitea/Forgejo share the `{base}/{owner}/{repo}/releases/...` endpoints
+/// and the shape of their responses, so they only differ by base URL, by the query
+/// parameter limiting the size of release listings and by how the token is sent.
+pub struct ReleasesApi {
+    /// URL the `{owner}/{repo}/releases` paths are appended to.
+    base_url: String,
+    /// Query parameter setting the number of releases in a listing.
+    page_size_param: &'static str,
+    /// Value of the `Authorization` header, if any.
+    authorization: Option<String>,
+}
+
+impl ReleasesApi {
+    /// The GitHub API, authenticated with [`get_github_token`] when available.
+    pub fn github() -> Self {
+        Self {
+            base_url: get_base_api_url(),
+            page_size_param: "per_page",
+            authorization: get_github_token()
+                .ok()
+                .map(|token| format!("Bearer {}", token)),
+        }
+    }
+
+    /// A Gitea or Forgejo API rooted at `base_url` (e.g. `https://codeberg.org/api/v1/repos`),
+    /// authenticated with `token` when given.
+    pub fn gitea(base_url: String, token: Option<String>) -> Self {
+        Self {
+            base_url,
+            page_size_param: "limit",
+            authorization: token.map(|token| format!("token {}", token)),
+        }
+    }
+
+    /// Fetch a release for `repo`.
+    ///
+    /// When `tag` is `None` the latest release is retrieved. When a tag string is
+    /// provided that specific release tag is fetched.
+    ///
+    /// When `use_cache` is `true` a fresh response cached by a previous call is
+    /// returned without contacting the API, and successful responses are cached
+    /// for the next calls (see [`cache`]). If the API cannot be reached, a
+    /// previously cached response is used even if expired, with a warning.
+    ///
+    /// When the `include_prereleases` config setting is enabled and no tag is given,
+    /// the most recent release is returned, even if it is a pre-release.
+    pub fn get_release(&self, repo: &str, tag: Option<&str>, use_cache: bool) -> Result<Release> {
+        let prereleases = tag.is_none() && config::get().include_prereleases;
+        let release_url = if prereleases {
+            self.releases_list_url(repo, 1)
+        } else {
+            self.release_url(repo, tag)
+        };
+        info!("Release URL: {}", release_url);
+
+        let body = match use_cache.then(|| cache::read(&release_url)).flatten() {
+            Some(body) => body,
+            None => match self.fetch_release_of_tag(repo, tag, &release_url) {
+                Ok(body) => {
+                    if use_cache {
+                        cache::write(&release_url, &body);
                     }
-                    Err(e) => {
-                        error!("Cannot parse JSON response: {}", e);
-                        // return Err instead of exit, wrapping the original error
-                        Err(anyhow!(e)
-                            .context(format!("Cannot parse JSON response from {}", release_url)))
+                    body
+                }
+                Err(e) => {
+                    let e = self.tag_not_found_error(repo, tag, e);
+                    if use_cache {
+                        cache::fallback_stale(&release_url, e)?
+                    } else {
+                        return Err(e);
                     }
                 }
-            } else {
-                error!("Request failed with status: {}", status);
-                // read body for context if possible
-                let error_body = response
-                    .text()
-                    .unwrap_or_else(|_| "Cannot read error response body".to_string());
-                // return Err instead of exit
-                Err(anyhow!(
-                    "Request to {} failed with status: {}. Response: {}",
-                    release_url,
-                    status,
-                    error_body
-                ))
+            },
+        };
+
+        // Attempt to parse the JSON response into a Release
+        let parsed = if prereleases {
+            // releases are listed newest first
+            serde_json::from_str::<Vec<Release>>(&body).map(|releases| releases.into_iter().next())
+        } else {
+            serde_json::from_str::<Release>(&body).map(Some)
+        };
+        match parsed {
+            Ok(None) => bail!("No releases found for {}", repo),
+            Ok(Some(release)) => {
+                if tag.is_some() {
+                    info!("Selected release tag: {}", release.tag_name());
+                } else {
+                    info!("Current latest release tag: {}", release.tag_name());
+                }
+                debug!("Published at: {}", release.published_at());
+                debug!("Available assets:");
+                for asset in release.assets() {
+                    debug!("\t{}", asset.name());
+                }
+                // return Ok on success
+                Ok(release)
+            }
+            Err(e) => {
+                error!("Cannot parse JSON response: {}", e);
+                // return Err instead of exit, wrapping the original error
+                Err(anyhow!(e).context(format!("Cannot parse JSON response from {}", release_url)))
             }
         }
-        Err(e) => {
-            error!("Failed: {}. Are you connected to the internet?", e);
-            // return Err instaed of exit
-            Err(anyhow!(e).context(format!("Cannot send request to {}", release_url)))
+    }
+
+    /// Returns `e` with a friendlier context when it is the 404 of the release of `tag`,
+    /// naming a few of the available tags of `repo`. Other errors are returned as they are.
+    fn tag_not_found_error(
+        &self,
+        repo: &str,
+        tag: Option<&str>,
+        e: anyhow::Error,
+    ) -> anyhow::Error {
+        let Some(tag) = tag else {
+            return e;
+        };
+        if !is_not_found(&e) {
+            return e;
+        }
+        let mut message = format!("Release tag '{}' not found for {}.", tag, repo);
+        match self.get_release_tags(repo) {
+            Ok(tags) if tags.is_empty() => message.push_str(" The repository has no releases."),
+            Ok(tags) => message.push_str(&format!(
+                " Available tags include: {}",
+                tags.iter()
+                    .take(SUGGESTED_TAGS)
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Err(e) => debug!("Cannot list the release tags of {}: {:#}", repo, e),
         }
+        error!("{}", message);
+        e.context(message)
+    }
+
+    /// Fetch the tags of the most recent releases of `repo`, newest first.
+    /// Pre-releases are included, callers filter them as needed.
+    pub fn get_release_tags(&self, repo: &str) -> Result<Vec<String>> {
+        let releases_url = self.releases_list_url(repo, RELEASES_PAGE_SIZE);
+        info!("Releases URL: {}", releases_url);
+        let body = self.fetch_release_body(&releases_url)?;
+        let releases: Vec<Release> = serde_json::from_str(&body).map_err(|e| {
+            error!("Cannot parse JSON response: {}", e);
+            anyhow!(e).context(format!("Cannot parse JSON response from {}", releases_url))
+        })?;
+        Ok(releases
+            .iter()
+            .map(|release| release.tag_name().to_string())
+            .collect())
+    }
+
+    /// Sends the request for `release_url`, the release of `tag` if given. When the
+    /// API does not know `tag`, it is retried with its `v` prefix added or stripped
+    /// (see [`normalise_tag`]), so that `1.2.3` finds the release tagged `v1.2.3`.
+    /// If no variant exists the error of `tag` itself is returned.
+    fn fetch_release_of_tag(
+        &self,
+        repo: &str,
+        tag: Option<&str>,
+        release_url: &str,
+    ) -> Result<String> {
+        let Some(tag) = tag else {
+            return self.fetch_release_body(release_url);
+        };
+        let e = match self.send_request(release_url) {
+            Ok(body) => return Ok(body),
+            Err(e) if is_not_found(&e) => e,
+            Err(e) => return Err(log_failed_status(e)),
+        };
+        for candidate in normalise_tag(tag)
+            .iter()
+            .filter(|candidate| *candidate != tag)
+        {
+            debug!("Release tag '{}' not found, trying '{}'", tag, candidate);
+            if let Ok(body) = self.send_request(&self.release_url(repo, Some(candidate))) {
+                return Ok(body);
+            }
+        }
+        Err(log_failed_status(e))
+    }
+
+    /// Sends the GET request for `release_url` and returns the raw body of a successful response.
+    /// Any other endpoint of the same API can be requested this way.
+    fn fetch_release_body(&self, release_url: &str) -> Result<String> {
+        self.send_request(release_url).map_err(log_failed_status)
+    }
+
+    /// Sends the GET request for `release_url` like [`Self::fetch_release_body`],
+    /// without logging the unsuccessful status the API may answer with.
+    fn send_request(&self, release_url: &str) -> Result<String> {
+        let client: Client = http::client()?;
+
+        let mut request: RequestBuilder = client
+            .get(release_url)
+            .header("User-Agent", GITHUB_API_USER_AGENT) // Keep User-Agent header for GitHub API
+            .header("Accept", GITHUB_API_ACCEPT);
+
+        // Add Authorization header if token is available to avoid rate limiting
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+
+        // Make the request
+        match request.send() {
+            Ok(response) => {
+                debug!("Response Status: {}", response.status());
+                let status = response.status(); // we store for error case
+
+                if response.status().is_success() {
+                    response
+                        .text()
+                        .with_context(|| format!("Cannot read response body from {}", release_url))
+                } else {
+                    // read body for context if possible
+                    let error_body = response
+                        .text()
+                        .unwrap_or_else(|_| "Cannot read error response body".to_string());
+                    // return Err instead of exit
+                    Err(anyhow!(StatusError {
+                        url: release_url.to_string(),
+                        status,
+                        body: error_body,
+                    }))
+                }
+            }
+            Err(e) => {
+                if !e.is_timeout() {
+                    error!("Failed: {}. Are you connected to the internet?", e);
+                }
+                // return Err instaed of exit
+                Err(http::request_error(e, release_url))
+            }
+        }
+    }
+
+    /// Build the API URL for a release.
+    ///
+    /// Returns the `/releases/tags/{tag}` endpoint when a specific tag is requested
+    /// or the `/releases/latest` endpoint otherwise.
+    pub fn release_url(&self, repo: &str, tag: Option<&str>) -> String {
+        match tag {
+            Some(tag) => format!("{}/{}/releases/tags/{}", self.base_url, repo, tag),
+            None => format!("{}/{}/releases/latest", self.base_url, repo),
+        }
+    }
+
+    /// Build the API URL listing the `count` most recent releases of `repo`, pre-releases included.
+    fn releases_list_url(&self, repo: &str, count: u32) -> String {
+        format!(
+            "{}/{}/releases?{}={}",
+            self.base_url, repo, self.page_size_param, count
+        )
     }
 }
 
-/// Build the GitHub API URL for a release.
+/// Fetch a GitHub release for `repo`.
 ///
-/// Returns the `/releases/tags/{tag}` endpoint when a specific tag is requested
-/// or the `/releases/latest` endpoint otherwise.
-pub fn get_release_url(repo: &str, tag: Option<&str>) -> String {
+/// See [`ReleasesApi::get_release`]. Attaches a `Bearer` token from the
+/// `GITHUB_TOKEN` environment variable when available to avoid rate limiting.
+/// The base API URL can be overridden via `POOF_GITHUB_API_URL` (useful in
+/// tests with a mock server).
+pub fn get_release(repo: &str, tag: Option<&str>, use_cache: bool) -> Result<Release> {
+    ReleasesApi::github().get_release(repo, tag, use_cache)
+}
+
+/// Fetch the tags of the most recent GitHub releases of `repo`, newest first.
+/// See [`ReleasesApi::get_release_tags`].
+pub fn get_release_tags(repo: &str) -> Result<Vec<String>> {
+    ReleasesApi::github().get_release_tags(repo)
+}
+
+/// Search GitHub repositories matching `query`, returning at most `limit` results,
+/// best match first.
+///
+/// The search endpoint lives next to the `repos` one, so `POOF_GITHUB_API_URL`
+/// overrides it too. A `GITHUB_TOKEN` raises the search rate limit.
+pub fn search_repositories(query: &str, limit: u32) -> Result<Vec<Repository>> {
     let base_url = get_base_api_url();
-    match tag {
-        Some(tag) => format!("{}/{}/releases/tags/{}", base_url, repo, tag),
-        None => format!("{}/{}/releases/latest", base_url, repo),
-    }
+    let root_url = base_url.strip_suffix("/repos").unwrap_or(&base_url);
+    let search_url = reqwest::Url::parse_with_params(
+        &format!("{}/search/repositories", root_url),
+        &[("q", query.to_string()), ("per_page", limit.to_string())],
+    )
+    .with_context(|| format!("Cannot build search URL for '{}'", query))?;
+    info!("Search URL: {}", search_url);
+
+    let body = ReleasesApi::github().fetch_release_body(search_url.as_str())?;
+    let results: RepositorySearch = serde_json::from_str(&body).map_err(|e| {
+        error!("Cannot parse JSON response: {}", e);
+        anyhow!(e).context(format!("Cannot parse JSON response from {}", search_url))
+    })?;
+    Ok(results.into_items())
 }
 
-/// Filter a release's assets to those compatible with the current platform.
+/// Filter a release's assets to those compatible with the platform described by `triple`.
 ///
-/// Delegates to [`get_env_compatible_assets`] and returns an error when no
-/// compatible assets are found for the release.
-pub fn get_assets(release: &Release) -> Result<Vec<ReleaseAsset>> {
-    let binaries: Option<Vec<ReleaseAsset>> =
-        get_env_compatible_assets(release.assets(), |asset| asset.name());
+/// Assets are scored by [`get_triple_compatible_assets`] and the ones with the
+/// highest confidence are returned. Returns an error when no compatible assets
+/// are found for the release.
+pub fn get_assets(release: &Release, triple: &AssetTriple) -> Result<Vec<ReleaseAsset>> {
+    if let Some(pattern) = triple.get_pattern() {
+        return get_assets_matching(release, pattern);
+    }
+    debug!("Selecting assets for {}", triple);
+    if is_rosetta_translated() == Some(true) {
+        // poof itself is an x86_64 build, so is the default platform it selects for
+        debug!("Running under Rosetta 2 translation");
+    }
+    let scored: Option<Vec<(ReleaseAsset, u32)>> =
+        get_triple_compatible_assets(release.assets(), triple, |asset| asset.name());
     let not_found = format!(
         "No compatible pre-built binaries found for release {} matching the specified criteria.",
         release.tag_name()
     );
 
-    if binaries.is_none() {
+    let Some(scored) = scored else {
         bail!(not_found);
-    }
+    };
 
-    let binaries: Vec<ReleaseAsset> = binaries.unwrap_or_default();
+    debug!("Compatible binaries found:");
+    for (binary, score) in &scored {
+        debug!("\t{} (confidence {})", binary.name(), score);
+    }
+    let binaries: Vec<ReleaseAsset> = best_scored(scored);
     if binaries.is_empty() {
         bail!(not_found);
     }
+    Ok(binaries)
+}
 
-    debug!("Compatible binaries found:");
-    for binary in &binaries {
-        debug!("\t{}", binary.name());
+/// Returns the assets of `release` whose name matches `pattern`, in place of the
+/// platform-compatible ones selected by [`get_assets`].
+fn get_assets_matching(release: &Release, pattern: &Regex) -> Result<Vec<ReleaseAsset>> {
+    let assets: Vec<ReleaseAsset> = release
+        .assets()
+        .iter()
+        .filter(|asset| pattern.is_match(asset.name()))
+        .cloned()
+        .collect();
+    if assets.is_empty() {
+        let message = format!(
+            "No asset of release {} matches pattern '{}'. Available: {}",
+            release.tag_name(),
+            pattern,
+            release
+                .assets()
+                .iter()
+                .map(|asset| asset.name().as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        error!("{}", message);
+        bail!(message);
     }
-    Ok(binaries)
+    for asset in &assets {
+        debug!("\t{} matches pattern '{}'", asset.name(), pattern);
+    }
+    Ok(assets)
 }
 
 #[cfg(test)]
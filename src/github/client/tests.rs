@@ -17,7 +17,7 @@ mod get_release_url {
 
     #[test]
     fn test_latest_release_url() {
-        let url = get_release_url("owner/repo", None);
+        let url = ReleasesApi::github().release_url("owner/repo", None);
         assert_eq!(
             url,
             "https://api.github.com/repos/owner/repo/releases/latest"
@@ -26,7 +26,7 @@ mod get_release_url {
 
     #[test]
     fn test_specific_tag_release_url() {
-        let url = get_release_url("owner/repo", Some("v1.0.0"));
+        let url = ReleasesApi::github().release_url("owner/repo", Some("v1.0.0"));
         assert_eq!(
             url,
             "https://api.github.com/repos/owner/repo/releases/tags/v1.0.0"
@@ -35,7 +35,7 @@ mod get_release_url {
 
     #[test]
     fn test_url_with_special_characters_in_repo() {
-        let url = get_release_url("user-name/repo_name", None);
+        let url = ReleasesApi::github().release_url("user-name/repo_name", None);
         assert_eq!(
             url,
             "https://api.github.com/repos/user-name/repo_name/releases/latest"
@@ -44,7 +44,7 @@ mod get_release_url {
 
     #[test]
     fn test_url_with_special_characters_in_tag() {
-        let url = get_release_url("owner/repo", Some("v1.0.0-beta.1"));
+        let url = ReleasesApi::github().release_url("owner/repo", Some("v1.0.0-beta.1"));
         assert_eq!(
             url,
             "https://api.github.com/repos/owner/repo/releases/tags/v1.0.0-beta.1"
@@ -53,7 +53,7 @@ mod get_release_url {
 
     #[test]
     fn test_url_with_numeric_repo() {
-        let url = get_release_url("owner123/repo456", Some("1.2.3"));
+        let url = ReleasesApi::github().release_url("owner123/repo456", Some("1.2.3"));
         assert_eq!(
             url,
             "https://api.github.com/repos/owner123/repo456/releases/tags/1.2.3"
@@ -210,3 +210,308 @@ mod integration_with_fixture {
         assert!(json.is_ok(), "Release should be serializable to JSON");
     }
 }
+
+mod get_release_cache {
+    use super::*;
+    use mockito::Server;
+    use tempfile::TempDir;
+
+    const BODY: &str = r#"{"tag_name":"v1.0.0","published_at":"2024-01-01T00:00:00Z","assets":[]}"#;
+
+    /// Calls `get_release` twice against a mock server expecting `hits` requests to reach it.
+    fn fetch_twice(use_cache: bool, hits: usize) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/repo/releases/latest")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(BODY)
+            .expect(hits)
+            .create();
+
+        temp_env::with_vars(
+            vec![
+                ("POOF_GITHUB_API_URL", Some(server.url().as_str())),
+                ("HOME", temp_dir.path().to_str()),
+                ("XDG_CACHE_HOME", temp_dir.path().join(".cache").to_str()),
+                ("POOF_API_CACHE_TTL_SECONDS", None),
+            ],
+            || {
+                for _ in 0..2 {
+                    let release = get_release("owner/repo", None, use_cache).unwrap();
+                    assert_eq!(release.tag_name(), "v1.0.0");
+                }
+            },
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_cached_response_is_reused() {
+        fetch_twice(true, 1);
+    }
+
+    #[test]
+    fn test_no_cache_always_queries_api() {
+        fetch_twice(false, 2);
+    }
+
+    #[test]
+    fn test_stale_cache_is_used_when_api_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new();
+        let ok = server
+            .mock("GET", "/owner/repo/releases/latest")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(BODY)
+            .create();
+
+        temp_env::with_vars(
+            vec![
+                ("POOF_GITHUB_API_URL", Some(server.url().as_str())),
+                ("HOME", temp_dir.path().to_str()),
+                ("XDG_CACHE_HOME", temp_dir.path().join(".cache").to_str()),
+                ("POOF_API_CACHE_TTL_SECONDS", Some("1")),
+            ],
+            || {
+                get_release("owner/repo", None, true).unwrap();
+                ok.remove();
+                // let the cached entry expire
+                std::thread::sleep(std::time::Duration::from_millis(1100));
+                let failing = server
+                    .mock("GET", "/owner/repo/releases/latest")
+                    .with_status(500)
+                    .expect(1)
+                    .create();
+                let release = get_release("owner/repo", None, true).unwrap();
+                assert_eq!(release.tag_name(), "v1.0.0");
+                failing.assert();
+            },
+        );
+    }
+
+    #[test]
+    fn test_stale_cache_is_not_used_when_release_is_gone() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new();
+        let ok = server
+            .mock("GET", "/owner/repo/releases/tags/v1.0.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(BODY)
+            .create();
+
+        temp_env::with_vars(
+            vec![
+                ("POOF_GITHUB_API_URL", Some(server.url().as_str())),
+                ("HOME", temp_dir.path().to_str()),
+                ("XDG_CACHE_HOME", temp_dir.path().join(".cache").to_str()),
+                ("POOF_API_CACHE_TTL_SECONDS", Some("1")),
+            ],
+            || {
+                get_release("owner/repo", Some("v1.0.0"), true).unwrap();
+                ok.remove();
+                // let the cached entry expire, then the release is deleted
+                std::thread::sleep(std::time::Duration::from_millis(1100));
+                let _gone = server
+                    .mock("GET", mockito::Matcher::Regex("^/owner/repo/".to_string()))
+                    .with_status(404)
+                    .with_body(r#"{"message":"Not Found"}"#)
+                    .create();
+                let e = get_release("owner/repo", Some("v1.0.0"), true).unwrap_err();
+                assert!(
+                    e.to_string()
+                        .starts_with("Release tag 'v1.0.0' not found for owner/repo."),
+                    "{}",
+                    e
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_api_error_without_cache_is_returned() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = Server::new();
+        let _mock = server
+            .mock("GET", "/owner/repo/releases/latest")
+            .with_status(500)
+            .create();
+
+        temp_env::with_vars(
+            vec![
+                ("POOF_GITHUB_API_URL", Some(server.url().as_str())),
+                ("HOME", temp_dir.path().to_str()),
+                ("XDG_CACHE_HOME", temp_dir.path().join(".cache").to_str()),
+                ("POOF_API_CACHE_TTL_SECONDS", None),
+            ],
+            || assert!(get_release("owner/repo", None, true).is_err()),
+        );
+    }
+}
+
+mod get_release_tags {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[test]
+    fn test_release_tags_are_listed_newest_first() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/repo/releases")
+            .match_query(Matcher::UrlEncoded("per_page".into(), "100".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"tag_name":"v2.0.0","published_at":"2024-03-01T00:00:00Z","assets":[]},
+                    {"tag_name":"v1.1.0","published_at":"2024-02-01T00:00:00Z","assets":[]}
+                ]"#,
+            )
+            .create();
+
+        let tags = temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+            get_release_tags("owner/repo")
+        })
+        .unwrap();
+        mock.assert();
+        assert_eq!(tags, vec!["v2.0.0", "v1.1.0"]);
+    }
+}
+
+mod get_release_by_tag {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    const BODY: &str = r#"{"tag_name":"v1.0.0","published_at":"2024-01-01T00:00:00Z","assets":[]}"#;
+
+    #[test]
+    fn test_unknown_tag_lists_available_tags() {
+        let mut server = Server::new();
+        let _tag = server
+            .mock("GET", "/owner/repo/releases/tags/v9.9.9")
+            .with_status(404)
+            .with_body(r#"{"message":"Not Found"}"#)
+            .create();
+        let _releases = server
+            .mock("GET", "/owner/repo/releases")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"tag_name":"v2.0.0","published_at":"2024-03-01T00:00:00Z","assets":[]},
+                    {"tag_name":"v1.0.0","published_at":"2024-01-01T00:00:00Z","assets":[]}
+                ]"#,
+            )
+            .create();
+
+        let e = temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+            get_release("owner/repo", Some("v9.9.9"), false)
+        })
+        .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Release tag 'v9.9.9' not found for owner/repo. Available tags include: v2.0.0, v1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_other_errors_are_left_alone() {
+        let mut server = Server::new();
+        let _tag = server
+            .mock("GET", "/owner/repo/releases/tags/v1.0.0")
+            .with_status(500)
+            .create();
+
+        let e = temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+            get_release("owner/repo", Some("v1.0.0"), false)
+        })
+        .unwrap_err();
+        assert!(e.to_string().contains("failed with status: 500"), "{}", e);
+    }
+
+    #[test]
+    fn test_existing_tag_is_returned() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/repo/releases/tags/v1.0.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(BODY)
+            .create();
+
+        let release = temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+            get_release("owner/repo", Some("v1.0.0"), false)
+        })
+        .unwrap();
+        mock.assert();
+        assert_eq!(release.tag_name(), "v1.0.0");
+    }
+
+    #[test]
+    fn test_tag_is_found_with_or_without_v_prefix() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/owner/repo/releases/tags/v1.0.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(BODY)
+            .expect(2)
+            .create();
+        let _unprefixed = server
+            .mock("GET", "/owner/repo/releases/tags/1.0.0")
+            .with_status(404)
+            .with_body(r#"{"message":"Not Found"}"#)
+            .create();
+
+        for tag in ["1.0.0", "v1.0.0"] {
+            let release = temp_env::with_var("POOF_GITHUB_API_URL", Some(server.url()), || {
+                get_release("owner/repo", Some(tag), false)
+            })
+            .unwrap();
+            assert_eq!(release.tag_name(), "v1.0.0", "requested {}", tag);
+        }
+        mock.assert();
+    }
+}
+
+mod search_repositories {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[test]
+    fn test_search_results_are_parsed() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/search/repositories")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("q".into(), "json viewer".into()),
+                Matcher::UrlEncoded("per_page".into(), "5".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"total_count":2,"items":[
+                    {"full_name":"owner/jv","stargazers_count":120,"description":"A JSON viewer"},
+                    {"full_name":"other/jless","stargazers_count":4000,"description":null}
+                ]}"#,
+            )
+            .create();
+
+        // the search endpoint is a sibling of the repos one
+        let base_url = format!("{}/repos", server.url());
+        let results = temp_env::with_var("POOF_GITHUB_API_URL", Some(base_url), || {
+            search_repositories("json viewer", 5)
+        })
+        .unwrap();
+        mock.assert();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].full_name(), "owner/jv");
+        assert_eq!(results[0].stargazers_count(), 120);
+        assert_eq!(results[0].description(), Some("A JSON viewer"));
+        assert_eq!(results[1].description(), None);
+    }
+}
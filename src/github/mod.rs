@@ -1,3 +1,5 @@
+/// File-based cache of GitHub API responses.
+pub mod cache;
 /// HTTP client for the GitHub Releases API.
 pub mod client;
 /// Data models deserialised from GitHub API responses.
@@ -2,8 +2,11 @@
 pub mod release;
 /// GitHub release asset model.
 pub mod release_asset;
+/// GitHub repository search models.
+pub mod repository;
 
 // Re-export the structs/items you want to be accessible
 // directly via `crate::github::models::`
 pub use release::Release;
 pub use release_asset::ReleaseAsset;
+pub use repository::{Repository, RepositorySearch};
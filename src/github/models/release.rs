@@ -11,9 +11,27 @@ pub struct Release {
     published_at: String,
     /// List of release assets attached to this release.
     assets: Vec<ReleaseAsset>,
+    /// Markdown release notes. GitHub returns `null` when none were written.
+    #[serde(default)]
+    body: Option<String>,
 }
 
 impl Release {
+    /// Build a release from its parts, e.g. when converting responses of other services.
+    pub fn new(
+        tag_name: String,
+        published_at: String,
+        assets: Vec<ReleaseAsset>,
+        body: Option<String>,
+    ) -> Self {
+        Self {
+            tag_name,
+            published_at,
+            assets,
+            body,
+        }
+    }
+
     /// Returns the release tag name.
     pub fn tag_name(&self) -> &String {
         &self.tag_name
@@ -28,4 +46,9 @@ impl Release {
     pub fn assets(&self) -> &Vec<ReleaseAsset> {
         &self.assets
     }
+
+    /// Returns the release notes, or an empty string when the release has none.
+    pub fn body(&self) -> &str {
+        self.body.as_deref().unwrap_or_default()
+    }
 }
@@ -7,8 +7,20 @@ pub struct ReleaseAsset {
     name: String,
     /// Direct download URL for the asset.
     browser_download_url: String,
+    /// MIME type of the asset, when the source reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
 }
 impl ReleaseAsset {
+    /// Build an asset from its file name and download URL.
+    pub fn new(name: String, browser_download_url: String) -> Self {
+        Self {
+            name,
+            browser_download_url,
+            content_type: None,
+        }
+    }
+
     /// Returns the asset file name.
     pub fn name(&self) -> &String {
         &self.name
@@ -18,4 +30,9 @@ impl ReleaseAsset {
     pub fn browser_download_url(&self) -> &String {
         &self.browser_download_url
     }
+
+    /// Returns the MIME type of the asset, if known.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// A repository as returned by the GitHub search API.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Repository {
+    /// Slug of the repository in the format `user/repo`.
+    full_name: String,
+    /// Number of stars of the repository.
+    #[serde(default)]
+    stargazers_count: u64,
+    /// Short description. GitHub returns `null` when none was written.
+    #[serde(default)]
+    description: Option<String>,
+}
+
+impl Repository {
+    /// Returns the `user/repo` slug of the repository.
+    pub fn full_name(&self) -> &String {
+        &self.full_name
+    }
+
+    /// Returns the number of stars of the repository.
+    pub fn stargazers_count(&self) -> u64 {
+        self.stargazers_count
+    }
+
+    /// Returns the description of the repository, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+/// A page of results of the GitHub repository search API.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RepositorySearch {
+    /// Repositories matching the query, best match first.
+    items: Vec<Repository>,
+}
+
+impl RepositorySearch {
+    /// Consumes the search page, returning the matching repositories.
+    pub fn into_items(self) -> Vec<Repository> {
+        self.items
+    }
+}
@@ -0,0 +1,157 @@
+//! GitLab API interaction for fetching releases and assets.
+
+use anyhow::{anyhow, Context, Result};
+use log::{debug, error, info};
+use reqwest::blocking::{Client, RequestBuilder};
+
+use crate::constants::{GITLAB_API_URL, GITLAB_API_URL_ENV, GITLAB_TOKEN_ENV};
+use crate::core::http::{self, StatusError};
+use crate::github::cache;
+use crate::github::models::Release;
+
+use super::models::GitlabRelease;
+
+/// `User-Agent` header value sent with every GitLab API request.
+const GITLAB_API_USER_AGENT: &str = "pirafrank/poof";
+
+/// Get the base API URL from environment or use the default
+fn get_base_api_url() -> String {
+    std::env::var(GITLAB_API_URL_ENV).unwrap_or_else(|_| GITLAB_API_URL.to_string())
+}
+
+/// Percent-encodes the characters of a path segment GitLab expects encoded
+/// (project paths are passed as `group%2Fproject`).
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .replace('%', "%25")
+        .replace('/', "%2F")
+        .replace('+', "%2B")
+}
+
+/// Fetch a GitLab release for `project` (in `group/project` format).
+///
+/// When `tag` is `None` the latest release is retrieved. When a tag string is
+/// provided that specific release tag is fetched. Attaches the `GITLAB_TOKEN`
+/// environment variable as `PRIVATE-TOKEN` when available, to access private
+/// projects. The base API URL can be overridden via `POOF_GITLAB_API_URL`.
+///
+/// The release is converted into the common [`Release`] model, so that the
+/// asset selection can be shared with GitHub. Responses are cached like
+/// GitHub ones when `use_cache` is `true`.
+pub fn get_release(project: &str, tag: Option<&str>, use_cache: bool) -> Result<Release> {
+    let release_url = get_release_url(project, tag);
+    info!("Release URL: {}", release_url);
+
+    let body = match use_cache.then(|| cache::read(&release_url)).flatten() {
+        Some(body) => body,
+        None if use_cache => match fetch_release_body(&release_url) {
+            Ok(body) => {
+                cache::write(&release_url, &body);
+                body
+            }
+            Err(e) => cache::fallback_stale(&release_url, e)?,
+        },
+        None => fetch_release_body(&release_url)?,
+    };
+
+    let release: Release = serde_json::from_str::<GitlabRelease>(&body)
+        .map(Release::from)
+        .map_err(|e| {
+            error!("Cannot parse JSON response: {}", e);
+            anyhow!(e).context(format!("Cannot parse JSON response from {}", release_url))
+        })?;
+    if let Some(tag) = tag {
+        info!("Selected release tag: {}", tag);
+    } else {
+        info!("Current latest release tag: {}", release.tag_name());
+    }
+    debug!("Published at: {}", release.published_at());
+    debug!("Available assets:");
+    for asset in release.assets() {
+        debug!("\t{}", asset.name());
+    }
+    Ok(release)
+}
+
+/// Fetch the tags of the most recent GitLab releases of `project`, newest first.
+pub fn get_release_tags(project: &str) -> Result<Vec<String>> {
+    let releases_url = format!(
+        "{}/projects/{}/releases?per_page=100",
+        get_base_api_url(),
+        encode_path_segment(project)
+    );
+    info!("Releases URL: {}", releases_url);
+    let body = fetch_release_body(&releases_url)?;
+    let releases: Vec<GitlabRelease> = serde_json::from_str(&body).map_err(|e| {
+        error!("Cannot parse JSON response: {}", e);
+        anyhow!(e).context(format!("Cannot parse JSON response from {}", releases_url))
+    })?;
+    Ok(releases
+        .into_iter()
+        .map(|release| Release::from(release).tag_name().to_string())
+        .collect())
+}
+
+/// Sends the GET request for `release_url` and returns the raw body of a successful response.
+fn fetch_release_body(release_url: &str) -> Result<String> {
+    let client: Client = http::client()?;
+
+    let mut request: RequestBuilder = client
+        .get(release_url)
+        .header("User-Agent", GITLAB_API_USER_AGENT);
+
+    // Add token header if available to access private projects
+    if let Ok(token) = std::env::var(GITLAB_TOKEN_ENV) {
+        if !token.is_empty() {
+            request = request.header("PRIVATE-TOKEN", token);
+        }
+    }
+
+    let response = request.send().map_err(|e| {
+        if !e.is_timeout() {
+            error!("Failed: {}. Are you connected to the internet?", e);
+        }
+        http::request_error(e, release_url)
+    })?;
+    let status = response.status();
+    debug!("Response Status: {}", status);
+    if status.is_success() {
+        response
+            .text()
+            .with_context(|| format!("Cannot read response body from {}", release_url))
+    } else {
+        error!("Request failed with status: {}", status);
+        let error_body = response
+            .text()
+            .unwrap_or_else(|_| "Cannot read error response body".to_string());
+        Err(anyhow!(StatusError {
+            url: release_url.to_string(),
+            status,
+            body: error_body,
+        }))
+    }
+}
+
+/// Build the GitLab API URL for a release.
+///
+/// Returns the `/releases/{tag}` endpoint when a specific tag is requested
+/// or the `/releases/permalink/latest` endpoint otherwise.
+pub fn get_release_url(project: &str, tag: Option<&str>) -> String {
+    let base_url = get_base_api_url();
+    let project = encode_path_segment(project);
+    match tag {
+        Some(tag) => format!(
+            "{}/projects/{}/releases/{}",
+            base_url,
+            project,
+            encode_path_segment(tag)
+        ),
+        None => format!(
+            "{}/projects/{}/releases/permalink/latest",
+            base_url, project
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests;
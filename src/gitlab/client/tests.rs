@@ -0,0 +1,172 @@
+//! Unit tests for GitLab client functions
+//! Tests without making actual network calls
+
+use super::*;
+use mockito::Server;
+use serde_json::json;
+
+/// A trimmed down GitLab release response.
+fn release_response() -> serde_json::Value {
+    json!({
+        "name": "v1.2.0",
+        "tag_name": "v1.2.0",
+        "description": "## Changes\n\n- Fixes",
+        "created_at": "2024-03-01T10:00:00.000Z",
+        "released_at": "2024-03-01T10:00:00.000Z",
+        "upcoming_release": false,
+        "assets": {
+            "count": 3,
+            "sources": [
+                {
+                    "format": "tar.gz",
+                    "url": "https://gitlab.com/group/project/-/archive/v1.2.0/project-v1.2.0.tar.gz"
+                }
+            ],
+            "links": [
+                {
+                    "id": 1,
+                    "name": "tool-x86_64-unknown-linux-gnu.tar.gz",
+                    "url": "https://gitlab.com/group/project/-/package_files/1/download",
+                    "direct_asset_url": "https://gitlab.com/group/project/-/releases/v1.2.0/downloads/tool-x86_64-unknown-linux-gnu.tar.gz",
+                    "link_type": "package"
+                },
+                {
+                    "id": 2,
+                    "name": "tool-aarch64-apple-darwin.tar.gz",
+                    "url": "https://gitlab.com/group/project/-/package_files/2/download",
+                    "link_type": "package"
+                }
+            ]
+        }
+    })
+}
+
+mod get_release_url {
+    use super::*;
+
+    #[test]
+    fn test_latest_release_url() {
+        let url = temp_env::with_var_unset("POOF_GITLAB_API_URL", || {
+            get_release_url("group/project", None)
+        });
+        assert_eq!(
+            url,
+            "https://gitlab.com/api/v4/projects/group%2Fproject/releases/permalink/latest"
+        );
+    }
+
+    #[test]
+    fn test_specific_tag_release_url() {
+        let url = temp_env::with_var_unset("POOF_GITLAB_API_URL", || {
+            get_release_url("group/project", Some("v1.0.0"))
+        });
+        assert_eq!(
+            url,
+            "https://gitlab.com/api/v4/projects/group%2Fproject/releases/v1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_tag_with_slash_is_encoded() {
+        let url = temp_env::with_var_unset("POOF_GITLAB_API_URL", || {
+            get_release_url("group/project", Some("tool/v1.0.0"))
+        });
+        assert!(url.ends_with("/releases/tool%2Fv1.0.0"));
+    }
+}
+
+mod get_release {
+    use super::*;
+
+    #[test]
+    fn test_release_is_converted_to_common_model() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/projects/group%2Fproject/releases/permalink/latest")
+            .match_header("User-Agent", "pirafrank/poof")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(release_response().to_string())
+            .create();
+
+        let release = temp_env::with_var("POOF_GITLAB_API_URL", Some(server.url()), || {
+            get_release("group/project", None, false)
+        })
+        .unwrap();
+        mock.assert();
+
+        assert_eq!(release.tag_name(), "v1.2.0");
+        assert_eq!(release.published_at(), "2024-03-01T10:00:00.000Z");
+        assert_eq!(release.body(), "## Changes\n\n- Fixes");
+        // source archives are not release assets
+        assert_eq!(release.assets().len(), 2);
+        assert_eq!(
+            release.assets()[0].name(),
+            "tool-x86_64-unknown-linux-gnu.tar.gz"
+        );
+        // the permanent URL is preferred when available
+        assert_eq!(
+            release.assets()[0].browser_download_url(),
+            "https://gitlab.com/group/project/-/releases/v1.2.0/downloads/tool-x86_64-unknown-linux-gnu.tar.gz"
+        );
+        assert_eq!(
+            release.assets()[1].browser_download_url(),
+            "https://gitlab.com/group/project/-/package_files/2/download"
+        );
+    }
+
+    #[test]
+    fn test_private_token_is_sent() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/projects/group%2Fproject/releases/v1.2.0")
+            .match_header("PRIVATE-TOKEN", "glpat-test")
+            .with_status(200)
+            .with_body(release_response().to_string())
+            .create();
+
+        let result = temp_env::with_vars(
+            [
+                ("POOF_GITLAB_API_URL", Some(server.url())),
+                ("GITLAB_TOKEN", Some("glpat-test".to_string())),
+            ],
+            || get_release("group/project", Some("v1.2.0"), false),
+        );
+        mock.assert();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_not_found() {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/projects/group%2Fmissing/releases/permalink/latest")
+            .with_status(404)
+            .with_body(r#"{"message":"404 Not Found"}"#)
+            .create();
+
+        let result = temp_env::with_var("POOF_GITLAB_API_URL", Some(server.url()), || {
+            get_release("group/missing", None, false)
+        });
+        mock.assert();
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("404"), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_assets_are_selected_like_github_ones() {
+        let release: Release = serde_json::from_value::<GitlabRelease>(release_response())
+            .map(Release::from)
+            .unwrap();
+        let triple = crate::models::asset_triple::AssetTriple::default();
+        // the selection pipeline works on the converted release
+        let result = crate::github::client::get_assets(&release, &triple);
+        #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+        assert_eq!(
+            result.unwrap()[0].name(),
+            "tool-x86_64-unknown-linux-gnu.tar.gz"
+        );
+        #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+        let _ = result;
+    }
+}
@@ -0,0 +1,4 @@
+/// HTTP client for the GitLab Releases API.
+pub mod client;
+/// Data models deserialised from GitLab API responses.
+pub mod models;
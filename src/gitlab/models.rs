@@ -0,0 +1,61 @@
+//! GitLab release models and their conversion into the common release model.
+
+use serde::Deserialize;
+
+use crate::github::models::{Release, ReleaseAsset};
+
+/// A GitLab release as returned by the GitLab REST API.
+#[derive(Deserialize, Debug)]
+pub struct GitlabRelease {
+    /// The version tag of the release (e.g. `"v1.2.3"`).
+    tag_name: String,
+    /// ISO 8601 timestamp of when the release was published.
+    #[serde(default)]
+    released_at: Option<String>,
+    /// Markdown release notes.
+    #[serde(default)]
+    description: Option<String>,
+    /// Assets attached to the release.
+    #[serde(default)]
+    assets: GitlabAssets,
+}
+
+/// The `assets` object of a GitLab release.
+#[derive(Deserialize, Debug, Default)]
+pub struct GitlabAssets {
+    /// Links to the release files. Generated source archives are not included.
+    #[serde(default)]
+    links: Vec<GitlabAssetLink>,
+}
+
+/// A link attached to a GitLab release.
+#[derive(Deserialize, Debug)]
+pub struct GitlabAssetLink {
+    /// Display name of the link, usually the file name.
+    name: String,
+    /// URL of the linked file.
+    url: String,
+    /// Permanent URL redirecting to `url`, when available.
+    #[serde(default)]
+    direct_asset_url: Option<String>,
+}
+
+impl From<GitlabRelease> for Release {
+    fn from(release: GitlabRelease) -> Self {
+        let assets = release
+            .assets
+            .links
+            .into_iter()
+            .map(|link| {
+                let url = link.direct_asset_url.unwrap_or(link.url);
+                ReleaseAsset::new(link.name, url)
+            })
+            .collect();
+        Release::new(
+            release.tag_name,
+            release.released_at.unwrap_or_default(),
+            assets,
+            release.description,
+        )
+    }
+}
@@ -5,9 +5,10 @@
 //! It requires no root access and no system-level package manager.
 #![warn(missing_docs)]
 
-use anyhow::{bail, Context, Result};
-use clap::Parser;
-use log::{debug, error, info};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{CommandFactory, Parser};
+use log::{debug, error, info, warn};
+use regex::Regex;
 use std::process::ExitCode;
 
 /// CLI argument parsing and command definitions.
@@ -20,8 +21,12 @@ mod constants;
 mod core;
 /// Archive extraction, filesystem, and file-utility helpers.
 mod files;
+/// Gitea and Forgejo API client.
+mod gitea;
 /// GitHub API client and response models.
 mod github;
+/// GitLab API client and response models.
+mod gitlab;
 /// Domain models: slugs, spells, asset triples, and shell definitions.
 mod models;
 /// Convenience macros for user-facing output.
@@ -30,22 +35,72 @@ mod output;
 mod utils;
 
 // Use modules locally
-use crate::cli::{Cli, Cmd};
-use crate::constants::THIS_REPO_URL;
+use crate::cli::{Cli, Cmd, CmdArgs};
+use crate::constants::{ASSET_PATTERN_ENV, THIS_REPO_URL, TIMEOUT_ENV};
+use crate::core::config;
+use crate::models::asset_triple::AssetTriple;
 use crate::models::slug::Slug;
+use crate::models::source::Source;
 use crate::models::spell::Spell;
-use crate::utils::semver::SemverStringConversion;
+use crate::models::supported_shells::SupportedShell;
 
-/// Returns `true` if the current OS is supported by poof (Linux or macOS).
+/// Returns `true` if the current OS is supported by poof (Linux, macOS, FreeBSD or Windows).
 fn is_supported_os() -> bool {
-    cfg!(any(target_os = "linux", target_os = "macos"))
+    cfg!(any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "freebsd",
+        target_os = "windows"
+    ))
 }
 
-/// Initialises logging, parses CLI arguments, and dispatches to the correct subcommand handler.
-fn run() -> Result<ExitCode> {
-    // Set up logging using RUST_LOG environment variable (defaults to info level)
+/// Builds the [`AssetTriple`] for the current platform, applying the `--musl` / `--gnu` overrides,
+/// or the `prefer_musl` config setting when neither flag is given.
+///
+/// The `--asset-pattern` override, or `POOF_ASSET_PATTERN` without the flag, replaces
+/// the platform detection. An invalid pattern in the environment is reported here,
+/// before any request is made.
+fn asset_triple_from_args(args: &CmdArgs) -> Result<AssetTriple> {
+    let musl = if args.musl {
+        Some(true)
+    } else if args.gnu {
+        Some(false)
+    } else {
+        None
+    };
+    let pattern = match &args.asset_pattern {
+        Some(pattern) => Some(pattern.clone()),
+        None => std::env::var(ASSET_PATTERN_ENV)
+            .ok()
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| {
+                Regex::new(&pattern).map_err(|e| {
+                    anyhow!(
+                        "Invalid asset pattern '{}' in {}: {}",
+                        pattern,
+                        ASSET_PATTERN_ENV,
+                        e
+                    )
+                })
+            })
+            .transpose()?,
+    };
+    Ok(AssetTriple::default()
+        .with_musl(core::config::get().musl_preference(musl))
+        .with_pattern(pattern))
+}
+
+/// Initialises logging and dispatches to the correct subcommand handler.
+fn run(cli: &Cli) -> Result<ExitCode> {
+    // Set up logging using RUST_LOG environment variable (defaults to info level),
+    // --quiet keeps the errors only
+    let level = if cli.quiet {
+        log::LevelFilter::Error
+    } else {
+        log::LevelFilter::Info
+    };
     env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
+        .filter_level(level)
         .format_timestamp(None)
         .format_module_path(false)
         .format_target(false)
@@ -75,46 +130,140 @@ fn run() -> Result<ExitCode> {
         );
     }
 
-    // Parse command-line arguments
-    let cli = Cli::parse();
+    // Network code reads the timeout from the environment, the flag takes precedence
+    if let Some(timeout) = cli.timeout {
+        std::env::set_var(TIMEOUT_ENV, timeout.to_string());
+    }
+
+    // Load user defaults, CLI flags override them
+    core::config::init(core::config::Config::load()?);
+    // A bin directory set by the user is checked once, before any command uses it
+    files::datadirs::validate_bin_dir()?;
 
     // Execute different logic based on command
     match &cli.command {
-        Cmd::Download(args) => {
+        Cmd::Download(download_args) => {
+            let args = &download_args.common;
+            let repo = args.repo.as_deref().context("Repository is required")?;
+            let download_dir = match download_args.output {
+                Some(ref dir) => dir.clone(),
+                None => std::env::current_dir().context("Cannot determine current directory")?,
+            };
             info!(
-                "Downloading {} {} to current dir",
-                &args.repo,
-                args.tag.as_deref().unwrap_or("(latest)")
+                "Downloading {} {} to {}",
+                repo,
+                args.tag.as_deref().unwrap_or("(latest)"),
+                download_dir.display()
             );
-            let current_dir =
-                std::env::current_dir().context("Cannot determine current directory")?;
-            debug!("Working directory: {}", current_dir.display());
+            debug!("Working directory: {}", download_dir.display());
 
-            let (_, assets) = commands::install::select_assets(&args.repo, args.tag.as_deref())?;
+            let triple = asset_triple_from_args(args)?.with_arch(download_args.arch.as_deref());
+            let (_, assets) =
+                commands::install::select_assets(repo, args.tag.as_deref(), &triple, true)?;
 
             for asset in assets {
-                commands::download::download_asset(
+                let downloaded = commands::download::download_asset(
                     asset.name(),
                     asset.browser_download_url(),
-                    &current_dir,
+                    &download_dir,
                 )
                 .with_context(|| {
                     format!(
                         "Cannot download asset for {} version {}",
-                        args.repo,
+                        repo,
                         args.tag.as_deref().unwrap_or("(latest)")
                     )
                 })?;
+                commands::download::log_asset_checksum(asset.name(), &downloaded)?;
+                if download_args.extract {
+                    let execs = commands::download::extract_asset(&downloaded, &download_dir)?;
+                    if execs.is_empty() {
+                        warn!("No executables found in {}", asset.name());
+                    }
+                    for exec in execs {
+                        output!("{}", exec.display());
+                    }
+                }
             }
             info!("All done.");
         }
-        Cmd::Install(args) => {
-            info!(
-                "Installing {} {}",
-                &args.repo,
-                args.tag.as_deref().unwrap_or("(latest)")
-            );
-            commands::install::install(&args.repo, args.tag.as_deref())?;
+        Cmd::Install(install_args) => {
+            let args = &install_args.common;
+            let options = commands::install::InstallOptions {
+                rename: install_args.rename.clone(),
+                binary: install_args.binary.clone(),
+                no_cache: install_args.no_cache,
+                no_symlink: install_args.no_symlink,
+                extract_dir: install_args.extract_dir.clone(),
+                // recorded for later commands, which may run from elsewhere
+                bin_dir: install_args
+                    .bin_dir
+                    .as_deref()
+                    .map(std::path::absolute)
+                    .transpose()
+                    .context("Cannot resolve the --bin-dir path")?,
+                keep_versions: config::get().keep_versions(install_args.keep_versions),
+                allow_root: install_args.allow_root,
+                print_path: if install_args.print_path_only {
+                    Some(commands::install::PrintPath::Only)
+                } else if install_args.print_path {
+                    Some(commands::install::PrintPath::Proceed)
+                } else {
+                    None
+                },
+                extract_only: install_args.extract_only,
+                force: install_args.force,
+                update_from: None,
+                #[cfg(feature = "cosign")]
+                verify_cosign: install_args.verify_cosign,
+            };
+            if let Some(ref url) = install_args.url {
+                info!("Installing {}", url);
+                commands::install::install_from_url(
+                    url,
+                    install_args.name.as_deref(),
+                    args.tag.as_deref(),
+                    &options,
+                )?;
+            } else if let Some(ref source) = install_args.source {
+                let repo = args.repo.as_deref().context("Repository is required")?;
+                if Source::split(repo).0 != Source::GitHub {
+                    bail!(
+                        "--source needs a repository in the format USERNAME/REPO, got: {}",
+                        repo
+                    );
+                }
+                info!("Installing {} as {}", source, repo);
+                commands::install::install_from_url(
+                    source,
+                    Some(repo),
+                    args.tag.as_deref(),
+                    &options,
+                )?;
+            } else {
+                let repo = args.repo.as_deref().context("Repository is required")?;
+                let triple = asset_triple_from_args(args)?;
+                let tag = match install_args.constraint {
+                    Some(ref req) => {
+                        Some(commands::install::resolve_version_constraint(repo, req)?)
+                    }
+                    None => args.tag.clone(),
+                };
+                if install_args.check_only {
+                    return commands::install::check_only(
+                        repo,
+                        tag.as_deref(),
+                        &triple,
+                        install_args.json,
+                    );
+                }
+                info!(
+                    "Installing {} {}",
+                    repo,
+                    tag.as_deref().unwrap_or("(latest)")
+                );
+                commands::install::install(repo, tag.as_deref(), &triple, &options)?;
+            }
         }
         Cmd::Use(args) => {
             if let Some(ref version) = args.version {
@@ -128,14 +277,45 @@ fn run() -> Result<ExitCode> {
                     &args.repo
                 );
             }
-            commands::make_default::set_default(&args.repo, args.version.as_deref())?;
+            let from_version = commands::list::active_versions().remove(&args.repo);
+            let result = commands::make_default::set_default(&args.repo, args.version.as_deref());
+            let to_version = result.as_deref().ok().or(args.version.as_deref());
+            commands::history::record(
+                commands::history::HistoryCommand::Use,
+                &args.repo,
+                from_version.as_deref(),
+                to_version,
+                &result,
+            );
+            result?;
         }
         Cmd::List(args) => {
-            let list: Vec<Spell> = if let Some(ref repo) = args.repo {
+            // validate the output format before doing anything else
+            let format = commands::list::ListFormat::from_args(
+                args.format.as_deref(),
+                args.json,
+                args.verbose,
+                args.outdated
+                    .then(|| std::time::Duration::from_millis(args.check_delay)),
+            )?;
+            let mut list: Vec<Spell> = if let Some(owner) =
+                args.repo.as_deref().filter(|repo| !repo.contains('/'))
+            {
+                let spells = commands::list::list_installed_spells_per_owner(owner);
+                if spells.is_empty() {
+                    bail!("No repositories of '{}' found. Check installed binaries using 'list' command.", owner);
+                }
+                spells
+            } else if let Some(ref repo) = args.repo {
                 let repo = Slug::new(repo)?;
                 match commands::list::list_installed_versions_per_slug(&repo)? {
                     Some(spell) => vec![spell],
                     None => {
+                        if let Some(hint) = files::datadirs::get_data_dir()
+                            .and_then(|dir| files::utils::similar_repos_hint(&dir, repo.as_str()))
+                        {
+                            error!("{}", hint);
+                        }
                         bail!("Repository '{}' not found. Check installed binaries using 'list' command.", repo);
                     }
                 }
@@ -143,75 +323,159 @@ fn run() -> Result<ExitCode> {
                 commands::list::list_installed_spells()
             };
 
+            if let Some(sort_by) = args.sort_by {
+                commands::list::sort_spells(&mut list, sort_by, args.reverse);
+            } else if args.reverse {
+                list.reverse();
+            }
+
             // output the list
-            if list.is_empty() {
-                info!("No installed binaries found.");
+            if args.only_outdated {
+                commands::list::print_outdated(
+                    &list,
+                    std::time::Duration::from_millis(args.check_delay),
+                )?;
             } else {
-                output!("");
-                output!("{:<40}\t{}", "Repository", "Versions");
-                output!("{:<40}\t{}", "----------", "--------");
-                for asset in list {
-                    output!(
-                        "{:<40}\t{}",
-                        asset.get_name(),
-                        asset.get_versions().to_string_vec().join(", ")
-                    );
-                }
+                commands::list::print_spells(&list, &format)?;
             }
         }
+        Cmd::Search(args) => {
+            commands::search::run_search(args)?;
+        }
         Cmd::Which(args) => {
             commands::which::run_which(args)?;
         }
+        Cmd::Changelog(args) => {
+            commands::changelog::show_changelog(&args.repo, args.tag.as_deref(), args.no_pager)?;
+        }
         Cmd::What(args) => {
             commands::what::run_what(args)?;
         }
         Cmd::Update(args) => {
-            commands::update::process_update(args)?; // we use ? here, it returns a Result
+            return commands::update::process_update(args);
+        }
+        Cmd::Pin(args) => {
+            commands::pin::run_pin(args)?;
+        }
+        Cmd::Unpin(args) => {
+            commands::pin::run_unpin(args)?;
         }
         Cmd::Check => {
             return commands::check::check_if_bin_in_path();
         }
-        Cmd::Version => {
-            output!("{}", crate::core::platform_info::long_version());
+        Cmd::Status(args) => {
+            return commands::status::run_status(args);
+        }
+        Cmd::Repair(args) => {
+            commands::repair::run_repair(args)?;
+        }
+        Cmd::Gc(args) => {
+            return commands::gc::run_gc(args);
+        }
+        Cmd::Prune(args) => {
+            return commands::prune::run_prune(args);
+        }
+        Cmd::Du(args) => {
+            commands::du::run_du(args)?;
+        }
+        Cmd::Verify(args) => {
+            return commands::verify::run_verify(args);
+        }
+        Cmd::Version(args) => {
+            commands::info::show_version(args.json)?;
         }
-        Cmd::Info => {
-            commands::info::show_info()?;
+        Cmd::Info(args) => match &args.repo {
+            Some(repo) => commands::info::show_repo_info(repo, args.json)?,
+            None => commands::info::show_info(args.json)?,
+        },
+        Cmd::Show(args) => {
+            commands::show::run_show(args)?;
+        }
+        Cmd::History(args) => {
+            commands::history::run_history(args)?;
+        }
+        Cmd::EnvVars(args) => {
+            commands::env_vars::run_env_vars(args);
         }
         Cmd::Enable(args) => {
-            commands::enable::run(args.shell)?;
+            commands::enable::run(shell_or_detected(args.shell))?;
         }
-        Cmd::Clean => {
-            commands::clean::run_clean()?;
+        Cmd::Disable(args) => {
+            commands::disable::run(shell_or_detected(args.shell))?;
+        }
+        Cmd::Clean(args) => {
+            commands::clean::run_clean(args)?;
         }
         Cmd::Unlink(args) => {
             commands::unlink::run_unlink(args)?;
         }
+        Cmd::Link(args) => {
+            commands::link::run_link(args)?;
+        }
         Cmd::Uninstall(args) => {
             commands::uninstall::run_uninstall(args)?;
         }
         Cmd::Completions(args) => {
-            commands::completions::generate_completions(args.shell);
+            let shell = shell_or_detected(args.shell.shell);
+            if args.install {
+                commands::completions::install_completions(shell, args.yes)?;
+            } else {
+                commands::completions::generate_completions(shell);
+            }
         }
         Cmd::Init(args) => {
-            commands::init::generate_init_script(args.shell)?;
+            commands::init::generate_init_script(shell_or_detected(args.shell))?;
         }
     }
     Ok(ExitCode::SUCCESS)
 }
 
-/// Binary entry point; delegates to [`run`] and maps errors to a non-zero exit code.
+/// Returns `shell`, or the shell detected from `$SHELL` when not given.
+/// Exits with a usage error when neither is available, as clap does for missing arguments.
+fn shell_or_detected(shell: Option<SupportedShell>) -> SupportedShell {
+    if let Some(shell) = shell.or_else(SupportedShell::detect) {
+        return shell;
+    }
+    Cli::command()
+        .error(
+            clap::error::ErrorKind::MissingRequiredArgument,
+            "the following required arguments were not provided:\n  --shell <SHELL>\n\n\
+            The current shell cannot be detected from $SHELL.",
+        )
+        .exit()
+}
+
+/// Exit code of a command that failed with an error.
+const EXIT_FAILURE: u8 = 1;
+
+/// Returns `true` when errors are to be printed as JSON: with `--json`, or
+/// with `POOF_JSON_ERRORS` set to `1` for scripts that cannot add a flag.
+fn json_errors(cli: &Cli) -> bool {
+    cli.json
+        || std::env::var(constants::JSON_ERRORS_ENV)
+            .is_ok_and(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+}
+
+/// Binary entry point; parses CLI arguments, delegates to [`run`] and maps errors
+/// to a non-zero exit code.
 fn main() -> ExitCode {
-    match run() {
+    let cli = Cli::parse();
+    match run(&cli) {
         Ok(code) => code,
         Err(e) => {
-            if log::log_enabled!(log::Level::Debug) {
+            if json_errors(&cli) {
+                utils::output::print_json_error(&e, EXIT_FAILURE.into());
+            } else if log::log_enabled!(log::Level::Debug) {
                 // Show full chain in debug mode
                 error!("{:?}", e);
+            } else if let Some(timeout) = e.downcast_ref::<core::http::TimeoutError>() {
+                // Timeouts are buried under context, but the user can act on them
+                error!("{}", timeout);
             } else {
                 // Show only top-level error in normal mode
                 error!("{}", e);
             }
-            ExitCode::FAILURE
+            ExitCode::from(EXIT_FAILURE)
         }
     }
 }
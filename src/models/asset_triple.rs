@@ -1,4 +1,5 @@
 //! A tuple of OS, Architecture, and Musl flag.
+use regex::Regex;
 use std::{
     env::consts::{ARCH, OS},
     fmt::Display,
@@ -12,22 +13,29 @@ use crate::core::musl::target_prefers_musl;
 /// of [`target_prefers_musl`]. A custom triple can be constructed with [`new`](AssetTriple::new)
 /// for testing or cross-compilation scenarios.
 pub struct AssetTriple {
-    /// Target operating system identifier (e.g. `"linux"`, `"macos"`).
+    /// Target operating system identifier (e.g. `"linux"`, `"macos"`, `"freebsd"`).
     os: String,
     /// Target CPU architecture identifier (e.g. `"x86_64"`, `"aarch64"`).
     arch: String,
     /// Whether the target prefers musl-linked binaries.
     musl: bool,
+    /// Pattern the asset names must match, replacing the platform detection when set.
+    pattern: Option<Regex>,
 }
 
 #[allow(dead_code)]
 impl AssetTriple {
     /// Create a new [`AssetTriple`] with explicitly provided `os`, `arch`, and `musl` values.
     pub fn new(os: String, arch: String, musl: bool) -> Self {
-        Self { os, arch, musl }
+        Self {
+            os,
+            arch,
+            musl,
+            pattern: None,
+        }
     }
 
-    /// Return the operating system identifier (e.g. `"linux"`, `"macos"`).
+    /// Return the operating system identifier (e.g. `"linux"`, `"macos"`, `"freebsd"`).
     pub fn get_os(&self) -> &String {
         &self.os
     }
@@ -41,6 +49,44 @@ impl AssetTriple {
     pub fn is_musl(&self) -> bool {
         self.musl
     }
+
+    /// Return the pattern the asset names must match, if any.
+    pub fn get_pattern(&self) -> Option<&Regex> {
+        self.pattern.as_ref()
+    }
+
+    /// Return the triple with its MUSL preference replaced by `musl`, when set.
+    ///
+    /// Used to honour the `--musl` / `--gnu` CLI flags, which take precedence
+    /// over the auto-detected libc. `None` keeps the current preference.
+    pub fn with_musl(mut self, musl: Option<bool>) -> Self {
+        if let Some(musl) = musl {
+            self.musl = musl;
+        }
+        self
+    }
+
+    /// Return the triple with its CPU architecture replaced by `arch`, when set.
+    ///
+    /// Used to honour the `--arch` CLI flag of the `download` command.
+    /// `None` keeps the current architecture.
+    pub fn with_arch(mut self, arch: Option<&str>) -> Self {
+        if let Some(arch) = arch {
+            self.arch = arch.to_string();
+        }
+        self
+    }
+
+    /// Return the triple selecting the assets whose name matches `pattern`, when set.
+    ///
+    /// Used to honour the `--asset-pattern` CLI flag and the `POOF_ASSET_PATTERN`
+    /// environment variable. `None` keeps the automatic selection.
+    pub fn with_pattern(mut self, pattern: Option<Regex>) -> Self {
+        if pattern.is_some() {
+            self.pattern = pattern;
+        }
+        self
+    }
 }
 
 impl Default for AssetTriple {
@@ -49,6 +95,7 @@ impl Default for AssetTriple {
             os: OS.to_string(),
             arch: ARCH.to_string(),
             musl: target_prefers_musl(),
+            pattern: None,
         }
     }
 }
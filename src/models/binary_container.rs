@@ -13,6 +13,12 @@ pub enum BinaryContainer {
     TarBz2,
     /// Zstandard-compressed tar archive (`.tar.zst` / `.tzst`).
     TarZstd,
+    /// LZ4-compressed tar archive (`.tar.lz4`).
+    TarLz4,
+    /// Lzip-compressed tar archive (`.tar.lz`).
+    TarLz,
+    /// LZMA-compressed tar archive (`.tar.lzma`).
+    TarLzma,
     /// Uncompressed tar archive (`.tar`).
     Tar,
     /// Gzip-compressed single file (`.gz`).
@@ -23,6 +29,10 @@ pub enum BinaryContainer {
     Bz2,
     /// Zstandard-compressed single file (`.zst`).
     Zstd,
+    /// LZ4-compressed single file (`.lz4`).
+    Lz4,
+    /// LZMA-compressed single file (`.lzma`).
+    Lzma,
     /// 7-Zip archive (`.7z`).
     SevenZ,
     /// Format could not be determined.
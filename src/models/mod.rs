@@ -1,5 +1,6 @@
 pub mod asset_triple;
 pub mod binary_container;
 pub mod slug;
+pub mod source;
 pub mod spell;
 pub mod supported_shells;
@@ -3,7 +3,10 @@
 use anyhow::bail;
 use anyhow::Error;
 
-/// A validated GitHub repository slug in the form `user/repo`.
+use crate::models::source::Source;
+
+/// A validated repository slug in the form `user/repo`, optionally prefixed by
+/// its release source (e.g. `gitlab:user/repo`, see [`Source::split`]).
 ///
 /// Both the user and repository parts must be non-empty after trimming.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -15,10 +18,14 @@ pub struct Slug(pub String);
 impl Slug {
     /// Parse a `"user/repo"` string into a [`Slug`], returning an error if the
     /// format is invalid (wrong number of `/` separators or empty components).
+    /// A source prefix is kept, so that the slug names the repository on its host.
     pub fn new(repo_slug: &str) -> Result<Self, Error> {
-        let mut parts = repo_slug.split('/');
+        let (source, bare) = Source::split(repo_slug);
+        let mut parts = bare.split('/');
         match (parts.next(), parts.next(), parts.next()) {
-            (Some(user), Some(repo), None) => Ok(Slug::from_parts(user, repo)?),
+            (Some(user), Some(repo), None) => {
+                Ok(Slug(source.qualify(&Slug::from_parts(user, repo)?)))
+            }
             _ => bail!("Invalid slug format: {}", repo_slug),
         }
     }
@@ -102,6 +109,18 @@ mod tests {
         // Too many parts
         let slug = Slug::new("user/repo/extra");
         assert!(slug.is_err());
+
+        // Empty parts after the source prefix
+        let slug = Slug::new("gitlab:/repo");
+        assert!(slug.is_err());
+    }
+
+    #[test]
+    fn test_slug_new_keeps_source_prefix() {
+        let slug = Slug::new("gitlab:user/repo");
+        assert_eq!(slug.unwrap().as_str(), "gitlab:user/repo");
+        let slug = Slug::new("gitea:codeberg.org/user/repo");
+        assert_eq!(slug.unwrap().as_str(), "gitea:codeberg.org/user/repo");
     }
 
     #[test]
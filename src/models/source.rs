@@ -0,0 +1,230 @@
+//! Release hosting services poof can install software from.
+
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+
+use crate::constants::{GITHUB_SUBDIR, GITLAB_SUBDIR};
+use crate::github::models::Release;
+
+/// Prefix selecting GitLab in `gitlab:group/project` slugs.
+const GITLAB_PREFIX: &str = "gitlab:";
+/// Prefix selecting a Gitea or Forgejo instance in `gitea:host/owner/repo` slugs.
+const GITEA_PREFIX: &str = "gitea:";
+
+/// A service hosting releases.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Source {
+    /// GitHub Releases, used for plain `user/repo` slugs.
+    #[default]
+    GitHub,
+    /// GitLab Releases, used for `gitlab:group/project` slugs.
+    GitLab,
+    /// A Gitea or Forgejo instance at the given host, used for `gitea:host/owner/repo` slugs.
+    Gitea(String),
+    /// A direct download URL, given with `poof install --url`. It has no releases.
+    Url,
+}
+
+impl Source {
+    /// Splits a possibly prefixed slug into its source and the `user/repo` part.
+    pub fn split(repo: &str) -> (Source, &str) {
+        if let Some(project) = repo.strip_prefix(GITLAB_PREFIX) {
+            return (Source::GitLab, project);
+        }
+        if let Some((host, repo)) = repo
+            .strip_prefix(GITEA_PREFIX)
+            .and_then(|rest| rest.split_once('/'))
+        {
+            return (Source::Gitea(host.to_string()), repo);
+        }
+        (Source::GitHub, repo)
+    }
+
+    /// Parses the name produced by the [`Display`] implementation.
+    pub fn from_name(name: &str) -> Option<Source> {
+        match name.trim() {
+            "github" => Some(Source::GitHub),
+            "gitlab" => Some(Source::GitLab),
+            "url" => Some(Source::Url),
+            name => name
+                .strip_prefix(GITEA_PREFIX)
+                .filter(|host| !host.is_empty())
+                .map(|host| Source::Gitea(host.to_string())),
+        }
+    }
+
+    /// Returns the sub-directory of the data root holding the repositories of this source,
+    /// named after its host. URL installs have no host prefix in their names and share
+    /// the GitHub one. The port of a Gitea host is kept, after an underscore.
+    pub fn data_subdir(&self) -> String {
+        match self {
+            Source::GitHub | Source::Url => GITHUB_SUBDIR.to_string(),
+            Source::GitLab => GITLAB_SUBDIR.to_string(),
+            Source::Gitea(host) => host.replace(':', "_"),
+        }
+    }
+
+    /// Parses the sub-directory name produced by [`Source::data_subdir`].
+    pub fn from_data_subdir(name: &str) -> Source {
+        match name {
+            GITHUB_SUBDIR => Source::GitHub,
+            GITLAB_SUBDIR => Source::GitLab,
+            host => Source::Gitea(host.replace('_', ":")),
+        }
+    }
+
+    /// Returns `repo` prefixed the way it is given on the command line for this source.
+    /// An already prefixed `repo` is returned unchanged.
+    pub fn qualify(&self, repo: &str) -> String {
+        let (_, repo) = Source::split(repo);
+        match self {
+            Source::GitHub | Source::Url => repo.to_string(),
+            Source::GitLab => format!("{}{}", GITLAB_PREFIX, repo),
+            Source::Gitea(host) => format!("{}{}/{}", GITEA_PREFIX, host, repo),
+        }
+    }
+
+    /// Returns the web page of `repo` on this source, `None` for URL installs.
+    pub fn web_url(&self, repo: &str) -> Option<String> {
+        let (_, repo) = Source::split(repo);
+        match self {
+            Source::GitHub => Some(format!("https://github.com/{}", repo)),
+            Source::GitLab => Some(format!("https://gitlab.com/{}", repo)),
+            Source::Gitea(host) => Some(format!("https://{}/{}", host, repo)),
+            Source::Url => None,
+        }
+    }
+
+    /// Fetch a release of `repo` from this source, `repo` may carry its source prefix.
+    /// See [`crate::github::client::get_release`] for the meaning of the arguments.
+    pub fn get_release(&self, repo: &str, tag: Option<&str>, use_cache: bool) -> Result<Release> {
+        let (_, repo) = Source::split(repo);
+        match self {
+            Source::GitHub => crate::github::client::get_release(repo, tag, use_cache),
+            Source::GitLab => crate::gitlab::client::get_release(repo, tag, use_cache),
+            Source::Gitea(host) => crate::gitea::client::get_release(host, repo, tag, use_cache),
+            Source::Url => bail!(
+                "{} has been installed from a URL and has no releases to check. Reinstall it with 'poof install --url'.",
+                repo
+            ),
+        }
+    }
+
+    /// Fetch the tags of the most recent releases of `repo` from this source, newest first.
+    pub fn get_release_tags(&self, repo: &str) -> Result<Vec<String>> {
+        let (_, repo) = Source::split(repo);
+        match self {
+            Source::GitHub => crate::github::client::get_release_tags(repo),
+            Source::GitLab => crate::gitlab::client::get_release_tags(repo),
+            Source::Gitea(host) => crate::gitea::client::get_release_tags(host, repo),
+            Source::Url => bail!(
+                "{} has been installed from a URL and has no releases.",
+                repo
+            ),
+        }
+    }
+}
+
+/// Lowercase name of the source, with the host for Gitea (e.g. `gitea:codeberg.org`).
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::GitHub => write!(f, "github"),
+            Source::GitLab => write!(f, "gitlab"),
+            Source::Gitea(host) => write!(f, "{}{}", GITEA_PREFIX, host),
+            Source::Url => write!(f, "url"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_plain_slug() {
+        assert_eq!(Source::split("user/repo"), (Source::GitHub, "user/repo"));
+    }
+
+    #[test]
+    fn test_split_gitlab_slug() {
+        assert_eq!(
+            Source::split("gitlab:group/project"),
+            (Source::GitLab, "group/project")
+        );
+    }
+
+    #[test]
+    fn test_split_gitea_slug() {
+        assert_eq!(
+            Source::split("gitea:codeberg.org/owner/repo"),
+            (Source::Gitea("codeberg.org".to_string()), "owner/repo")
+        );
+    }
+
+    #[test]
+    fn test_qualify_roundtrip() {
+        for source in [
+            Source::GitHub,
+            Source::GitLab,
+            Source::Gitea("git.example.com:3000".to_string()),
+        ] {
+            let qualified = source.qualify("user/repo");
+            assert_eq!(Source::split(&qualified), (source.clone(), "user/repo"));
+            assert_eq!(Source::from_name(&source.to_string()), Some(source));
+        }
+    }
+
+    #[test]
+    fn test_qualify_is_idempotent() {
+        assert_eq!(
+            Source::GitLab.qualify("gitlab:group/project"),
+            "gitlab:group/project"
+        );
+        let gitea = Source::Gitea("codeberg.org".to_string());
+        assert_eq!(
+            gitea.qualify(&gitea.qualify("owner/repo")),
+            "gitea:codeberg.org/owner/repo"
+        );
+    }
+
+    #[test]
+    fn test_data_subdir_roundtrip() {
+        assert_eq!(Source::GitHub.data_subdir(), "github.com");
+        assert_eq!(Source::GitLab.data_subdir(), "gitlab.com");
+        assert_eq!(Source::Url.data_subdir(), "github.com");
+        for source in [
+            Source::GitHub,
+            Source::GitLab,
+            Source::Gitea("codeberg.org".to_string()),
+            Source::Gitea("git.example.com:3000".to_string()),
+        ] {
+            assert_eq!(Source::from_data_subdir(&source.data_subdir()), source);
+        }
+    }
+
+    #[test]
+    fn test_url_source_has_no_releases() {
+        assert_eq!(Source::from_name("url"), Some(Source::Url));
+        assert_eq!(Source::Url.qualify("example.com/tool"), "example.com/tool");
+        assert!(Source::Url
+            .get_release("example.com/tool", None, true)
+            .is_err());
+    }
+
+    #[test]
+    fn test_web_url() {
+        assert_eq!(
+            Source::GitHub.web_url("user/repo").as_deref(),
+            Some("https://github.com/user/repo")
+        );
+        assert_eq!(
+            Source::Gitea("codeberg.org".to_string())
+                .web_url("user/repo")
+                .as_deref(),
+            Some("https://codeberg.org/user/repo")
+        );
+        assert_eq!(Source::Url.web_url("example.com/tool"), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Supported shell types for init and completions commands
 
 use std::fmt;
+use std::path::Path;
 use std::str::FromStr;
 
 /// A shell supported by poof's `init` and `completions` commands.
@@ -78,4 +79,30 @@ impl SupportedShell {
             "zsh",
         ]
     }
+
+    /// Detects the current shell from the path of the `SHELL` environment variable.
+    pub fn detect() -> Option<Self> {
+        std::env::var_os("SHELL").and_then(|shell| Self::from_shell_path(Path::new(&shell)))
+    }
+
+    /// Returns the shell run by the executable at `path` (e.g. `/usr/bin/nu`), if supported.
+    fn from_shell_path(path: &Path) -> Option<Self> {
+        path.file_stem()?.to_str()?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_shell_path() {
+        let shell = |path: &str| SupportedShell::from_shell_path(Path::new(path));
+        assert_eq!(shell("/usr/bin/nu"), Some(SupportedShell::Nushell));
+        assert_eq!(shell("/opt/homebrew/bin/fish"), Some(SupportedShell::Fish));
+        assert_eq!(shell("/bin/zsh"), Some(SupportedShell::Zsh));
+        assert_eq!(shell("pwsh.exe"), Some(SupportedShell::PowerShell));
+        assert_eq!(shell("/bin/tcsh"), None);
+        assert_eq!(shell(""), None);
+    }
 }
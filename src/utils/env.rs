@@ -0,0 +1,61 @@
+//!
+//! Detection of the environment poof runs in.
+//!
+
+/// Environment variables set by the CI services poof knows about.
+const CI_VARS: [&str; 4] = ["CI", "GITHUB_ACTIONS", "TRAVIS", "CIRCLECI"];
+
+/// Returns `true` when poof runs with root privileges.
+///
+/// The effective user id is checked, so `sudo poof ...` counts as root.
+/// Falls back to the `UID` environment variable, which some shells export.
+/// Always `false` on Windows, where binaries go to the user's profile anyway.
+#[cfg(unix)]
+pub fn is_root() -> bool {
+    // SAFETY: geteuid has no preconditions and cannot fail
+    let euid = unsafe { libc::geteuid() };
+    euid == 0 || std::env::var("UID").is_ok_and(|uid| uid == "0")
+}
+
+#[cfg(not(unix))]
+pub fn is_root() -> bool {
+    false
+}
+
+/// Returns `true` when one of the well-known CI environment variables is set.
+///
+/// `CI=false` and empty values are ignored.
+pub fn is_ci() -> bool {
+    CI_VARS.iter().any(|var| {
+        std::env::var(var).is_ok_and(|value| !value.is_empty() && value != "false" && value != "0")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_is_ci_detects_known_vars() {
+        let unset: Vec<(&str, Option<&str>)> = CI_VARS.iter().map(|v| (*v, None)).collect();
+        temp_env::with_vars(unset.clone(), || assert!(!is_ci()));
+        for var in CI_VARS {
+            let mut vars = unset.clone();
+            vars.retain(|(v, _)| *v != var);
+            vars.push((var, Some("true")));
+            temp_env::with_vars(vars, || assert!(is_ci(), "{} should be detected", var));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_is_ci_ignores_false_values() {
+        let mut vars: Vec<(&str, Option<&str>)> = CI_VARS.iter().map(|v| (*v, None)).collect();
+        vars[0] = ("CI", Some("false"));
+        temp_env::with_vars(vars.clone(), || assert!(!is_ci()));
+        vars[0] = ("CI", Some(""));
+        temp_env::with_vars(vars, || assert!(!is_ci()));
+    }
+}
@@ -1,4 +1,16 @@
+/// Detection of the environment poof runs in, e.g. root or CI.
+pub mod env;
+/// Machine-readable rendering of errors.
+pub mod output;
+/// Confirmation prompts for destructive operations.
+pub mod prompt;
 /// Lenient semver parsing, comparison, and sorting helpers.
 pub mod semver;
+/// Rolling download speed and remaining time estimation.
+pub mod speed;
 /// String manipulation utilities.
 pub mod string;
+/// Placeholder expansion for user-provided output templates.
+pub mod template;
+/// Formatting of timestamps for user-facing messages.
+pub mod time;
@@ -0,0 +1,47 @@
+//!
+//! Machine-readable rendering of errors, for scripts and CI pipelines parsing stderr.
+//!
+
+/// Serializes `err` as a JSON object: the top-level message in `error`, the
+/// messages of the errors it was caused by, outermost first, in `context`, and
+/// the `exit_code` poof exits with.
+///
+/// The same data is also kept under `message` and `causes`, the names used by
+/// the first releases of `--json`.
+fn error_to_json(err: &anyhow::Error, exit_code: i32) -> serde_json::Value {
+    let context: Vec<String> = err.chain().skip(1).map(|c| c.to_string()).collect();
+    serde_json::json!({
+        "error": err.to_string(),
+        "context": context,
+        "exit_code": exit_code,
+        "message": err.to_string(),
+        "causes": context,
+    })
+}
+
+/// Print `err` to stderr as a single-line JSON object, see [`error_to_json`].
+pub fn print_json_error(err: &anyhow::Error, exit_code: i32) {
+    eprintln!("{}", error_to_json(err, exit_code));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_error_to_json() {
+        let err = anyhow!("connection refused")
+            .context("Cannot send request")
+            .context("Cannot get release information for a/b");
+        let json = error_to_json(&err, 1);
+        assert_eq!(json["error"], "Cannot get release information for a/b");
+        assert_eq!(
+            json["context"],
+            serde_json::json!(["Cannot send request", "connection refused"])
+        );
+        assert_eq!(json["exit_code"], 1);
+        assert_eq!(json["message"], json["error"]);
+        assert_eq!(json["causes"], json["context"]);
+    }
+}
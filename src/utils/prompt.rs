@@ -0,0 +1,18 @@
+//! Interactive prompts shown before destructive operations.
+
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+
+/// Asks the user to confirm, returns `true` if they answered `y` or `yes`.
+pub fn confirm() -> Result<bool> {
+    print!("Proceed? (y/yes): ");
+    io::stdout().flush().context("Cannot flush stdout")?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .context("Cannot read user input")?;
+
+    let input = input.trim().to_lowercase();
+    Ok(input == "y" || input == "yes")
+}
@@ -9,7 +9,12 @@ lazy_static! {
 }
 
 /// Parses a version string loosely, handling leading zeros, 'v'/'r' prefixes, etc.
+/// Build metadata is dropped, as it does not take part in version precedence.
 pub fn parse_lenient(version_str: &str) -> Option<semver::Version> {
+    let version_str = version_str
+        .split_once('+')
+        .map_or(version_str, |(version, _build)| version);
+
     // Try standard parse first
     if let Ok(v) = semver::Version::parse(version_str) {
         return Some(v);
@@ -254,6 +259,32 @@ impl SemverStringPrefix for &str {
     }
 }
 
+/// Returns the tag among `tags` with the highest version satisfying `req`.
+/// Tags that cannot be parsed as a version are ignored.
+pub fn newest_matching<'a>(tags: &'a [String], req: &semver::VersionReq) -> Option<&'a str> {
+    tags.iter()
+        .filter_map(|tag| parse_lenient(tag).map(|version| (version, tag)))
+        .filter(|(version, _)| req.matches(version))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag.as_str())
+}
+
+/// Returns the tags a release requested as `tag` may be published under: `tag`
+/// itself first, then the same version with its `v` prefix stripped or added,
+/// e.g. `["1.2.3", "v1.2.3"]`. Tags that are not versions are only tried as given.
+pub fn normalise_tag(tag: &str) -> Vec<String> {
+    let stripped = tag.strip_v();
+    if !stripped.starts_with(|c: char| c.is_ascii_digit()) {
+        return vec![tag.to_string()];
+    }
+    let alternative = if stripped == tag {
+        format!("v{}", tag)
+    } else {
+        stripped.to_string()
+    };
+    vec![tag.to_string(), alternative]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,4 +513,68 @@ mod tests {
         assert_eq!(v.to_string(), "01.02.03");
         assert_eq!(v.version.unwrap().to_string(), "1.2.3");
     }
+
+    #[test]
+    fn test_newest_matching() {
+        let tags: Vec<String> = ["v2.1.0", "v1.9.3", "nightly", "v1.10.0", "v1.2.0", "v0.9.0"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let req = |r: &str| semver::VersionReq::parse(r).unwrap();
+
+        assert_eq!(newest_matching(&tags, &req("^1.2")), Some("v1.10.0"));
+        assert_eq!(newest_matching(&tags, &req(">=2, <3")), Some("v2.1.0"));
+        assert_eq!(newest_matching(&tags, &req("~1.2")), Some("v1.2.0"));
+        assert_eq!(newest_matching(&tags, &req(">=3")), None);
+    }
+
+    #[test]
+    fn test_newest_matching_skips_prereleases() {
+        let tags = vec!["v2.0.0-rc.1".to_string(), "v1.5.0".to_string()];
+        let req = semver::VersionReq::parse(">=1").unwrap();
+        assert_eq!(newest_matching(&tags, &req), Some("v1.5.0"));
+    }
+
+    #[test]
+    fn test_parse_lenient_ignores_build_metadata() {
+        let plain = parse_lenient("1.2.0").unwrap();
+        assert_eq!(parse_lenient("1.2.0+build.7").unwrap(), plain);
+        assert_eq!(parse_lenient("v1.2.0+20240101").unwrap(), plain);
+        assert_eq!(
+            parse_lenient("1.2.0-rc1+build").unwrap(),
+            parse_lenient("1.2.0-rc1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_raw_version_v_prefix_and_build_metadata_are_equal() {
+        let installed = RawVersion::new("1.2.0".to_string());
+        for tag in ["v1.2.0", "V1.2.0", "1.2.0+build", "v1.2.0+build.7"] {
+            let latest = RawVersion::parse(tag).unwrap();
+            assert_eq!(latest, installed, "{} should equal 1.2.0", tag);
+            assert!(
+                latest <= installed,
+                "{} should not be newer than 1.2.0",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn test_raw_version_prerelease_ordering() {
+        let rc = RawVersion::parse("1.2.0-rc1").unwrap();
+        let release = RawVersion::parse("v1.2.0").unwrap();
+        assert!(rc < release);
+        assert!(RawVersion::parse("v1.2.0-rc1+build").unwrap() < release);
+    }
+
+    #[test]
+    fn test_normalise_tag() {
+        assert_eq!(normalise_tag("1.0.0"), vec!["1.0.0", "v1.0.0"]);
+        assert_eq!(normalise_tag("v1.0.0"), vec!["v1.0.0", "1.0.0"]);
+        assert_eq!(normalise_tag("V2"), vec!["V2", "2"]);
+        // not versions, nothing to add or strip
+        assert_eq!(normalise_tag("nightly"), vec!["nightly"]);
+        assert_eq!(normalise_tag("vendor-1.0"), vec!["vendor-1.0"]);
+    }
 }
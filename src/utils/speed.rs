@@ -0,0 +1,143 @@
+//!
+//! Rolling download speed and remaining time estimation.
+//!
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Number of samples the rolling average is computed over.
+const WINDOW_SIZE: usize = 10;
+
+/// Tracks the download speed over a sliding window of the most recent samples.
+#[derive(Debug, Default)]
+pub struct SpeedTracker {
+    /// `(downloaded bytes, timestamp)` pairs, oldest first.
+    samples: VecDeque<(u64, Instant)>,
+}
+
+impl SpeedTracker {
+    /// Creates a tracker with no samples.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `downloaded` bytes have been received so far, at time `at`.
+    pub fn record(&mut self, downloaded: u64, at: Instant) {
+        if self.samples.len() == WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((downloaded, at));
+    }
+
+    /// Average speed over the window, `0.0` until two samples have been recorded.
+    pub fn bytes_per_sec(&self) -> f64 {
+        let (Some((first_bytes, first_at)), Some((last_bytes, last_at))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0.0;
+        };
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        last_bytes.saturating_sub(*first_bytes) as f64 / elapsed
+    }
+
+    /// Seconds left to download `total_bytes` at the current speed,
+    /// infinite while the speed is unknown.
+    pub fn eta_seconds(&self, total_bytes: u64, downloaded: u64) -> f64 {
+        let speed = self.bytes_per_sec();
+        if speed <= 0.0 {
+            return f64::INFINITY;
+        }
+        total_bytes.saturating_sub(downloaded) as f64 / speed
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Formats a number of seconds as `1h02m`, `3m05s` or `42s`, `?` when unknown.
+pub fn format_eta(seconds: f64) -> String {
+    if !seconds.is_finite() || seconds < 0.0 {
+        return "?".to_string();
+    }
+    let seconds = seconds.ceil() as u64;
+    match seconds {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, (s % 3600) / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{}s", s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Feeds the tracker one `(bytes, milliseconds since start)` sample after the other.
+    fn tracker_with(samples: &[(u64, u64)]) -> SpeedTracker {
+        let start = Instant::now();
+        let mut tracker = SpeedTracker::new();
+        for (bytes, ms) in samples {
+            tracker.record(*bytes, start + Duration::from_millis(*ms));
+        }
+        tracker
+    }
+
+    #[test]
+    fn test_speed_unknown_without_samples() {
+        assert_eq!(tracker_with(&[]).bytes_per_sec(), 0.0);
+        assert_eq!(tracker_with(&[(1000, 0)]).bytes_per_sec(), 0.0);
+        assert!(tracker_with(&[(1000, 0)])
+            .eta_seconds(2000, 1000)
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_speed_constant_rate() {
+        // 1000 bytes every 100ms
+        let samples: Vec<(u64, u64)> = (0..5).map(|i| (i * 1000, i * 100)).collect();
+        let tracker = tracker_with(&samples);
+        assert!((tracker.bytes_per_sec() - 10_000.0).abs() < 1e-6);
+        assert!((tracker.eta_seconds(24_000, 4_000) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_speed_only_considers_recent_samples() {
+        // a slow start: 100 bytes every 100ms for 1s...
+        let mut samples: Vec<(u64, u64)> = (0..10).map(|i| (i * 100, i * 100)).collect();
+        // ...then 10 samples at 1000 bytes every 100ms push the slow ones out of the window
+        samples.extend((1..=10).map(|i| (900 + i * 1000, 900 + i * 100)));
+        let tracker = tracker_with(&samples);
+        assert!((tracker.bytes_per_sec() - 10_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(10 * 1024 * 1024), "10.0 MiB");
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(f64::INFINITY), "?");
+        assert_eq!(format_eta(41.2), "42s");
+        assert_eq!(format_eta(185.0), "3m05s");
+        assert_eq!(format_eta(3720.0), "1h02m");
+    }
+}
@@ -0,0 +1,143 @@
+//!
+//! Lightweight template expander for user-provided output formats.
+//!
+//! Templates are plain strings where `{token}` placeholders are replaced by
+//! values. `\t` and `\n` escape sequences are turned into tabs and newlines, so
+//! that formats can be easily passed from a shell.
+//!
+
+use anyhow::{bail, Result};
+
+/// A single piece of a parsed template.
+#[derive(Debug, PartialEq)]
+enum Part {
+    /// Text copied as-is to the output.
+    Literal(String),
+    /// Name of a placeholder to be replaced by its value.
+    Token(String),
+}
+
+/// A parsed template, ready to be rendered many times.
+#[derive(Debug)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parse `format`, accepting only the placeholders listed in `tokens`.
+    ///
+    /// Returns an error for unknown or unterminated placeholders, so that
+    /// mistakes are reported before any output is produced.
+    pub fn parse(format: &str, tokens: &[&str]) -> Result<Self> {
+        let mut parts: Vec<Part> = Vec::new();
+        let mut literal = String::new();
+        let mut chars = format.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some(other) => {
+                        literal.push('\\');
+                        literal.push(other);
+                    }
+                    None => literal.push('\\'),
+                },
+                '{' => {
+                    let mut token = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        token.push(c);
+                    }
+                    if !closed {
+                        bail!("Unterminated placeholder '{{{}' in format template.", token);
+                    }
+                    if !tokens.contains(&token.as_str()) {
+                        bail!(
+                            "Unknown placeholder '{{{}}}' in format template. Supported placeholders: {}",
+                            token,
+                            tokens
+                                .iter()
+                                .map(|t| format!("{{{}}}", t))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        );
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Token(token));
+                }
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Render the template, asking `value_of` for the value of each placeholder.
+    pub fn render<F>(&self, value_of: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => text.clone(),
+                Part::Token(token) => value_of(token),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKENS: &[&str] = &["repo", "count"];
+
+    #[test]
+    fn test_render_replaces_tokens() {
+        let template = Template::parse("{repo} has {count} versions", TOKENS).unwrap();
+        let rendered = template.render(|token| match token {
+            "repo" => "owner/repo".to_string(),
+            _ => "2".to_string(),
+        });
+        assert_eq!(rendered, "owner/repo has 2 versions");
+    }
+
+    #[test]
+    fn test_parse_handles_escapes() {
+        let template = Template::parse("{repo}\\t{count}\\n", TOKENS).unwrap();
+        assert_eq!(template.render(|t| t.to_string()), "repo\tcount\n");
+        let template = Template::parse("a\\b", TOKENS).unwrap();
+        assert_eq!(template.render(|t| t.to_string()), "a\\b");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_token() {
+        let err = Template::parse("{repo} {nope}", TOKENS).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("{nope}"), "{}", msg);
+        assert!(msg.contains("{repo}, {count}"), "{}", msg);
+    }
+
+    #[test]
+    fn test_parse_rejects_unterminated_token() {
+        assert!(Template::parse("{repo", TOKENS).is_err());
+    }
+
+    #[test]
+    fn test_parse_without_tokens() {
+        let template = Template::parse("plain text", TOKENS).unwrap();
+        assert_eq!(template.render(|_| unreachable!()), "plain text");
+    }
+}
@@ -0,0 +1,75 @@
+//! Formatting of timestamps for user-facing messages.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Converts a number of days since the Unix epoch into a `(year, month, day)` civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's algorithm, shifted so that years start on March 1st
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp,
+/// e.g. `2024-01-01T00:00:00Z`, the format used by the release APIs.
+pub fn format_utc(seconds: u64) -> String {
+    let (year, month, day) = civil_from_days((seconds / SECONDS_PER_DAY) as i64);
+    let secs_of_day = seconds % SECONDS_PER_DAY;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Formats a [`SystemTime`] with [`format_utc`]. Times before the epoch are clamped to it.
+pub fn format_system_time(time: SystemTime) -> String {
+    format_utc(
+        time.duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_utc_epoch() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_format_utc_known_dates() {
+        assert_eq!(format_utc(1_704_067_200), "2024-01-01T00:00:00Z");
+        // leap day
+        assert_eq!(format_utc(1_709_210_096), "2024-02-29T12:34:56Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(1_735_689_599), "2024-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_format_system_time() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        assert_eq!(format_system_time(time), "2024-01-01T00:00:00Z");
+        assert_eq!(
+            format_system_time(UNIX_EPOCH - Duration::from_secs(10)),
+            "1970-01-01T00:00:00Z"
+        );
+    }
+}
@@ -18,6 +18,7 @@ pub struct TestFixture {
     pub data_dir: PathBuf,
     pub cache_dir: PathBuf,
     pub bin_dir: PathBuf,
+    pub config_dir: PathBuf,
     // Removed: original env vars no longer needed since we don't set them globally
     #[allow(dead_code)]
     original_home: Option<String>,
@@ -37,40 +38,22 @@ impl TestFixture {
         // Note: data_dir includes "github.com" subdir. Let's pretend all
         // fake binaries come from GitHub because original source doesn't really matter
         // in fake environment tests.
-        // Platform-specific paths:
-        // - Linux: ~/.local/share/poof/data/github.com
-        // - macOS: ~/Library/Application Support/poof/data/github.com
-        #[cfg(target_os = "linux")]
-        let data_dir = home_dir
-            .join(".local")
-            .join("share")
-            .join("poof")
-            .join("data")
-            .join("github.com");
-        #[cfg(target_os = "linux")]
+        // POOF_DATA_DIR and POOF_CACHE_DIR point poof to these directories,
+        // so the layout is the same on every platform:
+        // - data: ~/.local/share/poof/data/github.com
+        // - bin: ~/.local/share/poof/bin
+        // - cache: ~/.cache/poof
+        let data_root = home_dir.join(".local").join("share").join("poof");
+        let data_dir = data_root.join("data").join("github.com");
         let cache_dir = home_dir.join(".cache").join("poof");
+        let bin_dir = data_root.join("bin");
         #[cfg(target_os = "linux")]
-        let bin_dir = home_dir
-            .join(".local")
-            .join("share")
-            .join("poof")
-            .join("bin");
-
-        #[cfg(target_os = "macos")]
-        let data_dir = home_dir
-            .join("Library")
-            .join("Application Support")
-            .join("poof")
-            .join("data")
-            .join("github.com");
+        let config_dir = home_dir.join(".config").join("poof");
         #[cfg(target_os = "macos")]
-        let cache_dir = home_dir.join("Library").join("Caches").join("poof");
-        #[cfg(target_os = "macos")]
-        let bin_dir = home_dir
+        let config_dir = home_dir
             .join("Library")
             .join("Application Support")
-            .join("poof")
-            .join("bin");
+            .join("poof");
 
         std::fs::create_dir_all(&data_dir)?;
         std::fs::create_dir_all(&cache_dir)?;
@@ -85,6 +68,7 @@ impl TestFixture {
             data_dir,
             cache_dir,
             bin_dir,
+            config_dir,
             original_home: None,
             original_xdg_data_home: None,
             original_xdg_cache_home: None,
@@ -102,59 +86,55 @@ impl TestFixture {
         ("HOME", self.home_dir.to_str().unwrap().to_string())
     }
 
-    /// Returns the XDG_DATA_HOME environment variable tuple for use with Command::env()
-    /// On Linux, this points to ~/.local/share
-    /// On macOS, this returns None as XDG_DATA_HOME is not used
-    ///
-    /// # Example
-    /// ```
-    /// if let Some((key, value)) = fixture.env_data_home() {
-    ///     cmd.env(key, value);
-    /// }
-    /// ```
-    #[cfg(target_os = "linux")]
-    pub fn env_data_home(&self) -> Option<(&str, String)> {
-        Some((
-            "XDG_DATA_HOME",
+    /// Returns the POOF_DATA_DIR environment variable tuple for use with Command::env()
+    /// It points to ~/.local/share/poof, holding both the data and the bin directories
+    pub fn env_data_dir(&self) -> (&str, String) {
+        (
+            "POOF_DATA_DIR",
             self.home_dir
                 .join(".local")
                 .join("share")
+                .join("poof")
                 .to_str()
                 .unwrap()
                 .to_string(),
-        ))
+        )
     }
 
-    #[cfg(target_os = "macos")]
-    pub fn env_data_home(&self) -> Option<(&str, String)> {
-        // macOS doesn't use XDG_DATA_HOME
-        None
+    /// Returns the POOF_CACHE_DIR environment variable tuple for use with Command::env()
+    pub fn env_cache_dir(&self) -> (&str, String) {
+        (
+            "POOF_CACHE_DIR",
+            self.cache_dir.to_str().unwrap().to_string(),
+        )
     }
 
-    /// Returns the XDG_CACHE_HOME environment variable tuple for use with Command::env()
-    /// On Linux, this points to ~/.cache
-    /// On macOS, this returns None as XDG_CACHE_HOME is not used
-    ///
-    /// # Example
-    /// ```
-    /// if let Some((key, value)) = fixture.env_cache_home() {
-    ///     cmd.env(key, value);
-    /// }
-    /// ```
+    /// Returns the XDG_CONFIG_HOME environment variable tuple for use with Command::env()
+    /// On Linux, this points to ~/.config
+    /// On macOS, this returns None as XDG_CONFIG_HOME is not used
     #[cfg(target_os = "linux")]
-    pub fn env_cache_home(&self) -> Option<(&str, String)> {
+    pub fn env_config_home(&self) -> Option<(&str, String)> {
         Some((
-            "XDG_CACHE_HOME",
-            self.home_dir.join(".cache").to_str().unwrap().to_string(),
+            "XDG_CONFIG_HOME",
+            self.home_dir.join(".config").to_str().unwrap().to_string(),
         ))
     }
 
     #[cfg(target_os = "macos")]
-    pub fn env_cache_home(&self) -> Option<(&str, String)> {
-        // macOS doesn't use XDG_CACHE_HOME
+    pub fn env_config_home(&self) -> Option<(&str, String)> {
+        // macOS doesn't use XDG_CONFIG_HOME
         None
     }
 
+    /// Write `content` to poof's config file
+    #[allow(dead_code)]
+    pub fn write_config(&self, content: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(&self.config_dir)?;
+        let config_file = self.config_dir.join("config.toml");
+        std::fs::write(&config_file, content)?;
+        Ok(config_file)
+    }
+
     /// Create a fake binary installation for testing
     pub fn create_fake_installation(
         &self,
@@ -11,14 +11,19 @@ pub fn set_test_env(cmd: &mut Command, fixture: &TestFixture) {
     let (home_key, home_val) = fixture.env_home();
     cmd.env(home_key, home_val);
 
-    // these are automatically set by the TestFixture
-    // and support for Linux and macOS is handled by the TestFixture.
-    if let Some((data_key, data_val)) = fixture.env_data_home() {
-        cmd.env(data_key, data_val);
-    }
+    // data and cache directories are the same on every platform.
+    // The XDG variables of the user running the tests must not leak in,
+    // other paths like the bash completions one fall back to HOME.
+    cmd.env_remove("XDG_DATA_HOME").env_remove("XDG_CACHE_HOME");
+    let (data_key, data_val) = fixture.env_data_dir();
+    cmd.env(data_key, data_val);
+    let (cache_key, cache_val) = fixture.env_cache_dir();
+    cmd.env(cache_key, cache_val);
+
+    // the config file has no poof-specific override
 
-    if let Some((cache_key, cache_val)) = fixture.env_cache_home() {
-        cmd.env(cache_key, cache_val);
+    if let Some((config_key, config_val)) = fixture.env_config_home() {
+        cmd.env(config_key, config_val);
     }
 }
 
@@ -4,6 +4,8 @@
 #[path = "common/mod.rs"]
 mod common;
 
+#[path = "integration/commands/changelog.rs"]
+mod changelog;
 #[path = "integration/commands/check.rs"]
 mod check;
 #[path = "integration/command_handling/claps.rs"]
@@ -12,8 +14,12 @@ mod claps;
 mod clean;
 #[path = "integration/commands/completions.rs"]
 mod completions;
+#[path = "integration/command_handling/config.rs"]
+mod config;
 #[path = "integration/commands/help.rs"]
 mod help;
+#[path = "integration/commands/history.rs"]
+mod history;
 #[path = "integration/commands/info.rs"]
 mod info;
 #[path = "integration/commands/init.rs"]
@@ -21,14 +27,36 @@ mod init;
 #[path = "integration/commands/version.rs"]
 mod version;
 
+#[path = "integration/commands/disable.rs"]
+mod disable;
 #[path = "integration/commands/download.rs"]
 mod download;
+#[path = "integration/commands/du.rs"]
+mod du;
 #[path = "integration/commands/enable.rs"]
 mod enable;
+#[path = "integration/commands/env_vars.rs"]
+mod env_vars;
+#[path = "integration/commands/gc.rs"]
+mod gc;
 #[path = "integration/commands/install.rs"]
 mod install;
+#[path = "integration/commands/link.rs"]
+mod link;
 #[path = "integration/commands/list.rs"]
 mod list;
+#[path = "integration/commands/pin.rs"]
+mod pin;
+#[path = "integration/commands/prune.rs"]
+mod prune;
+#[path = "integration/commands/repair.rs"]
+mod repair;
+#[path = "integration/commands/search.rs"]
+mod search;
+#[path = "integration/commands/show.rs"]
+mod show;
+#[path = "integration/commands/status.rs"]
+mod status;
 #[path = "integration/commands/uninstall.rs"]
 mod uninstall;
 #[path = "integration/commands/unlink.rs"]
@@ -37,6 +65,8 @@ mod unlink;
 mod update;
 #[path = "integration/commands/use.rs"]
 mod r#use;
+#[path = "integration/commands/verify.rs"]
+mod verify;
 #[path = "integration/commands/what.rs"]
 mod what;
 #[path = "integration/commands/which.rs"]
@@ -13,3 +13,24 @@ fn test_invalid_command() -> Result<(), Box<dyn std::error::Error>> {
         .stderr(predicate::str::contains("unrecognized subcommand"));
     Ok(())
 }
+
+#[test]
+fn test_timeout_is_a_global_flag() -> Result<(), Box<dyn std::error::Error>> {
+    for args in [["--timeout", "5", "version"], ["version", "--timeout", "5"]] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        cmd.args(args).assert().success();
+    }
+    Ok(())
+}
+
+#[test]
+fn test_timeout_must_be_positive() -> Result<(), Box<dyn std::error::Error>> {
+    for value in ["0", "soon"] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        cmd.args(["version", "--timeout", value])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value"));
+    }
+    Ok(())
+}
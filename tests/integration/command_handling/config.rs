@@ -0,0 +1,72 @@
+//! Integration tests for the config file
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+#[serial]
+#[test]
+fn test_invalid_config_file_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.write_config("prefer_musl = \"maybe\"\n")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Cannot parse config file"),
+        "Should report the broken config file: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_config_bin_dir_is_used() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let custom_bin_dir = fixture.home_dir.join("opt").join("bin");
+    fixture.write_config(&format!(
+        "bin_dir = {:?}\n",
+        custom_bin_dir.display().to_string()
+    ))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("check").env("PATH", "/usr/bin:/bin");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&custom_bin_dir.display().to_string()),
+        "Should use the bin directory from the config file: {}",
+        stderr
+    );
+
+    // the environment variable wins over the config file
+    let env_bin_dir = fixture.home_dir.join("env").join("bin");
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("check")
+        .env("PATH", "/usr/bin:/bin")
+        .env("POOF_BIN_DIR", &env_bin_dir);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&env_bin_dir.display().to_string()),
+        "POOF_BIN_DIR should take precedence: {}",
+        stderr
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Integration tests for the 'changelog' command
+
+use assert_cmd::{assert::OutputAssertExt, cargo};
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::repo_format_validation::*;
+
+#[serial]
+#[test]
+fn test_changelog_requires_args() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("changelog").assert().failure();
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_changelog_comprehensive_invalid_repo_formats() -> Result<(), Box<dyn std::error::Error>> {
+    test_invalid_repo_formats_for_command("changelog")
+}
+
+#[serial]
+#[test]
+fn test_changelog_with_tag_and_no_pager() -> Result<(), Box<dyn std::error::Error>> {
+    // Test that --tag and --no-pager flags are accepted
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("changelog")
+        .arg("user/repo")
+        .arg("--tag")
+        .arg("v1.0.0")
+        .arg("--no-pager")
+        .output()?;
+
+    // Should not fail on argument parsing
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("unexpected argument") && !stderr.contains("unknown flag"),
+        "Flags should be accepted: {}",
+        stderr
+    );
+
+    Ok(())
+}
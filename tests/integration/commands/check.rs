@@ -109,3 +109,41 @@ fn test_check_command_with_extra_args() -> Result<(), Box<dyn std::error::Error>
     cmd.arg("check").arg("extra").assert().failure(); // clap should reject extra positional args
     Ok(())
 }
+
+#[test]
+fn test_check_reports_bin_dir_override() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let custom_bin_dir = temp_dir.path().join("opt").join("bin");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("check")
+        .env("HOME", temp_dir.path())
+        .env(
+            "XDG_DATA_HOME",
+            temp_dir.path().join(".local").join("share"),
+        )
+        .env("POOF_BIN_DIR", &custom_bin_dir)
+        .env("PATH", "/usr/bin:/bin")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&custom_bin_dir.display().to_string()),
+        "Should report the overridden bin directory: {}",
+        stderr
+    );
+
+    // and it is considered in PATH once added
+    let path = format!("{}:/usr/bin:/bin", custom_bin_dir.display());
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("check")
+        .env("HOME", temp_dir.path())
+        .env("POOF_BIN_DIR", &custom_bin_dir)
+        .env("PATH", &path)
+        .assert()
+        .success();
+
+    Ok(())
+}
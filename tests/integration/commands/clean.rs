@@ -305,3 +305,188 @@ fn test_clean_case_insensitive_y() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[serial]
+#[test]
+fn test_clean_keeps_api_cache() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    // Create a downloaded asset and a cached API response
+    let api_cache_dir = fixture.cache_dir.join("api");
+    std::fs::create_dir_all(fixture.cache_dir.join("user/repo/1.0.0"))?;
+    std::fs::create_dir_all(&api_cache_dir)?;
+    std::fs::write(api_cache_dir.join("0123456789abcdef.json"), b"0\n{}")?;
+
+    let output = run_clean_with_input(&fixture, b"y\n")?;
+
+    assert!(output.status.success());
+    assert!(
+        !fixture.cache_dir.join("user").exists(),
+        "Downloaded assets should be deleted"
+    );
+    assert!(
+        api_cache_dir.join("0123456789abcdef.json").exists(),
+        "Cached API responses should be kept"
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_clean_reports_freed_size() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let asset_dir = fixture.cache_dir.join("user/repo/1.0.0");
+    std::fs::create_dir_all(&asset_dir)?;
+    std::fs::write(asset_dir.join("asset_0"), vec![0u8; 1024])?;
+    std::fs::write(fixture.cache_dir.join("leftover"), vec![0u8; 512])?;
+
+    let output = run_clean_with_input(&fixture, b"yes\n")?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("freed 1.5 KiB"), "stderr: {}", stderr);
+    assert!(!fixture.cache_dir.exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_clean_dry_run_keeps_files() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let asset = fixture.cache_dir.join("user/repo/1.0.0/asset_0");
+    std::fs::create_dir_all(asset.parent().unwrap())?;
+    std::fs::write(&asset, vec![0u8; 2048])?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["clean", "--dry-run"]).stdin(Stdio::null());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("Would free 2.0 KiB"), "stderr: {}", stderr);
+    assert!(stderr.contains("user"), "stderr: {}", stderr);
+    assert!(asset.exists(), "Dry run should not delete anything");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_clean_yes_skips_confirmation() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    std::fs::create_dir_all(&fixture.cache_dir)?;
+    std::fs::write(fixture.cache_dir.join("test_file.txt"), b"test content")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["clean", "--yes"]).stdin(Stdio::null());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        !String::from_utf8_lossy(&output.stdout).contains("Proceed?"),
+        "No prompt expected with --yes"
+    );
+    assert!(
+        !fixture.cache_dir.exists(),
+        "Cache directory should be deleted"
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_clean_without_input_cancels() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    std::fs::create_dir_all(&fixture.cache_dir)?;
+    std::fs::write(fixture.cache_dir.join("test_file.txt"), b"test content")?;
+
+    // no terminal and no --yes: nothing to read, so nothing is deleted
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("clean").stdin(Stdio::null());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("Cleanup cancelled."), "stderr: {}", stderr);
+    assert!(fixture.cache_dir.join("test_file.txt").exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_clean_keep_latest_spares_newest_installed_version() -> Result<(), Box<dyn std::error::Error>>
+{
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    fixture.create_fake_installation("user/repo", "2.0.0")?;
+
+    let old_asset = fixture
+        .cache_dir
+        .join("github.com/user/repo/1.0.0/asset_1/repo.tar.gz");
+    let new_asset = fixture
+        .cache_dir
+        .join("github.com/user/repo/2.0.0/asset_1/repo.tar.gz");
+    for asset in [&old_asset, &new_asset] {
+        std::fs::create_dir_all(asset.parent().unwrap())?;
+        std::fs::write(asset, vec![0u8; 1024])?;
+    }
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["clean", "--keep-latest", "--yes"])
+        .stdin(Stdio::null());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Deleted 1 cached files, freed 1.0 KiB."),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!old_asset.exists());
+    assert!(
+        !fixture.cache_dir.join("user/repo/1.0.0").exists(),
+        "Emptied directories should be removed"
+    );
+    assert!(new_asset.exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_clean_older_than_keeps_recent_files() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let asset = fixture
+        .cache_dir
+        .join("github.com/user/repo/1.0.0/asset_1/repo.tar.gz");
+    std::fs::create_dir_all(asset.parent().unwrap())?;
+    std::fs::write(&asset, vec![0u8; 1024])?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["clean", "--older-than", "1", "--yes"])
+        .stdin(Stdio::null());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Nothing to clean up."),
+        "stderr: {}",
+        stderr
+    );
+    assert!(asset.exists());
+
+    Ok(())
+}
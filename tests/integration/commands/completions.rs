@@ -118,3 +118,197 @@ fn test_completions_help() -> Result<(), Box<dyn std::error::Error>> {
         .stdout(predicate::str::contains("--shell"));
     Ok(())
 }
+
+#[test]
+fn test_completions_invalid_shell() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("completions")
+        .arg("--shell")
+        .arg("tcsh")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Possible values"));
+    Ok(())
+}
+
+#[test]
+fn test_completions_list_every_subcommand() -> Result<(), Box<dyn std::error::Error>> {
+    for shell in ["bash", "zsh", "fish", "elvish", "powershell", "nushell"] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        let output = cmd.args(["completions", "--shell", shell]).output()?;
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for subcommand in ["install", "link", "repair", "search", "status", "verify"] {
+            assert!(
+                stdout.contains(subcommand),
+                "{} completions should include '{}'",
+                shell,
+                subcommand
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_completions_fish_uses_complete_builtin() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["completions", "--shell", "fish"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("complete -c poof"));
+    Ok(())
+}
+
+#[test]
+fn test_completions_install_fish() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = tempfile::TempDir::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["completions", "--shell", "fish", "--install", "--yes"])
+        .env("HOME", temp_home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let script = temp_home
+        .path()
+        .join(".config")
+        .join("fish")
+        .join("completions")
+        .join("poof.fish");
+    let content = std::fs::read_to_string(&script)?;
+    assert!(content.contains("complete -c poof"), "{}", content);
+    Ok(())
+}
+
+#[test]
+fn test_completions_install_unsupported_shell() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = tempfile::TempDir::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["completions", "--shell", "elvish", "--install", "--yes"])
+        .env("HOME", temp_home.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not supported for elvish"));
+    Ok(())
+}
+
+#[test]
+fn test_completions_install_nushell() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = tempfile::TempDir::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["completions", "--shell", "nushell", "--install", "--yes"])
+        .env("HOME", temp_home.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("source"));
+
+    let script = temp_home
+        .path()
+        .join(".config")
+        .join("nushell")
+        .join("completions")
+        .join("poof.nu");
+    let content = std::fs::read_to_string(&script)?;
+    assert!(content.contains("export extern"), "{}", content);
+    Ok(())
+}
+
+#[test]
+fn test_completions_install_bash_and_zsh() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = tempfile::TempDir::new()?;
+    let scripts = [
+        (
+            "bash",
+            temp_home
+                .path()
+                .join(".local")
+                .join("share")
+                .join("bash-completion")
+                .join("completions")
+                .join("poof"),
+            "complete -F _poof",
+        ),
+        (
+            "zsh",
+            temp_home
+                .path()
+                .join(".zsh")
+                .join("completions")
+                .join("_poof"),
+            "#compdef poof",
+        ),
+    ];
+    for (shell, script, expected) in scripts {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        cmd.args(["completions", "--shell", shell, "--install", "--yes"])
+            .env("HOME", temp_home.path())
+            .env_remove("XDG_DATA_HOME")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("Completions installed."));
+
+        let content = std::fs::read_to_string(&script)?;
+        assert!(content.contains(expected), "{}: {}", shell, content);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_completions_install_asks_for_confirmation() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = tempfile::TempDir::new()?;
+    let script = temp_home
+        .path()
+        .join(".config")
+        .join("fish")
+        .join("completions")
+        .join("poof.fish");
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["completions", "--shell", "fish", "--install"])
+        .env("HOME", temp_home.path())
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Proceed?"))
+        .stderr(predicate::str::contains("cancelled"));
+    assert!(!script.exists());
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["completions", "--shell", "fish", "--install"])
+        .env("HOME", temp_home.path())
+        .write_stdin("y\n")
+        .assert()
+        .success();
+    assert!(script.is_file());
+    Ok(())
+}
+
+/// Compares the nushell script with the golden file.
+/// Run with `UPDATE_GOLDEN=1` to regenerate it after changing the CLI.
+/// The golden file is generated with the default features.
+#[cfg(not(feature = "cosign"))]
+#[test]
+fn test_completions_nushell_golden() -> Result<(), Box<dyn std::error::Error>> {
+    let golden = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("completions")
+        .join("nushell.nu");
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["completions", "--shell", "nushell"]).output()?;
+    assert!(output.status.success());
+    let script = String::from_utf8(output.stdout)?;
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(golden.parent().unwrap())?;
+        std::fs::write(&golden, &script)?;
+    }
+    assert_eq!(
+        script,
+        std::fs::read_to_string(&golden)?,
+        "nushell completions changed, run the tests with UPDATE_GOLDEN=1 to update {}",
+        golden.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Integration tests for the 'disable' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_poof(
+    home: &Path,
+    args: &[&str],
+) -> Result<std::process::Output, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(args)
+        .env("HOME", home)
+        .env("XDG_DATA_HOME", home.join(".local").join("share"))
+        .env_remove("POOF_BIN_DIR");
+    Ok(cmd.output()?)
+}
+
+#[serial]
+#[test]
+fn test_disable_removes_enabled_block() -> Result<(), Box<dyn std::error::Error>> {
+    let shells = [
+        ("bash", ".bashrc"),
+        ("zsh", ".zshrc"),
+        ("fish", ".config/fish/config.fish"),
+        ("elvish", ".config/elvish/rc.elv"),
+        ("nu", ".config/nushell/env.nu"),
+        ("xonsh", ".xonshrc"),
+        (
+            "pwsh",
+            ".config/powershell/Microsoft.PowerShell_profile.ps1",
+        ),
+    ];
+
+    for (shell_name, config_file) in shells {
+        let temp_home = TempDir::new()?;
+        let config_path = temp_home.path().join(config_file);
+        fs::create_dir_all(config_path.parent().unwrap())?;
+        fs::write(&config_path, "# my settings\n")?;
+
+        let output = run_poof(temp_home.path(), &["enable", "--shell", shell_name])?;
+        assert!(output.status.success(), "{}: enable failed", shell_name);
+        let output = run_poof(temp_home.path(), &["disable", "--shell", shell_name])?;
+        assert!(
+            output.status.success(),
+            "{}: stderr: {}",
+            shell_name,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let contents = fs::read_to_string(&config_path)?;
+        assert_eq!(
+            contents, "# my settings\n",
+            "{}: managed block should be removed",
+            shell_name
+        );
+        assert!(!contents.contains("poof"), "{}: {}", shell_name, contents);
+    }
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_disable_without_block() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = TempDir::new()?;
+    let bashrc = temp_home.path().join(".bashrc");
+    fs::write(&bashrc, "export A=1\n")?;
+
+    let output = run_poof(temp_home.path(), &["disable", "--shell", "bash"])?;
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("No poof configuration found"),
+        "Should report that nothing was found: {}",
+        stderr
+    );
+    assert_eq!(fs::read_to_string(&bashrc)?, "export A=1\n");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_disable_missing_config_file() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = TempDir::new()?;
+
+    let output = run_poof(temp_home.path(), &["disable", "--shell", "zsh"])?;
+    assert!(output.status.success());
+    assert!(!temp_home.path().join(".zshrc").exists());
+
+    Ok(())
+}
@@ -5,6 +5,9 @@ use serial_test::serial;
 use std::process::Command;
 
 // Common module is included from the parent integration.rs file
+use super::common::fixtures::mock_github::{MockAsset, MockGitHub};
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
 use super::common::repo_format_validation::*;
 
 #[serial]
@@ -49,3 +52,408 @@ fn test_download_with_tag() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[serial]
+#[test]
+fn test_download_with_libc_override() -> Result<(), Box<dyn std::error::Error>> {
+    // Test that --musl and --gnu flags are accepted
+    for flag in ["--musl", "--gnu"] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        let output = cmd.arg("download").arg("user/repo").arg(flag).output()?;
+
+        // Should not fail on argument parsing
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            !stderr.contains("unexpected argument") && !stderr.contains("unknown flag"),
+            "{} flag should be accepted: {}",
+            flag,
+            stderr
+        );
+    }
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_musl_and_gnu_conflict() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("download")
+        .arg("user/repo")
+        .arg("--musl")
+        .arg("--gnu")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot be used with"),
+        "--musl and --gnu should be mutually exclusive: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_with_arch() -> Result<(), Box<dyn std::error::Error>> {
+    // Test that --arch flag is accepted
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("download")
+        .arg("user/repo")
+        .arg("--arch")
+        .arg("aarch64")
+        .output()?;
+
+    // Should not fail on argument parsing
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("unexpected argument") && !stderr.contains("invalid value"),
+        "Arch flag should be accepted: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_with_unknown_arch() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("download")
+        .arg("user/repo")
+        .arg("--arch")
+        .arg("sparc")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid value"),
+        "Unknown arch should be rejected: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_rejects_rename() -> Result<(), Box<dyn std::error::Error>> {
+    // --rename is install-only: downloads are never linked into PATH
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("download")
+        .arg("user/repo")
+        .arg("--rename")
+        .arg("tool")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unexpected argument"),
+        "Rename flag should be rejected by download: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_rejects_no_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("download")
+        .arg("user/repo")
+        .arg("--no-symlink")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unexpected argument"),
+        "No-symlink flag should be rejected by download: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_rejects_url() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args(["download", "--url", "https://example.com/tool.tar.gz"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unexpected argument"),
+        "Url flag should be rejected by download: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_rejects_unknown_source_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.arg("download").arg("bitbucket:user/repo").output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("gitlab:GROUP/PROJECT"),
+        "Should mention the accepted formats: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[serial]
+#[test]
+fn test_download_from_gitlab() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut server = mockito::Server::new();
+    let asset_name = "tool-x86_64-unknown-linux-gnu.tar.gz";
+    let release_mock = server
+        .mock("GET", "/projects/group%2Fproject/releases/permalink/latest")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            serde_json::json!({
+                "tag_name": "v1.0.0",
+                "released_at": "2024-01-01T00:00:00Z",
+                "assets": {
+                    "links": [{
+                        "name": asset_name,
+                        "url": format!("{}/files/{}", server.url(), asset_name),
+                    }]
+                }
+            })
+            .to_string(),
+        )
+        .create();
+    let asset_mock = server
+        .mock("GET", format!("/files/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(b"archive")
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .args(["download", "gitlab:group/project"])
+        .env("HOME", temp_dir.path())
+        .env("XDG_CACHE_HOME", temp_dir.path().join("cache"))
+        .env("POOF_GITLAB_API_URL", server.url())
+        .output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    release_mock.assert();
+    asset_mock.assert();
+    assert_eq!(std::fs::read(temp_dir.path().join(asset_name))?, b"archive");
+
+    Ok(())
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[serial]
+#[test]
+fn test_download_from_gitea() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut server = mockito::Server::new();
+    let asset_name = "tool-x86_64-unknown-linux-gnu.tar.gz";
+    let release_mock = server
+        .mock("GET", "/owner/tool/releases/latest")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            serde_json::json!({
+                "tag_name": "v1.0.0",
+                "published_at": "2024-01-01T00:00:00Z",
+                "assets": [{
+                    "name": asset_name,
+                    "browser_download_url": format!("{}/files/{}", server.url(), asset_name),
+                }]
+            })
+            .to_string(),
+        )
+        .create();
+    let asset_mock = server
+        .mock("GET", format!("/files/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(b"archive")
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .args(["download", "gitea:codeberg.org/owner/tool"])
+        .env("HOME", temp_dir.path())
+        .env("XDG_CACHE_HOME", temp_dir.path().join("cache"))
+        .env("POOF_GITEA_API_URL", server.url())
+        .output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    release_mock.assert();
+    asset_mock.assert();
+    assert_eq!(std::fs::read(temp_dir.path().join(asset_name))?, b"archive");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_extract_to_output_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let asset_name = format!(
+        "alpha-{}-{}.tar.gz",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let archive = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/archives/archive.tar.gz"),
+    )?;
+    let _download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(archive)
+        .create();
+
+    let output_dir = fixture.home_dir.join("downloads");
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["download", "user/alpha", "--extract", "--output"])
+        .arg(&output_dir)
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    for file in ["file.txt", "README", "text.txt"] {
+        assert!(output_dir.join(file).is_file(), "{} not extracted", file);
+    }
+    // the archive is kept, and the staging directory is gone
+    assert!(output_dir.join(&asset_name).is_file());
+    assert_eq!(std::fs::read_dir(&output_dir)?.count(), 4);
+    // the fixture archive ships no executables
+    assert!(
+        stderr.contains("No executables found"),
+        "stderr: {}",
+        stderr
+    );
+    // nothing is installed
+    assert!(!fixture.get_install_path("user/alpha", "1.0.0").exists());
+
+    Ok(())
+}
+
+/// Serve a release of user/alpha with an asset for the current platform and an
+/// `alpha-special.bin` one the automatic selection ignores.
+fn mock_release_with_special_asset(github: &mut MockGitHub) -> mockito::Mock {
+    let platform_asset = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let base_url = github.base_url();
+    github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![
+            MockAsset::new(
+                &platform_asset,
+                &format!("{}/download/{}", base_url, platform_asset),
+            ),
+            MockAsset::new(
+                "alpha-special.bin",
+                &format!("{}/download/alpha-special.bin", base_url),
+            ),
+        ],
+    )
+}
+
+#[serial]
+#[test]
+fn test_download_asset_pattern_from_env() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _release = mock_release_with_special_asset(&mut github);
+    let download = github
+        .server
+        .mock("GET", "/download/alpha-special.bin")
+        .with_status(200)
+        .with_body("special")
+        .expect(1)
+        .create();
+
+    let output_dir = fixture.home_dir.join("downloads");
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["download", "user/alpha", "--output"])
+        .arg(&output_dir)
+        .env("POOF_GITHUB_API_URL", github.base_url())
+        .env("POOF_ASSET_PATTERN", r"special\.bin$");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    download.assert();
+    assert!(output_dir.join("alpha-special.bin").is_file());
+    assert_eq!(std::fs::read_dir(&output_dir)?.count(), 1);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_download_invalid_asset_pattern_env() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    // reported before the release is looked up
+    let release = mock_release_with_special_asset(&mut github);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["download", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url())
+        .env("POOF_ASSET_PATTERN", "alpha-[");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("Invalid asset pattern 'alpha-[' in POOF_ASSET_PATTERN"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!release.matched());
+
+    Ok(())
+}
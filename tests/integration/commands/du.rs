@@ -0,0 +1,91 @@
+//! Integration tests for the 'du' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+fn run_du(
+    fixture: &TestFixture,
+    args: &[&str],
+) -> Result<std::process::Output, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("du").args(args);
+    set_test_env(&mut cmd, fixture);
+    Ok(cmd.output()?)
+}
+
+#[serial]
+#[test]
+fn test_du_no_installations() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let output = run_du(&fixture, &[])?;
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("No installed binaries found"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_du_sorts_largest_first() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    // the fake executables are 28 bytes each
+    let small = fixture.create_fake_installation("user/alpha", "1.0.0")?;
+    std::fs::write(small.join("README"), vec![b'a'; 72])?;
+    let big = fixture.create_fake_installation("user/zulu", "1.0.0")?;
+    std::fs::write(big.join("README"), vec![b'z'; 996])?;
+    let big_new = fixture.create_fake_installation("user/zulu", "2.0.0")?;
+    std::fs::write(big_new.join("README"), vec![b'z'; 996])?;
+
+    let output = run_du(&fixture, &[])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+
+    let zulu = stdout.find("user/zulu").expect("zulu should be listed");
+    let alpha = stdout.find("user/alpha").expect("alpha should be listed");
+    assert!(
+        zulu < alpha,
+        "Largest repository should come first: {}",
+        stdout
+    );
+    let zulu_line = stdout.lines().find(|l| l.contains("user/zulu")).unwrap();
+    assert!(zulu_line.contains("2.0 KiB"), "stdout: {}", stdout);
+    let alpha_line = stdout.lines().find(|l| l.contains("user/alpha")).unwrap();
+    assert!(alpha_line.contains("100 B"), "stdout: {}", stdout);
+    assert!(!stdout.contains("2.0.0"), "Versions only with --verbose");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_usage_alias_verbose_lists_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/zulu", "1.0.0")?;
+    std::fs::write(old.join("README"), vec![b'z'; 996])?;
+    fixture.create_fake_installation("user/zulu", "2.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["usage", "--verbose"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+
+    let v1 = stdout.lines().find(|l| l.contains("1.0.0")).unwrap();
+    assert!(v1.contains("1.0 KiB"), "stdout: {}", stdout);
+    let v2 = stdout.lines().find(|l| l.contains("2.0.0")).unwrap();
+    assert!(v2.contains("28 B"), "stdout: {}", stdout);
+
+    Ok(())
+}
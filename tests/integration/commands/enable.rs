@@ -58,7 +58,7 @@ fn test_enable_creates_bashrc_entry() -> Result<(), Box<dyn std::error::Error>>
         ".bashrc should contain eval line"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         ".bashrc should contain comment marker"
     );
 
@@ -117,7 +117,7 @@ fn test_enable_creates_zshrc_entry() -> Result<(), Box<dyn std::error::Error>> {
         ".zshrc should contain eval line"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         ".zshrc should contain comment marker"
     );
 
@@ -180,7 +180,7 @@ fn test_enable_creates_fish_entry() -> Result<(), Box<dyn std::error::Error>> {
         "fish config should contain fish_add_path"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         "fish config should contain comment marker"
     );
     assert!(
@@ -247,7 +247,7 @@ fn test_enable_creates_elvish_entry() -> Result<(), Box<dyn std::error::Error>>
         "elvish config should contain eval line"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         "elvish config should contain comment marker"
     );
 
@@ -310,7 +310,7 @@ fn test_enable_creates_nushell_entry() -> Result<(), Box<dyn std::error::Error>>
         "nushell config should contain $env.PATH"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         "nushell config should contain comment marker"
     );
     assert!(
@@ -321,6 +321,41 @@ fn test_enable_creates_nushell_entry() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[serial]
+#[test]
+fn test_enable_detects_nushell_from_env() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = TempDir::new()?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("enable")
+        .env("HOME", temp_home.path())
+        .env("SHELL", "/usr/bin/nu");
+    #[cfg(target_os = "linux")]
+    {
+        cmd.env(
+            "XDG_DATA_HOME",
+            temp_home.path().join(".local").join("share"),
+        );
+    }
+    let output = cmd.output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let nushell_config = temp_home
+        .path()
+        .join(".config")
+        .join("nushell")
+        .join("env.nu");
+    let contents = fs::read_to_string(&nushell_config)?;
+    assert!(contents.contains("$env.PATH"));
+    assert!(!temp_home.path().join(".bashrc").exists());
+
+    Ok(())
+}
+
 #[serial]
 #[test]
 fn test_enable_creates_xonsh_entry() -> Result<(), Box<dyn std::error::Error>> {
@@ -373,7 +408,7 @@ fn test_enable_creates_xonsh_entry() -> Result<(), Box<dyn std::error::Error>> {
         "xonsh config should contain $PATH.insert"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         "xonsh config should contain comment marker"
     );
     assert!(
@@ -444,7 +479,7 @@ fn test_enable_creates_powershell_entry() -> Result<(), Box<dyn std::error::Erro
         "powershell config should contain poof init command"
     );
     assert!(
-        contents.contains("# added by poof"),
+        contents.contains("# >>> poof >>>"),
         "powershell config should contain comment marker"
     );
 
@@ -675,7 +710,10 @@ fn test_enable_requires_shell_argument() -> Result<(), Box<dyn std::error::Error
     fs::create_dir_all(&_bin_dir)?;
 
     let mut cmd = Command::new(cargo::cargo_bin!("poof"));
-    cmd.arg("enable").env("HOME", temp_home.path());
+    // without $SHELL the shell cannot be detected either
+    cmd.arg("enable")
+        .env("HOME", temp_home.path())
+        .env_remove("SHELL");
     #[cfg(target_os = "linux")]
     {
         cmd.env(
@@ -752,14 +790,75 @@ fn test_enable_all_shells_are_idempotent() -> Result<(), Box<dyn std::error::Err
         let config_path = temp_home.path().join(config_file);
         let contents = fs::read_to_string(&config_path)?;
 
-        // Should only have one "# added by poof" marker
+        // Should only have one managed block
         assert_eq!(
-            contents.matches("# added by poof").count(),
+            contents.matches("# >>> poof >>>").count(),
             1,
-            "{}: comment marker should appear exactly once",
+            "{}: block start marker should appear exactly once",
             shell_name
         );
+        assert_eq!(
+            contents.matches("# <<< poof <<<").count(),
+            1,
+            "{}: block end marker should appear exactly once",
+            shell_name
+        );
+    }
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_enable_twice_keeps_one_block_with_current_bin_dir() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_home = TempDir::new()?;
+    let config_path = temp_home.path().join(".config/fish/config.fish");
+    fs::create_dir_all(config_path.parent().unwrap())?;
+    fs::write(&config_path, "set -x EDITOR vim\n")?;
+
+    // the bin directory moves between the two runs
+    let first_bin = temp_home.path().join("first-bin");
+    let second_bin = temp_home.path().join("second-bin");
+    for bin_dir in [&first_bin, &second_bin] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        let output = cmd
+            .args(["enable", "--shell", "fish"])
+            .env("HOME", temp_home.path())
+            .env("XDG_DATA_HOME", temp_home.path().join(".local/share"))
+            .env("POOF_BIN_DIR", bin_dir)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
     }
 
+    let contents = fs::read_to_string(&config_path)?;
+    assert_eq!(
+        contents.matches("# >>> poof >>>").count(),
+        1,
+        "{}",
+        contents
+    );
+    assert_eq!(
+        contents.matches("# <<< poof <<<").count(),
+        1,
+        "{}",
+        contents
+    );
+    assert!(
+        contents.contains(&format!("fish_add_path -p \"{}\"", second_bin.display())),
+        "Block should point to the current bin directory: {}",
+        contents
+    );
+    assert!(
+        !contents.contains(&first_bin.display().to_string()),
+        "Previous bin directory should be gone: {}",
+        contents
+    );
+    assert!(contents.starts_with("set -x EDITOR vim\n"), "{}", contents);
+
     Ok(())
 }
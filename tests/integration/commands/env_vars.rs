@@ -0,0 +1,33 @@
+//! Integration tests for the 'env-vars' command
+
+use assert_cmd::{assert::OutputAssertExt, cargo};
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_env_vars_masks_tokens() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("env-vars")
+        .env("GITHUB_TOKEN", "ghp_supersecret")
+        .env("POOF_TIMEOUT", "42")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("POOF_GITHUB_API_URL"))
+        .stdout(predicate::str::contains("ghp_****"))
+        .stdout(predicate::str::contains("supersecret").not())
+        .stdout(predicate::str::is_match(r"POOF_TIMEOUT\s+42\s+60")?);
+    Ok(())
+}
+
+#[test]
+fn test_env_vars_unset_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["env-vars", "--unset-only"])
+        .env("POOF_TIMEOUT", "42")
+        .env_remove("POOF_BIN_DIR")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("POOF_BIN_DIR"))
+        .stdout(predicate::str::contains("POOF_TIMEOUT").not());
+    Ok(())
+}
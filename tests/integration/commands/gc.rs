@@ -0,0 +1,101 @@
+//! Integration tests for the 'gc' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+fn run_gc(
+    fixture: &TestFixture,
+    args: &[&str],
+) -> Result<std::process::Output, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("gc").args(args);
+    set_test_env(&mut cmd, fixture);
+    Ok(cmd.output()?)
+}
+
+#[serial]
+#[test]
+fn test_gc_nothing_to_do() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+
+    let output = run_gc(&fixture, &["-y"])?;
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Nothing to do"), "stderr: {}", stderr);
+    assert!(fixture.get_install_path("user/repo", "1.0.0").exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_gc_dry_run_removes_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    fixture.create_fake_installation("user/repo", "2.0.0")?;
+    let linked = fixture.create_fake_installation("user/repo", "3.0.0")?;
+    fixture.create_bin_symlink("repo", &linked.join("repo"))?;
+    fixture.create_fake_installation("user/repo", "4.0.0")?;
+
+    let output = run_gc(&fixture, &["--dry-run"])?;
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Would remove 2"), "stderr: {}", stderr);
+    assert!(stderr.contains("user/repo 1.0.0"), "stderr: {}", stderr);
+    assert!(stderr.contains("user/repo 2.0.0"), "stderr: {}", stderr);
+    assert!(old.exists(), "Dry run should not remove anything");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_gc_removes_unlinked_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    let linked = fixture.create_fake_installation("user/repo", "2.0.0")?;
+    fixture.create_bin_symlink("repo", &linked.join("repo"))?;
+    let newer = fixture.create_fake_installation("user/repo", "3.0.0")?;
+    let latest = fixture.create_fake_installation("user/repo", "4.0.0")?;
+
+    let output = run_gc(&fixture, &["--keep-latest", "2", "-y"])?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!old.exists(), "Unlinked old version should be removed");
+    assert!(linked.exists(), "Linked version should be kept");
+    assert!(newer.exists(), "Second newest version should be kept");
+    assert!(latest.exists(), "Newest version should be kept");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_gc_cancelled() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    fixture.create_fake_installation("user/repo", "2.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("gc");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.stdin(std::process::Stdio::null()).output()?;
+    assert!(output.status.success());
+    assert!(
+        old.exists(),
+        "Nothing should be removed without confirmation"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//! Integration tests for the 'history' command
+
+use assert_cmd::{assert::OutputAssertExt, cargo};
+use predicates::prelude::*;
+use serial_test::serial;
+use std::process::Command;
+
+use super::common::fixtures::mock_github::{MockAsset, MockGitHub};
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+/// Run poof with `args` in the environment of `fixture`, returning its stdout.
+fn run_poof(fixture: &TestFixture, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(args);
+    set_test_env(&mut cmd, fixture);
+    let output = cmd.output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Returns the data lines of the `history` table in `stdout`, newest first.
+fn history_rows(stdout: &str) -> Vec<&str> {
+    stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .collect()
+}
+
+#[serial]
+#[test]
+fn test_history_records_use_and_uninstall() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+    fixture.create_fake_installation("user/alpha", "2.0.0")?;
+
+    run_poof(&fixture, &["use", "user/alpha", "1.0.0"])?;
+    run_poof(&fixture, &["use", "user/alpha", "2.0.0"])?;
+    run_poof(&fixture, &["use", "user/alpha", "9.9.9"])?;
+    run_poof(
+        &fixture,
+        &["uninstall", "user/alpha", "--version", "1.0.0", "-y"],
+    )?;
+
+    let stdout = run_poof(&fixture, &["history"])?;
+    let rows = history_rows(&stdout);
+    assert_eq!(rows.len(), 4, "stdout: {}", stdout);
+    assert!(rows[0].contains("uninstall") && rows[0].contains("1.0.0"));
+    assert!(rows[0].ends_with("success"), "row: {}", rows[0]);
+    assert!(rows[1].contains("use") && rows[1].contains("2.0.0 -> 9.9.9"));
+    assert!(rows[1].contains("failure: "), "row: {}", rows[1]);
+    assert!(rows[2].contains("1.0.0 -> 2.0.0"), "row: {}", rows[2]);
+    assert!(rows[3].contains("use") && rows[3].contains("1.0.0"));
+
+    // the log is one JSON object per line, next to the data directory
+    let log = std::fs::read_to_string(fixture.data_dir.join("..").join("history.jsonl"))?;
+    let last: serde_json::Value = serde_json::from_str(log.lines().last().unwrap())?;
+    assert_eq!(last["command"], "uninstall");
+    assert_eq!(last["repo"], "user/alpha");
+    assert_eq!(last["from_version"], "1.0.0");
+    assert!(last["to_version"].is_null());
+    assert_eq!(last["status"], "success");
+
+    let stdout = run_poof(&fixture, &["history", "--limit", "1"])?;
+    assert_eq!(history_rows(&stdout).len(), 1);
+    assert!(history_rows(&stdout)[0].contains("uninstall"));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_history_records_install() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let asset_name = format!(
+        "multi-{}-{}.tar.gz",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        "user/multi",
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let _download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(std::fs::read(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/archives/multi.tar.gz"),
+        )?)
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/multi"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert().success();
+
+    let stdout = run_poof(&fixture, &["history", "--repo", "user/multi"])?;
+    let rows = history_rows(&stdout);
+    assert_eq!(rows.len(), 1, "stdout: {}", stdout);
+    assert!(rows[0].contains("install") && rows[0].contains("1.0.0"));
+    assert!(rows[0].ends_with("success"), "row: {}", rows[0]);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_history_repo_filter_and_clear() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+    run_poof(&fixture, &["use", "user/alpha", "1.0.0"])?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["history", "--repo", "user/other"]);
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("No operations recorded."));
+
+    // the history is not part of the cache
+    run_poof(&fixture, &["clean", "--yes"])?;
+    assert_eq!(history_rows(&run_poof(&fixture, &["history"])?).len(), 1);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["history", "--clear", "--yes"]);
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("History cleared."));
+    assert!(history_rows(&run_poof(&fixture, &["history"])?).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_history_clear_conflicts_with_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["history", "--clear", "--repo", "user/alpha"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
@@ -4,6 +4,10 @@ use assert_cmd::{assert::OutputAssertExt, cargo};
 use predicates::prelude::*;
 use std::process::Command;
 
+use super::common::fixtures::mock_github::{MockAsset, MockGitHub};
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
 #[test]
 fn test_info_command_exists() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::new(cargo::cargo_bin!("poof"));
@@ -86,3 +90,164 @@ fn test_info_command_with_extra_args() -> Result<(), Box<dyn std::error::Error>>
     cmd.arg("info").arg("extra").assert().failure(); // clap should reject extra positional args
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_info_reflects_directory_overrides() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let data_home = temp_dir.path().join("data");
+    let config_home = temp_dir.path().join("config");
+    let bin_dir = temp_dir.path().join("custom-bin");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("info")
+        .env("HOME", temp_dir.path())
+        .env("XDG_DATA_HOME", &data_home)
+        .env("XDG_CACHE_HOME", temp_dir.path().join("cache"))
+        .env("XDG_CONFIG_HOME", &config_home)
+        .env("POOF_BIN_DIR", &bin_dir)
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let expected_data_dir = format!(
+        "Data dir  : {}",
+        data_home.join("poof").join("data").display()
+    );
+    assert!(
+        stdout.contains(&expected_data_dir),
+        "Should print the data dir from XDG_DATA_HOME: {}",
+        stdout
+    );
+    let expected_bin_dir = format!("Bin dir   : {}", bin_dir.display());
+    assert!(
+        stdout.contains(&expected_bin_dir),
+        "Should print the bin dir from POOF_BIN_DIR: {}",
+        stdout
+    );
+    let expected_config_dir = format!("Config dir: {}", config_home.join("poof").display());
+    assert!(
+        stdout.contains(&expected_config_dir),
+        "Should print the config dir from XDG_CONFIG_HOME: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("(not found)"),
+        "Config file does not exist: {}",
+        stdout
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_info_json_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["info", "--json"]).output()?;
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let object = json
+        .as_object()
+        .expect("info --json should print an object");
+    // renaming or removing any of these fields is a breaking change
+    for key in [
+        "poof_version",
+        "commit",
+        "build_date",
+        "os_family",
+        "os_type",
+        "os_version",
+        "arch",
+        "endianness",
+        "kernel",
+        "executable_path",
+        "cwd",
+        "bin_dir",
+        "data_dir",
+        "cache_dir",
+        "path_status",
+    ] {
+        assert!(object.contains_key(key), "Missing key {}: {}", key, json);
+    }
+    assert_eq!(json["poof_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["os_type"], std::env::consts::OS);
+
+    Ok(())
+}
+
+#[serial_test::serial]
+#[test]
+fn test_info_repo_shows_release_and_installed_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v2.0.0",
+        vec![MockAsset::new(
+            "alpha-linux-x86_64.tar.gz",
+            "https://example.com/alpha-linux-x86_64.tar.gz",
+        )],
+    );
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["info", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Latest release  : v2.0.0"))
+        .stdout(predicate::str::contains(
+            "Published at    : 2024-01-01T00:00:00Z",
+        ))
+        .stdout(predicate::str::contains("alpha-linux-x86_64.tar.gz"))
+        .stdout(predicate::str::contains("Installed       : 1.0.0"))
+        .stdout(predicate::str::contains("Update available"));
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["info", "user/alpha", "--json"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(info["latest_tag"], "v2.0.0");
+    assert_eq!(info["installed_versions"], serde_json::json!(["1.0.0"]));
+    assert_eq!(info["update_available"], true);
+
+    Ok(())
+}
+
+#[serial_test::serial]
+#[test]
+fn test_info_repo_falls_back_to_stale_cache() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let release = github.mock_latest_release("user/alpha", "v2.0.0", vec![]);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["info", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    cmd.env("POOF_API_CACHE_TTL_SECONDS", "1");
+    cmd.assert().success();
+
+    // the cached response expires, then the API starts failing
+    release.remove();
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let _error = github.mock_network_error("user/alpha");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["info", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    cmd.env("POOF_API_CACHE_TTL_SECONDS", "1");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Latest release  : v2.0.0"))
+        .stderr(predicate::str::contains("using cached data from"));
+
+    Ok(())
+}
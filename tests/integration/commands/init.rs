@@ -126,6 +126,7 @@ fn test_init_help() -> Result<(), Box<dyn std::error::Error>> {
 fn test_init_missing_shell_arg() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::new(cargo::cargo_bin!("poof"));
     cmd.arg("init")
+        .env_remove("SHELL")
         .assert()
         .failure()
         .stderr(predicate::str::contains(
@@ -134,6 +135,48 @@ fn test_init_missing_shell_arg() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_init_unsupported_detected_shell() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("init")
+        .env("SHELL", "/bin/tcsh")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--shell"));
+    Ok(())
+}
+
+#[test]
+fn test_init_detects_nushell_from_env() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_home = tempfile::TempDir::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("init")
+        .env("HOME", temp_home.path())
+        .env("SHELL", "/usr/bin/nu");
+    #[cfg(target_os = "linux")]
+    cmd.env(
+        "XDG_DATA_HOME",
+        temp_home.path().join(".local").join("share"),
+    );
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("$env.PATH = ($env.PATH | prepend"),
+        "stdout: {}",
+        stdout
+    );
+    // the snippet points to the resolved bin directory
+    let bin_dir = temp_home.path().canonicalize()?;
+    assert!(
+        stdout.contains(bin_dir.to_string_lossy().as_ref()) && stdout.contains("poof/bin"),
+        "stdout: {}",
+        stdout
+    );
+    Ok(())
+}
+
 #[test]
 fn test_init_invalid_shell() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::new(cargo::cargo_bin!("poof"));
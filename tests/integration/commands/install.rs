@@ -5,6 +5,7 @@ use serial_test::serial;
 use std::process::Command;
 
 // Common module is included from the parent integration.rs file
+use super::common::fixtures::mock_github::{MockAsset, MockGitHub};
 use super::common::fixtures::test_env::TestFixture;
 use super::common::helpers::set_test_env;
 use super::common::repo_format_validation::*;
@@ -52,6 +53,209 @@ fn test_install_with_tag() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[serial]
+#[test]
+fn test_install_rejects_arch_override() -> Result<(), Box<dyn std::error::Error>> {
+    // --arch is download-only: installing a foreign architecture makes no sense
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("install")
+        .arg("user/repo")
+        .arg("--arch")
+        .arg("aarch64")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unexpected argument"),
+        "Arch flag should be rejected by install: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_rejects_invalid_rename() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .arg("install")
+        .arg("user/repo")
+        .arg("--rename")
+        .arg("bad/name")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid value"),
+        "Rename with a path separator should be rejected: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_url_rejects_non_http_schemes() -> Result<(), Box<dyn std::error::Error>> {
+    for url in [
+        "ftp://example.com/tool.tar.gz",
+        "file:///tmp/tool.tar.gz",
+        "example.com/tool",
+    ] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        let output = cmd.args(["install", "--url", url]).output()?;
+
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("invalid value"),
+            "{} should be rejected: {}",
+            url,
+            stderr
+        );
+    }
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_url_conflicts_with_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args([
+            "install",
+            "user/repo",
+            "--url",
+            "https://example.com/tool.tar.gz",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot be used with"),
+        "Repository and --url should be mutually exclusive: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_name_requires_url() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["install", "--name", "user/tool"]).output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--url"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_source_rejects_plain_http() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args([
+            "install",
+            "user/tool",
+            "--source",
+            "http://example.com/tool.tar.gz",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("https://"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_source_requires_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args(["install", "--source", "https://example.com/tool.tar.gz"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<REPO>"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_source_rejects_prefixed_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args([
+        "install",
+        "gitlab:group/tool",
+        "--source",
+        "https://example.com/tool.tar.gz",
+    ]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("USERNAME/REPO"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_constraint_conflicts_with_tag() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args([
+            "install",
+            "user/repo",
+            "--constraint",
+            "^1.2",
+            "--tag",
+            "v1.2.0",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_rejects_invalid_constraint() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args(["install", "user/repo", "--version-constraint", "one-ish"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid version constraint"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
 #[serial]
 #[test]
 fn test_install_creates_directories() -> Result<(), Box<dyn std::error::Error>> {
@@ -374,3 +578,1072 @@ fn test_install_clean_scenario_no_conflicts() -> Result<(), Box<dyn std::error::
 
     Ok(())
 }
+
+#[serial]
+#[test]
+fn test_install_bin_dir_conflicts_with_no_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args(["install", "user/repo", "--bin-dir", "/tmp", "--no-symlink"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_keep_versions_prunes_oldest() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+
+    for version in ["1.0.0", "2.0.0", "3.0.0"] {
+        fixture.create_fake_installation(repo, version)?;
+    }
+    let active = fixture.get_install_path(repo, "3.0.0").join("alpha");
+    fixture.create_bin_symlink("alpha", &active)?;
+
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        repo,
+        "v4.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let _asset = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha 4.0.0'\n")
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo, "--keep-versions", "2"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(!fixture.get_install_path(repo, "1.0.0").exists());
+    assert!(!fixture.get_install_path(repo, "2.0.0").exists());
+    assert!(fixture.get_install_path(repo, "3.0.0").exists());
+    assert!(fixture.get_install_path(repo, "4.0.0").exists());
+    // the freshly installed version is the active one
+    let target = std::fs::read_link(fixture.bin_dir.join("alpha"))?;
+    assert!(target.starts_with(fixture.get_install_path(repo, "4.0.0")));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_keep_versions_rejects_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args(["install", "user/repo", "--keep-versions", "0"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("positive integer"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_check_only_finds_asset() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let asset_name = format!(
+        "alpha-{}-{}.tar.gz",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .expect(0)
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--check-only"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains(&format!(
+            "Compatible asset found: {} ({})",
+            asset_name, download_url
+        )),
+        "stdout: {}",
+        stdout
+    );
+    download.assert();
+    assert!(!fixture.get_install_path("user/alpha", "1.0.0").exists());
+
+    // the JSON output carries the content type too
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--dry-run-download", "--json"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json[0]["name"], asset_name.as_str());
+    assert_eq!(json[0]["url"], download_url.as_str());
+    assert_eq!(json[0]["content_type"], "application/octet-stream");
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_check_only_exit_code_without_asset() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![MockAsset::new(
+            "alpha-plan9-mips.dmg",
+            &format!("{}/download/alpha-plan9-mips.dmg", github.base_url()),
+        )],
+    );
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--check-only"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No compatible"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_json_requires_check_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["install", "user/repo", "--json"]).output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--check-only"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[serial]
+#[test]
+fn test_install_warns_when_running_as_root() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let github = MockGitHub::new();
+    let warning = "Running as root; binaries will be installed into root's home directory";
+
+    // UID=0 makes the check pass whatever user runs the tests
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url())
+        .env("UID", "0");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(warning), "stderr: {}", stderr);
+    assert!(stderr.contains("--allow-root"), "stderr: {}", stderr);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url())
+        .env("UID", "0");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains(warning), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_json_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _not_found = github.mock_not_found("user/missing");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["--json", "install", "user/missing", "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error_line = stderr.lines().last().ok_or("no error printed to stderr")?;
+    let json: serde_json::Value = serde_json::from_str(error_line)?;
+    assert!(
+        json["message"].as_str().is_some_and(|m| !m.is_empty()),
+        "stderr: {}",
+        stderr
+    );
+    assert!(json["causes"].is_array(), "stderr: {}", stderr);
+
+    // errors stay human-readable by default
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/missing", "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[ERROR]"), "stderr: {}", stderr);
+    assert!(!stderr.contains("\"message\""), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_json_errors_from_env() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _not_found = github.mock_not_found("user/missing");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/missing", "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url())
+        .env("POOF_JSON_ERRORS", "1");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error_line = stderr.lines().last().ok_or("no error printed to stderr")?;
+    let json: serde_json::Value = serde_json::from_str(error_line)?;
+    assert!(
+        json["error"].as_str().is_some_and(|e| !e.is_empty()),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        json["context"].as_array().is_some_and(|c| !c.is_empty()),
+        "stderr: {}",
+        stderr
+    );
+    assert_eq!(json["exit_code"], 1, "stderr: {}", stderr);
+    // the names of the --json output are kept
+    assert_eq!(json["message"], json["error"], "stderr: {}", stderr);
+    assert_eq!(json["causes"], json["context"], "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_print_path() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        repo,
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha 1.0.0'\n")
+        .expect(1)
+        .create();
+    let expected = [
+        format!(
+            "Install dir: {}",
+            fixture.get_install_path(repo, "1.0.0").display()
+        ),
+        format!("Symlink: {}", fixture.bin_dir.join("alpha").display()),
+        format!("Asset: {} ({})", asset_name, download_url),
+    ];
+
+    // --print-path-only stops before downloading
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo, "--print-path-only"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    for line in &expected {
+        assert!(stdout.contains(line.as_str()), "stdout: {}", stdout);
+    }
+    assert!(!fixture.get_install_path(repo, "1.0.0").exists());
+
+    // --print-path goes on with the installation
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo, "--print-path"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for line in &expected {
+        assert!(stdout.contains(line.as_str()), "stdout: {}", stdout);
+    }
+    assert!(fixture
+        .get_install_path(repo, "1.0.0")
+        .join("alpha")
+        .exists());
+    download.assert();
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_records_asset_checksum() -> Result<(), Box<dyn std::error::Error>> {
+    use sha2::{Digest, Sha256};
+
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let body = "#!/bin/sh\necho 'alpha 1.0.0'\n";
+    let _release = github.mock_latest_release(
+        repo,
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let _asset = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(body)
+        .create();
+    let expected: String = Sha256::digest(body.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains(&format!("SHA-256 of {}: {}", asset_name, expected)),
+        "stderr: {}",
+        stderr
+    );
+
+    // the digest is kept and shown by 'list --verbose'
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--verbose"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(
+        stdout.contains(&format!("1.0.0 {}  sha256:{}", asset_name, expected)),
+        "stdout: {}",
+        stdout
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_tag_already_installed_skips_network() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    // no mocks: any request to the API would fail the install
+    let github = MockGitHub::new();
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--tag", "v1.0.0", "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Version 1.0.0 is already installed (skipping download)"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_latest_reports_different_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        repo,
+        "v2.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let _asset = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha 2.0.0'\n")
+        .create();
+    fixture.create_fake_installation(repo, "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo, "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("A different version is installed; reinstalling"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(fixture.get_install_path(repo, "2.0.0").exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_force_reinstalls_installed_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_release_by_tag(
+        repo,
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let _asset = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha 1.0.0 repaired'\n")
+        .create();
+    let install_dir = fixture.create_fake_installation(repo, "1.0.0")?;
+    std::fs::write(install_dir.join("leftover"), b"stale")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args([
+        "install",
+        repo,
+        "--tag",
+        "v1.0.0",
+        "--force",
+        "--allow-root",
+    ])
+    .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Forcing reinstall of user/alpha 1.0.0"),
+        "stderr: {}",
+        stderr
+    );
+    let binary = std::fs::read_to_string(install_dir.join("alpha"))?;
+    assert!(binary.contains("repaired"), "binary: {}", binary);
+    assert!(!install_dir.join("leftover").exists());
+    assert!(fixture.bin_dir.join("alpha").is_symlink());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_version_without_v_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_release_by_tag(
+        repo,
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    // the release is not known by the tag as typed
+    let _not_found = github.mock_not_found(repo);
+    let _asset = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha 1.0.0'\n")
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo, "--version", "1.0.0", "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Selected release tag: v1.0.0"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(fixture.get_install_path(repo, "1.0.0").exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_records_install_time() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let asset_name = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        repo,
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let _asset = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha 1.0.0'\n")
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", repo, "--allow-root"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let recorded = std::fs::read_to_string(
+        fixture
+            .get_install_path(repo, "1.0.0")
+            .join(".poof-installed-at"),
+    )?;
+    let seconds: u64 = recorded.trim().parse()?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    assert!(now - seconds < 60, "recorded: {}", recorded);
+
+    // the time is read back by 'list'
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--json"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let installed_at = json[0]["installed_at"]["1.0.0"]
+        .as_str()
+        .ok_or("no install time listed")?;
+    assert!(
+        installed_at.ends_with('Z'),
+        "installed_at: {}",
+        installed_at
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_asset_pattern_overrides_selection() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let platform_asset = format!("alpha-{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let _release = github.mock_latest_release(
+        repo,
+        "v1.0.0",
+        vec![
+            MockAsset::new(
+                &platform_asset,
+                &format!("{}/download/{}", github.base_url(), platform_asset),
+            ),
+            MockAsset::new(
+                "alpha-portable",
+                &format!("{}/download/alpha-portable", github.base_url()),
+            ),
+        ],
+    );
+    // only the asset matching the pattern is served
+    let download = github
+        .server
+        .mock("GET", "/download/alpha-portable")
+        .with_status(200)
+        .with_body("#!/bin/sh\necho 'alpha portable'\n")
+        .expect(1)
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args([
+        "install",
+        repo,
+        "--asset-pattern",
+        "portable$",
+        "--allow-root",
+    ])
+    .env("POOF_GITHUB_API_URL", github.base_url())
+    // the flag wins over the environment
+    .env("POOF_ASSET_PATTERN", "no-such-asset");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    download.assert();
+    assert!(fixture.get_install_path(repo, "1.0.0").exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_asset_pattern_without_match() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repo = "user/alpha";
+    let _release = github.mock_latest_release(
+        repo,
+        "v1.0.0",
+        vec![MockAsset::new(
+            "alpha-linux-x86_64",
+            &format!("{}/download/alpha-linux-x86_64", github.base_url()),
+        )],
+    );
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args([
+        "install",
+        repo,
+        "--asset-pattern",
+        "windows",
+        "--allow-root",
+    ])
+    .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains(
+            "No asset of release v1.0.0 matches pattern 'windows'. Available: alpha-linux-x86_64"
+        ),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!fixture.get_install_path(repo, "1.0.0").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_install_rejects_invalid_asset_pattern() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--asset-pattern", "(unclosed"]);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid asset pattern '(unclosed'"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_install_force_conflicts_with_check_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--force", "--check-only"]);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_install_extract_only_does_not_install() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let asset_name = format!(
+        "alpha-{}-{}.tar.gz",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let archive = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/archives/archive.tar.gz"),
+    )?;
+    let _download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(archive)
+        .create();
+
+    let extract_dir = fixture.home_dir.join("extracted");
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--extract-only", "--extract-dir"])
+        .arg(&extract_dir)
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains(&format!("Extracted to {}", extract_dir.display())),
+        "stderr: {}",
+        stderr
+    );
+    for file in ["file.txt", "README", "text.txt"] {
+        assert!(extract_dir.join(file).is_file(), "{} not extracted", file);
+    }
+    assert!(extract_dir.join(&asset_name).is_file());
+    assert!(!fixture.get_install_path("user/alpha", "1.0.0").exists());
+    assert_eq!(std::fs::read_dir(&fixture.bin_dir)?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+#[serial]
+fn test_install_finds_binary_nested_in_archive() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let asset_name = format!(
+        "alpha-{}-{}.tar.gz",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+
+    // alpha-1.0.0/bin/alpha, next to docs and a shared library that must be left out
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    for (path, content) in [
+        ("alpha-1.0.0/bin/alpha", "#!/bin/sh\necho alpha\n"),
+        ("alpha-1.0.0/lib/libalpha.so.1", "#!/bin/sh\n"),
+        ("alpha-1.0.0/README.md", "alpha\n"),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, path, content.as_bytes())?;
+    }
+    let archive = builder.into_inner()?.finish()?;
+    let _download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(archive)
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let install_dir = fixture.get_install_path("user/alpha", "1.0.0");
+    assert!(install_dir.join("alpha").is_file(), "stderr: {}", stderr);
+    assert!(!install_dir.join("libalpha.so.1").exists());
+    assert!(fixture.bin_dir.join("alpha").exists());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[serial]
+fn test_install_arch_linux_package() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let asset_name = "alpha-1.0.0-1-x86_64.pkg.tar.zst";
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v1.0.0",
+        vec![
+            MockAsset::new("alpha-1.0.0-darwin-arm64.tar.gz", &download_url),
+            MockAsset::new(asset_name, &download_url),
+        ],
+    );
+
+    // the commands are in usr/bin, executables elsewhere are helpers
+    let mut builder = tar::Builder::new(zstd::stream::write::Encoder::new(Vec::new(), 0)?);
+    for (path, content) in [
+        (".PKGINFO", "pkgname = alpha\npkgver = 1.0.0-1\n"),
+        ("usr/bin/alpha", "#!/bin/sh\necho alpha\n"),
+        ("usr/lib/alpha/alpha-helper", "#!/bin/sh\necho helper\n"),
+        ("usr/share/doc/alpha/README", "alpha\n"),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, path, content.as_bytes())?;
+    }
+    let archive = builder.into_inner()?.finish()?;
+    let _download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(archive)
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let install_dir = fixture.get_install_path("user/alpha", "1.0.0");
+    assert!(install_dir.join("alpha").is_file(), "stderr: {}", stderr);
+    assert!(!install_dir.join("alpha-helper").exists());
+    assert!(fixture.bin_dir.join("alpha").is_symlink());
+
+    Ok(())
+}
+
+/// Serve the fixture archive shipping the `alpha` and `beta` executables as the latest release of user/multi.
+fn mock_multi_binary_release(github: &mut MockGitHub) -> Vec<mockito::Mock> {
+    let asset_name = format!(
+        "multi-{}-{}.tar.gz",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+    let release = github.mock_latest_release(
+        "user/multi",
+        "v1.0.0",
+        vec![MockAsset::new(&asset_name, &download_url)],
+    );
+    let archive = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/archives/multi.tar.gz"),
+    )
+    .unwrap();
+    let download = github
+        .server
+        .mock("GET", format!("/download/{}", asset_name).as_str())
+        .with_status(200)
+        .with_body(archive)
+        .create();
+    vec![release, download]
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+#[serial]
+fn test_install_multi_binary_archive_skips_conflicting_link(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _mocks = mock_multi_binary_release(&mut github);
+    // a foreign 'beta' is already in the bin directory
+    std::fs::write(fixture.bin_dir.join("beta"), "#!/bin/sh\necho foreign\n")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/multi"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let install_dir = fixture.get_install_path("user/multi", "1.0.0");
+    assert!(install_dir.join("alpha").is_file());
+    assert!(install_dir.join("beta").is_file());
+    assert!(fixture.bin_dir.join("alpha").is_symlink());
+    assert!(!fixture.bin_dir.join("beta").is_symlink());
+    assert!(
+        stderr.contains("Skipping creation of symlink 'beta'"),
+        "stderr: {}",
+        stderr
+    );
+
+    // both binaries are reported as provided by the repository
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["what", "user/multi"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("- alpha"), "stdout: {}", stdout);
+    assert!(stdout.contains("- beta"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+#[serial]
+fn test_install_binary_installs_only_the_named_executable() -> Result<(), Box<dyn std::error::Error>>
+{
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _mocks = mock_multi_binary_release(&mut github);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/multi", "--binary", "beta"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let install_dir = fixture.get_install_path("user/multi", "1.0.0");
+    assert!(install_dir.join("beta").is_file());
+    assert!(!install_dir.join("alpha").exists());
+    assert!(fixture.bin_dir.join("beta").is_symlink());
+    assert!(!fixture.bin_dir.join("alpha").exists());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+#[serial]
+fn test_install_bin_name_with_rename_links_the_selected_executable(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _mocks = mock_multi_binary_release(&mut github);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args([
+        "install",
+        "user/multi",
+        "--bin-name",
+        "alpha",
+        "--rename",
+        "a",
+    ])
+    .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let install_dir = fixture.get_install_path("user/multi", "1.0.0");
+    assert!(install_dir.join("alpha").is_file());
+    assert!(!install_dir.join("beta").exists());
+    assert_eq!(
+        std::fs::read_link(fixture.bin_dir.join("a"))?,
+        install_dir.join("alpha")
+    );
+    assert!(!fixture.bin_dir.join("alpha").exists());
+
+    Ok(())
+}
+#[test]
+#[cfg(not(target_os = "windows"))]
+#[serial]
+fn test_install_binary_not_in_archive_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _mocks = mock_multi_binary_release(&mut github);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/multi", "--binary", "gamma"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("No executable named 'gamma' found in the archive. Available: alpha, beta"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+#[test]
+fn test_install_extract_only_conflicts_with_no_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["install", "user/alpha", "--extract-only", "--no-symlink"]);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! Integration tests for the 'link' command
+
+use assert_cmd::{assert::OutputAssertExt, cargo};
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+#[serial]
+#[test]
+fn test_link_requires_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("link").assert().failure();
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_link_not_installed() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool", "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not installed"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_link_creates_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool", "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let symlink = fixture.bin_dir.join("tool");
+    assert!(symlink.is_symlink());
+    assert_eq!(std::fs::read_link(&symlink)?, install_dir.join("tool"));
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_link_defaults_to_newest_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let newest = fixture.create_fake_installation("owner/tool", "1.10.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_link(fixture.bin_dir.join("tool"))?,
+        newest.join("tool")
+    );
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_link_refuses_symlink_of_other_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let other = fixture.create_fake_installation("other/tool", "2.0.0")?;
+    fixture.create_bin_symlink("tool", &other.join("tool"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool", "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("is already installed and points to"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("--force"), "stderr: {}", stderr);
+    // the existing symlink is left alone
+    assert_eq!(
+        std::fs::read_link(fixture.bin_dir.join("tool"))?,
+        other.join("tool")
+    );
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_link_force_replaces_symlink_of_other_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let other = fixture.create_fake_installation("other/tool", "2.0.0")?;
+    fixture.create_bin_symlink("tool", &other.join("tool"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool", "1.0.0", "--force"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Replacing it"), "stderr: {}", stderr);
+    assert_eq!(
+        std::fs::read_link(fixture.bin_dir.join("tool"))?,
+        install_dir.join("tool")
+    );
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_link_force_keeps_regular_files() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let foreign = fixture.bin_dir.join("tool");
+    std::fs::write(&foreign, b"foreign")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool", "1.0.0", "--force"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    assert!(!foreign.is_symlink());
+    assert_eq!(std::fs::read(&foreign)?, b"foreign");
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_link_replaces_broken_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &fixture.data_dir.join("gone").join("tool"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["link", "owner/tool", "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_link(fixture.bin_dir.join("tool"))?,
+        install_dir.join("tool")
+    );
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ use serial_test::serial;
 use std::process::Command;
 
 // Common module is included from the parent integration.rs file
+use super::common::fixtures::mock_github::MockGitHub;
 use super::common::fixtures::test_env::TestFixture;
 use super::common::helpers::set_test_env;
 
@@ -375,6 +376,38 @@ fn test_list_with_slug_filters_other_repos() -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+#[serial]
+#[test]
+fn test_list_keeps_same_named_repos_of_different_hosts_apart(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    // data_dir points to the github.com subdir, GitLab installs live next to it
+    let installed = fixture.create_fake_installation("user/repo", "2.0.0")?;
+    let gitlab_dir = fixture
+        .data_dir
+        .parent()
+        .unwrap()
+        .join("gitlab.com")
+        .join("user")
+        .join("repo");
+    std::fs::create_dir_all(&gitlab_dir)?;
+    std::fs::rename(installed, gitlab_dir.join("2.0.0"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list").arg("gitlab:user/repo");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(stdout.contains("gitlab:user/repo"), "stdout: {}", stdout);
+    assert!(stdout.contains("2.0.0"), "stdout: {}", stdout);
+    assert!(!stdout.contains("1.0.0"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
 #[serial]
 #[test]
 fn test_list_with_non_existent_slug() -> Result<(), Box<dyn std::error::Error>> {
@@ -459,10 +492,71 @@ fn test_list_with_slug_partial_match() -> Result<(), Box<dyn std::error::Error>>
 #[serial]
 #[test]
 fn test_list_with_invalid_slug_formats() -> Result<(), Box<dyn std::error::Error>> {
-    use super::common::repo_format_validation::test_invalid_repo_formats_for_command;
+    // list also accepts a bare USERNAME, so the shared invalid formats
+    // without a slash do not apply here
+    let invalid_formats = vec![
+        "user/repo/extra",
+        "/repo",
+        "user/",
+        "user repo",
+        "user@repo",
+        "user#repo",
+    ];
+
+    for invalid in invalid_formats {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        let output = cmd.arg("list").arg(invalid).output()?;
+        assert!(
+            !output.status.success(),
+            "List should reject format '{}', but it didn't",
+            invalid
+        );
+    }
+
+    Ok(())
+}
 
-    // Use the reusable validation function for invalid formats
-    test_invalid_repo_formats_for_command("list")?;
+#[serial]
+#[test]
+fn test_list_with_owner_filters_other_owners() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user1/repo1", "1.0.0")?;
+    fixture.create_fake_installation("user1/repo2", "2.0.0")?;
+    fixture.create_fake_installation("user2/repo3", "3.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list").arg("user1");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success(), "List with owner should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("user1/repo1"), "stdout: {}", stdout);
+    assert!(stdout.contains("user1/repo2"), "stdout: {}", stdout);
+    assert!(!stdout.contains("user2/repo3"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_with_owner_without_installations() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    // a prefix of the owner name must not match
+    fixture.create_fake_installation("user10/repo1", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list").arg("user1");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("No repositories of 'user1' found"),
+        "stderr: {}",
+        stderr
+    );
 
     Ok(())
 }
@@ -517,6 +611,41 @@ fn test_list_with_slug_and_empty_version_dir() -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+#[serial]
+#[test]
+fn test_list_skips_version_dir_without_executable() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "2.0.0")?;
+    // as left behind by a failed install
+    let phantom = fixture.get_install_path("user/repo", "1.0.0");
+    std::fs::create_dir_all(&phantom)?;
+    std::fs::write(phantom.join("README.md"), "# repo\n")?;
+
+    for args in [vec!["list", "user/repo"], vec!["list"]] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        cmd.args(&args);
+        set_test_env(&mut cmd, &fixture);
+        let output = cmd.output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert!(output.status.success(), "{:?} should succeed", args);
+        assert!(
+            stdout.contains("2.0.0"),
+            "{:?} should list the installed version: {}",
+            args,
+            stdout
+        );
+        assert!(
+            !stdout.contains("1.0.0"),
+            "{:?} should skip the version without executables: {}",
+            args,
+            stdout
+        );
+    }
+
+    Ok(())
+}
+
 #[serial]
 #[test]
 fn test_list_with_slug_output_format_consistency() -> Result<(), Box<dyn std::error::Error>> {
@@ -583,3 +712,503 @@ fn test_list_with_slug_output_format_consistency() -> Result<(), Box<dyn std::er
 
     Ok(())
 }
+
+#[serial]
+#[test]
+fn test_list_with_format_repo_only() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user1/repo1", "1.0.0")?;
+    fixture.create_fake_installation("user1/repo1", "1.1.0")?;
+    fixture.create_fake_installation("user2/repo2", "2.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list").arg("--format").arg("{repo}");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec!["user1/repo1", "user2/repo2"],
+        "Expected one line per repo and no extra columns: {}",
+        stdout
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_with_format_all_placeholders() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user1/repo1", "1.0.0")?;
+    let install_dir = fixture.create_fake_installation("user1/repo1", "1.1.0")?;
+    fixture.create_bin_symlink("repo1", &install_dir.join("repo1"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list")
+        .arg("--format")
+        .arg("{repo}\\t{active_version}\\t{versions}\\t{latest_version}\\t{count}");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    #[cfg(not(target_os = "windows"))]
+    assert_eq!(
+        stdout.trim_end(),
+        "user1/repo1\t1.1.0\t1.0.0, 1.1.0\t1.1.0\t2"
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_with_unknown_format_placeholder() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user1/repo1", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list").arg("--format").arg("{repo} {oops}");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty(), "Nothing should be printed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown placeholder '{oops}'"),
+        "Error should name the unknown placeholder: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_json() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user1/repo1", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list").arg("--json");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed[0]["repo"], "user1/repo1");
+    assert_eq!(parsed[0]["versions"][0], "1.0.0");
+    assert_eq!(parsed[0]["count"], 1);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_marks_unlinked_installations() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let install_dir = fixture.create_fake_installation("user/linked", "1.0.0")?;
+    fixture.create_bin_symlink("linked", &install_dir.join("linked"))?;
+    fixture.create_fake_installation("user/hidden", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("list");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("user/hidden (unlinked)"),
+        "Installation without symlink should be marked: {}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("user/linked (unlinked)"),
+        "Linked installation should not be marked: {}",
+        stdout
+    );
+
+    Ok(())
+}
+
+/// Installs three repositories whose name, version and size orders all differ.
+fn create_sortable_installations(fixture: &TestFixture) -> Result<(), Box<dyn std::error::Error>> {
+    let alpha = fixture.create_fake_installation("user/alpha", "2.0.0")?;
+    fixture.create_fake_installation("user/bravo", "10.0.0")?;
+    let charlie = fixture.create_fake_installation("user/charlie", "1.0.0")?;
+    std::fs::write(alpha.join("data.bin"), vec![0u8; 5000])?;
+    std::fs::write(charlie.join("data.bin"), vec![0u8; 1000])?;
+    Ok(())
+}
+
+/// Runs `poof list` with `args`, returning the repositories in printed order.
+fn list_order(
+    fixture: &TestFixture,
+    args: &[&str],
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--format", "{repo}"]).args(args);
+    set_test_env(&mut cmd, fixture);
+    let output = cmd.output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+#[serial]
+#[test]
+fn test_list_sort_by() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    create_sortable_installations(&fixture)?;
+
+    for (args, expected) in [
+        (vec![], ["user/alpha", "user/bravo", "user/charlie"]),
+        (
+            vec!["--sort-by", "repo"],
+            ["user/alpha", "user/bravo", "user/charlie"],
+        ),
+        (
+            vec!["--reverse"],
+            ["user/charlie", "user/bravo", "user/alpha"],
+        ),
+        (
+            vec!["--sort-by", "version"],
+            ["user/charlie", "user/alpha", "user/bravo"],
+        ),
+        (
+            vec!["--sort-by", "version", "--reverse"],
+            ["user/bravo", "user/alpha", "user/charlie"],
+        ),
+        (
+            vec!["--sort-by", "size"],
+            ["user/bravo", "user/charlie", "user/alpha"],
+        ),
+    ] {
+        assert_eq!(list_order(&fixture, &args)?, expected, "{:?}", args);
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_list_sort_by_date() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::{Duration, SystemTime};
+
+    let fixture = TestFixture::new()?;
+    create_sortable_installations(&fixture)?;
+    // charlie installed first, then alpha, bravo last
+    let now = SystemTime::now();
+    for (repo, age) in [
+        ("user/charlie", 300),
+        ("user/alpha", 200),
+        ("user/bravo", 100),
+    ] {
+        let version_dir = std::fs::read_dir(fixture.data_dir.join(repo))?
+            .next()
+            .unwrap()?
+            .path();
+        std::fs::File::open(&version_dir)?.set_modified(now - Duration::from_secs(age))?;
+    }
+
+    assert_eq!(
+        list_order(&fixture, &["--sort-by", "date"])?,
+        ["user/charlie", "user/alpha", "user/bravo"]
+    );
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_sort_by_json() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    create_sortable_installations(&fixture)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--json", "--sort-by", "version", "--reverse"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let repos: Vec<&str> = parsed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["repo"].as_str().unwrap())
+        .collect();
+    assert_eq!(repos, ["user/bravo", "user/alpha", "user/charlie"]);
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_sort_by_unknown_field() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["list", "--sort-by", "stars"]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("possible values"), "stderr: {}", stderr);
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_quiet_keeps_stdout_only() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "-q"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("user/alpha"), "stdout: {}", stdout);
+    assert!(
+        output.stderr.is_empty(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_quiet_still_prints_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["--quiet", "list", "user/missing"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not found"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_only_outdated() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    for repo in ["user/alpha", "user/bravo", "user/zulu"] {
+        fixture.create_fake_installation(repo, "1.0.0")?;
+    }
+    let _alpha = github.mock_latest_release("user/alpha", "v2.0.0", vec![]);
+    let _bravo = github.mock_latest_release("user/bravo", "v1.0.0", vec![]);
+    let _zulu = github.mock_not_found("user/zulu");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--only-outdated", "--check-delay", "0"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(stdout.contains("Installed"), "stdout: {}", stdout);
+    assert!(stdout.contains("Latest"), "stdout: {}", stdout);
+    let row = |repo: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(repo))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+    };
+    assert_eq!(
+        row("user/alpha"),
+        Some(vec!["user/alpha", "1.0.0", "2.0.0"])
+    );
+    // up to date repositories are left out
+    assert_eq!(row("user/bravo"), None);
+    // a failed check does not abort the whole command
+    assert_eq!(row("user/zulu"), Some(vec!["user/zulu", "1.0.0", "?"]));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_outdated_annotates_every_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    for repo in ["user/alpha", "user/bravo", "user/zulu"] {
+        fixture.create_fake_installation(repo, "1.0.0")?;
+    }
+    let _alpha = github.mock_latest_release("user/alpha", "v2.0.0", vec![]);
+    let _bravo = github.mock_latest_release("user/bravo", "v1.0.0", vec![]);
+    let _zulu = github.mock_network_error("user/zulu");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--outdated", "--check-delay", "0"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let row = |repo: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(repo))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+    };
+    assert_eq!(
+        row("user/alpha"),
+        Some(vec![
+            "user/alpha",
+            "1.0.0",
+            "2.0.0",
+            "(update",
+            "available)"
+        ])
+    );
+    // up to date repositories are listed too
+    assert_eq!(
+        row("user/bravo"),
+        Some(vec!["user/bravo", "1.0.0", "1.0.0"])
+    );
+    // a failed check does not abort the whole command
+    assert_eq!(
+        row("user/zulu"),
+        Some(vec!["user/zulu", "1.0.0", "unknown"])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_list_outdated_conflicts_with_json() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--outdated", "--json"]);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_verbose_install_time_survives_use() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let repo = "user/alpha";
+    let old_dir = fixture.create_fake_installation(repo, "1.0.0")?;
+    fixture.create_fake_installation(repo, "2.0.0")?;
+    // 2024-01-01T00:00:00Z
+    std::fs::write(old_dir.join(".poof-installed-at"), "1704067200\n")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["use", repo, "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "--verbose"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(
+        stdout.contains("1.0.0 installed at 2024-01-01T00:00:00Z"),
+        "stdout: {}",
+        stdout
+    );
+    // versions installed before the time was recorded fall back to the directory time
+    assert!(stdout.contains("2.0.0 installed at "), "stdout: {}", stdout);
+
+    Ok(())
+}
+#[serial]
+#[test]
+fn test_list_suggests_similar_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["list", "user/rep0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("Did you mean: user/repo?"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_with_slug_marks_active_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    fixture.create_fake_installation("user/repo", "2.0.0")?;
+
+    let list = |fixture: &TestFixture| -> Result<String, Box<dyn std::error::Error>> {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        cmd.args(["list", "user/repo"]);
+        set_test_env(&mut cmd, fixture);
+        let output = cmd.output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    // nothing is linked yet
+    let stdout = list(&fixture)?;
+    assert!(!stdout.contains("(active)"), "stdout: {}", stdout);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["use", "user/repo", "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = list(&fixture)?;
+    assert!(
+        stdout.contains("1.0.0 (active), 2.0.0"),
+        "The version in use should be marked: {}",
+        stdout
+    );
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Integration tests for the 'pin' and 'unpin' commands
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+fn run_poof(
+    fixture: &TestFixture,
+    args: &[&str],
+) -> Result<std::process::Output, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(args);
+    set_test_env(&mut cmd, fixture);
+    Ok(cmd.output()?)
+}
+
+#[serial]
+#[test]
+fn test_pin_requires_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let output = run_poof(&fixture, &["pin", "user/repo"])?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_pin_version_not_installed() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+
+    let output = run_poof(&fixture, &["pin", "user/repo", "2.0.0"])?;
+    assert!(!output.status.success());
+    assert!(!fixture.data_dir.join("user/repo/.pinned/2.0.0").exists());
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_pin_and_unpin() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    let marker = fixture.data_dir.join("user/repo/.pinned/1.0.0");
+
+    let output = run_poof(&fixture, &["pin", "user/repo", "1.0.0"])?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(marker.is_file(), "Pin marker should be created");
+
+    let output = run_poof(&fixture, &["unpin", "user/repo"])?;
+    assert!(output.status.success());
+    assert!(!marker.exists(), "Pin marker should be removed");
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_update_skips_pinned_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    run_poof(&fixture, &["pin", "user/repo", "1.0.0"])?;
+
+    // no network access is needed, the repository is skipped before any lookup
+    let output = run_poof(&fixture, &["update", "user/repo"])?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("pinned to version 1.0.0"),
+        "Update should report the pin: {}",
+        stderr
+    );
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_list_shows_pin() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    fixture.create_fake_installation("user/repo", "2.0.0")?;
+    run_poof(&fixture, &["pin", "user/repo", "1.0.0"])?;
+
+    let output = run_poof(&fixture, &["list"])?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[pinned 1.0.0]"),
+        "List should show the pin: {}",
+        stdout
+    );
+    // the pins directory is not an installed version
+    assert!(!stdout.contains(".pinned"), "Unexpected output: {}", stdout);
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_uninstall_pinned_version_requires_force() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    run_poof(&fixture, &["pin", "user/repo", "1.0.0"])?;
+
+    let output = run_poof(&fixture, &["uninstall", "user/repo", "-v", "1.0.0", "-y"])?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--force"), "Unexpected error: {}", stderr);
+    assert!(install_dir.exists(), "Pinned version should be kept");
+
+    let output = run_poof(
+        &fixture,
+        &["uninstall", "user/repo", "-v", "1.0.0", "-y", "--force"],
+    )?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!install_dir.exists(), "Pinned version should be removed");
+    assert!(!fixture.data_dir.join("user/repo/.pinned").exists());
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_uninstall_other_version_of_pinned_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+    let other_dir = fixture.create_fake_installation("user/repo", "2.0.0")?;
+    run_poof(&fixture, &["pin", "user/repo", "1.0.0"])?;
+
+    let output = run_poof(&fixture, &["uninstall", "user/repo", "-v", "2.0.0", "-y"])?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!other_dir.exists());
+    assert!(fixture.data_dir.join("user/repo/.pinned/1.0.0").exists());
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Integration tests for the 'prune' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+fn run_prune(
+    fixture: &TestFixture,
+    args: &[&str],
+) -> Result<std::process::Output, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("prune").args(args);
+    set_test_env(&mut cmd, fixture);
+    Ok(cmd.output()?)
+}
+
+#[serial]
+#[test]
+fn test_prune_requires_repo_or_all() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let output = run_prune(&fixture, &[])?;
+    assert!(!output.status.success());
+
+    let output = run_prune(&fixture, &["user/repo", "--all"])?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_prune_keeps_only_active_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    let active = fixture.create_fake_installation("user/repo", "2.0.0")?;
+    fixture.create_bin_symlink("repo", &active.join("repo"))?;
+    let symlink = fixture.bin_dir.join("repo");
+    let newer = fixture.create_fake_installation("user/repo", "3.0.0")?;
+
+    let output = run_prune(&fixture, &["user/repo", "-y"])?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("Removed 2 inactive"), "stderr: {}", stderr);
+    assert!(stderr.contains("reclaimed"), "stderr: {}", stderr);
+    assert!(!old.exists(), "Older version should be removed");
+    assert!(!newer.exists(), "Newer inactive version should be removed");
+    assert!(active.exists(), "Active version should be kept");
+    assert!(
+        symlink.exists(),
+        "Symlink of the active version should be kept"
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_prune_all_skips_repos_without_active_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    let active = fixture.create_fake_installation("user/repo", "2.0.0")?;
+    fixture.create_bin_symlink("repo", &active.join("repo"))?;
+    let unlinked_old = fixture.create_fake_installation("user/other", "1.0.0")?;
+    let unlinked_new = fixture.create_fake_installation("user/other", "2.0.0")?;
+
+    let output = run_prune(&fixture, &["--all", "-y"])?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("no active version"), "stderr: {}", stderr);
+    assert!(!old.exists());
+    assert!(active.exists());
+    assert!(unlinked_old.exists() && unlinked_new.exists());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_prune_dry_run_removes_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    let active = fixture.create_fake_installation("user/repo", "2.0.0")?;
+    fixture.create_bin_symlink("repo", &active.join("repo"))?;
+
+    let output = run_prune(&fixture, &["user/repo", "--dry-run"])?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("Would remove 1"), "stderr: {}", stderr);
+    assert!(old.exists(), "Dry run should not remove anything");
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Integration tests for the 'repair' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+#[serial]
+#[test]
+fn test_repair_nothing_to_do() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &install_dir.join("tool"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("repair");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("No broken symlinks found"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_repair_repoints_to_newer_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let old_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let new_dir = fixture.create_fake_installation("owner/tool", "2.0.0")?;
+    fixture.create_bin_symlink("tool", &old_dir.join("tool"))?;
+    std::fs::remove_dir_all(&old_dir)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("repair");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::fs::read_link(fixture.bin_dir.join("tool"))?,
+        new_dir.join("tool")
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_repair_removes_orphan_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &install_dir.join("tool"))?;
+    std::fs::remove_dir_all(&install_dir)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("repair");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    assert!(!fixture.bin_dir.join("tool").is_symlink());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_repair_dry_run_changes_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &install_dir.join("tool"))?;
+    std::fs::remove_dir_all(&install_dir)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["repair", "--dry-run"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Would remove"), "stderr: {}", stderr);
+    assert!(fixture.bin_dir.join("tool").is_symlink());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_repair_leaves_foreign_files() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    std::fs::write(fixture.bin_dir.join("manual"), b"")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("repair");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not managed by poof"), "stderr: {}", stderr);
+    assert!(fixture.bin_dir.join("manual").exists());
+
+    Ok(())
+}
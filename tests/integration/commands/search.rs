@@ -0,0 +1,71 @@
+//! Integration tests for the 'search' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+#[serial]
+#[test]
+fn test_search_requires_query() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.arg("search").output()?;
+
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_search_prints_full_names() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/search/repositories")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            serde_json::json!({
+                "total_count": 2,
+                "items": [
+                    {"full_name": "BurntSushi/ripgrep", "stargazers_count": 50000, "description": "Fast grep"},
+                    {"full_name": "someone/rg-wrapper", "stargazers_count": 12, "description": null},
+                ]
+            })
+            .to_string(),
+        )
+        .create();
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args(["search", "ripgrep"])
+        .env("HOME", temp_dir.path())
+        .env("POOF_GITHUB_API_URL", server.url())
+        .output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    mock.assert();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("BurntSushi/ripgrep"), "stdout: {}", stdout);
+    assert!(stdout.contains("someone/rg-wrapper"), "stdout: {}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("poof install"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_search_rejects_out_of_range_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["search", "ripgrep", "--limit", "0"]).output()?;
+
+    assert!(!output.status.success());
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Integration tests for the 'show' command
+
+use assert_cmd::{assert::OutputAssertExt, cargo};
+use predicates::prelude::*;
+use serial_test::serial;
+use std::process::Command;
+
+use super::common::fixtures::mock_github::{MockAsset, MockGitHub};
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+#[serial]
+#[test]
+fn test_show_installed_and_latest_release() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _release = github.mock_latest_release(
+        "user/alpha",
+        "v2.0.0",
+        vec![MockAsset::new(
+            "alpha-linux-x86_64.tar.gz",
+            "https://example.com/alpha-linux-x86_64.tar.gz",
+        )],
+    );
+    let install_dir = fixture.create_fake_installation("user/alpha", "1.0.0")?;
+    fixture.create_bin_symlink("alpha", &install_dir.join("alpha"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["show", "user/alpha"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "URL             : https://github.com/user/alpha",
+        ))
+        .stdout(predicate::str::is_match(
+            r"1\.0\.0 \(active\) installed at \d{4}-\d{2}-\d{2}T",
+        )?)
+        .stdout(predicate::str::contains("provides: alpha"))
+        .stdout(predicate::str::contains("Latest release  : v2.0.0"))
+        .stdout(predicate::str::contains("alpha-linux-x86_64.tar.gz"))
+        .stdout(predicate::str::contains("Update available"));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_show_installed_only_skips_api() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    // no mocks: any request to the API would fail the command
+    let github = MockGitHub::new();
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["show", "user/alpha", "--installed-only", "--json"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(info["repo"], "user/alpha");
+    assert_eq!(info["installed"][0]["version"], "1.0.0");
+    assert_eq!(
+        info["installed"][0]["binaries"],
+        serde_json::json!(["alpha"])
+    );
+    assert_eq!(info["installed"][0]["active"], false);
+    assert!(info["latest_tag"].is_null());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_show_not_installed() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let _release = github.mock_latest_release("user/beta", "v0.3.0", vec![]);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["show", "user/beta"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Installed       : none"))
+        .stdout(predicate::str::contains("Latest release  : v0.3.0"))
+        .stdout(predicate::str::contains("Update available").not());
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Integration tests for the 'status' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+#[serial]
+#[test]
+fn test_status_empty() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("status");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No installed binaries found"));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_status_all_ok() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &install_dir.join("tool"))?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("status");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Repository"));
+    assert!(stdout.contains("owner/tool"));
+    assert!(stdout.contains("OK"));
+    // stdout is not a terminal, no colours
+    assert!(!stdout.contains('\x1b'));
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_status_reports_missing_and_broken() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("owner/unlinked", "1.0.0")?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &install_dir.join("tool"))?;
+    std::fs::remove_dir_all(&install_dir)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("status");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("MISSING"), "stdout: {}", stdout);
+    assert!(stdout.contains("BROKEN"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_status_fix_removes_broken_symlinks() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    fixture.create_bin_symlink("tool", &install_dir.join("tool"))?;
+    std::fs::remove_dir_all(&install_dir)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["status", "--fix", "--yes"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    assert!(!fixture.bin_dir.join("tool").is_symlink());
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_status_yes_requires_fix() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["status", "--yes"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+
+    Ok(())
+}
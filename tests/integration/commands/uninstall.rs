@@ -27,8 +27,12 @@ fn run_uninstall_with_input(
 
     {
         let mut stdin = child.stdin.take().expect("Cannot open stdin");
-        stdin.write_all(input)?;
-        stdin.flush()?;
+        // poof may exit before reading its input, e.g. when there is nothing to uninstall
+        if let Err(e) = stdin.write_all(input).and_then(|_| stdin.flush()) {
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(e.into());
+            }
+        }
     }
 
     Ok(child.wait_with_output()?)
@@ -604,3 +608,125 @@ fn test_uninstall_with_v_and_yes_flag() -> Result<(), Box<dyn std::error::Error>
 
     Ok(())
 }
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_uninstall_cleans_recorded_bin_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("user/repo", "1.0.0")?;
+    // as left by 'install --bin-dir'
+    let team_bin = fixture.home_dir.join("team-bin");
+    std::fs::create_dir_all(&team_bin)?;
+    std::os::unix::fs::symlink(install_dir.join("repo"), team_bin.join("repo"))?;
+    std::fs::write(
+        install_dir.join(".poof-bin-dir"),
+        format!("{}\n", team_bin.display()),
+    )?;
+
+    let output = run_uninstall_with_input(&fixture, &["user/repo", "--all", "-y"], b"")?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!install_dir.exists());
+    assert!(
+        !team_bin.join("repo").is_symlink(),
+        "Symlink in the recorded bin directory should be removed"
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_uninstall_keep_latest() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let repo = "testuser/multitool";
+    let versions = ["1.0.0", "1.10.0", "1.9.0", "2.0.0"];
+    for version in &versions {
+        fixture.create_fake_installation(repo, version)?;
+    }
+
+    let output = run_uninstall_with_input(&fixture, &[repo, "--keep-latest", "-y"], b"")?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for version in ["1.0.0", "1.10.0", "1.9.0"] {
+        assert!(
+            !fixture.get_install_path(repo, version).exists(),
+            "Version {} should be deleted",
+            version
+        );
+    }
+    assert!(
+        fixture.get_install_path(repo, "2.0.0").exists(),
+        "The newest version should be kept"
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_uninstall_older_than() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let repo = "testuser/multitool";
+    let versions = ["1.0.0", "1.9.0", "2.0.0", "2.1.0"];
+    for version in &versions {
+        fixture.create_fake_installation(repo, version)?;
+    }
+
+    let output = run_uninstall_with_input(&fixture, &[repo, "--older-than", "2.0.0", "-y"], b"")?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for version in ["1.0.0", "1.9.0"] {
+        assert!(
+            !fixture.get_install_path(repo, version).exists(),
+            "Version {} should be deleted",
+            version
+        );
+    }
+    for version in ["2.0.0", "2.1.0"] {
+        assert!(
+            fixture.get_install_path(repo, version).exists(),
+            "Version {} should be kept",
+            version
+        );
+    }
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_uninstall_conflicts_keep_latest_and_version() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd
+        .args([
+            "uninstall",
+            "user/repo",
+            "--keep-latest",
+            "--version",
+            "1.0.0",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot be used"),
+        "Error should mention conflicting flags: {}",
+        stderr
+    );
+
+    Ok(())
+}
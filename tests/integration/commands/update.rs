@@ -5,6 +5,7 @@ use serial_test::serial;
 use std::process::Command;
 
 // Common module is included from the parent integration.rs file
+use super::common::fixtures::mock_github::{MockAsset, MockGitHub};
 use super::common::fixtures::test_env::TestFixture;
 use super::common::helpers::set_test_env;
 use super::common::repo_format_validation::*;
@@ -77,6 +78,21 @@ fn test_update_all_flag() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[serial]
+#[test]
+fn test_update_all_no_cache_flag() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    // Nothing installed, so nothing is fetched: only the flag parsing matters
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("update").arg("--all").arg("--no-cache");
+    set_test_env(&mut cmd, &fixture);
+
+    cmd.assert().success();
+
+    Ok(())
+}
+
 #[serial]
 #[test]
 fn test_update_repo_and_all_conflict() -> Result<(), Box<dyn std::error::Error>> {
@@ -291,3 +307,238 @@ fn test_update_sets_new_version_as_default() -> Result<(), Box<dyn std::error::E
     }
     Ok(())
 }
+
+#[serial]
+#[test]
+fn test_update_all_jobs_rejects_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["update", "--all", "--jobs", "0"]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("positive integer"), "stderr: {}", stderr);
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_update_jobs_requires_all() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["update", "user/repo", "--jobs", "2"]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "stderr: {}", stderr);
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[serial]
+#[test]
+fn test_update_all_with_jobs_updates_every_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    let repos = [("user1", "alpha"), ("user2", "bravo")];
+
+    let mut mocks = Vec::new();
+    for (user, name) in repos {
+        let repo = format!("{}/{}", user, name);
+        let old_dir = fixture.create_fake_installation(&repo, "1.0.0")?;
+        fixture.create_bin_symlink(name, &old_dir.join(name))?;
+
+        // a shebang script passes the executable checks on every platform
+        let asset_name = format!(
+            "{}-{}-{}",
+            name,
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        let download_url = format!("{}/download/{}", github.base_url(), asset_name);
+        mocks.push(github.mock_latest_release(
+            &repo,
+            "v2.0.0",
+            vec![MockAsset::new(&asset_name, &download_url)],
+        ));
+        mocks.push(github.mock_release_by_tag(
+            &repo,
+            "v2.0.0",
+            vec![MockAsset::new(&asset_name, &download_url)],
+        ));
+        mocks.push(
+            github
+                .server
+                .mock("GET", format!("/download/{}", asset_name).as_str())
+                .with_status(200)
+                .with_body(format!("#!/bin/sh\necho '{} 2.0.0'\n", name))
+                .create(),
+        );
+    }
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["update", "--all", "--jobs", "2"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for mock in &mocks {
+        mock.assert();
+    }
+
+    // each repository got its own binary, none was overwritten by the other job
+    for (user, name) in repos {
+        let repo = format!("{}/{}", user, name);
+        let new_dir = fixture.get_install_path(&repo, "2.0.0");
+        let binaries: Vec<_> = std::fs::read_dir(&new_dir)?
+            .flatten()
+            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            .collect();
+        assert_eq!(binaries.len(), 1, "{}: {:?}", repo, binaries);
+        let content = std::fs::read_to_string(binaries[0].path())?;
+        assert!(content.contains(&format!("{} 2.0.0", name)), "{}", content);
+
+        let link = std::fs::read_link(fixture.bin_dir.join(name))?;
+        assert!(
+            link.starts_with(&new_dir),
+            "{} -> {}\n{}",
+            name,
+            link.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_update_self_conflicts() -> Result<(), Box<dyn std::error::Error>> {
+    for args in [
+        vec!["update", "--self", "--all"],
+        vec!["update", "--self", "user/repo"],
+    ] {
+        let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+        let output = cmd.args(&args).output()?;
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("cannot be used with"),
+            "{:?}: {}",
+            args,
+            stderr
+        );
+    }
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_update_suggests_similar_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["update", "user/rep0"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("Did you mean: user/repo?"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_update_all_partial_failure_summary() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+    fixture.create_fake_installation("user/bravo", "1.0.0")?;
+    let _alpha = github.mock_latest_release("user/alpha", "v1.0.0", vec![]);
+    let _bravo = github.mock_not_found("user/bravo");
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["update", "--all"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Updated: 0, Already up-to-date: 1, Skipped (pinned): 0, Failed: 1"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Update failed for user/bravo"),
+        "stderr: {}",
+        stderr
+    );
+
+    // --fail-fast gives up at the first failure, as a plain error
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["update", "--all", "--fail-fast"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        !stderr.contains("Already up-to-date:"),
+        "stderr: {}",
+        stderr
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_update_all_progress_and_summary_only() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let mut github = MockGitHub::new();
+    fixture.create_fake_installation("user/alpha", "1.0.0")?;
+    let _alpha = github.mock_latest_release("user/alpha", "v1.0.0", vec![]);
+    let summary = "Updated: 0, Already up-to-date: 1, Skipped (pinned): 0, Failed: 0";
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["update", "--all"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("Checking user/alpha..."),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("user/alpha is up-to-date"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains(summary), "stderr: {}", stderr);
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["update", "--all", "--summary-only"])
+        .env("POOF_GITHUB_API_URL", github.base_url());
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(
+        !stderr.contains("Checking user/alpha"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains(summary), "stderr: {}", stderr);
+
+    Ok(())
+}
@@ -355,3 +355,19 @@ fn test_use_explicit_version_with_multiple_installations() -> Result<(), Box<dyn
 
     Ok(())
 }
+
+#[serial]
+#[test]
+fn test_use_suggests_similar_repo() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/repo", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["use", "user/rep0", "1.0.0"]);
+    set_test_env(&mut cmd, &fixture);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Did you mean: user/repo?"));
+
+    Ok(())
+}
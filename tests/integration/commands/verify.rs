@@ -0,0 +1,117 @@
+//! Integration tests for the 'verify' command
+
+use assert_cmd::cargo;
+use serial_test::serial;
+use std::process::Command;
+
+// Common module is included from the parent integration.rs file
+use super::common::fixtures::test_env::TestFixture;
+use super::common::helpers::set_test_env;
+
+/// SHA-256 of the fake binary written by `TestFixture::create_fake_installation`.
+fn fake_binary_sha256(fixture: &TestFixture, repo: &str, version: &str) -> String {
+    let binary = repo.split('/').next_back().unwrap();
+    let output = Command::new("sha256sum")
+        .arg(fixture.get_install_path(repo, version).join(binary))
+        .output()
+        .expect("sha256sum should be available");
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+fn run_verify(
+    fixture: &TestFixture,
+    args: &[&str],
+) -> Result<std::process::Output, Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("verify").args(args);
+    set_test_env(&mut cmd, fixture);
+    Ok(cmd.output()?)
+}
+
+#[serial]
+#[test]
+fn test_verify_requires_repo_or_all() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let output = run_verify(&fixture, &[])?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_verify_pass() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let hash = fake_binary_sha256(&fixture, "owner/tool", "1.0.0");
+    std::fs::write(install_dir.join("tool.sha256"), format!("{}  tool\n", hash))?;
+
+    let output = run_verify(&fixture, &["owner/tool"])?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("tool"), "stdout: {}", stdout);
+    assert!(stdout.contains("PASS"), "stdout: {}", stdout);
+    // metadata files are not verified themselves
+    assert_eq!(stdout.matches("PASS").count(), 1, "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_verify_fail_on_modified_binary() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let hash = fake_binary_sha256(&fixture, "owner/tool", "1.0.0");
+    std::fs::write(install_dir.join("tool.sha256"), format!("{}  tool\n", hash))?;
+    std::fs::write(install_dir.join("tool"), b"tampered")?;
+
+    let output = run_verify(&fixture, &["owner/tool", "1.0.0"])?;
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("FAIL"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_verify_all_reports_unknown_checksums() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    let install_dir = fixture.create_fake_installation("owner/tool", "1.0.0")?;
+    let hash = fake_binary_sha256(&fixture, "owner/tool", "1.0.0");
+    std::fs::write(install_dir.join("tool.sha256"), format!("{}  tool\n", hash))?;
+    fixture.create_fake_installation("owner/other", "2.0.0")?;
+
+    let output = run_verify(&fixture, &["--all"])?;
+
+    // binaries without checksum do not make the command fail
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("PASS"), "stdout: {}", stdout);
+    assert!(stdout.contains("UNKNOWN"), "stdout: {}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no known checksum"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_verify_version_not_installed() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("owner/tool", "1.0.0")?;
+
+    let output = run_verify(&fixture, &["owner/tool", "9.9.9"])?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not found"), "stderr: {}", stderr);
+
+    Ok(())
+}
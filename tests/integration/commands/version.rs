@@ -54,3 +54,31 @@ fn test_version_command_with_extra_args() -> Result<(), Box<dyn std::error::Erro
     cmd.arg("version").arg("extra").assert().failure(); // clap should reject extra positional args
     Ok(())
 }
+
+#[test]
+fn test_version_json_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    let output = cmd.args(["version", "--json"]).output()?;
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let object = json
+        .as_object()
+        .expect("version --json should print an object");
+    // renaming or removing any of these fields is a breaking change
+    for key in [
+        "version",
+        "commit",
+        "build_date",
+        "compile_c_lib",
+        "linking_type",
+        "glibc_version",
+    ] {
+        assert!(object.contains_key(key), "Missing key {}: {}", key, json);
+    }
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["commit"], env!("GIT_COMMIT_HASH"));
+    assert_eq!(json["compile_c_lib"], env!("C_LIB"));
+
+    Ok(())
+}
@@ -347,3 +347,66 @@ fn test_what_prerelease_versions() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+// ============================================================================
+// All Versions Tests
+// ============================================================================
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_what_verbose_lists_versions_of_each_binary() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("testuser/multibin", "1.0.0")?;
+    let install_dir = fixture.create_fake_installation("testuser/multibin", "2.0.0")?;
+    let helper = install_dir.join("helper");
+    std::fs::write(&helper, b"#!/bin/sh\necho 'helper'")?;
+    make_executable(&helper)?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["what", "testuser/multibin", "--verbose"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("- helper (versions 2.0.0)"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("- multibin (versions 1.0.0, 2.0.0)"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains(&helper.display().to_string()));
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["what", "testuser/multibin", "--json"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["repo"], "testuser/multibin");
+    let binaries = json["binaries"].as_array().unwrap();
+    assert_eq!(binaries.len(), 2);
+    assert_eq!(binaries[0]["name"], "helper");
+    assert_eq!(binaries[0]["versions"].as_array().unwrap().len(), 1);
+    assert_eq!(binaries[0]["versions"][0]["version"], "2.0.0");
+    assert_eq!(binaries[1]["name"], "multibin");
+    let versions: Vec<&str> = binaries[1]["versions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["version"].as_str().unwrap())
+        .collect();
+    assert_eq!(versions, ["1.0.0", "2.0.0"]);
+    assert_eq!(
+        binaries[1]["versions"][1]["path"],
+        install_dir.join("multibin").display().to_string()
+    );
+
+    Ok(())
+}
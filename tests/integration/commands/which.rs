@@ -446,3 +446,152 @@ fn test_which_multiple_versions() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+// ============================================================================
+// Active Version Tests
+// ============================================================================
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_which_marks_active_version() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/tool", "1.0.0")?;
+    create_managed_symlink(&fixture, "tool", "user/tool", "2.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("which").arg("tool");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success(), "Command should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let active_lines: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("user/tool"))
+        .filter(|line| line.trim_end().ends_with('*'))
+        .collect();
+    assert_eq!(active_lines.len(), 1, "stdout: {}", stdout);
+    assert!(active_lines[0].contains("2.0.0"), "stdout: {}", stdout);
+    assert!(!stdout.contains("not linked"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_which_reports_installed_but_unlinked() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/tool", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("which").arg("tool");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success(), "Command should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("user/tool"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("is installed but not linked"),
+        "stdout: {}",
+        stdout
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_which_never_installed_suggests_similar_binaries() -> Result<(), Box<dyn std::error::Error>>
+{
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/tool", "1.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.arg("which").arg("tol");
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success(), "Command should fail");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not found"), "stderr: {}", stderr);
+    assert!(stderr.contains("Did you mean: tool?"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_which_json_output() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    fixture.create_fake_installation("user/tool", "1.0.0")?;
+    create_managed_symlink(&fixture, "tool", "user/tool", "2.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["which", "tool", "--json"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success(), "Command should succeed");
+    let providers: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let providers = providers.as_array().expect("a JSON array");
+    assert_eq!(providers.len(), 2);
+    for provider in providers {
+        assert_eq!(provider["repo"], "user/tool");
+        let active = provider["version"] == "2.0.0";
+        assert_eq!(provider["active"], active);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Inventory Tests
+// ============================================================================
+
+#[serial]
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_which_all_lists_every_binary() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+    create_managed_symlink(&fixture, "alpha", "user/alpha", "1.0.0")?;
+    fixture.create_fake_installation("other/beta", "2.0.0")?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["which", "--all"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(output.status.success(), "Command should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = |binary: &str| -> Vec<String> {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(binary))
+            .map(|line| line.split_whitespace().map(String::from).collect())
+            .unwrap_or_default()
+    };
+    assert_eq!(row("alpha"), ["alpha", "user/alpha", "1.0.0"], "{}", stdout);
+    assert_eq!(row("beta"), ["beta", "other/beta", "-"], "{}", stdout);
+
+    Ok(())
+}
+
+#[serial]
+#[test]
+fn test_which_all_conflicts_with_binary_name() -> Result<(), Box<dyn std::error::Error>> {
+    let fixture = TestFixture::new()?;
+
+    let mut cmd = Command::new(cargo::cargo_bin!("poof"));
+    cmd.args(["which", "--all", "tool"]);
+    set_test_env(&mut cmd, &fixture);
+    let output = cmd.output()?;
+
+    assert!(!output.status.success());
+
+    Ok(())
+}